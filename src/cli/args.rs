@@ -0,0 +1,154 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+// What a subcommand accepts: options that take a value, flags that don't, and the names of its positionals
+pub struct ArgSpec {
+    pub options: &'static [&'static str],
+    pub flags: &'static [&'static str],
+    pub positional: &'static [&'static str]
+}
+
+// Options every subcommand takes
+const COMMON_OPTIONS: [&str; 1] = ["league"];
+
+// Command line arguments after the subcommand: positionals, plus options as "--name value" or "--name=value" and
+// flags as a bare "--name"
+pub struct Args {
+    positional: Vec<String>,
+    options: HashMap<String, String>,
+    flags: HashSet<String>
+}
+
+impl Args {
+    // Errors on anything spec doesn't know, so typos aren't silently ignored
+    pub fn parse(args: &[String], spec: &ArgSpec) -> Result<Self, String> {
+        let mut positional = Vec::new();
+        let mut options = HashMap::new();
+        let mut flags = HashSet::new();
+        let mut i = 0;
+        while i < args.len() {
+            let arg = &args[i];
+            i += 1;
+            if !arg.starts_with("--") || arg.len() == 2 {
+                if positional.len() >= spec.positional.len() {
+                    return Err(format!("Unexpected argument \"{}\"", arg));
+                }
+                positional.push(arg.clone());
+                continue;
+            }
+
+            let (name, value) = match arg[2..].split_once('=') {
+                Some((name, value)) => (name, Some(value.to_string())),
+                None => (&arg[2..], None),
+            };
+            if spec.flags.contains(&name) {
+                if value.is_some() {
+                    return Err(format!("--{} doesn't take a value", name));
+                }
+                flags.insert(name.to_string());
+            } else if spec.options.contains(&name) || COMMON_OPTIONS.contains(&name) {
+                let value = match value {
+                    Some(value) => value,
+                    None => {
+                        if i >= args.len() || args[i].starts_with("--") {
+                            return Err(format!("--{} needs a value", name));
+                        }
+                        i += 1;
+                        args[i - 1].clone()
+                    }
+                };
+                options.insert(name.to_string(), value);
+            } else {
+                return Err(format!("Unknown option --{}", name));
+            }
+        }
+        Ok(Args {
+            positional,
+            options,
+            flags
+        })
+    }
+
+    pub fn has(&self, name: &str) -> bool {
+        self.flags.contains(name) || self.options.contains_key(name)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.options.get(name).map(|value| value.as_str())
+    }
+
+    pub fn get_required(&self, name: &str) -> Result<&str, String> {
+        let value = self.get(name);
+        if value.is_none() {
+            return Err(format!("--{} is required", name));
+        }
+        Ok(value.unwrap())
+    }
+
+    pub fn get_int<T: std::str::FromStr>(&self, name: &str) -> Result<Option<T>, String> {
+        let value = self.get(name);
+        if value.is_none() {
+            return Ok(None);
+        }
+        let parsed = value.unwrap().parse::<T>();
+        if parsed.is_err() {
+            return Err(format!("--{} must be a whole number, got \"{}\"", name, value.unwrap()));
+        }
+        Ok(Some(parsed.ok().unwrap()))
+    }
+
+    // Comma separated, like "--give 12,34". Empty if the option isn't given.
+    pub fn get_int_list<T: std::str::FromStr>(&self, name: &str) -> Result<Vec<T>, String> {
+        let value = self.get(name);
+        if value.is_none() {
+            return Ok(Vec::new());
+        }
+        let mut values = Vec::new();
+        for item in value.unwrap().split(',').map(|item| item.trim()).filter(|item| !item.is_empty()) {
+            let parsed = item.parse::<T>();
+            if parsed.is_err() {
+                return Err(format!("--{} must be a comma separated list of whole numbers, got \"{}\"", name, item));
+            }
+            values.push(parsed.ok().unwrap());
+        }
+        Ok(values)
+    }
+
+    pub fn get_positional(&self, index: usize, name: &str) -> Result<&str, String> {
+        let value = self.positional.get(index);
+        if value.is_none() {
+            return Err(format!("Missing <{}>", name));
+        }
+        Ok(value.unwrap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPEC: ArgSpec = ArgSpec { options: &["days", "until"], flags: &["force"], positional: &["gid"] };
+
+    fn parse(args: &[&str]) -> Result<Args, String> {
+        Args::parse(&args.iter().map(|arg| arg.to_string()).collect::<Vec<String>>(), &SPEC)
+    }
+
+    #[test]
+    fn options_flags_and_positionals() {
+        let args = parse(&["--days", "3", "--force", "12", "--until=end", "--league", "a.db"]).unwrap();
+        assert_eq!(args.get_int::<i32>("days").unwrap(), Some(3));
+        assert!(args.has("force"));
+        assert_eq!(args.get("until"), Some("end"));
+        assert_eq!(args.get("league"), Some("a.db"));
+        assert_eq!(args.get_positional(0, "gid").unwrap(), "12");
+    }
+
+    #[test]
+    fn anything_else_is_an_error() {
+        assert_eq!(parse(&["--dayz", "3"]).err().unwrap(), "Unknown option --dayz");
+        assert_eq!(parse(&["--force=1"]).err().unwrap(), "--force doesn't take a value");
+        assert_eq!(parse(&["--days", "--force"]).err().unwrap(), "--days needs a value");
+        assert_eq!(parse(&["--days"]).err().unwrap(), "--days needs a value");
+        assert_eq!(parse(&["1", "2"]).err().unwrap(), "Unexpected argument \"2\"");
+    }
+}
\ No newline at end of file
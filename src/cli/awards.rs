@@ -0,0 +1,87 @@
+use crate::cli::args::Args;
+use bbgm::common::types::AwardPlayer;
+use bbgm::common::types::AwardTeam;
+use bbgm::worker::db::LeagueDb;
+
+fn format_winner(winner: &AwardPlayer, stats: &[&str]) -> String {
+    let mut line = format!("{} ({}, {})", winner.name, winner.abbrev, winner.pos);
+    for stat in stats.iter() {
+        let value = match *stat {
+            "pts" => winner.pts,
+            "trb" => winner.trb,
+            "ast" => winner.ast,
+            "stl" => winner.stl,
+            _ => winner.blk,
+        };
+        line.push_str(&format!(" {:.1} {}", value, stat));
+    }
+    if let Some(per) = winner.per {
+        line.push_str(&format!(" {:.1} PER", per));
+    }
+    line
+}
+
+fn print_teams(name: &str, award_teams: &[AwardTeam], stats: &[&str]) {
+    for team in award_teams.iter() {
+        println!();
+        println!("{} {}", team.title, name);
+        for winner in team.players.iter() {
+            println!("  {}", format_winner(winner, stats));
+        }
+    }
+}
+
+// One player's awards, oldest first
+fn player_awards(db: &mut LeagueDb, pid: i32) -> Result<(), String> {
+    let p = db.transaction(|tx| tx.get_player(pid))?;
+    if p.is_none() {
+        return Err(format!("No player with pid {}", pid));
+    }
+    let p = p.unwrap();
+    if p.awards.is_empty() {
+        println!("{} hasn't won any awards", p.name());
+        return Ok(());
+    }
+    println!("{}", p.name());
+    for award in p.awards.iter() {
+        println!("{} {}", award.season, award.award_type);
+    }
+    Ok(())
+}
+
+pub fn awards(db: &mut LeagueDb, args: &Args) -> Result<(), String> {
+    let pid: Option<i32> = args.get_int("player")?;
+    if let Some(pid) = pid {
+        return player_awards(db, pid);
+    }
+
+    let (g, all_awards) = db.transaction(|tx| Ok((tx.get_g()?, tx.get_all_awards()?)))?;
+    // The latest awards by default, since the current season's come after its playoffs
+    let season = args.get_int("season")?.or(all_awards.last().map(|awards| awards.season)).unwrap_or(g.season());
+    let awards = all_awards.iter().find(|awards| awards.season == season);
+    if awards.is_none() {
+        println!("No awards for {}, they're given out once the playoffs are over, unless there were --fast games", season);
+        return Ok(());
+    }
+    let awards = awards.unwrap();
+
+    println!("{} awards", season);
+    let singles = [
+        ("Most Valuable Player", &awards.mvp, &["pts", "trb", "ast"][..]),
+        ("Finals MVP", &awards.finals_mvp, &["pts", "trb", "ast"][..]),
+        ("Defensive Player of the Year", &awards.dpoy, &["trb", "stl", "blk"][..]),
+        ("Rookie of the Year", &awards.roy, &["pts", "trb", "ast"][..]),
+        ("Sixth Man of the Year", &awards.smoy, &["pts", "trb", "ast"][..]),
+        ("Most Improved Player", &awards.mip, &["pts", "trb", "ast"][..]),
+    ];
+    for (name, winner, stats) in singles.iter() {
+        let text = match winner {
+            Some(winner) => format_winner(winner, stats),
+            None => "-".to_string(),
+        };
+        println!("{:<29} {}", name, text);
+    }
+    print_teams("All-League", &awards.all_league, &["pts", "trb", "ast"]);
+    print_teams("All-Defensive", &awards.all_defensive, &["trb", "stl", "blk"]);
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,77 @@
+use crate::cli::args::Args;
+use crate::cli::team_name;
+use bbgm::common::types::BoxScore;
+use bbgm::common::types::GameResult;
+use bbgm::common::types::Team;
+use bbgm::worker::db::LeagueDb;
+
+pub fn print_score(game: &GameResult, teams: &[Team]) {
+    let overtimes = match game.overtimes {
+        0 => "".to_string(),
+        1 => " (OT)".to_string(),
+        n => format!(" ({}OT)", n),
+    };
+    println!(
+        "{} {}, {} {}{}",
+        team_name(teams, game.teams[1].tid),
+        game.teams[1].pts,
+        team_name(teams, game.teams[0].tid),
+        game.teams[0].pts,
+        overtimes
+    );
+}
+
+pub fn print_box_score(box_score: &BoxScore, teams: &[Team]) {
+    // Away team first, like a box score in the paper
+    for t in box_score.teams.iter().rev() {
+        let pts_qtrs: Vec<String> = t.pts_qtrs.iter().map(|pts| pts.to_string()).collect();
+        println!();
+        println!("{} {} ({})", team_name(teams, t.tid), t.pts, pts_qtrs.join(" "));
+        println!("{:<24} {:>4} {:>6} {:>6} {:>6} {:>4} {:>4} {:>4} {:>4} {:>4} {:>4} {:>4} {:>4}", "", "MIN", "FG", "3PT", "FT", "ORB", "TRB", "AST", "STL", "BLK", "TOV", "PF", "PTS");
+        for p in t.players.iter() {
+            let name = if p.gs {
+                format!("{} {}", p.name, p.pos)
+            } else {
+                p.name.clone()
+            };
+            println!(
+                "{:<24} {:>4.0} {:>6} {:>6} {:>6} {:>4} {:>4} {:>4} {:>4} {:>4} {:>4} {:>4} {:>4}",
+                name,
+                p.min,
+                format!("{}-{}", p.fg, p.fga),
+                format!("{}-{}", p.tp, p.tpa),
+                format!("{}-{}", p.ft, p.fta),
+                p.orb,
+                p.orb + p.drb,
+                p.ast,
+                p.stl,
+                p.blk,
+                p.tov,
+                p.pf,
+                p.pts
+            );
+        }
+    }
+}
+
+pub fn box_score(db: &mut LeagueDb, args: &Args) -> Result<(), String> {
+    let gid = args.get_positional(0, "gid")?;
+    let gid: i32 = gid.parse().map_err(|_| format!("Invalid gid \"{}\"", gid))?;
+
+    let (game, box_score, teams) = db.transaction(|tx| {
+        Ok((tx.get_game(gid)?, tx.get_box_score(gid)?, tx.get_teams()?))
+    })?;
+    if game.is_none() {
+        return Err(format!("No game with gid {}", gid));
+    }
+    let game = game.unwrap();
+
+    println!("Game {}, {}{}", game.gid, game.season, if game.playoffs { " playoffs" } else { "" });
+    print_score(&game, &teams);
+    if box_score.is_none() {
+        println!("No box score was saved for this game");
+        return Ok(());
+    }
+    print_box_score(box_score.as_ref().unwrap(), &teams);
+    Ok(())
+}
\ No newline at end of file
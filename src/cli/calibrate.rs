@@ -0,0 +1,30 @@
+use crate::cli::args::Args;
+use bbgm::worker::core::game::fast_sim::get_fast_team_ratings;
+use bbgm::worker::core::game::fast_sim::FastModel;
+use bbgm::worker::db::LeagueDb;
+use std::collections::HashMap;
+
+// Fits the fast model to a season's GameSim games and saves it in the league, replacing the model from any earlier
+// calibration. Games played with --fast are left out, since they'd only fit the model to itself. Players only have
+// their current team, so the fit is best for the current season, before trades.
+pub fn calibrate(db: &mut LeagueDb, args: &Args) -> Result<(), String> {
+    let model = db.transaction(|tx| {
+        let g = tx.get_g()?;
+        let season = args.get_int("season")?.unwrap_or(g.season());
+        let tids: Vec<i32> = tx.get_teams()?.iter().map(|t| t.tid).collect();
+        let ratings: HashMap<(i32, i32), _> = get_fast_team_ratings(&tids, &tx.get_players()?, season, g.num_players_on_court() as usize)?
+            .into_iter()
+            .map(|(tid, rating)| ((season, tid), rating))
+            .collect();
+        let model = FastModel::calibrate(&tx.get_game_sim_games(season)?, &ratings, &g)?;
+        tx.put_fast_model(&model)?;
+        Ok(model)
+    })?;
+
+    println!("Calibrated on {} GameSim games", model.num_games);
+    println!("Margin per rating point {:.1}", model.margin_per_rating);
+    println!("Home court {:+.2}", model.home_court);
+    println!("Margin standard deviation {:.2}", model.margin_sd);
+    println!("Points per team {:.1}", model.pts);
+    Ok(())
+}
\ No newline at end of file
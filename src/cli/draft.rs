@@ -0,0 +1,109 @@
+use crate::cli::args::Args;
+use crate::cli::find_tid;
+use crate::cli::team_name;
+use bbgm::common::constants::PHASE_DRAFT;
+use bbgm::common::constants::PHASE_DRAFT_LOTTERY;
+use bbgm::common::types::DraftPick;
+use bbgm::common::types::Player;
+use bbgm::common::types::Team;
+use bbgm::worker::core::draft::gen_order::gen_order;
+use bbgm::worker::core::draft::run_picks::get_prospects;
+use bbgm::worker::core::draft::run_picks::run_picks;
+use bbgm::worker::core::draft::select_player::select_player;
+use bbgm::worker::core::phase::get_phase::check_phase;
+use bbgm::worker::core::phase::new_phase::new_phase;
+use bbgm::worker::db::LeagueDb;
+
+// Prospects shown along with the draft order
+const NUM_PROSPECTS_SHOWN: usize = 20;
+
+fn pick_name(dp: &DraftPick, teams: &[Team]) -> String {
+    let mut name = format!("{}-{:<2} {}", dp.round, dp.pick, team_name(teams, dp.tid));
+    if dp.tid != dp.original_tid {
+        name.push_str(&format!(" (from {})", team_name(teams, dp.original_tid)));
+    }
+    name
+}
+
+fn player_line(p: &Player, season: i32) -> String {
+    let ratings = p.ratings.last().unwrap();
+    format!(
+        "{:<24} {:<2} {:>3} {:>3} {:>3}",
+        p.name(),
+        ratings.pos,
+        p.age(season),
+        ratings.ovr.round(),
+        ratings.pot.round()
+    )
+}
+
+fn print_picks(picks: &[(DraftPick, Player)], teams: &[Team], season: i32) {
+    for (dp, p) in picks.iter() {
+        println!("{:<40} {}", pick_name(dp, teams), player_line(p, season));
+    }
+}
+
+pub fn draft(db: &mut LeagueDb, args: &Args) -> Result<(), String> {
+    let num_actions = [args.has("pick"), args.has("until"), args.has("auto")].iter().filter(|has| **has).count();
+    if num_actions > 1 {
+        return Err("draft takes only one of --pick PID, --until TEAM and --auto".to_string());
+    }
+
+    let (g, teams) = db.transaction(|tx| Ok((tx.get_g()?, tx.get_teams()?)))?;
+    let season = g.season();
+    let phase = db.transaction(|tx| check_phase(tx, &[PHASE_DRAFT_LOTTERY, PHASE_DRAFT], "open the draft"))?;
+
+    // The lottery runs the first time the draft is opened, which starts the draft phase
+    if phase == PHASE_DRAFT_LOTTERY {
+        let lottery = db.transaction(|tx| {
+            let lottery = gen_order(tx)?;
+            new_phase(tx, PHASE_DRAFT)?;
+            Ok(lottery)
+        })?;
+        println!("{} draft lottery ({})", season, g.draft_type());
+        println!("{:>2}  {:<32} Chance of #1", "#", "Team");
+        for t in lottery.iter() {
+            let chance = t.chance.map(|chance| format!("{:>5.1}%", chance * 100.0)).unwrap_or_default();
+            println!("{:>2}. {:<32} {}", t.pick, team_name(&teams, t.original_tid), chance);
+        }
+        println!();
+    }
+
+    if args.has("pick") {
+        let pid: i32 = args.get_int("pick")?.unwrap();
+        let pick = db.transaction(|tx| {
+            let dp = tx.get_draft_picks(season)?.into_iter().next().unwrap();
+            select_player(tx, &dp, pid)?;
+            Ok((dp, tx.get_player(pid)?.unwrap()))
+        })?;
+        print_picks(&[pick], &teams, season);
+    } else if args.has("until") {
+        let tid = find_tid(&teams, args.get_required("until")?)?;
+        let picks = db.transaction(|tx| run_picks(tx, season, Some(tid)))?;
+        print_picks(&picks, &teams, season);
+    } else if args.has("auto") {
+        let picks = db.transaction(|tx| run_picks(tx, season, None))?;
+        print_picks(&picks, &teams, season);
+    }
+
+    let (draft_picks, prospects) = db.transaction(|tx| Ok((tx.get_draft_picks(season)?, get_prospects(tx, season)?)))?;
+    if draft_picks.is_empty() {
+        println!("The {} draft is over, undrafted players are now free agents and teams can re-sign players", season);
+        return Ok(());
+    }
+    if num_actions > 0 {
+        println!();
+    }
+    println!("On the clock: {}", pick_name(&draft_picks[0], &teams));
+    println!();
+    println!("Remaining picks");
+    for dp in draft_picks.iter() {
+        println!("  {}", pick_name(dp, &teams));
+    }
+    println!();
+    println!("{:>5} {:<24} {:<2} {:>3} {:>3} {:>3}", "PID", "Best available", "", "Age", "Ovr", "Pot");
+    for p in prospects.iter().take(NUM_PROSPECTS_SHOWN) {
+        println!("{:>5} {}", p.pid, player_line(p, season));
+    }
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,51 @@
+use crate::cli::args::Args;
+use bbgm::common::constants::PHASE_DRAFT_LOTTERY;
+use bbgm::common::constants::PHASE_EXPANSION_DRAFT;
+use bbgm::worker::core::expansion_draft::add_team::add_team;
+use bbgm::worker::core::expansion_draft::run_picks::get_available_players;
+use bbgm::worker::core::expansion_draft::run_picks::get_expansion_teams;
+use bbgm::worker::core::expansion_draft::run_picks::NUM_PROTECTED_PLAYERS;
+use bbgm::worker::core::phase::get_phase::get_phase;
+use bbgm::worker::core::phase::new_phase::new_phase;
+use bbgm::worker::db::LeagueDb;
+
+// Population in millions of an expansion team without --pop
+const DEFAULT_POP: f64 = 2.0;
+
+pub fn expansion(db: &mut LeagueDb, args: &Args) -> Result<(), String> {
+    if args.has("team") {
+        let abbrev = args.get_required("team")?;
+        let region = args.get_required("region")?;
+        let name = args.get_required("name")?;
+        let pop = match args.get("pop") {
+            Some(pop) => pop.parse::<f64>().map_err(|_| format!("--pop must be a number, got \"{}\"", pop))?,
+            None => DEFAULT_POP,
+        };
+        let t = db.transaction(|tx| {
+            let t = add_team(tx, abbrev, region, name, pop)?;
+            if get_phase(tx)? == PHASE_DRAFT_LOTTERY {
+                new_phase(tx, PHASE_EXPANSION_DRAFT)?;
+            }
+            Ok(t)
+        })?;
+        println!("Added the {} {} ({})", t.region, t.name, t.abbrev);
+        println!();
+    }
+
+    if db.transaction(get_phase)? != PHASE_EXPANSION_DRAFT {
+        println!("No expansion draft, add a team with --team during the draft lottery to start one");
+        return Ok(());
+    }
+    let (expansion_teams, available) = db.transaction(|tx| {
+        let expansion_teams = get_expansion_teams(tx)?;
+        let tids: Vec<i32> = expansion_teams.iter().map(|t| t.tid).collect();
+        Ok((expansion_teams, get_available_players(tx, &tids)?))
+    })?;
+    println!("Expansion teams");
+    for t in expansion_teams.iter() {
+        println!("  {} {} ({})", t.region, t.name, t.abbrev);
+    }
+    println!();
+    println!("{} players available, every other team protects its best {}", available.len(), NUM_PROTECTED_PLAYERS);
+    Ok(())
+}
\ No newline at end of file
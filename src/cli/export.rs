@@ -0,0 +1,22 @@
+use crate::cli::args::Args;
+use bbgm::worker::core::league::export_league::export_league;
+use bbgm::worker::db::LeagueDb;
+use std::fs;
+
+// BBGM JSON league file, to --out or stdout
+pub fn export(db: &mut LeagueDb, args: &Args) -> Result<(), String> {
+    let league = db.transaction(|tx| tx.get_league())?;
+    let (json, warnings) = export_league(&league);
+    for warning in warnings.iter() {
+        eprintln!("Warning: {}", warning);
+    }
+
+    match args.get("out") {
+        Some(path) => {
+            fs::write(path, json).map_err(|err| format!("Can't write {}: {}", path, err))?;
+            eprintln!("Exported to {}", path);
+        }
+        None => println!("{}", json),
+    }
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,150 @@
+use crate::cli::args::Args;
+use crate::cli::find_tid;
+use crate::cli::team_name;
+use bbgm::common::constants::PHASE_FREE_AGENCY;
+use bbgm::common::constants::PHASE_RESIGN_PLAYERS;
+use bbgm::common::constants::PLAYER_FREE_AGENT;
+use bbgm::common::helpers::format_currency;
+use bbgm::common::types::Player;
+use bbgm::worker::core::free_agents::negotiate::asking_contract;
+use bbgm::worker::core::free_agents::negotiate::get_first_contract_season;
+use bbgm::worker::core::free_agents::negotiate::negotiate;
+use bbgm::worker::core::free_agents::play::play;
+use bbgm::worker::core::player::mood_info::is_re_signing;
+use bbgm::worker::core::player::mood_info::mood_info;
+use bbgm::worker::core::phase::get_phase::get_phase;
+use bbgm::worker::core::phase::new_phase::new_phase;
+use bbgm::worker::core::player::mood_info::MoodContext;
+use bbgm::worker::db::LeagueDb;
+use bbgm::worker::util::g::G;
+
+// Free agents shown in the list, best first
+const NUM_FREE_AGENTS_SHOWN: usize = 30;
+
+fn print_players(g: &G, players: &[Player], tid: Option<i32>, ctx: &MoodContext, first_season: i32) {
+    let mut header = format!("{:>5} {:<24} {:<2} {:>3} {:>3} {:>3} {:>8} {:>5}", "PID", "Player", "", "Age", "Ovr", "Pot", "Asking", "Exp");
+    if tid.is_some() {
+        header.push_str(&format!(" {:>5} {:>5} {:>5} {:>5} {:>5}", "Mkt", "Perf", "PT", "Rook", "Mood"));
+    }
+    println!("{}", header);
+    for p in players.iter() {
+        let ratings = p.ratings.last().unwrap();
+        let contract = match tid {
+            Some(tid) => asking_contract(p, tid, ctx, g, first_season),
+            None => p.contract.clone(),
+        };
+        let mut line = format!(
+            "{:>5} {:<24} {:<2} {:>3} {:>3} {:>3} {:>8} {:>5}",
+            p.pid,
+            p.name(),
+            ratings.pos,
+            p.age(g.season()),
+            ratings.ovr.round(),
+            ratings.pot.round(),
+            format_currency(contract.amount),
+            contract.exp
+        );
+        if let Some(tid) = tid {
+            let mood = mood_info(p, tid, ctx, g);
+            let components = &mood.components;
+            line.push_str(&format!(
+                " {:>+5.1} {:>+5.1} {:>+5.1} {:>+5.1} {:>+5.1}{}",
+                components.market_size,
+                components.team_performance,
+                components.playing_time,
+                components.rookie_contract,
+                mood.sum_components,
+                if mood.willing { "" } else { "  unwilling" }
+            ));
+        }
+        println!("{}", line);
+    }
+}
+
+// The free agents, with how they feel about --team and what they ask from it, plus that team's expiring contracts
+// while it can re-sign them
+fn list(db: &mut LeagueDb, args: &Args) -> Result<(), String> {
+    let (g, teams) = db.transaction(|tx| Ok((tx.get_g()?, tx.get_teams()?)))?;
+    let tid = match args.get("team") {
+        Some(team) => Some(find_tid(&teams, team)?),
+        None => None,
+    };
+    let (phase, days_left, first_season, ctx, mut free_agents) = db.transaction(|tx| {
+        Ok((get_phase(tx)?, tx.get_free_agency_days_left()?, get_first_contract_season(tx)?, MoodContext::load(tx)?, tx.get_players_by_tid(PLAYER_FREE_AGENT)?))
+    })?;
+
+    if phase == PHASE_FREE_AGENCY {
+        println!("Free agency after {}: {} days left", g.season(), days_left.unwrap_or(0));
+    } else {
+        println!("Free agents in {}", g.season());
+    }
+    if let Some(tid) = tid {
+        println!("Mood toward {}", team_name(&teams, tid));
+    }
+    println!();
+
+    free_agents.sort_by(|a, b| b.value.total_cmp(&a.value));
+    free_agents.truncate(NUM_FREE_AGENTS_SHOWN);
+    if free_agents.is_empty() {
+        println!("No free agents");
+    } else {
+        print_players(&g, &free_agents, tid, &ctx, first_season);
+    }
+
+    if let Some(tid) = tid.filter(|_| phase == PHASE_RESIGN_PLAYERS) {
+        let mut expiring: Vec<Player> = ctx.roster(tid).iter().filter(|p| is_re_signing(p, tid, &g)).cloned().collect();
+        if !expiring.is_empty() {
+            expiring.sort_by(|a, b| b.value.total_cmp(&a.value));
+            println!();
+            println!("Expiring contracts");
+            print_players(&g, &expiring, Some(tid), &ctx, first_season);
+        }
+    }
+    Ok(())
+}
+
+pub fn fa(db: &mut LeagueDb, args: &Args) -> Result<(), String> {
+    let num_actions = [args.has("start"), args.has("days"), args.has("sign")].iter().filter(|has| **has).count();
+    if num_actions > 1 {
+        return Err("fa takes only one of --start, --days N and --sign PID".to_string());
+    }
+
+    if args.has("start") {
+        let events = db.transaction(|tx| new_phase(tx, PHASE_FREE_AGENCY))?;
+        for event in events.iter() {
+            println!("{}", event.text);
+        }
+        let num_free_agents = db.transaction(|tx| tx.get_players_by_tid(PLAYER_FREE_AGENT))?.len();
+        println!("{} players re-signed, {} free agents", events.len(), num_free_agents);
+        return Ok(());
+    }
+
+    if args.has("days") {
+        let num_days: i32 = args.get_int("days")?.unwrap();
+        if num_days < 1 {
+            return Err("--days must be at least 1".to_string());
+        }
+        let (events, days_left) = db.transaction(|tx| Ok((play(tx, num_days)?, tx.get_free_agency_days_left()?.unwrap_or(0))))?;
+        for event in events.iter() {
+            println!("{}", event.text);
+        }
+        println!("{} signings, {} days of free agency left", events.len(), days_left);
+        return Ok(());
+    }
+
+    if args.has("sign") {
+        let teams = db.transaction(|tx| tx.get_teams())?;
+        let tid = find_tid(&teams, args.get_required("team")?)?;
+        let pid: i32 = args.get_int("sign")?.unwrap();
+        let amount: Option<i32> = args.get_int("amount")?;
+        let exp: Option<i32> = args.get_int("exp")?;
+        let event = db.transaction(|tx| negotiate(tx, pid, tid, amount.map(|amount| amount as f64), exp))?;
+        println!("{}", event.text);
+        return Ok(());
+    }
+
+    if args.has("amount") || args.has("exp") {
+        return Err("--amount and --exp go with --sign".to_string());
+    }
+    list(db, args)
+}
\ No newline at end of file
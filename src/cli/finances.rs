@@ -0,0 +1,103 @@
+use crate::cli::args::Args;
+use crate::cli::find_tid;
+use crate::cli::team_name;
+use bbgm::common::helpers::format_currency;
+use bbgm::common::types::Player;
+use bbgm::worker::core::finances::get_luxury_tax::get_luxury_tax;
+use bbgm::worker::core::finances::get_luxury_tax::get_min_payroll_penalty;
+use bbgm::worker::core::team::get_payroll::sum_contracts;
+use bbgm::worker::db::LeagueDb;
+use bbgm::worker::util::g::G;
+
+fn contract_notes(p: &Player) -> String {
+    let mut notes = Vec::new();
+    if p.contract.rookie {
+        notes.push("rookie".to_string());
+    }
+    if !p.contract.option.is_empty() {
+        notes.push(format!("{} option", p.contract.option));
+    }
+    notes.join(", ")
+}
+
+fn print_roster(g: &G, name: &str, mut players: Vec<Player>) {
+    players.sort_by(|a, b| b.contract.amount.total_cmp(&a.contract.amount));
+    println!("{}", name);
+    println!("{:<24} {:<2} {:>3} {:>3} {:>8} {:>5}", "Player", "", "Age", "Ovr", "Amount", "Exp");
+    for p in players.iter() {
+        let ratings = p.ratings.last().unwrap();
+        let line = format!(
+            "{:<24} {:<2} {:>3} {:>3} {:>8} {:>5}  {}",
+            p.name(),
+            ratings.pos,
+            p.age(g.season()),
+            ratings.ovr.round(),
+            format_currency(p.contract.amount),
+            p.contract.exp,
+            contract_notes(p)
+        );
+        println!("{}", line.trim_end());
+    }
+    let payroll = sum_contracts(&players);
+    println!();
+    println!("Payroll {}, cap space {}", format_currency(payroll), format_currency(g.salary_cap() - payroll));
+}
+
+// Payrolls and what they cost against the cap, the luxury tax and the minimum payroll. The current season is
+// projected from today's rosters until its payrolls are assessed after the playoffs.
+pub fn finances(db: &mut LeagueDb, args: &Args) -> Result<(), String> {
+    let (mut g, teams) = db.transaction(|tx| Ok((tx.get_g()?, tx.get_teams()?)))?;
+    if args.has("team") {
+        if args.has("season") {
+            return Err("--team shows the current contracts, it can't be used with --season".to_string());
+        }
+        let tid = find_tid(&teams, args.get_required("team")?)?;
+        let players = db.transaction(|tx| tx.get_players_by_tid(tid))?;
+        print_roster(&g, &team_name(&teams, tid), players);
+        return Ok(());
+    }
+
+    let season = args.get_int("season")?.unwrap_or(g.season());
+    g.set_season(season);
+    let team_seasons = db.transaction(|tx| tx.get_team_seasons(season))?;
+    if team_seasons.is_empty() {
+        return Err(format!("No finances for {}", season));
+    }
+    let assessed = team_seasons.iter().all(|ts| ts.payroll_end_of_season.is_some());
+    if !assessed && season != db.transaction(|tx| tx.get_g())?.season() {
+        return Err(format!("The {} payrolls were never assessed", season));
+    }
+
+    println!(
+        "{} {} cap {}, min payroll {}, luxury tax {} x amount over {}",
+        season,
+        g.salary_cap_type(),
+        format_currency(g.salary_cap()),
+        format_currency(g.min_payroll()),
+        g.luxury_tax(),
+        format_currency(g.luxury_payroll())
+    );
+    if !assessed {
+        println!("Projected from current rosters, payrolls are assessed after the playoffs");
+    }
+    println!();
+    println!("{:<32} {:>8} {:>9} {:>8} {:>8} {:>8}", "Team", "Payroll", "Cap space", "Lux tax", "Min pen", "Tax shr");
+    for ts in team_seasons.iter() {
+        let (payroll, luxury_tax, min_payroll_penalty, luxury_tax_share) = if assessed {
+            (ts.payroll_end_of_season.unwrap(), ts.luxury_tax, ts.min_payroll_penalty, ts.luxury_tax_share)
+        } else {
+            let payroll = sum_contracts(&db.transaction(|tx| tx.get_players_by_tid(ts.tid))?);
+            (payroll, get_luxury_tax(&g, payroll), get_min_payroll_penalty(&g, payroll), 0.0)
+        };
+        println!(
+            "{:<32} {:>8} {:>9} {:>8} {:>8} {:>8}",
+            team_name(&teams, ts.tid),
+            format_currency(payroll),
+            format_currency(g.salary_cap() - payroll),
+            format_currency(luxury_tax),
+            format_currency(min_payroll_penalty),
+            format_currency(luxury_tax_share)
+        );
+    }
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,197 @@
+use crate::cli::args::Args;
+use crate::cli::box_score::print_box_score;
+use crate::cli::box_score::print_score;
+use crate::cli::find_tid;
+use crate::cli::get_sim_game;
+use crate::cli::team_name;
+use bbgm::common::types::Team;
+use bbgm::worker::core::game::fast_sim::load_fast_sim;
+use bbgm::worker::core::game::full_sim::load_full_sim;
+use bbgm::worker::core::game::play::GameToSim;
+use bbgm::worker::core::game::play::SimGameResult;
+use bbgm::worker::core::GameSim_basketball::index::GameSim;
+use bbgm::worker::core::GameSim_basketball::index::PossessionResult;
+use bbgm::worker::core::GameSim_basketball::index::StepResult;
+use bbgm::worker::core::GameSim_basketball::index::TeamNum;
+use bbgm::worker::db::LeagueDb;
+use bbgm::worker::util::g::G;
+use bbgm::worker::util::random::random_seed;
+use bbgm::worker::util::random::rng_from_seed;
+use rand::rngs::StdRng;
+use std::io::BufRead;
+use std::io::Write;
+
+fn print_possession(result: &PossessionResult, teams: &[Team], tids: &[i32; 2]) {
+    let minutes = result.t.max(0.0).floor();
+    let seconds = ((result.t.max(0.0) - minutes) * 60.0).floor();
+    println!(
+        "Q{} {:>2}:{:02} {:>3}-{:<3} {:>5.1}% {:<28} {}",
+        result.period,
+        minutes,
+        seconds,
+        result.score[1],
+        result.score[0],
+        100.0 * result.win_probability,
+        team_name(teams, tids[result.o]),
+        result.outcome
+    );
+}
+
+fn print_coached_team(game_sim: &GameSim, t: TeamNum) {
+    println!();
+    println!("{:>5} {:<24} {:<3} {:>6} {:>5} {:>4} {:>3}", "PID", "Name", "Pos", "Energy", "Min", "Pts", "PF");
+    for p in game_sim.get_player_statuses(t) {
+        let status = if p.injured { " injured" } else if p.on_court { " *" } else { "" };
+        println!(
+            "{:>5} {:<24} {:<3} {:>6.2} {:>5.1} {:>4} {:>3}{}",
+            p.pid,
+            p.name,
+            p.pos,
+            p.energy,
+            p.min,
+            p.pts,
+            p.pf,
+            status
+        );
+    }
+    println!("* on the court, {} timeouts left", game_sim.get_timeouts_remaining(t));
+}
+
+// Reads commands for team t until it's told to play. Returns how many possessions to play before the next pause, None
+// for the rest of the period, or Err at the end of the input.
+fn read_commands(game_sim: &mut GameSim, g: &G, t: TeamNum, lines: &mut impl Iterator<Item = std::io::Result<String>>) -> Result<Option<i32>, ()> {
+    print_coached_team(game_sim, t);
+    loop {
+        print!("> ");
+        std::io::stdout().flush().ok();
+        let line = lines.next();
+        if line.is_none() {
+            println!();
+            return Err(());
+        }
+        let line = line.unwrap().map_err(|_| ())?;
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let result = match words.as_slice() {
+            ["play"] => return Ok(None),
+            ["play", n] => match n.parse::<i32>() {
+                Ok(n) if n > 0 => return Ok(Some(n)),
+                _ => Err(format!("Invalid number of possessions \"{}\"", n)),
+            },
+            ["sub", pid_on, pid_off] => match (pid_on.parse::<i32>(), pid_off.parse::<i32>()) {
+                (Ok(pid_on), Ok(pid_off)) => game_sim.substitute(g, t, pid_on, pid_off),
+                _ => Err("Usage: sub PID_ON PID_OFF".to_string()),
+            },
+            ["timeout"] => game_sim.call_timeout(g, t).map(|_| println!("Timeout, {} left", game_sim.get_timeouts_remaining(t))),
+            ["team"] => {
+                print_coached_team(game_sim, t);
+                Ok(())
+            },
+            [] => Ok(()),
+            _ => Err("Commands: play [N], sub PID_ON PID_OFF, timeout, team".to_string()),
+        };
+        if let Err(error) = result {
+            println!("{}", error);
+        }
+    }
+}
+
+// Plays the game with GameSim, pausing at the end of every period (or after play N possessions) for the coach of
+// --coach to make substitutions and call timeouts. Their team has no automatic substitutions, except for players who
+// foul out or get injured. At the end of the input, the rest of the game is played without pauses or the coach.
+fn coach_game(db: &mut LeagueDb, g: &G, game: &GameToSim, coach_tid: i32, teams: &[Team], rng: &mut StdRng) -> Result<SimGameResult, String> {
+    let full_sim = db.transaction(load_full_sim)?;
+    let mut game_sim = full_sim.new_game_sim(g, game, rng)?;
+    let tids = [game.home_tid, game.away_tid];
+    let t = if coach_tid == game.home_tid { 0 } else { 1 };
+    game_sim.set_auto_subs(t, false);
+    game_sim.pause();
+
+    let stdin = std::io::stdin();
+    let mut lines = stdin.lock().lines();
+    let mut coaching = true;
+    // Possessions left before the next pause, None to pause at the end of the period
+    let mut possessions_left = None;
+    loop {
+        match game_sim.step(g) {
+            StepResult::Possession(result) => {
+                print_possession(&result, teams, &tids);
+                if possessions_left.is_some() {
+                    possessions_left = Some(possessions_left.unwrap() - 1);
+                }
+                if coaching && (result.t <= 0.0 || possessions_left == Some(0)) {
+                    game_sim.pause();
+                }
+            },
+            StepResult::Paused => {
+                match read_commands(&mut game_sim, g, t, &mut lines) {
+                    Ok(n) => possessions_left = n,
+                    Err(()) => {
+                        coaching = false;
+                        game_sim.set_auto_subs(t, true);
+                    },
+                }
+                game_sim.resume();
+            },
+            StepResult::Over => break,
+        }
+    }
+    Ok(game_sim.get_result())
+}
+
+// Plays a single exhibition game. Nothing is saved, and the same seed gives the same game.
+pub fn game(db: &mut LeagueDb, args: &Args) -> Result<(), String> {
+    let seed = args.get_int("seed")?.unwrap_or_else(random_seed);
+
+    let (g, teams, gid) = db.transaction(|tx| Ok((tx.get_g()?, tx.get_teams()?, tx.get_next_gid()?)))?;
+    let home_tid = find_tid(&teams, args.get_required("home")?)?;
+    let away_tid = find_tid(&teams, args.get_required("away")?)?;
+    if home_tid == away_tid {
+        return Err("--home and --away must be different teams".to_string());
+    }
+    let coach_tid = match args.get("coach") {
+        Some(team) => Some(find_tid(&teams, team)?),
+        None => None,
+    };
+    if coach_tid.is_some() && coach_tid != Some(home_tid) && coach_tid != Some(away_tid) {
+        return Err("--coach must be --home or --away".to_string());
+    }
+    if coach_tid.is_some() && args.has("fast") {
+        return Err("--coach can't be used with --fast".to_string());
+    }
+
+    // With the fast model, the odds are known before the game is played
+    if args.has("fast") {
+        let fast_sim = db.transaction(load_fast_sim)?;
+        let home = &fast_sim.ratings[&home_tid];
+        let away = &fast_sim.ratings[&away_tid];
+        println!(
+            "{} win probability {:.1}%, expected margin {:+.1}",
+            team_name(&teams, home_tid),
+            100.0 * fast_sim.model.win_probability(home, away),
+            fast_sim.model.expected_margin(home, away)
+        );
+    }
+
+    let game = GameToSim {
+        gid,
+        season: g.season(),
+        day: 0,
+        home_tid,
+        away_tid,
+        playoffs: false
+    };
+    let mut rng = rng_from_seed(seed);
+    let result = if let Some(coach_tid) = coach_tid {
+        coach_game(db, &g, &game, coach_tid, &teams, &mut rng)?
+    } else {
+        let sim_game = get_sim_game(db, args)?;
+        sim_game(&g, &game, &mut rng)?
+    };
+
+    println!("Seed {}", seed);
+    print_score(&result.result, &teams);
+    if let Some(box_score) = &result.box_score {
+        print_box_score(box_score, &teams);
+    }
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,56 @@
+use crate::cli::team_name;
+use bbgm::common::types::AwardPlayer;
+use bbgm::worker::core::season::new_schedule_playoffs_day::get_champion;
+use bbgm::worker::db::LeagueDb;
+
+fn winner_name(winner: &Option<AwardPlayer>) -> String {
+    match winner {
+        Some(winner) => format!("{} ({})", winner.name, winner.abbrev),
+        None => "-".to_string(),
+    }
+}
+
+// Champions and the main awards of every season, oldest first
+pub fn history(db: &mut LeagueDb) -> Result<(), String> {
+    let (mut g, starting_season, teams, all_awards) = db.transaction(|tx| {
+        Ok((tx.get_g()?, tx.get_starting_season()?, tx.get_teams()?, tx.get_all_awards()?))
+    })?;
+    let first_season = all_awards.first().map(|awards| awards.season).unwrap_or(g.season()).min(starting_season.unwrap_or(g.season()));
+
+    let last_season = g.season();
+
+    let mut any = false;
+    for season in first_season..=last_season {
+        g.set_season(season);
+        let playoff_series = db.transaction(|tx| tx.get_playoff_series(season))?;
+        let champion = playoff_series.as_ref().and_then(|playoff_series| get_champion(playoff_series, g.num_games_playoff_series()));
+        let awards = all_awards.iter().find(|awards| awards.season == season);
+        if champion.is_none() && awards.is_none() {
+            continue;
+        }
+        any = true;
+
+        println!();
+        if let Some(champion) = champion {
+            let playoff_series = playoff_series.as_ref().unwrap();
+            let finals = &playoff_series.series[playoff_series.current_round][0];
+            let runner_up = if finals.home.tid == champion { finals.away.as_ref().map(|t| t.tid) } else { Some(finals.home.tid) };
+            let mut line = format!("{} Champion: {}", season, team_name(&teams, champion));
+            if let Some(runner_up) = runner_up {
+                line.push_str(&format!(", runner-up: {}", team_name(&teams, runner_up)));
+            }
+            println!("{}", line);
+        } else {
+            println!("{}", season);
+        }
+        if let Some(awards) = awards {
+            println!("  MVP: {}, Finals MVP: {}", winner_name(&awards.mvp), winner_name(&awards.finals_mvp));
+            println!("  DPOY: {}, ROY: {}", winner_name(&awards.dpoy), winner_name(&awards.roy));
+            println!("  6MOY: {}, MIP: {}", winner_name(&awards.smoy), winner_name(&awards.mip));
+        }
+    }
+    if !any {
+        println!("No completed seasons yet");
+    }
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,304 @@
+pub mod args;
+pub mod awards;
+pub mod box_score;
+pub mod calibrate;
+pub mod draft;
+pub mod expansion;
+pub mod export;
+pub mod fa;
+pub mod finances;
+pub mod game;
+pub mod history;
+pub mod new_league;
+pub mod odds;
+pub mod pbp;
+pub mod phase;
+pub mod settings;
+pub mod sim;
+pub mod standings;
+pub mod trade;
+pub mod transactions;
+
+use crate::cli::args::ArgSpec;
+use crate::cli::args::Args;
+use bbgm::common::types::Team;
+use bbgm::worker::core::game::play::GameToSim;
+use bbgm::worker::core::game::fast_sim::load_fast_sim;
+use bbgm::worker::core::game::full_sim::load_full_sim;
+use bbgm::worker::core::game::sim_games::SimGame;
+use bbgm::worker::db::LeagueDb;
+use bbgm::worker::util::g::G;
+use rand::rngs::StdRng;
+use std::path::Path;
+
+const DEFAULT_LEAGUE: &str = "league.db";
+
+// Help text is wrapped to this many columns
+const WIDTH: usize = 120;
+
+// A subcommand: its usage line, what it accepts and its help text
+struct Command {
+    name: &'static str,
+    usage: &'static str,
+    spec: ArgSpec,
+    help: &'static str
+}
+
+const COMMANDS: [Command; 19] = [
+    Command {
+        name: "new-league",
+        usage: "[--settings FILE | --import LEAGUE.json] [--teams N] [--season YEAR] [--seed S] [--force]",
+        spec: ArgSpec { options: &["settings", "import", "teams", "season", "seed"], flags: &["force"], positional: &[] },
+        help: "A new random league gets generated rosters of 13 players per team, a few free agents and a draft class. \
+            --import starts from a BBGM league file instead."
+    },
+    Command {
+        name: "sim",
+        usage: "--days N | --until playoffs|end [--threads N] [--fast]",
+        spec: ArgSpec { options: &["days", "until", "threads"], flags: &["fast"], positional: &[] },
+        help: "Plays days of games. --fast plays them with a quick model of the team ratings instead of the full GameSim. \
+            --fast games record no stats or box scores, and injuries only come from GameSim games."
+    },
+    Command {
+        name: "game",
+        usage: "--home TEAM --away TEAM [--seed S] [--fast | --coach TEAM]",
+        spec: ArgSpec { options: &["home", "away", "seed", "coach"], flags: &["fast"], positional: &[] },
+        help: "Plays an exhibition game. --coach pauses at the end of every period for commands from stdin: play [N] plays \
+            on (N possessions before pausing again), sub PID_ON PID_OFF, timeout and team. The coached team only gets \
+            automatic substitutions for fouled out or injured players, until the end of the input."
+    },
+    Command {
+        name: "standings",
+        usage: "[--season YEAR] [--by div|conf|league]",
+        spec: ArgSpec { options: &["season", "by"], flags: &[], positional: &[] },
+        help: ""
+    },
+    Command {
+        name: "odds",
+        usage: "[--runs N] [--seed S] [--threads N] [--seeds] [--fast]",
+        spec: ArgSpec { options: &["runs", "seed", "threads"], flags: &["seeds", "fast"], positional: &[] },
+        help: "Plays the rest of the season --runs times (1000 by default) and shows each team's chances of making the \
+            playoffs (PO), the play-in (PI) and winning each round, or of each seed with --seeds."
+    },
+    Command {
+        name: "calibrate",
+        usage: "[--season YEAR]",
+        spec: ArgSpec { options: &["season"], flags: &[], positional: &[] },
+        help: "Refits the --fast model to the GameSim games already played in a season (the current one by default) with \
+            the current rosters, leaving out --fast games."
+    },
+    Command {
+        name: "box-score",
+        usage: "<gid>",
+        spec: ArgSpec { options: &[], flags: &[], positional: &["gid"] },
+        help: ""
+    },
+    Command {
+        name: "pbp",
+        usage: "<gid>",
+        spec: ArgSpec { options: &[], flags: &[], positional: &["gid"] },
+        help: "Play-by-play is only kept for the current season's games."
+    },
+    Command {
+        name: "draft",
+        usage: "[--pick PID | --until TEAM | --auto]",
+        spec: ArgSpec { options: &["pick", "until"], flags: &["auto"], positional: &[] },
+        help: "Open once the playoffs are over, in the draft lottery phase. The first time, it sets the order and runs the \
+            lottery (see the draftType setting). --pick drafts a player for the team on the clock, --until lets the AI \
+            pick until TEAM is on the clock, and --auto lets it make every remaining pick."
+    },
+    Command {
+        name: "finances",
+        usage: "[--season YEAR] | --team TEAM",
+        spec: ArgSpec { options: &["season", "team"], flags: &[], positional: &[] },
+        help: "Shows each team's payroll against the salary cap, luxury tax and minimum payroll (see the salaryCap \
+            settings), or the contracts on one team with --team."
+    },
+    Command {
+        name: "trade",
+        usage: "--team TEAM --with TEAM [--give PIDS] [--get PIDS] [--give-picks DPIDS] [--get-picks DPIDS] [--dry-run] \
+            [--force]",
+        spec: ArgSpec {
+            options: &["team", "with", "give", "get", "give-picks", "get-picks"],
+            flags: &["dry-run", "force"],
+            positional: &[]
+        },
+        help: "Offers players and picks (comma separated pids and dpids) from --team to --with, which accepts if the deal \
+            makes it better off. Without any, it lists what both teams have. If the offer is rejected, or would be with \
+            --dry-run, it shows what --with would want added. --force makes a trade that follows the rules even if it's \
+            rejected."
+    },
+    Command {
+        name: "transactions",
+        usage: "[--season YEAR] [--team TEAM]",
+        spec: ArgSpec { options: &["season", "team"], flags: &[], positional: &[] },
+        help: ""
+    },
+    Command {
+        name: "fa",
+        usage: "[--team TEAM] | --start | --days N | --team TEAM --sign PID [--amount K] [--exp YEAR]",
+        spec: ArgSpec { options: &["team", "days", "sign", "amount", "exp"], flags: &["start"], positional: &[] },
+        help: "Lists the best free agents, with each one's mood toward --team (market size, team performance, playing \
+            time, rookie contract) and what they ask from it. --start opens free agency after re-signing players: AI \
+            teams re-sign some of their expiring contracts and everyone else becomes a free agent. --days plays days of \
+            AI signings (30 in all), with demands dropping each day. --sign offers PID a contract for --amount thousand \
+            dollars through --exp, which default to what they ask for. Free agents can be signed in the preseason, \
+            regular season and free agency, and a team's own expiring contracts in the re-sign players phase."
+    },
+    Command {
+        name: "phase",
+        usage: "[--next]",
+        spec: ArgSpec { options: &[], flags: &["next"], positional: &[] },
+        help: "Shows the season phase: preseason, regular season, after the trade deadline (see the tradeDeadline \
+            setting), playoffs, draft lottery, draft, re-sign players and free agency. --next moves on from the phases \
+            that don't end on their own, and free agency moves on to the next season's preseason, where players \
+            develop."
+    },
+    Command {
+        name: "awards",
+        usage: "[--season YEAR] | --player PID",
+        spec: ArgSpec { options: &["season", "player"], flags: &[], positional: &[] },
+        help: "Shows who won what in a season (the latest by default), or every award PID has won. They're given out when \
+            the playoffs end, from the regular season stats of players who played 70% of the games, except the Finals \
+            MVP, who is the champion's best player in the finals box scores. --fast games record no stats, so a season \
+            with any gets no awards."
+    },
+    Command {
+        name: "history",
+        usage: "",
+        spec: ArgSpec { options: &[], flags: &[], positional: &[] },
+        help: "Shows each season's champion and award winners."
+    },
+    Command {
+        name: "expansion",
+        usage: "[--team ABBREV --region REGION --name NAME [--pop MILLIONS]]",
+        spec: ArgSpec { options: &["team", "region", "name", "pop"], flags: &[], positional: &[] },
+        help: "Adds a team during the draft lottery and starts an expansion draft, where new teams take unprotected \
+            players until they have minRosterSize; phase --next runs it."
+    },
+    Command {
+        name: "export",
+        usage: "[--out FILE]",
+        spec: ArgSpec { options: &["out"], flags: &[], positional: &[] },
+        help: ""
+    },
+    Command {
+        name: "settings",
+        usage: "[--out FILE]",
+        spec: ArgSpec { options: &["out"], flags: &[], positional: &[] },
+        help: "Shows the league's settings as TOML, or saves them to a TOML or JSON file with --out, in the format \
+            new-league --settings reads."
+    },
+];
+
+const GENERAL_HELP: &str = "--league defaults to league.db. TEAM is an abbreviation or a tid. Games are reproducible from \
+    the league's seed, whatever the number of threads.";
+
+// Splits text into lines of at most width columns, breaking between words, with indent before every line but the first
+fn wrap(text: &str, width: usize, indent: &str) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        if !line.is_empty() && line.len() + 1 + word.len() > width {
+            lines.push(line);
+            line = indent.to_string();
+        } else if !line.is_empty() && line != indent {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+    lines.push(line);
+    lines.join("\n")
+}
+
+pub fn usage() -> String {
+    let mut text = "Usage: bbgm <command> [--league FILE] [options]\n\nCommands:\n".to_string();
+    for command in COMMANDS.iter() {
+        let line = format!("{} {}", command.name, command.usage);
+        for line in wrap(&line, WIDTH - 2, &" ".repeat(command.name.len() + 1)).lines() {
+            text.push_str(&format!("  {}\n", line));
+        }
+    }
+    text.push('\n');
+    text.push_str(&wrap(GENERAL_HELP, WIDTH, ""));
+    for command in COMMANDS.iter().filter(|command| !command.help.is_empty()) {
+        text.push_str("\n\n");
+        text.push_str(&wrap(&format!("{}: {}", command.name, command.help), WIDTH, ""));
+    }
+    text
+}
+
+pub fn team_name(teams: &[Team], tid: i32) -> String {
+    let t = teams.iter().find(|t| t.tid == tid);
+    if t.is_none() {
+        return format!("Team {}", tid);
+    }
+    let t = t.unwrap();
+    format!("{} {}", t.region, t.name)
+}
+
+pub fn find_tid(teams: &[Team], team: &str) -> Result<i32, String> {
+    let t = teams.iter().find(|t| t.abbrev.eq_ignore_ascii_case(team) || t.tid.to_string() == team);
+    if t.is_none() {
+        return Err(format!("Unknown team \"{}\"", team));
+    }
+    Ok(t.unwrap().tid)
+}
+
+// The fast model with --fast, otherwise the full GameSim. Either way, the rosters are the ones from when it's called.
+pub fn get_sim_game(db: &mut LeagueDb, args: &Args) -> Result<Box<SimGame<'static>>, String> {
+    if !args.has("fast") {
+        let full_sim = db.transaction(load_full_sim)?;
+        return Ok(Box::new(move |g: &G, game: &GameToSim, rng: &mut StdRng| full_sim.sim_game(g, game, rng)));
+    }
+    let fast_sim = db.transaction(load_fast_sim)?;
+    Ok(Box::new(move |g: &G, game: &GameToSim, rng: &mut StdRng| fast_sim.sim_game(g, game, rng)))
+}
+
+fn open_league(path: &Path) -> Result<LeagueDb, String> {
+    if !path.exists() {
+        return Err(format!("{} doesn't exist, create it with new-league", path.display()));
+    }
+    LeagueDb::open(path)
+}
+
+pub fn run(argv: &[String]) -> Result<(), String> {
+    if argv.is_empty() || argv[0] == "help" || argv[0] == "--help" {
+        println!("{}", usage());
+        return Ok(());
+    }
+    let command = COMMANDS.iter().find(|command| command.name == argv[0]);
+    if command.is_none() {
+        return Err(format!("Unknown command \"{}\"\n\n{}", argv[0], usage()));
+    }
+    let command = command.unwrap();
+    let args = Args::parse(&argv[1..], &command.spec)?;
+    let path = Path::new(args.get("league").unwrap_or(DEFAULT_LEAGUE)).to_path_buf();
+
+    if command.name == "new-league" {
+        return new_league::new_league(&path, &args);
+    }
+
+    let mut db = open_league(&path)?;
+    match command.name {
+        "sim" => sim::sim(&mut db, &args),
+        "game" => game::game(&mut db, &args),
+        "standings" => standings::standings(&mut db, &args),
+        "odds" => odds::odds(&mut db, &args),
+        "calibrate" => calibrate::calibrate(&mut db, &args),
+        "draft" => draft::draft(&mut db, &args),
+        "box-score" => box_score::box_score(&mut db, &args),
+        "pbp" => pbp::pbp(&mut db, &args),
+        "finances" => finances::finances(&mut db, &args),
+        "trade" => trade::trade(&mut db, &args),
+        "transactions" => transactions::transactions(&mut db, &args),
+        "fa" => fa::fa(&mut db, &args),
+        "phase" => phase::phase(&mut db, &args),
+        "expansion" => expansion::expansion(&mut db, &args),
+        "awards" => awards::awards(&mut db, &args),
+        "history" => history::history(&mut db),
+        "export" => export::export(&mut db, &args),
+        "settings" => settings::settings(&mut db, &args),
+        _ => Err(format!("Unknown command \"{}\"\n\n{}", command.name, usage())),
+    }
+}
\ No newline at end of file
@@ -0,0 +1,150 @@
+use crate::cli::args::Args;
+use bbgm::common::constants::PHASE_AFTER_TRADE_DEADLINE;
+use bbgm::common::constants::PHASE_REGULAR_SEASON;
+use bbgm::common::league_settings;
+use bbgm::common::team_infos::TEAM_INFOS;
+use bbgm::common::types::Team;
+use bbgm::worker::core::draft::gen_picks::gen_picks;
+use bbgm::worker::core::game::play::start_regular_season;
+use bbgm::worker::core::league::gen_random_players::gen_random_players;
+use bbgm::worker::core::league::import_league::import_league;
+use bbgm::worker::core::league::League;
+use bbgm::worker::core::player::gen_ratings::TalentDistribution;
+use bbgm::worker::core::season::gen_play_ins::is_play_in_enabled;
+use bbgm::worker::core::season::gen_play_ins::NUM_PLAY_IN_SPOTS;
+use bbgm::worker::core::season::gen_play_ins::NUM_PLAY_IN_TEAMS;
+use bbgm::worker::core::season::gen_playoff_series::get_num_playoff_teams;
+use bbgm::worker::db::LeagueDb;
+use bbgm::worker::util::g::G;
+use bbgm::worker::util::random::random_seed;
+use std::fs;
+use std::path::Path;
+
+const DEFAULT_SEASON: i32 = 2025;
+
+// Default teams, spread evenly over the divisions in order
+fn gen_teams(g: &G, num_teams: usize) -> Result<Vec<Team>, String> {
+    if num_teams < 2 || num_teams > TEAM_INFOS.len() {
+        return Err(format!("--teams must be between 2 and {}, got {}", TEAM_INFOS.len(), num_teams));
+    }
+    let divs = g.divs();
+    if divs.is_empty() {
+        return Err("divs: a new league needs at least one division".to_string());
+    }
+
+    let mut teams = Vec::new();
+    for i in 0..num_teams {
+        let team_info = &TEAM_INFOS[i * TEAM_INFOS.len() / num_teams];
+        let div = &divs[i * divs.len() / num_teams];
+        teams.push(Team {
+            tid: i as i32,
+            cid: div.cid,
+            did: div.did,
+            region: team_info.region.to_string(),
+            name: team_info.name.to_string(),
+            abbrev: team_info.abbrev.to_string(),
+            pop: team_info.pop,
+            coaching_rank: None
+        });
+    }
+    Ok(teams)
+}
+
+fn has_enough_teams_for_playoffs(g: &G, teams: &[Team]) -> bool {
+    let num_playoff_teams = get_num_playoff_teams(g);
+    let num_play_in_teams = if is_play_in_enabled(g.play_in_type()) {
+        NUM_PLAY_IN_TEAMS - NUM_PLAY_IN_SPOTS
+    } else {
+        0
+    };
+    let confs = g.confs();
+    if confs.len() == 2 && g.num_games_playoff_series().len() >= 2 && num_playoff_teams.is_multiple_of(2) {
+        return confs.iter().all(|conf| {
+            teams.iter().filter(|t| t.cid == conf.cid).count() >= num_playoff_teams / 2 + num_play_in_teams
+        });
+    }
+    teams.len() >= num_playoff_teams + num_play_in_teams
+}
+
+// The default playoffs are for 30 teams, so smaller leagues drop early rounds, and the play-in if needed
+fn fit_playoffs_to_teams(g: &mut G, teams: &[Team]) -> Result<(), String> {
+    while !has_enough_teams_for_playoffs(g, teams) {
+        let num_games_playoff_series = g.num_games_playoff_series().clone();
+        if num_games_playoff_series.len() > 1 {
+            g.set_num_games_playoff_series(i32::MIN, num_games_playoff_series[1..].to_vec())?;
+        } else if is_play_in_enabled(g.play_in_type()) {
+            g.set_play_in_type(i32::MIN, "")?;
+        } else {
+            break;
+        }
+    }
+    Ok(())
+}
+
+fn new_random_league(args: &Args, seed: u64) -> Result<League, String> {
+    let season = args.get_int("season")?.unwrap_or(DEFAULT_SEASON);
+    let mut g = match args.get("settings") {
+        Some(path) => G::new(league_settings::load_file(Path::new(path))?, season)?,
+        None => G::from_defaults(season)?,
+    };
+    let teams = gen_teams(&g, args.get_int("teams")?.unwrap_or(TEAM_INFOS.len()))?;
+    // A settings file is used as is, so a mistake in it is reported rather than fixed behind the user's back
+    if !args.has("settings") {
+        fit_playoffs_to_teams(&mut g, &teams)?;
+    }
+    let players = gen_random_players(&teams, &g, &TalentDistribution::default(), seed)?;
+    Ok(League {
+        g,
+        starting_season: season,
+        phase: PHASE_REGULAR_SEASON,
+        teams,
+        players,
+        games: Vec::new(),
+        team_seasons: Vec::new(),
+        draft_picks: Vec::new(),
+        events: Vec::new(),
+        awards: Vec::new()
+    })
+}
+
+fn import_league_file(path: &str) -> Result<League, String> {
+    let text = fs::read_to_string(path).map_err(|err| format!("Can't read {}: {}", path, err))?;
+    let (league, warnings) = import_league(&text)?;
+    for warning in warnings.iter() {
+        eprintln!("Warning: {}", warning);
+    }
+    Ok(league)
+}
+
+pub fn new_league(path: &Path, args: &Args) -> Result<(), String> {
+    let seed = args.get_int("seed")?.unwrap_or_else(random_seed);
+    if path.exists() {
+        if !args.has("force") {
+            return Err(format!("{} already exists, use --force to replace it", path.display()));
+        }
+        fs::remove_file(path).map_err(|err| format!("Can't remove {}: {}", path.display(), err))?;
+    }
+
+    let league = match args.get("import") {
+        Some(import_path) => {
+            if args.has("settings") || args.has("teams") || args.has("season") {
+                return Err("--import can't be combined with --settings, --teams or --season".to_string());
+            }
+            import_league_file(import_path)?
+        }
+        None => new_random_league(args, seed)?,
+    };
+
+    let mut db = LeagueDb::open(path)?;
+    db.transaction(|tx| {
+        tx.put_league(&league)?;
+        tx.put_seed(seed)?;
+        if league.phase == PHASE_REGULAR_SEASON || league.phase == PHASE_AFTER_TRADE_DEADLINE {
+            return start_regular_season(tx);
+        }
+        gen_picks(tx)
+    })?;
+
+    println!("Created {} with {} teams, season {}", path.display(), league.teams.len(), league.g.season());
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,101 @@
+use crate::cli::args::Args;
+use crate::cli::get_sim_game;
+use crate::cli::team_name;
+use bbgm::worker::core::season::get_playoff_odds::get_playoff_odds;
+use bbgm::worker::core::season::get_playoff_odds::SeasonSoFar;
+use bbgm::worker::core::season::get_playoff_odds::TeamPlayoffOdds;
+use bbgm::worker::db::LeagueDb;
+use bbgm::worker::util::parallel::default_num_threads;
+use bbgm::worker::util::random::coin_flip_seed;
+use bbgm::worker::util::random::random_seed;
+
+const DEFAULT_NUM_RUNS: usize = 1000;
+
+fn format_odds(p: f64) -> String {
+    if p == 0.0 {
+        return "-".to_string();
+    }
+    format!("{:.1}", 100.0 * p)
+}
+
+fn get_round_name(round: usize, num_rounds: usize) -> String {
+    if round + 1 == num_rounds {
+        return "Title".to_string();
+    }
+    format!("R{}", round + 1)
+}
+
+// Projects the rest of the season. Nothing is saved, and the same seed gives the same odds.
+pub fn odds(db: &mut LeagueDb, args: &Args) -> Result<(), String> {
+    let num_runs = args.get_int("runs")?.unwrap_or(DEFAULT_NUM_RUNS);
+    let seed = args.get_int("seed")?.unwrap_or_else(random_seed);
+    let num_threads = args.get_int("threads")?.unwrap_or_else(default_num_threads);
+    if num_threads == 0 {
+        return Err("--threads must be at least 1".to_string());
+    }
+
+    let (g, teams, league_seed) = db.transaction(|tx| Ok((tx.get_g()?, tx.get_teams()?, tx.get_seed()?)))?;
+    let (team_seasons, games, schedule, playoff_series) = db.transaction(|tx| {
+        Ok((tx.get_team_seasons(g.season())?, tx.get_games(g.season())?, tx.get_schedule()?, tx.get_playoff_series(g.season())?))
+    })?;
+    if team_seasons.is_empty() {
+        return Err(format!("No season {} to project", g.season()));
+    }
+
+    let sim_game = get_sim_game(db, args)?;
+    let season = SeasonSoFar {
+        team_seasons: &team_seasons,
+        games: &games,
+        schedule: &schedule,
+        playoff_series: playoff_series.as_ref(),
+        coin_flip_seed: coin_flip_seed(league_seed, g.season())
+    };
+    let mut all_odds = get_playoff_odds(
+        &g,
+        &season,
+        num_runs,
+        seed,
+        num_threads,
+        &*sim_game
+    )?;
+    all_odds.sort_by(|a, b| {
+        b.title().total_cmp(&a.title()).then(b.playoffs.total_cmp(&a.playoffs)).then(a.tid.cmp(&b.tid))
+    });
+
+    println!("Seed {}, {} runs, odds in %", seed, num_runs);
+    println!();
+    let show_seeds = args.has("seeds");
+    let mut header = format!("{:<32}", "Team");
+    if show_seeds {
+        for i in 0..all_odds[0].seeds.len() {
+            header.push_str(&format!(" {:>5}", i + 1));
+        }
+    } else {
+        header.push_str(&format!(" {:>5} {:>5}", "PO", "PI"));
+        let num_rounds = all_odds[0].rounds.len();
+        for round in 0..num_rounds {
+            header.push_str(&format!(" {:>5}", get_round_name(round, num_rounds)));
+        }
+    }
+    println!("{}", header);
+
+    for team_odds in all_odds.iter() {
+        println!("{}", format_line(team_odds, &team_name(&teams, team_odds.tid), show_seeds));
+    }
+    Ok(())
+}
+
+fn format_line(team_odds: &TeamPlayoffOdds, name: &str, show_seeds: bool) -> String {
+    let mut line = format!("{:<32}", name);
+    if show_seeds {
+        for p in team_odds.seeds.iter() {
+            line.push_str(&format!(" {:>5}", format_odds(*p)));
+        }
+        return line;
+    }
+    line.push_str(&format!(" {:>5} {:>5}", format_odds(team_odds.playoffs), format_odds(team_odds.play_in)));
+    for p in team_odds.rounds.iter() {
+        line.push_str(&format!(" {:>5}", format_odds(*p)));
+    }
+    line
+}
\ No newline at end of file
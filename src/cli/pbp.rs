@@ -0,0 +1,63 @@
+use crate::cli::args::Args;
+use crate::cli::team_name;
+use bbgm::common::types::BoxScore;
+use bbgm::common::types::BoxScorePlayer;
+use bbgm::common::types::Team;
+use bbgm::worker::db::LeagueDb;
+
+fn print_win_probability_added(box_score: &BoxScore, teams: &[Team]) {
+    let mut players: Vec<(&BoxScorePlayer, i32)> = box_score.teams
+        .iter()
+        .flat_map(|t| t.players.iter().map(move |p| (p, t.tid)))
+        .collect();
+    players.sort_by(|(a, _), (b, _)| b.wpa.total_cmp(&a.wpa));
+
+    println!();
+    println!("{:<24} {:<28} {:>6}", "Win probability added", "", "WPA");
+    for (p, tid) in players.iter() {
+        println!("{:<24} {:<28} {:>+6.1}", p.name, team_name(teams, *tid), 100.0 * p.wpa);
+    }
+}
+
+pub fn pbp(db: &mut LeagueDb, args: &Args) -> Result<(), String> {
+    let gid = args.get_positional(0, "gid")?;
+    let gid: i32 = gid.parse().map_err(|_| format!("Invalid gid \"{}\"", gid))?;
+
+    let (play_by_play, box_score, teams) = db.transaction(|tx| {
+        Ok((tx.get_play_by_play(gid)?, tx.get_box_score(gid)?, tx.get_teams()?))
+    })?;
+    if play_by_play.is_none() {
+        return Err(format!("No play-by-play was saved for game {}", gid));
+    }
+    let play_by_play = play_by_play.unwrap();
+
+    for event in play_by_play.iter() {
+        let minutes = event.t.floor();
+        let seconds = ((event.t - minutes) * 60.0).floor();
+        let team = match event.tid {
+            Some(tid) => team_name(&teams, tid),
+            None => "".to_string(),
+        };
+        // Home team's win probability, when the game recorded it
+        let wp = match event.wp {
+            Some(wp) => format!("{:>5.1}%", 100.0 * wp),
+            None => "".to_string(),
+        };
+        println!(
+            "Q{} {:>2}:{:02} {:>3}-{:<3} {:>6} {:<28} {}",
+            event.period,
+            minutes,
+            seconds,
+            event.score[1],
+            event.score[0],
+            wp,
+            team,
+            event.text
+        );
+    }
+
+    if let Some(box_score) = box_score.filter(|_| play_by_play.iter().any(|event| event.wp.is_some())) {
+        print_win_probability_added(&box_score, &teams);
+    }
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,49 @@
+use crate::cli::args::Args;
+use bbgm::common::constants::phase_text;
+use bbgm::common::constants::PHASE_AFTER_TRADE_DEADLINE;
+use bbgm::common::constants::PHASE_DRAFT;
+use bbgm::common::constants::PHASE_DRAFT_LOTTERY;
+use bbgm::common::constants::PHASE_EXPANSION_DRAFT;
+use bbgm::common::constants::PHASE_FREE_AGENCY;
+use bbgm::common::constants::PHASE_PLAYOFFS;
+use bbgm::common::constants::PHASE_PRESEASON;
+use bbgm::common::constants::PHASE_REGULAR_SEASON;
+use bbgm::common::constants::PHASE_RESIGN_PLAYERS;
+use bbgm::worker::core::phase::get_phase::get_phase;
+use bbgm::worker::core::phase::new_phase::get_next_phase;
+use bbgm::worker::core::phase::new_phase::new_phase;
+use bbgm::worker::db::LeagueDb;
+
+// What to do to get out of phase
+fn next_step(phase: i32) -> &'static str {
+    match phase {
+        PHASE_PRESEASON => "phase --next or sim starts the regular season",
+        PHASE_REGULAR_SEASON | PHASE_AFTER_TRADE_DEADLINE => "sim plays the rest of the regular season, then the playoffs",
+        PHASE_PLAYOFFS => "sim plays the rest of the playoffs",
+        PHASE_DRAFT_LOTTERY => "draft or phase --next runs the lottery, expansion adds teams for an expansion draft",
+        PHASE_DRAFT => "draft makes the remaining picks",
+        PHASE_RESIGN_PLAYERS => "fa --team TEAM --sign PID re-signs players, phase --next or fa --start starts free agency",
+        PHASE_FREE_AGENCY => "fa --days N plays free agency, phase --next plays the rest and starts the next season",
+        PHASE_EXPANSION_DRAFT => "phase --next runs the expansion draft and goes back to the draft lottery",
+        _ => "",
+    }
+}
+
+pub fn phase(db: &mut LeagueDb, args: &Args) -> Result<(), String> {
+    if args.has("next") {
+        let from = db.transaction(get_phase)?;
+        let to = get_next_phase(from);
+        if to.is_none() {
+            return Err(format!("The {} phase ends on its own: {}", phase_text(from), next_step(from)));
+        }
+        let events = db.transaction(|tx| new_phase(tx, to.unwrap()))?;
+        for event in events.iter() {
+            println!("{}", event.text);
+        }
+    }
+
+    let (g, phase) = db.transaction(|tx| Ok((tx.get_g()?, get_phase(tx)?)))?;
+    println!("{} {}", g.season(), phase_text(phase));
+    println!("Next: {}", next_step(phase));
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,18 @@
+use crate::cli::args::Args;
+use bbgm::common::league_settings;
+use bbgm::worker::db::LeagueDb;
+use std::path::Path;
+
+// The league's settings as a league settings file, to --out or stdout as TOML
+pub fn settings(db: &mut LeagueDb, args: &Args) -> Result<(), String> {
+    let g = db.transaction(|tx| tx.get_g())?;
+
+    match args.get("out") {
+        Some(path) => {
+            league_settings::save_file(Path::new(path), g.game_attributes())?;
+            eprintln!("Saved settings to {}", path);
+        }
+        None => print!("{}", league_settings::to_toml(g.game_attributes())),
+    }
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,74 @@
+use crate::cli::args::Args;
+use crate::cli::get_sim_game;
+use bbgm::common::constants::phase_text;
+use bbgm::common::constants::PHASE_PLAYOFFS;
+use bbgm::common::constants::PHASE_PRESEASON;
+use bbgm::common::constants::PHASE_REGULAR_SEASON;
+use bbgm::worker::core::game::play::get_season_state;
+use bbgm::worker::core::game::play::play_day;
+use bbgm::worker::core::game::play::SeasonState;
+use bbgm::worker::core::phase::get_phase::get_phase;
+use bbgm::worker::core::phase::new_phase::new_phase;
+use bbgm::worker::db::LeagueDb;
+use bbgm::worker::util::parallel::default_num_threads;
+
+fn is_done(db: &mut LeagueDb, until: &str) -> Result<bool, String> {
+    let phase = db.transaction(get_phase)?;
+    Ok(match until {
+        "playoffs" => !(PHASE_REGULAR_SEASON..PHASE_PLAYOFFS).contains(&phase),
+        _ => !(PHASE_REGULAR_SEASON..=PHASE_PLAYOFFS).contains(&phase),
+    })
+}
+
+pub fn sim(db: &mut LeagueDb, args: &Args) -> Result<(), String> {
+    let num_threads = args.get_int("threads")?.unwrap_or_else(default_num_threads);
+    if num_threads == 0 {
+        return Err("--threads must be at least 1".to_string());
+    }
+    let days: Option<u32> = args.get_int("days")?;
+    let until = args.get("until");
+    if days.is_some() == until.is_some() {
+        return Err("sim needs either --days N or --until playoffs|end".to_string());
+    }
+    if let Some(until) = until.filter(|until| !["playoffs", "end"].contains(until)) {
+        return Err(format!("--until must be playoffs or end, got \"{}\"", until));
+    }
+
+    // Playing from the preseason starts the regular season
+    let (g, phase) = db.transaction(|tx| Ok((tx.get_g()?, get_phase(tx)?)))?;
+    if phase == PHASE_PRESEASON {
+        db.transaction(|tx| new_phase(tx, PHASE_REGULAR_SEASON))?;
+        println!("Started the {} regular season", g.season());
+    } else if !(PHASE_REGULAR_SEASON..=PHASE_PLAYOFFS).contains(&phase) {
+        return Err(format!("There are no games to play in the current phase ({})", phase_text(phase)));
+    }
+
+    let mut num_days = 0;
+    let mut num_games = 0;
+    loop {
+        if days.is_some() && num_days >= days.unwrap() {
+            break;
+        }
+        if until.is_some() && is_done(db, until.unwrap())? {
+            break;
+        }
+        if days.is_some() && is_done(db, "end")? {
+            break;
+        }
+        // Reloaded every day, so injuries and roster moves from the day before count
+        let sim_game = get_sim_game(db, args)?;
+        let results = play_day(db, &*sim_game, num_threads)?;
+        num_days += 1;
+        num_games += results.len();
+    }
+
+    let state = db.transaction(get_season_state)?;
+    println!("Simulated {} days, {} games", num_days, num_games);
+    if let SeasonState::Done(tid) = state {
+        let t = db.transaction(|tx| tx.get_teams())?.into_iter().find(|t| t.tid == tid);
+        if let Some(t) = t {
+            println!("Season over, {} {} won the title", t.region, t.name);
+        }
+    }
+    Ok(())
+}
\ No newline at end of file
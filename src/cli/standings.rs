@@ -0,0 +1,74 @@
+use crate::cli::args::Args;
+use crate::cli::team_name;
+use bbgm::common::helpers::calc_winp;
+use bbgm::common::types::Team;
+use bbgm::worker::core::season::get_standings::get_standings;
+use bbgm::worker::core::season::get_standings::StandingsGroup;
+use bbgm::worker::db::LeagueDb;
+use bbgm::worker::util::random::coin_flip_seed;
+
+fn print_group(group: &StandingsGroup, teams: &[Team], ties: bool, otl: bool) {
+    println!();
+    let mut header = format!("{:<32} {:>3} {:>3}", group.name, "W", "L");
+    if ties {
+        header.push_str(&format!(" {:>3}", "T"));
+    }
+    if otl {
+        header.push_str(&format!(" {:>3}", "OTL"));
+    }
+    header.push_str(&format!(" {:>5}", "PCT"));
+    if group.teams.iter().any(|entry| entry.points.is_some()) {
+        header.push_str(&format!(" {:>5}", "PTS"));
+    }
+    println!("{}", header);
+
+    for entry in group.teams.iter() {
+        let ts = &entry.season_attrs;
+        let mut line = format!("{:>2}. {:<28} {:>3} {:>3}", entry.rank, team_name(teams, ts.tid), ts.won, ts.lost);
+        if ties {
+            line.push_str(&format!(" {:>3}", ts.tied));
+        }
+        if otl {
+            line.push_str(&format!(" {:>3}", ts.otl));
+        }
+        line.push_str(&format!(" {:>5.3}", calc_winp(ts.won, ts.lost, ts.tied, ts.otl)));
+        if let Some(points) = entry.points {
+            line.push_str(&format!(" {:>5}", points));
+        }
+        let tiebreaker = entry.tiebreaker_name();
+        // Before any games, every ordering is a coin flip, which isn't worth pointing out
+        if let Some(tiebreaker) = tiebreaker.filter(|_| ts.gp > 0) {
+            line.push_str(&format!("  (tiebreaker: {})", tiebreaker));
+        }
+        println!("{}", line);
+    }
+}
+
+pub fn standings(db: &mut LeagueDb, args: &Args) -> Result<(), String> {
+    let (mut g, teams) = db.transaction(|tx| Ok((tx.get_g()?, tx.get_teams()?)))?;
+    let season = args.get_int("season")?.unwrap_or(g.season());
+    g.set_season(season);
+    let (team_seasons, games, seed) = db.transaction(|tx| Ok((tx.get_team_seasons(season)?, tx.get_games(season)?, tx.get_seed()?)))?;
+    if team_seasons.is_empty() {
+        return Err(format!("No standings for {}", season));
+    }
+
+    let standings = get_standings(&team_seasons, &games, &g, coin_flip_seed(seed, season));
+    let ties = g.ties();
+    let otl = g.otl();
+    match args.get("by").unwrap_or("div") {
+        "div" => {
+            for group in standings.divs.iter() {
+                print_group(group, &teams, ties, otl);
+            }
+        }
+        "conf" => {
+            for group in standings.confs.iter() {
+                print_group(group, &teams, ties, otl);
+            }
+        }
+        "league" => print_group(&standings.league, &teams, ties, otl),
+        by => return Err(format!("--by must be div, conf or league, got \"{}\"", by)),
+    }
+    Ok(())
+}
\ No newline at end of file
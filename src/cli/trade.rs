@@ -0,0 +1,126 @@
+use crate::cli::args::Args;
+use crate::cli::find_tid;
+use crate::cli::team_name;
+use bbgm::common::helpers::format_currency;
+use bbgm::common::types::Player;
+use bbgm::common::types::Team;
+use bbgm::common::types::TradeTeam;
+use bbgm::worker::core::trade::make_it_work::make_it_work;
+use bbgm::worker::core::trade::process_trade::describe_pick;
+use bbgm::worker::core::trade::propose::check_trades_allowed;
+use bbgm::worker::core::trade::propose::propose;
+use bbgm::worker::core::trade::summary::summary;
+use bbgm::worker::core::trade::summary::TradeSummary;
+use bbgm::worker::core::trade::value_change::value_change;
+use bbgm::worker::db::LeagueDb;
+use bbgm::worker::util::g::G;
+
+fn player_line(p: &Player, g: &G) -> String {
+    let ratings = p.ratings.last().unwrap();
+    format!(
+        "{:>5} {:<24} {:<2} {:>3} {:>3} {:>3} {:>8} thru {}",
+        p.pid,
+        p.name(),
+        ratings.pos,
+        p.age(g.season()),
+        ratings.ovr.round(),
+        ratings.pot.round(),
+        format_currency(p.contract.amount),
+        p.contract.exp
+    )
+}
+
+// Everything the two teams could trade, with the pids and dpids to pass to --give and --get
+fn print_assets(db: &mut LeagueDb, g: &G, teams: &[Team], tids: [i32; 2]) -> Result<(), String> {
+    for tid in tids {
+        let (mut players, draft_picks) = db.transaction(|tx| Ok((tx.get_players_by_tid(tid)?, tx.get_all_draft_picks()?)))?;
+        players.sort_by(|a, b| b.value.total_cmp(&a.value));
+        println!("{}", team_name(teams, tid));
+        println!("{:>5} {:<24} {:<2} {:>3} {:>3} {:>3} {:>8}", "PID", "Player", "", "Age", "Ovr", "Pot", "Contract");
+        for p in players.iter() {
+            println!("{}", player_line(p, g));
+        }
+        for dp in draft_picks.iter().filter(|dp| dp.tid == tid) {
+            println!("{:>5} {}", dp.dpid, describe_pick(dp, teams));
+        }
+        println!();
+    }
+    Ok(())
+}
+
+fn print_summary(trade_summary: &TradeSummary, g: &G, teams: &[Team]) {
+    for t in trade_summary.teams.iter() {
+        println!("{} give:", team_name(teams, t.tid));
+        for p in t.players.iter() {
+            println!("{}", player_line(p, g));
+        }
+        for dp in t.picks.iter() {
+            println!("{:>5} {}", dp.dpid, describe_pick(dp, teams));
+        }
+        if t.players.is_empty() && t.picks.is_empty() {
+            println!("      nothing");
+        }
+        println!("      Payroll {} -> {}", format_currency(t.payroll_before), format_currency(t.payroll_after));
+        println!();
+    }
+    for warning in trade_summary.warnings.iter() {
+        println!("Not allowed: {}", warning);
+    }
+}
+
+pub fn trade(db: &mut LeagueDb, args: &Args) -> Result<(), String> {
+    let (g, teams) = db.transaction(|tx| Ok((tx.get_g()?, tx.get_teams()?)))?;
+    let tid = find_tid(&teams, args.get_required("team")?)?;
+    let other_tid = find_tid(&teams, args.get_required("with")?)?;
+    let trade_teams = [
+        TradeTeam {
+            tid,
+            pids: args.get_int_list("give")?,
+            dpids: args.get_int_list("give-picks")?
+        },
+        TradeTeam {
+            tid: other_tid,
+            pids: args.get_int_list("get")?,
+            dpids: args.get_int_list("get-picks")?
+        }
+    ];
+    if trade_teams.iter().all(|t| t.pids.is_empty() && t.dpids.is_empty()) {
+        return print_assets(db, &g, &teams, [tid, other_tid]);
+    }
+
+    db.transaction(check_trades_allowed)?;
+    let trade_summary = db.transaction(|tx| summary(tx, &trade_teams))?;
+    print_summary(&trade_summary, &g, &teams);
+    if !trade_summary.warnings.is_empty() {
+        return Err("The trade breaks the salary cap or roster rules".to_string());
+    }
+
+    let other_name = team_name(&teams, other_tid);
+    if args.has("dry-run") {
+        let dv = db.transaction(|tx| value_change(tx, &trade_summary, 1))?;
+        println!("{} would {} ({:+.3})", other_name, if dv > 0.0 { "accept" } else { "reject" }, dv);
+        if dv > 0.0 {
+            return Ok(());
+        }
+    } else {
+        let result = db.transaction(|tx| propose(tx, &trade_teams, args.has("force")))?;
+        println!("{} ({:+.3})", result.message, result.dv);
+        if result.accepted {
+            return Ok(());
+        }
+    }
+
+    // Rejected, so ask what it would take
+    let counter = db.transaction(|tx| make_it_work(tx, &trade_teams))?;
+    if counter.is_none() {
+        println!("{} isn't interested in anything {} could add", other_name, team_name(&teams, tid));
+        return Ok(());
+    }
+    let counter = counter.unwrap();
+    let counter_summary = db.transaction(|tx| summary(tx, &counter))?;
+    println!();
+    println!("{} would accept this instead:", other_name);
+    println!();
+    print_summary(&counter_summary, &g, &teams);
+    Ok(())
+}
\ No newline at end of file
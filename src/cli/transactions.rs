@@ -0,0 +1,23 @@
+use crate::cli::args::Args;
+use crate::cli::find_tid;
+use bbgm::worker::db::LeagueDb;
+
+pub fn transactions(db: &mut LeagueDb, args: &Args) -> Result<(), String> {
+    let (g, teams) = db.transaction(|tx| Ok((tx.get_g()?, tx.get_teams()?)))?;
+    let season = args.get_int("season")?.unwrap_or(g.season());
+    let tid = match args.get("team") {
+        Some(team) => Some(find_tid(&teams, team)?),
+        None => None,
+    };
+
+    let events = db.transaction(|tx| tx.get_events(season))?;
+    let events: Vec<_> = events.iter().filter(|event| tid.is_none() || event.tids.contains(&tid.unwrap())).collect();
+    if events.is_empty() {
+        println!("No transactions in {}", season);
+        return Ok(());
+    }
+    for event in events.iter() {
+        println!("{} {:<9} {}", event.season, event.event_type, event.text);
+    }
+    Ok(())
+}
\ No newline at end of file
@@ -1,27 +1,142 @@
+// Named after zengm's constant
+#[allow(clippy::upper_case_acronyms)]
 pub struct TIEBREAKERS {
-    commonOpponentsRecord: String,
-    confRecordIfSame: String,
-    divRecordIfSame: String,
-    divWinner: String,
-    headToHeadRecord: String,
-    marginOfVictory: String,
-    strengthOfVictory: String,
-    strengthOfSchedule: String,
-    coinFlip: String,
+    common_opponents_record: String,
+    conf_record_if_same: String,
+    div_record_if_same: String,
+    div_winner: String,
+    head_to_head_record: String,
+    margin_of_victory: String,
+    strength_of_victory: String,
+    strength_of_schedule: String,
+    coin_flip: String,
+}
+
+impl Default for TIEBREAKERS {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl TIEBREAKERS {
     pub fn new() -> Self {
-        return TIEBREAKERS {
-            commonOpponentsRecord: "Common Opponents Record".to_string(),
-            confRecordIfSame: "Conference Record".to_string(),
-            divRecordIfSame: "Division Record".to_string(),
-            divWinner: "Division Winner".to_string(),
-            headToHeadRecord: "Head-To-Head Record".to_string(),
-            marginOfVictory: "Margin of Victory".to_string(),
-            strengthOfVictory: "Strength of Victory".to_string(),
-            strengthOfSchedule: "Strength of Schedule".to_string(),
-            coinFlip: "Coin Flip".to_string(),
-        };
+        TIEBREAKERS {
+            common_opponents_record: "Common Opponents Record".to_string(),
+            conf_record_if_same: "Conference Record".to_string(),
+            div_record_if_same: "Division Record".to_string(),
+            div_winner: "Division Winner".to_string(),
+            head_to_head_record: "Head-To-Head Record".to_string(),
+            margin_of_victory: "Margin of Victory".to_string(),
+            strength_of_victory: "Strength of Victory".to_string(),
+            strength_of_schedule: "Strength of Schedule".to_string(),
+            coin_flip: "Coin Flip".to_string(),
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&String> {
+        match key {
+            "commonOpponentsRecord" => Some(&self.common_opponents_record),
+            "confRecordIfSame" => Some(&self.conf_record_if_same),
+            "divRecordIfSame" => Some(&self.div_record_if_same),
+            "divWinner" => Some(&self.div_winner),
+            "headToHeadRecord" => Some(&self.head_to_head_record),
+            "marginOfVictory" => Some(&self.margin_of_victory),
+            "strengthOfVictory" => Some(&self.strength_of_victory),
+            "strengthOfSchedule" => Some(&self.strength_of_schedule),
+            "coinFlip" => Some(&self.coin_flip),
+            _ => None,
+        }
+    }
+}
+
+pub const DEFAULT_TIEBREAKERS: [&str; 6] = [
+    "headToHeadRecord",
+    "divWinner",
+    "divRecordIfSame",
+    "confRecordIfSame",
+    "marginOfVictory",
+    "coinFlip",
+];
+
+// "" has no play-in, "nba" is the NBA's 7-8 and 9-10 games, and "singleElimination" is a 4 team bracket
+pub const PLAY_IN_TYPES: [&str; 3] = ["", "nba", "singleElimination"];
+
+// Composite ratings used by GameSim, as weighted averages of raw ratings. Numbers in the ratings list are used as
+// constant ratings.
+pub const COMPOSITE_WEIGHTS: [(&str, &[&str], &[f64]); 21] = [
+    ("pace", &["spd", "jmp", "dnk", "tp", "drb", "pss"], &[1.0, 1.0, 1.0, 1.0, 1.0, 1.0]),
+    ("usage", &["ins", "dnk", "fg", "tp", "spd", "hgt", "drb", "oiq"], &[1.5, 1.0, 1.0, 1.0, 0.5, 0.5, 0.5, 0.5]),
+    ("dribbling", &["drb", "spd"], &[1.0, 1.0]),
+    ("passing", &["drb", "pss", "oiq"], &[0.4, 1.0, 0.5]),
+    ("turnovers", &["50", "ins", "pss", "oiq"], &[0.5, 1.0, 1.0, -1.0]),
+    ("shootingAtRim", &["hgt", "stre", "dnk", "oiq"], &[2.0, 0.3, 0.3, 0.2]),
+    ("shootingLowPost", &["hgt", "stre", "spd", "ins", "oiq"], &[1.0, 0.6, 0.2, 1.0, 0.4]),
+    ("shootingMidRange", &["oiq", "fg", "stre"], &[-0.5, 1.0, 0.2]),
+    ("shootingThreePointer", &["oiq", "tp"], &[0.1, 1.0]),
+    ("shootingFT", &["ft"], &[1.0]),
+    ("rebounding", &["hgt", "stre", "jmp", "reb", "oiq", "diq"], &[2.0, 0.1, 0.1, 2.0, 0.5, 0.5]),
+    ("stealing", &["50", "spd", "diq"], &[1.0, 1.0, 2.0]),
+    ("blocking", &["hgt", "jmp", "diq"], &[2.5, 1.5, 0.5]),
+    ("fouling", &["50", "hgt", "diq", "spd"], &[3.0, 1.0, -1.0, -1.0]),
+    ("drawingFouls", &["hgt", "spd", "drb", "dnk", "oiq"], &[1.0, 1.0, 1.0, 1.0, 1.0]),
+    ("defense", &["hgt", "stre", "spd", "jmp", "diq"], &[1.0, 1.0, 1.0, 0.5, 2.0]),
+    ("defenseInterior", &["hgt", "stre", "spd", "jmp", "diq"], &[2.5, 1.0, 0.5, 0.5, 2.0]),
+    ("defensePerimeter", &["hgt", "stre", "spd", "jmp", "diq"], &[0.5, 0.5, 2.0, 0.5, 1.0]),
+    ("endurance", &["50", "endu"], &[1.0, 1.0]),
+    ("athleticism", &["stre", "spd", "jmp", "hgt"], &[1.0, 1.0, 1.0, 0.75]),
+    ("jumpBall", &["hgt", "jmp"], &[1.0, 0.25]),
+];
+
+// Player tids that aren't teams
+pub const PLAYER_FREE_AGENT: i32 = -1;
+pub const PLAYER_UNDRAFTED: i32 = -2;
+pub const PLAYER_RETIRED: i32 = -3;
+
+// Season phases, numbered like g.phase in BBGM, which also has a fantasy draft (-1) and an after draft phase (6)
+pub const PHASE_EXPANSION_DRAFT: i32 = -2;
+pub const PHASE_PRESEASON: i32 = 0;
+pub const PHASE_REGULAR_SEASON: i32 = 1;
+pub const PHASE_AFTER_TRADE_DEADLINE: i32 = 2;
+pub const PHASE_PLAYOFFS: i32 = 3;
+pub const PHASE_DRAFT_LOTTERY: i32 = 4;
+pub const PHASE_DRAFT: i32 = 5;
+pub const PHASE_RESIGN_PLAYERS: i32 = 7;
+pub const PHASE_FREE_AGENCY: i32 = 8;
+
+pub const PHASES: [i32; 9] = [
+    PHASE_EXPANSION_DRAFT,
+    PHASE_PRESEASON,
+    PHASE_REGULAR_SEASON,
+    PHASE_AFTER_TRADE_DEADLINE,
+    PHASE_PLAYOFFS,
+    PHASE_DRAFT_LOTTERY,
+    PHASE_DRAFT,
+    PHASE_RESIGN_PLAYERS,
+    PHASE_FREE_AGENCY,
+];
+
+pub fn phase_text(phase: i32) -> &'static str {
+    match phase {
+        PHASE_EXPANSION_DRAFT => "expansion draft",
+        PHASE_PRESEASON => "preseason",
+        PHASE_REGULAR_SEASON => "regular season",
+        PHASE_AFTER_TRADE_DEADLINE => "regular season, after trade deadline",
+        PHASE_PLAYOFFS => "playoffs",
+        PHASE_DRAFT_LOTTERY => "draft lottery",
+        PHASE_DRAFT => "draft",
+        PHASE_RESIGN_PLAYERS => "re-sign players",
+        PHASE_FREE_AGENCY => "free agency",
+        _ => "unknown phase",
     }
-}
\ No newline at end of file
+}
+
+pub const POSITIONS: [&str; 5] = ["PG", "SG", "SF", "PF", "C"];
+
+// "nba2019" draws the top 4 picks with the NBA's odds since 2019, "randomLottery" draws every lottery pick with equal
+// odds, and "noLottery" goes by record. Only teams that missed the playoffs are in the lottery, and every round after
+// the first goes by record.
+pub const DRAFT_TYPES: [&str; 3] = ["nba2019", "randomLottery", "noLottery"];
+
+// "soft" lets teams over the cap re-sign their own players and sign anyone for the minimum, "hard" never lets a
+// payroll go over the cap, and "none" has no cap at all
+pub const SALARY_CAP_TYPES: [&str; 3] = ["soft", "hard", "none"];
\ No newline at end of file
@@ -5,5 +5,24 @@ pub fn bound(x: f64, min: f64, max: f64) -> f64 {
     if x > max {
         return max;
     }
-    return x;
+    x
+}
+
+// Ties count as half a win, overtime losses count as losses
+pub fn calc_winp(won: i32, lost: i32, tied: i32, otl: i32) -> f64 {
+    let gp = won + lost + tied + otl;
+    if gp == 0 {
+        return 0.0;
+    }
+    (won as f64 + 0.5 * tied as f64) / gp as f64
+}
+
+// Money is stored in thousands of dollars, shown as $1.5M or $750k
+pub fn format_currency(amount: f64) -> String {
+    let sign = if amount < 0.0 { "-" } else { "" };
+    let amount = amount.abs();
+    if amount >= 1000.0 {
+        return format!("{}${:.1}M", sign, amount / 1000.0);
+    }
+    format!("{}${:.0}k", sign, amount)
 }
\ No newline at end of file
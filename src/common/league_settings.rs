@@ -0,0 +1,502 @@
+use crate::common::types::Conf;
+use crate::common::types::Div;
+use crate::common::types::GameAttributes;
+use crate::common::types::GameAttributesNonLeague;
+use crate::common::types::GameAttributesWithHistory;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+
+// A league settings file, in TOML or JSON. Every key is optional and omitted keys get the default value from
+// GameAttributes::new. Every setting except lid has history, so it can be a plain value, which applies to every
+// season, or a list of changes like [{ value = [5, 7, 7, 7] }, { start = 2010, value = [7, 7, 7, 7] }].
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct ConfSettings {
+    pub cid: u8,
+    pub name: String
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct DivSettings {
+    pub did: u8,
+    pub cid: u8,
+    pub name: String
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct ChangeSettings<T> {
+    // Omitted for the value that applies before any other change
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start: Option<i32>,
+    pub value: T
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum HistorySettings<T> {
+    Changes(Vec<ChangeSettings<T>>),
+    Value(T)
+}
+
+impl<S> HistorySettings<S> {
+    fn map<T>(self, convert: &dyn Fn(S) -> T) -> HistorySettings<T> {
+        match self {
+            HistorySettings::Changes(changes) => HistorySettings::Changes(changes.into_iter().map(|change| ChangeSettings {
+                start: change.start,
+                value: convert(change.value)
+            }).collect()),
+            HistorySettings::Value(value) => HistorySettings::Value(convert(value)),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase", default, deny_unknown_fields)]
+pub struct LeagueSettingsFile {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lid: Option<u8>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fouls_needed_to_foul_out: Option<HistorySettings<i32>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub num_players_on_court: Option<HistorySettings<i32>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quarter_length: Option<HistorySettings<f64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disable_injuries: Option<HistorySettings<bool>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pace: Option<HistorySettings<f64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fouls_until_bonus: Option<HistorySettings<Vec<i32>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub foul_rate_factor: Option<HistorySettings<f64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub turnover_factor: Option<HistorySettings<f64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub draft_type: Option<HistorySettings<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub num_draft_rounds: Option<HistorySettings<i32>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub salary_cap: Option<HistorySettings<f64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub salary_cap_type: Option<HistorySettings<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_payroll: Option<HistorySettings<f64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub luxury_payroll: Option<HistorySettings<f64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub luxury_tax: Option<HistorySettings<f64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_contract: Option<HistorySettings<f64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_contract: Option<HistorySettings<f64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_contract_length: Option<HistorySettings<i32>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_contract_length: Option<HistorySettings<i32>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rookie_contract_lengths: Option<HistorySettings<Vec<i32>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub draft_pick_auto_contract_percent: Option<HistorySettings<f64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub draft_pick_auto_contract_rounds: Option<HistorySettings<i32>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_roster_size: Option<HistorySettings<i32>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_roster_size: Option<HistorySettings<i32>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trade_deadline: Option<HistorySettings<f64>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confs: Option<HistorySettings<Vec<ConfSettings>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub divs: Option<HistorySettings<Vec<DivSettings>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub num_games: Option<HistorySettings<i32>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub num_games_playoff_series: Option<HistorySettings<Vec<u8>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub num_playoff_byes: Option<HistorySettings<u8>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub otl: Option<HistorySettings<bool>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub playoffs_num_teams_div: Option<HistorySettings<u8>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub play_in_type: Option<HistorySettings<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub points_formula: Option<HistorySettings<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tiebreakers: Option<HistorySettings<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ties: Option<HistorySettings<bool>>
+}
+
+fn to_history<T>(name: &str, settings: HistorySettings<T>) -> Result<GameAttributesWithHistory<T>, String> {
+    match settings {
+        HistorySettings::Value(value) => {
+            Ok(GameAttributesWithHistory::new(value))
+        }
+        HistorySettings::Changes(changes) => {
+            let mut changes = changes.into_iter();
+            let first = changes.next();
+            if first.is_none() {
+                return Err(format!("{}: needs at least one value", name));
+            }
+            let first = first.unwrap();
+            if first.start.is_some() {
+                return Err(format!("{}: the first change can't have a start season, it applies to every season before the next change", name));
+            }
+            let mut history = GameAttributesWithHistory::new(first.value);
+            let mut prev_start = i32::MIN;
+            for change in changes {
+                if change.start.is_none() {
+                    return Err(format!("{}: every change after the first needs a start season", name));
+                }
+                let start = change.start.unwrap();
+                if start <= prev_start {
+                    return Err(format!("{}: changes must be in order of start season", name));
+                }
+                prev_start = start;
+                history.set_from(start, change.value);
+            }
+            Ok(history)
+        }
+    }
+}
+
+fn from_history<T: Clone>(history: &GameAttributesWithHistory<T>) -> HistorySettings<T> {
+    let changes: Vec<ChangeSettings<T>> = history.iter().map(|change| ChangeSettings {
+        start: if change.start == i32::MIN {
+            None
+        } else {
+            Some(change.start)
+        },
+        value: change.value.clone()
+    }).collect();
+    if changes.len() == 1 {
+        return HistorySettings::Value(changes.into_iter().next().unwrap().value);
+    }
+    HistorySettings::Changes(changes)
+}
+
+fn to_confs(confs: Vec<ConfSettings>) -> Vec<Conf> {
+    confs.into_iter().map(|conf| Conf {
+        cid: conf.cid,
+        name: conf.name
+    }).collect()
+}
+
+fn to_divs(divs: Vec<DivSettings>) -> Vec<Div> {
+    divs.into_iter().map(|div| Div {
+        did: div.did,
+        cid: div.cid,
+        name: div.name
+    }).collect()
+}
+
+fn from_confs(confs: Vec<Conf>) -> Vec<ConfSettings> {
+    confs.into_iter().map(|conf| ConfSettings {
+        cid: conf.cid,
+        name: conf.name
+    }).collect()
+}
+
+fn from_divs(divs: Vec<Div>) -> Vec<DivSettings> {
+    divs.into_iter().map(|div| DivSettings {
+        did: div.did,
+        cid: div.cid,
+        name: div.name
+    }).collect()
+}
+
+impl LeagueSettingsFile {
+    // Fills omitted keys with defaults and validates the result
+    pub fn to_game_attributes(self) -> Result<GameAttributes, String> {
+        let mut game_attributes = GameAttributes::new(None);
+        if self.lid.is_some() {
+            game_attributes.non_league = Some(GameAttributesNonLeague {
+                lid: self.lid
+            });
+        }
+
+        let league_with_history = game_attributes.league_with_history.as_mut().unwrap();
+        let league = &mut league_with_history.game_attributes_league;
+        if let Some(fouls_needed_to_foul_out) = self.fouls_needed_to_foul_out {
+            league.fouls_needed_to_foul_out = to_history("foulsNeededToFoulOut", fouls_needed_to_foul_out)?;
+        }
+        if let Some(num_players_on_court) = self.num_players_on_court {
+            league.num_players_on_court = to_history("numPlayersOnCourt", num_players_on_court)?;
+        }
+        if let Some(quarter_length) = self.quarter_length {
+            league.quarter_length = to_history("quarterLength", quarter_length)?;
+        }
+        if let Some(disable_injuries) = self.disable_injuries {
+            league.disable_injuries = to_history("disableInjuries", disable_injuries)?;
+        }
+        if let Some(pace) = self.pace {
+            league.pace = to_history("pace", pace)?;
+        }
+        if let Some(fouls_until_bonus) = self.fouls_until_bonus {
+            league.fouls_until_bonus = to_history("foulsUntilBonus", fouls_until_bonus)?;
+        }
+        if let Some(foul_rate_factor) = self.foul_rate_factor {
+            league.foul_rate_factor = to_history("foulRateFactor", foul_rate_factor)?;
+        }
+        if let Some(turnover_factor) = self.turnover_factor {
+            league.turnover_factor = to_history("turnoverFactor", turnover_factor)?;
+        }
+        if let Some(draft_type) = self.draft_type {
+            league.draft_type = to_history("draftType", draft_type)?;
+        }
+        if let Some(num_draft_rounds) = self.num_draft_rounds {
+            league.num_draft_rounds = to_history("numDraftRounds", num_draft_rounds)?;
+        }
+        if let Some(salary_cap) = self.salary_cap {
+            league.salary_cap = to_history("salaryCap", salary_cap)?;
+        }
+        if let Some(salary_cap_type) = self.salary_cap_type {
+            league.salary_cap_type = to_history("salaryCapType", salary_cap_type)?;
+        }
+        if let Some(min_payroll) = self.min_payroll {
+            league.min_payroll = to_history("minPayroll", min_payroll)?;
+        }
+        if let Some(luxury_payroll) = self.luxury_payroll {
+            league.luxury_payroll = to_history("luxuryPayroll", luxury_payroll)?;
+        }
+        if let Some(luxury_tax) = self.luxury_tax {
+            league.luxury_tax = to_history("luxuryTax", luxury_tax)?;
+        }
+        if let Some(min_contract) = self.min_contract {
+            league.min_contract = to_history("minContract", min_contract)?;
+        }
+        if let Some(max_contract) = self.max_contract {
+            league.max_contract = to_history("maxContract", max_contract)?;
+        }
+        if let Some(min_contract_length) = self.min_contract_length {
+            league.min_contract_length = to_history("minContractLength", min_contract_length)?;
+        }
+        if let Some(max_contract_length) = self.max_contract_length {
+            league.max_contract_length = to_history("maxContractLength", max_contract_length)?;
+        }
+        if let Some(rookie_contract_lengths) = self.rookie_contract_lengths {
+            league.rookie_contract_lengths = to_history("rookieContractLengths", rookie_contract_lengths)?;
+        }
+        if let Some(draft_pick_auto_contract_percent) = self.draft_pick_auto_contract_percent {
+            league.draft_pick_auto_contract_percent = to_history("draftPickAutoContractPercent", draft_pick_auto_contract_percent)?;
+        }
+        if let Some(draft_pick_auto_contract_rounds) = self.draft_pick_auto_contract_rounds {
+            league.draft_pick_auto_contract_rounds = to_history("draftPickAutoContractRounds", draft_pick_auto_contract_rounds)?;
+        }
+        if let Some(max_roster_size) = self.max_roster_size {
+            league.max_roster_size = to_history("maxRosterSize", max_roster_size)?;
+        }
+        if let Some(min_roster_size) = self.min_roster_size {
+            league.min_roster_size = to_history("minRosterSize", min_roster_size)?;
+        }
+        if let Some(trade_deadline) = self.trade_deadline {
+            league.trade_deadline = to_history("tradeDeadline", trade_deadline)?;
+        }
+
+        if let Some(confs) = self.confs {
+            league_with_history.confs = to_history("confs", confs.map(&to_confs))?;
+        }
+        if let Some(divs) = self.divs {
+            league_with_history.divs = to_history("divs", divs.map(&to_divs))?;
+        }
+        if let Some(num_games) = self.num_games {
+            league_with_history.num_games = to_history("numGames", num_games)?;
+        }
+        if let Some(num_games_playoff_series) = self.num_games_playoff_series {
+            league_with_history.num_games_playoff_series = to_history("numGamesPlayoffSeries", num_games_playoff_series)?;
+        }
+        if let Some(num_playoff_byes) = self.num_playoff_byes {
+            league_with_history.num_playoff_byes = to_history("numPlayoffByes", num_playoff_byes)?;
+        }
+        if let Some(otl) = self.otl {
+            league_with_history.otl = to_history("otl", otl)?;
+        }
+        if let Some(playoffs_num_teams_div) = self.playoffs_num_teams_div {
+            league_with_history.playoffs_num_teams_div = to_history("playoffsNumTeamsDiv", playoffs_num_teams_div)?;
+        }
+        if let Some(play_in_type) = self.play_in_type {
+            league_with_history.play_in_type = to_history("playInType", play_in_type)?;
+        }
+        if let Some(points_formula) = self.points_formula {
+            league_with_history.points_formula = to_history("pointsFormula", points_formula)?;
+        }
+        if let Some(tiebreakers) = self.tiebreakers {
+            league_with_history.tiebreakers = to_history("tiebreakers", tiebreakers)?;
+        }
+        if let Some(ties) = self.ties {
+            league_with_history.ties = to_history("ties", ties)?;
+        }
+
+        league_with_history.game_attributes_league.validate()?;
+        league_with_history.validate()?;
+
+        Ok(game_attributes)
+    }
+
+    // Writes out every setting, so the file documents the values actually in use
+    pub fn from_game_attributes(game_attributes: &GameAttributes) -> Self {
+        let league_with_history = game_attributes.league_with_history.as_ref().unwrap();
+        let league = &league_with_history.game_attributes_league;
+        LeagueSettingsFile {
+            lid: game_attributes.non_league.as_ref().and_then(|non_league| non_league.lid),
+            fouls_needed_to_foul_out: Some(from_history(&league.fouls_needed_to_foul_out)),
+            num_players_on_court: Some(from_history(&league.num_players_on_court)),
+            quarter_length: Some(from_history(&league.quarter_length)),
+            disable_injuries: Some(from_history(&league.disable_injuries)),
+            pace: Some(from_history(&league.pace)),
+            fouls_until_bonus: Some(from_history(&league.fouls_until_bonus)),
+            foul_rate_factor: Some(from_history(&league.foul_rate_factor)),
+            turnover_factor: Some(from_history(&league.turnover_factor)),
+            draft_type: Some(from_history(&league.draft_type)),
+            num_draft_rounds: Some(from_history(&league.num_draft_rounds)),
+            salary_cap: Some(from_history(&league.salary_cap)),
+            salary_cap_type: Some(from_history(&league.salary_cap_type)),
+            min_payroll: Some(from_history(&league.min_payroll)),
+            luxury_payroll: Some(from_history(&league.luxury_payroll)),
+            luxury_tax: Some(from_history(&league.luxury_tax)),
+            min_contract: Some(from_history(&league.min_contract)),
+            max_contract: Some(from_history(&league.max_contract)),
+            min_contract_length: Some(from_history(&league.min_contract_length)),
+            max_contract_length: Some(from_history(&league.max_contract_length)),
+            rookie_contract_lengths: Some(from_history(&league.rookie_contract_lengths)),
+            draft_pick_auto_contract_percent: Some(from_history(&league.draft_pick_auto_contract_percent)),
+            draft_pick_auto_contract_rounds: Some(from_history(&league.draft_pick_auto_contract_rounds)),
+            max_roster_size: Some(from_history(&league.max_roster_size)),
+            min_roster_size: Some(from_history(&league.min_roster_size)),
+            trade_deadline: Some(from_history(&league.trade_deadline)),
+            confs: Some(from_history(&league_with_history.confs).map(&from_confs)),
+            divs: Some(from_history(&league_with_history.divs).map(&from_divs)),
+            num_games: Some(from_history(&league_with_history.num_games)),
+            num_games_playoff_series: Some(from_history(&league_with_history.num_games_playoff_series)),
+            num_playoff_byes: Some(from_history(&league_with_history.num_playoff_byes)),
+            otl: Some(from_history(&league_with_history.otl)),
+            playoffs_num_teams_div: Some(from_history(&league_with_history.playoffs_num_teams_div)),
+            play_in_type: Some(from_history(&league_with_history.play_in_type)),
+            points_formula: Some(from_history(&league_with_history.points_formula)),
+            tiebreakers: Some(from_history(&league_with_history.tiebreakers)),
+            ties: Some(from_history(&league_with_history.ties))
+        }
+    }
+}
+
+fn parse<T: DeserializeOwned>(text: &str, is_json: bool) -> Result<T, String> {
+    if is_json {
+        return serde_json::from_str(text).map_err(|err| err.to_string());
+    }
+    toml::from_str(text).map_err(|err| err.to_string())
+}
+
+pub fn from_toml(text: &str) -> Result<GameAttributes, String> {
+    let file: LeagueSettingsFile = parse(text, false)?;
+    file.to_game_attributes()
+}
+
+pub fn from_json(text: &str) -> Result<GameAttributes, String> {
+    let file: LeagueSettingsFile = parse(text, true)?;
+    file.to_game_attributes()
+}
+
+pub fn to_toml(game_attributes: &GameAttributes) -> String {
+    toml::to_string_pretty(&LeagueSettingsFile::from_game_attributes(game_attributes)).unwrap()
+}
+
+pub fn to_json(game_attributes: &GameAttributes) -> String {
+    serde_json::to_string_pretty(&LeagueSettingsFile::from_game_attributes(game_attributes)).unwrap()
+}
+
+fn is_json_path(path: &Path) -> bool {
+    path.extension().map(|ext| ext == "json").unwrap_or(false)
+}
+
+// .json files are read as JSON, anything else as TOML
+pub fn load_file(path: &Path) -> Result<GameAttributes, String> {
+    let text = fs::read_to_string(path);
+    if text.is_err() {
+        return Err(format!("Could not read {}: {}", path.display(), text.err().unwrap()));
+    }
+    let game_attributes = if is_json_path(path) {
+        from_json(&text.unwrap())
+    } else {
+        from_toml(&text.unwrap())
+    };
+    game_attributes.map_err(|err| format!("{}: {}", path.display(), err))
+}
+
+// Written in the same format load_file reads from path
+pub fn save_file(path: &Path, game_attributes: &GameAttributes) -> Result<(), String> {
+    let text = if is_json_path(path) {
+        to_json(game_attributes)
+    } else {
+        to_toml(game_attributes)
+    };
+    fs::write(path, text).map_err(|err| format!("Could not write {}: {}", path.display(), err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::worker::util::g::G;
+
+    #[test]
+    fn history_changes_apply_from_their_start_season() {
+        let game_attributes = from_toml("
+            ties = true
+            numGamesPlayoffSeries = [{ value = [5, 7, 7, 7] }, { start = 2030, value = [7, 7, 7, 7] }]
+            salaryCap = [{ value = 140000 }, { start = 2030, value = 150000 }]
+        ").unwrap();
+        let mut g = G::new(game_attributes, 2029).unwrap();
+        assert!(g.ties());
+        assert_eq!(*g.num_games_playoff_series(), vec![5, 7, 7, 7]);
+        assert_eq!(g.salary_cap(), 140000.0);
+        g.set_season(2030);
+        assert_eq!(*g.num_games_playoff_series(), vec![7, 7, 7, 7]);
+        assert_eq!(g.salary_cap(), 150000.0);
+        // Omitted keys get the defaults
+        assert_eq!(g.quarter_length(), 12.0);
+    }
+
+    #[test]
+    fn invalid_settings() {
+        let error = |text: &str| from_toml(text).err().unwrap();
+        assert!(error("numGamez = 82").contains("unknown field `numGamez`"));
+        assert_eq!(
+            error("numPlayoffByes = [{ start = 2030, value = 4 }]"),
+            "numPlayoffByes: the first change can't have a start season, it applies to every season before the next change"
+        );
+        assert_eq!(
+            error("numPlayoffByes = [{ value = 2 }, { value = 4 }]"),
+            "numPlayoffByes: every change after the first needs a start season"
+        );
+        assert_eq!(
+            error("numPlayoffByes = [{ value = 2 }, { start = 2030, value = 4 }, { start = 2020, value = 3 }]"),
+            "numPlayoffByes: changes must be in order of start season"
+        );
+        assert_eq!(
+            error("numGamesPlayoffSeries = [7, 4, 7]"),
+            "numGamesPlayoffSeries: every round needs an odd number of games, got 4"
+        );
+    }
+
+    #[test]
+    fn round_trips() {
+        let game_attributes = from_json(r#"{
+            "salaryCapType": "hard",
+            "numPlayoffByes": [{ "value": 2 }, { "start": 2030, "value": 4 }]
+        }"#).unwrap();
+        let toml = to_toml(&game_attributes);
+        assert_eq!(to_toml(&from_toml(&toml).unwrap()), toml);
+        let json = to_json(&game_attributes);
+        assert_eq!(to_json(&from_json(&json).unwrap()), json);
+        assert_eq!(toml, to_toml(&from_json(&json).unwrap()));
+    }
+}
\ No newline at end of file
@@ -1,2 +1,7 @@
 pub mod types;
-pub mod helpers;
\ No newline at end of file
+pub mod helpers;
+pub mod constants;
+pub mod points_formula;
+pub mod league_settings;
+pub mod team_infos;
+pub mod names;
\ No newline at end of file
@@ -0,0 +1,132 @@
+pub struct CountryNames {
+    pub country: &'static str,
+    // Relative share of generated players born there
+    pub weight: f64,
+    pub first: &'static [&'static str],
+    pub last: &'static [&'static str]
+}
+
+// Name lists for generated players, by country of birth
+pub const NAMES: [CountryNames; 16] = [
+    CountryNames {
+        country: "USA",
+        weight: 70.0,
+        first: &[
+            "Aaron", "Adrian", "Alex", "Andre", "Anthony", "Antonio", "Brandon", "Brian", "Bryce", "Cameron", "Carl",
+            "Chris", "Cody", "Corey", "Damian", "Daniel", "Darius", "David", "DeAndre", "Derrick", "Devin", "Donovan",
+            "Dwayne", "Eric", "Evan", "Gary", "Isaiah", "Jalen", "Jamal", "James", "Jason", "Jaylen", "Jeff", "Jerome",
+            "John", "Jordan", "Josh", "Justin", "Kevin", "Kyle", "Lamar", "Malik", "Marcus", "Mark", "Michael", "Mike",
+            "Nate", "Nick", "Paul", "Quentin", "Ray", "Robert", "Ryan", "Scott", "Shawn", "Terrence", "Tony", "Travis",
+            "Trey", "Tyler", "Tyrese", "Victor", "Will", "Zach",
+        ],
+        last: &[
+            "Adams", "Allen", "Anderson", "Bailey", "Baker", "Banks", "Bell", "Brooks", "Brown", "Bryant", "Butler",
+            "Campbell", "Carter", "Clark", "Coleman", "Collins", "Cooper", "Davis", "Edwards", "Evans", "Fisher",
+            "Ford", "Foster", "Gordon", "Graham", "Grant", "Green", "Griffin", "Hall", "Harris", "Hayes", "Henderson",
+            "Hill", "Holmes", "Howard", "Hughes", "Jackson", "James", "Jenkins", "Johnson", "Jones", "King", "Lewis",
+            "Marshall", "Martin", "Miller", "Mitchell", "Moore", "Morris", "Murray", "Nelson", "Parker", "Patterson",
+            "Perry", "Powell", "Reed", "Richardson", "Roberts", "Robinson", "Russell", "Sanders", "Scott", "Simmons",
+            "Smith", "Stewart", "Taylor", "Thomas", "Thompson", "Turner", "Walker", "Wallace", "Washington", "Watson",
+            "White", "Williams", "Wilson", "Wright", "Young",
+        ]
+    },
+    CountryNames {
+        country: "Canada",
+        weight: 4.0,
+        first: &["Andrew", "Ben", "Cory", "Dillon", "Dwight", "Jamal", "Kelly", "Kyle", "Luguentz", "Nickeil", "Olivier", "RJ", "Shai", "Trey", "Tristan"],
+        last: &["Alexander", "Barrett", "Bennett", "Boucher", "Brooks", "Cyr", "Dort", "Joseph", "Lyles", "Murray", "Nembhard", "Olynyk", "Powell", "Thompson", "Wiggins"]
+    },
+    CountryNames {
+        country: "France",
+        weight: 3.0,
+        first: &["Alexandre", "Boris", "Evan", "Frank", "Guerschon", "Killian", "Mathias", "Nicolas", "Rudy", "Sekou", "Theo", "Timothe", "Victor", "Vincent", "Zaccharie"],
+        last: &["Batum", "Coulibaly", "Diaw", "Doumbouya", "Fournier", "Gobert", "Lessort", "Luwawu", "Maledon", "Ntilikina", "Parker", "Poirier", "Risacher", "Sarr", "Yabusele"]
+    },
+    CountryNames {
+        country: "Spain",
+        weight: 2.0,
+        first: &["Alberto", "Alejandro", "Carlos", "Dario", "Guillermo", "Jaime", "Jorge", "Jose", "Juan", "Marc", "Pau", "Ricky", "Rudy", "Sergio", "Willy"],
+        last: &["Abrines", "Aldama", "Brizuela", "Calderon", "Diaz", "Fernandez", "Garcia", "Garuba", "Gasol", "Hernangomez", "Ibaka", "Llull", "Lopez", "Navarro", "Rubio"]
+    },
+    CountryNames {
+        country: "Serbia",
+        weight: 2.0,
+        first: &["Aleksandar", "Bogdan", "Boban", "Dejan", "Dragan", "Marko", "Milos", "Miroslav", "Nemanja", "Nikola", "Novak", "Stefan", "Uros", "Vasilije", "Vladimir"],
+        last: &["Bjelica", "Bogdanovic", "Jokic", "Jovic", "Kalinic", "Krstic", "Marjanovic", "Micic", "Milicic", "Nedovic", "Petrovic", "Radulovic", "Stojakovic", "Teodosic", "Vucevic"]
+    },
+    CountryNames {
+        country: "Australia",
+        weight: 2.0,
+        first: &["Aron", "Ben", "Dante", "Dyson", "Jock", "Joe", "Josh", "Luc", "Matisse", "Matthew", "Patty", "Ryan", "Thon", "Will", "Xavier"],
+        last: &["Baynes", "Bogut", "Cooks", "Daniels", "Dellavedova", "Exum", "Giddey", "Green", "Ingles", "Landale", "Longley", "Maker", "Mills", "Simmons", "Thybulle"]
+    },
+    CountryNames {
+        country: "Nigeria",
+        weight: 2.0,
+        first: &["Abdul", "Akeem", "Chimezie", "Chuma", "Ekpe", "Emeka", "Festus", "Ike", "Josh", "Michael", "Obinna", "Olumide", "Precious", "Uche", "Udoka"],
+        last: &["Achiuwa", "Adebayo", "Azubuike", "Diogu", "Ezeli", "Ibekwe", "Metu", "Nwaba", "Nwora", "Okafor", "Okogie", "Okongwu", "Olajuwon", "Oladipo", "Udoh"]
+    },
+    CountryNames {
+        country: "Germany",
+        weight: 2.0,
+        first: &["Daniel", "Dennis", "Detlef", "Dirk", "Franz", "Isaac", "Johannes", "Maodo", "Maxi", "Moritz", "Niels", "Paul", "Robin", "Tristan", "Tibor"],
+        last: &["Bonga", "Da Silva", "Giffey", "Hartenstein", "Kleber", "Lo", "Nowitzki", "Obst", "Pleiss", "Schroder", "Schrempf", "Theis", "Thiemann", "Voigtmann", "Wagner"]
+    },
+    CountryNames {
+        country: "Lithuania",
+        weight: 1.5,
+        first: &["Arvydas", "Darius", "Deividas", "Domantas", "Donatas", "Ignas", "Jonas", "Linas", "Mantas", "Marius", "Rimas", "Paulius", "Sarunas", "Tadas", "Zydrunas"],
+        last: &["Grigonis", "Ilgauskas", "Jasikevicius", "Jokubaitis", "Kleiza", "Kuzminskas", "Motiejunas", "Sabonis", "Sirvydis", "Songaila", "Valanciunas", "Kurtinaitis", "Macijauskas", "Brazdeikis", "Kavaliauskas"]
+    },
+    CountryNames {
+        country: "Brazil",
+        weight: 1.5,
+        first: &["Anderson", "Bruno", "Cristiano", "Gui", "Leandro", "Lucas", "Marcelo", "Marcelinho", "Nene", "Oscar", "Rafael", "Raul", "Thiago", "Tiago", "Yago"],
+        last: &["Barbosa", "Caboclo", "Felicio", "Hilario", "Huertas", "Lima", "Machado", "Melli", "Neto", "Nogueira", "Santos", "Schmidt", "Silva", "Splitter", "Varejao"]
+    },
+    CountryNames {
+        country: "Greece",
+        weight: 1.5,
+        first: &["Alex", "Andreas", "Dimitris", "Georgios", "Giannis", "Ioannis", "Kostas", "Kosta", "Nick", "Nikos", "Panagiotis", "Theo", "Thanasis", "Tyler", "Vassilis"],
+        last: &["Antetokounmpo", "Bourousis", "Calathes", "Diamantidis", "Dorsey", "Galis", "Koufos", "Mavrokefalidis", "Papagiannis", "Papaloukas", "Papanikolaou", "Printezis", "Schortsanitis", "Sloukas", "Spanoulis"]
+    },
+    CountryNames {
+        country: "Croatia",
+        weight: 1.0,
+        first: &["Ante", "Bojan", "Dario", "Dino", "Drazen", "Ivica", "Luka", "Mario", "Roko", "Toni", "Zoran", "Kresimir", "Marko", "Stipe", "Tomislav"],
+        last: &["Bogdanovic", "Cosic", "Hezonja", "Kukoc", "Petrovic", "Planinic", "Radja", "Saric", "Tomic", "Ukic", "Zubac", "Zizic", "Prkacin", "Samanic", "Radoncic"]
+    },
+    CountryNames {
+        country: "Turkey",
+        weight: 1.0,
+        first: &["Alperen", "Cedi", "Enes", "Ersan", "Furkan", "Hedo", "Mehmet", "Omer", "Semih", "Sertac", "Shane", "Mustafa", "Kerem", "Ercan", "Tarik"],
+        last: &["Asik", "Erden", "Ilyasova", "Kanter", "Korkmaz", "Okur", "Osman", "Sengun", "Turkoglu", "Sanli", "Yurtseven", "Bitim", "Sipahi", "Arslan", "Guler"]
+    },
+    CountryNames {
+        country: "Argentina",
+        weight: 1.0,
+        first: &["Andres", "Carlos", "Facundo", "Fabricio", "Gabriel", "Leandro", "Luis", "Manu", "Nicolas", "Pablo", "Patricio", "Juan", "Marcos", "Luca", "Francisco"],
+        last: &["Bolmaro", "Brussino", "Campazzo", "Delfino", "Deck", "Garino", "Ginobili", "Laprovittola", "Nocioni", "Oberto", "Prigioni", "Scola", "Herrmann", "Vildoza", "Gonzalez"]
+    },
+    CountryNames {
+        country: "Slovenia",
+        weight: 0.5,
+        first: &["Anthony", "Beno", "Boban", "Goran", "Jaka", "Klemen", "Luka", "Matej", "Primoz", "Rasho", "Sasha", "Uros", "Vlatko", "Zoran", "Gregor"],
+        last: &["Blazic", "Brezec", "Cancar", "Doncic", "Dragic", "Lorbek", "Nachbar", "Nesterovic", "Prepelic", "Randolph", "Slokar", "Tobey", "Udrih", "Vidmar", "Zupan"]
+    },
+    CountryNames {
+        country: "Cameroon",
+        weight: 1.0,
+        first: &["Christian", "Joel", "Luc", "Pascal", "Ruben", "Yannick", "Jean", "Ulrich", "Franck", "Samuel", "Paul", "Alain", "Landry", "Serge", "Patrick"],
+        last: &["Embiid", "Mbah a Moute", "Siakam", "Nembot", "Koumadje", "Mbala", "Ngando", "Tchuente", "Fotsing", "Eyenga", "Ngongba", "Kamdem", "Mbaye", "Njoya", "Abega"]
+    },
+];
+
+// Colleges for generated players born in the USA or Canada. The rest come straight from overseas.
+pub const COLLEGES: [&str; 40] = [
+    "Arizona", "Arkansas", "Auburn", "Baylor", "Butler", "California", "Cincinnati", "Connecticut", "Creighton",
+    "Duke", "Florida", "Georgetown", "Gonzaga", "Houston", "Illinois", "Indiana", "Iowa", "Kansas", "Kentucky",
+    "Louisville", "LSU", "Marquette", "Maryland", "Memphis", "Miami (FL)", "Michigan", "Michigan State", "Missouri",
+    "North Carolina", "Ohio State", "Oklahoma", "Oregon", "Purdue", "Stanford", "Syracuse", "Texas", "UCLA",
+    "Villanova", "Virginia", "Wake Forest",
+];
\ No newline at end of file
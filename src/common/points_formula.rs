@@ -0,0 +1,226 @@
+// Parses and evaluates points_formula strings like "2*W + OTL". Only numbers, the variables W, L, T and OTL,
+// + - * / and parentheses are allowed, so a formula from a league file can never do anything but arithmetic.
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Num(f64),
+    Var(String),
+    Plus,
+    Minus,
+    Times,
+    Divide,
+    LParen,
+    RParen
+}
+
+#[derive(Clone, Debug)]
+enum Node {
+    Num(f64),
+    Var(String),
+    Neg(Box<Node>),
+    BinOp(Token, Box<Node>, Box<Node>)
+}
+
+#[derive(Clone, Debug)]
+pub struct PointsFormula {
+    root: Node
+}
+
+const VARIABLES: [&str; 4] = ["W", "L", "T", "OTL"];
+
+fn tokenize(formula: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = formula.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c.is_ascii_digit() || c == '.' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                i += 1;
+            }
+            let text: String = chars[start..i].iter().collect();
+            let num = text.parse::<f64>();
+            if num.is_err() {
+                return Err(format!("Invalid number \"{}\"", text));
+            }
+            tokens.push(Token::Num(num.unwrap()));
+        } else if c.is_ascii_alphabetic() {
+            let start = i;
+            while i < chars.len() && chars[i].is_ascii_alphabetic() {
+                i += 1;
+            }
+            let text: String = chars[start..i].iter().collect();
+            if !VARIABLES.contains(&text.as_str()) {
+                return Err(format!("Unknown variable \"{}\", expected one of W, L, T, OTL", text));
+            }
+            tokens.push(Token::Var(text));
+        } else {
+            let token = match c {
+                '+' => Token::Plus,
+                '-' => Token::Minus,
+                '*' => Token::Times,
+                '/' => Token::Divide,
+                '(' => Token::LParen,
+                ')' => Token::RParen,
+                _ => {
+                    return Err(format!("Unexpected character \"{}\"", c));
+                }
+            };
+            tokens.push(token);
+            i += 1;
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    // expr = term (("+" | "-") term)*
+    fn parse_expr(&mut self) -> Result<Node, String> {
+        let mut node = self.parse_term()?;
+        while self.peek() == Some(&Token::Plus) || self.peek() == Some(&Token::Minus) {
+            let op = self.next().unwrap();
+            let right = self.parse_term()?;
+            node = Node::BinOp(op, Box::new(node), Box::new(right));
+        }
+        Ok(node)
+    }
+
+    // term = factor (("*" | "/") factor)*
+    fn parse_term(&mut self) -> Result<Node, String> {
+        let mut node = self.parse_factor()?;
+        while self.peek() == Some(&Token::Times) || self.peek() == Some(&Token::Divide) {
+            let op = self.next().unwrap();
+            let right = self.parse_factor()?;
+            node = Node::BinOp(op, Box::new(node), Box::new(right));
+        }
+        Ok(node)
+    }
+
+    // factor = number | variable | "-" factor | "(" expr ")"
+    fn parse_factor(&mut self) -> Result<Node, String> {
+        match self.next() {
+            Some(Token::Num(num)) => Ok(Node::Num(num)),
+            Some(Token::Var(var)) => Ok(Node::Var(var)),
+            Some(Token::Minus) => Ok(Node::Neg(Box::new(self.parse_factor()?))),
+            Some(Token::LParen) => {
+                let node = self.parse_expr()?;
+                if self.next() != Some(Token::RParen) {
+                    return Err("Missing closing parenthesis".to_string());
+                }
+                Ok(node)
+            }
+            Some(token) => Err(format!("Unexpected {:?}", token)),
+            None => Err("Unexpected end of formula".to_string()),
+        }
+    }
+}
+
+fn evaluate_node(node: &Node, won: f64, lost: f64, tied: f64, otl: f64) -> f64 {
+    match node {
+        Node::Num(num) => *num,
+        Node::Var(var) => match var.as_str() {
+            "W" => won,
+            "L" => lost,
+            "T" => tied,
+            _ => otl,
+        },
+        Node::Neg(inner) => -evaluate_node(inner, won, lost, tied, otl),
+        Node::BinOp(op, left, right) => {
+            let left = evaluate_node(left, won, lost, tied, otl);
+            let right = evaluate_node(right, won, lost, tied, otl);
+            match op {
+                Token::Plus => left + right,
+                Token::Minus => left - right,
+                Token::Times => left * right,
+                _ => {
+                    if right == 0.0 {
+                        0.0
+                    } else {
+                        left / right
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl PointsFormula {
+    pub fn new(formula: &str) -> Result<Self, String> {
+        let tokens = tokenize(formula)?;
+        if tokens.is_empty() {
+            return Err("Points formula is empty".to_string());
+        }
+        let mut parser = Parser {
+            tokens,
+            pos: 0
+        };
+        let root = parser.parse_expr()?;
+        if parser.pos < parser.tokens.len() {
+            return Err(format!("Unexpected {:?}", parser.tokens[parser.pos]));
+        }
+        Ok(PointsFormula {
+            root
+        })
+    }
+
+    pub fn evaluate(&self, won: i32, lost: i32, tied: i32, otl: i32) -> f64 {
+        evaluate_node(&self.root, won as f64, lost as f64, tied as f64, otl as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_error(formula: &str) -> String {
+        PointsFormula::new(formula).unwrap_err()
+    }
+
+    #[test]
+    fn evaluates_with_precedence() {
+        let points_formula = PointsFormula::new("2*W + OTL").unwrap();
+        assert_eq!(points_formula.evaluate(10, 5, 0, 3), 23.0);
+
+        let points_formula = PointsFormula::new("3 + 2 * (W - L) / -2").unwrap();
+        assert_eq!(points_formula.evaluate(6, 2, 0, 0), -1.0);
+    }
+
+    #[test]
+    fn division_by_zero_is_zero() {
+        let points_formula = PointsFormula::new("W / L").unwrap();
+        assert_eq!(points_formula.evaluate(4, 0, 0, 0), 0.0);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(parse_error(""), "Points formula is empty");
+        assert_eq!(parse_error("   "), "Points formula is empty");
+        assert_eq!(parse_error("2*X"), "Unknown variable \"X\", expected one of W, L, T, OTL");
+        assert_eq!(parse_error("w"), "Unknown variable \"w\", expected one of W, L, T, OTL");
+        assert_eq!(parse_error("W % 2"), "Unexpected character \"%\"");
+        assert_eq!(parse_error("1.2.3"), "Invalid number \"1.2.3\"");
+        assert_eq!(parse_error("(W + 1"), "Missing closing parenthesis");
+        assert_eq!(parse_error("W +"), "Unexpected end of formula");
+        assert_eq!(parse_error("W L"), "Unexpected Var(\"L\")");
+        assert_eq!(parse_error("* W"), "Unexpected Times");
+        assert_eq!(parse_error("W)"), "Unexpected RParen");
+    }
+}
\ No newline at end of file
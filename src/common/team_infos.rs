@@ -0,0 +1,42 @@
+pub struct TeamInfo {
+    pub abbrev: &'static str,
+    pub region: &'static str,
+    pub name: &'static str,
+    // Millions
+    pub pop: f64
+}
+
+// Default teams for new random leagues, grouped so that taking them in order fills the default divisions
+// (Atlantic, Central, Southeast, Southwest, Northwest, Pacific) 5 at a time
+pub const TEAM_INFOS: [TeamInfo; 30] = [
+    TeamInfo { abbrev: "BOS", region: "Boston", name: "Massacre", pop: 7.3 },
+    TeamInfo { abbrev: "MON", region: "Montreal", name: "Mounties", pop: 4.1 },
+    TeamInfo { abbrev: "NYC", region: "New York", name: "Bullies", pop: 20.1 },
+    TeamInfo { abbrev: "PHI", region: "Philadelphia", name: "Phantoms", pop: 5.6 },
+    TeamInfo { abbrev: "TOR", region: "Toronto", name: "Beavers", pop: 6.2 },
+    TeamInfo { abbrev: "CHI", region: "Chicago", name: "Whirlwinds", pop: 9.1 },
+    TeamInfo { abbrev: "CIN", region: "Cincinnati", name: "Riots", pop: 1.8 },
+    TeamInfo { abbrev: "CLE", region: "Cleveland", name: "Curses", pop: 1.7 },
+    TeamInfo { abbrev: "DET", region: "Detroit", name: "Muscle", pop: 4.1 },
+    TeamInfo { abbrev: "PIT", region: "Pittsburgh", name: "Rivers", pop: 1.7 },
+    TeamInfo { abbrev: "ATL", region: "Atlanta", name: "Gold", pop: 5.3 },
+    TeamInfo { abbrev: "BAL", region: "Baltimore", name: "Crabs", pop: 2.2 },
+    TeamInfo { abbrev: "MIA", region: "Miami", name: "Cyclones", pop: 5.8 },
+    TeamInfo { abbrev: "TPA", region: "Tampa", name: "Turtles", pop: 2.9 },
+    TeamInfo { abbrev: "WAS", region: "Washington", name: "Signals", pop: 5.2 },
+    TeamInfo { abbrev: "DAL", region: "Dallas", name: "Snipers", pop: 6.8 },
+    TeamInfo { abbrev: "HOU", region: "Houston", name: "Apollos", pop: 6.7 },
+    TeamInfo { abbrev: "MXC", region: "Mexico City", name: "Aztecs", pop: 20.5 },
+    TeamInfo { abbrev: "SA", region: "San Antonio", name: "Churros", pop: 2.3 },
+    TeamInfo { abbrev: "STL", region: "St. Louis", name: "Spirits", pop: 2.2 },
+    TeamInfo { abbrev: "DEN", region: "Denver", name: "High", pop: 2.9 },
+    TeamInfo { abbrev: "MIN", region: "Minneapolis", name: "Freezers", pop: 3.3 },
+    TeamInfo { abbrev: "POR", region: "Portland", name: "Roses", pop: 2.0 },
+    TeamInfo { abbrev: "SEA", region: "Seattle", name: "Symphony", pop: 3.5 },
+    TeamInfo { abbrev: "VAN", region: "Vancouver", name: "Whalers", pop: 2.3 },
+    TeamInfo { abbrev: "LA", region: "Los Angeles", name: "Earthquakes", pop: 12.5 },
+    TeamInfo { abbrev: "LV", region: "Las Vegas", name: "Blue Chips", pop: 2.2 },
+    TeamInfo { abbrev: "PHO", region: "Phoenix", name: "Vultures", pop: 4.3 },
+    TeamInfo { abbrev: "SD", region: "San Diego", name: "Pandas", pop: 2.9 },
+    TeamInfo { abbrev: "SF", region: "San Francisco", name: "Venture", pop: 6.2 },
+];
\ No newline at end of file
@@ -1,95 +1,1078 @@
+use crate::common::constants::DEFAULT_TIEBREAKERS;
+use crate::common::constants::DRAFT_TYPES;
+use crate::common::constants::PLAY_IN_TYPES;
+use crate::common::constants::SALARY_CAP_TYPES;
+use crate::common::constants::TIEBREAKERS;
+use crate::common::points_formula::PointsFormula;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Map;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
 
-
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct PlayerInjury {
-    gamesRemaining: u8,
-    injuryType: String,
-    score: Option<u8>
+    pub games_remaining: u8,
+    #[serde(rename = "type")]
+    pub injury_type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub score: Option<u8>
+}
+
+// Raw ratings for one season, on a 0-100 scale
+#[derive(Clone, Serialize, Deserialize)]
+pub struct PlayerRatings {
+    pub season: i32,
+    pub hgt: f64,
+    pub stre: f64,
+    pub spd: f64,
+    pub jmp: f64,
+    pub endu: f64,
+    pub ins: f64,
+    pub dnk: f64,
+    pub ft: f64,
+    pub fg: f64,
+    pub tp: f64,
+    pub oiq: f64,
+    pub diq: f64,
+    pub drb: f64,
+    pub pss: f64,
+    pub reb: f64,
+    #[serde(default)]
+    pub pos: String,
+    #[serde(default)]
+    pub skills: Vec<String>,
+    #[serde(default)]
+    pub ovr: f64,
+    #[serde(default)]
+    pub pot: f64,
+    // ovr at each position
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub ovrs: BTreeMap<String, f64>,
+    // Anything else in imported ratings, like BBGM's fuzz and injury adjusted ratings, so it can be reported
+    #[serde(flatten, skip_serializing)]
+    pub extra: Map<String, Value>
+}
+
+impl PlayerRatings {
+    pub fn get(&self, key: &str) -> Option<f64> {
+        match key {
+            "hgt" => Some(self.hgt),
+            "stre" => Some(self.stre),
+            "spd" => Some(self.spd),
+            "jmp" => Some(self.jmp),
+            "endu" => Some(self.endu),
+            "ins" => Some(self.ins),
+            "dnk" => Some(self.dnk),
+            "ft" => Some(self.ft),
+            "fg" => Some(self.fg),
+            "tp" => Some(self.tp),
+            "oiq" => Some(self.oiq),
+            "diq" => Some(self.diq),
+            "drb" => Some(self.drb),
+            "pss" => Some(self.pss),
+            "reb" => Some(self.reb),
+            _ => None,
+        }
+    }
+
+    pub fn set(&mut self, key: &str, value: f64) {
+        match key {
+            "hgt" => self.hgt = value,
+            "stre" => self.stre = value,
+            "spd" => self.spd = value,
+            "jmp" => self.jmp = value,
+            "endu" => self.endu = value,
+            "ins" => self.ins = value,
+            "dnk" => self.dnk = value,
+            "ft" => self.ft = value,
+            "fg" => self.fg = value,
+            "tp" => self.tp = value,
+            "oiq" => self.oiq = value,
+            "diq" => self.diq = value,
+            "drb" => self.drb = value,
+            "pss" => self.pss = value,
+            "reb" => self.reb = value,
+            _ => {}
+        }
+    }
+}
+
+impl PlayerInjury {
+    pub fn healthy() -> Self {
+        PlayerInjury {
+            games_remaining: 0,
+            injury_type: "Healthy".to_string(),
+            score: None
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct PlayerBorn {
+    pub year: i32,
+    // Country, or state for American players
+    pub loc: String
+}
+
+// How the player entered the league. round and pick are 0 for undrafted players, and tid is the team that drafted
+// them, which may have traded for the pick from original_tid.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerDraft {
+    pub round: i32,
+    pub pick: i32,
+    pub tid: i32,
+    pub original_tid: i32,
+    // Season of the draft the player was or will be in
+    pub year: i32
+}
+
+impl Default for PlayerDraft {
+    fn default() -> Self {
+        PlayerDraft {
+            round: 0,
+            pick: 0,
+            tid: -1,
+            original_tid: -1,
+            year: 0
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct PlayerContract {
+    // Thousands of dollars per season
+    pub amount: f64,
+    // Last season of the contract
+    pub exp: i32,
+    // Signed as a draft pick, on the rookie scale
+    #[serde(default)]
+    pub rookie: bool,
+    // "" for none, or "team" or "player" for an option on the last season, decided after the season before it
+    #[serde(default)]
+    pub option: String
+}
+
+// Totals for one season with one team. Regular season and playoffs get separate rows.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct PlayerStats {
+    pub season: i32,
+    pub tid: i32,
+    pub playoffs: bool,
+    pub gp: i32,
+    pub gs: i32,
+    pub min: f64,
+    pub pts: i32,
+    pub fg: i32,
+    pub fga: i32,
+    pub tp: i32,
+    pub tpa: i32,
+    pub ft: i32,
+    pub fta: i32,
+    pub orb: i32,
+    pub drb: i32,
+    pub ast: i32,
+    pub stl: i32,
+    pub blk: i32,
+    pub tov: i32,
+    pub pf: i32
+}
+
+impl PlayerStats {
+    pub fn new(season: i32, tid: i32, playoffs: bool) -> Self {
+        PlayerStats {
+            season,
+            tid,
+            playoffs,
+            gp: 0,
+            gs: 0,
+            min: 0.0,
+            pts: 0,
+            fg: 0,
+            fga: 0,
+            tp: 0,
+            tpa: 0,
+            ft: 0,
+            fta: 0,
+            orb: 0,
+            drb: 0,
+            ast: 0,
+            stl: 0,
+            blk: 0,
+            tov: 0,
+            pf: 0
+        }
+    }
+}
+
+// One award a player won, like "Most Valuable Player" or "First Team All-League"
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerAward {
+    pub season: i32,
+    #[serde(rename = "type")]
+    pub award_type: String
+}
+
+fn default_pt_modifier() -> f64 {
+    1.0
+}
+
+// A player over their whole career. tid is a team, or one of the PLAYER_* constants.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Player {
+    pub pid: i32,
+    pub tid: i32,
+    pub first_name: String,
+    pub last_name: String,
+    pub born: PlayerBorn,
+    // Inches
+    pub hgt: f64,
+    // Pounds
+    pub weight: f64,
+    pub college: String,
+    #[serde(default)]
+    pub draft: PlayerDraft,
+    // One entry per season, oldest first
+    pub ratings: Vec<PlayerRatings>,
+    pub contract: PlayerContract,
+    #[serde(default = "PlayerInjury::healthy")]
+    pub injury: PlayerInjury,
+    #[serde(default)]
+    pub stats: Vec<PlayerStats>,
+    #[serde(default)]
+    pub value: f64,
+    #[serde(default)]
+    pub value_no_pot: f64,
+    // Playing time set by the user, 1 is normal
+    #[serde(default = "default_pt_modifier")]
+    pub pt_modifier: f64,
+    // Oldest first
+    #[serde(default)]
+    pub awards: Vec<PlayerAward>
+}
+
+impl Player {
+    pub fn name(&self) -> String {
+        format!("{} {}", self.first_name, self.last_name)
+    }
+
+    pub fn age(&self, season: i32) -> i32 {
+        season - self.born.year
+    }
+
+    // Latest ratings from before or during season
+    pub fn ratings_for_season(&self, season: i32) -> &PlayerRatings {
+        self.ratings
+            .iter()
+            .rfind(|ratings| ratings.season <= season)
+            .unwrap_or(&self.ratings[0])
+    }
+
+    pub fn is_injured(&self) -> bool {
+        self.injury.games_remaining > 0
+    }
 }
 
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Team {
+    pub tid: i32,
+    pub cid: u8,
+    pub did: u8,
+    pub region: String,
+    pub name: String,
+    pub abbrev: String,
+    pub pop: f64,
+    // 1 is the best coaching staff in the league. None is the middle of the league.
+    #[serde(default)]
+    pub coaching_rank: Option<i32>
+}
+
+// Settings used by GameSim, the draft, contracts and rosters. Like the settings in GameAttributesLeagueWithHistory,
+// each one is a timeline, so a change can be scheduled for a later season.
+#[derive(Clone)]
 pub struct GameAttributesLeague {
-    pub foulsNeededToFoulOut: i32,
-    pub numPlayersOnCourt: i32,
-    pub quarterLength: f64,
-    pub disableInjuries: bool,
-    pub pace: f64,
-    pub foulsUntilBonus: Vec<i32>,
-    pub foulRateFactor: f64,
-    pub turnoverFactor: f64,
+    pub fouls_needed_to_foul_out: GameAttributesWithHistory<i32>,
+    pub num_players_on_court: GameAttributesWithHistory<i32>,
+    pub quarter_length: GameAttributesWithHistory<f64>,
+    pub disable_injuries: GameAttributesWithHistory<bool>,
+    pub pace: GameAttributesWithHistory<f64>,
+    pub fouls_until_bonus: GameAttributesWithHistory<Vec<i32>>,
+    pub foul_rate_factor: GameAttributesWithHistory<f64>,
+    pub turnover_factor: GameAttributesWithHistory<f64>,
+    // One of DRAFT_TYPES, see worker::core::draft::gen_order
+    pub draft_type: GameAttributesWithHistory<String>,
+    pub num_draft_rounds: GameAttributesWithHistory<i32>,
+    // Money is in thousands of dollars per season
+    pub salary_cap: GameAttributesWithHistory<f64>,
+    // One of SALARY_CAP_TYPES, see worker::core::finances::can_pay_contract
+    pub salary_cap_type: GameAttributesWithHistory<String>,
+    // Teams below it pay the difference as a penalty
+    pub min_payroll: GameAttributesWithHistory<f64>,
+    // Teams above it pay luxury_tax times the amount over it
+    pub luxury_payroll: GameAttributesWithHistory<f64>,
+    pub luxury_tax: GameAttributesWithHistory<f64>,
+    pub min_contract: GameAttributesWithHistory<f64>,
+    pub max_contract: GameAttributesWithHistory<f64>,
+    // Seasons
+    pub min_contract_length: GameAttributesWithHistory<i32>,
+    pub max_contract_length: GameAttributesWithHistory<i32>,
+    // Seasons of a rookie contract by round, the last entry applies to any later rounds
+    pub rookie_contract_lengths: GameAttributesWithHistory<Vec<i32>>,
+    // The first pick's salary as a percentage of max_contract, scaling down to min_contract at the last pick of
+    // draft_pick_auto_contract_rounds. Later rounds get min_contract.
+    pub draft_pick_auto_contract_percent: GameAttributesWithHistory<f64>,
+    pub draft_pick_auto_contract_rounds: GameAttributesWithHistory<i32>,
+    // Trades and signings can't leave a team with more players than max_roster_size. AI teams sign free agents until
+    // they have min_roster_size.
+    pub max_roster_size: GameAttributesWithHistory<i32>,
+    pub min_roster_size: GameAttributesWithHistory<i32>,
+    // Share of the regular season's games after which trades stop until the playoffs are over, 1 for no deadline
+    pub trade_deadline: GameAttributesWithHistory<f64>,
 }
 
+impl GameAttributesLeague {
+    // Checked for every season where any setting changes, since some settings are limited by others
+    pub fn validate(&self) -> Result<(), String> {
+        let mut seasons = BTreeSet::new();
+        seasons.extend(self.fouls_needed_to_foul_out.starts());
+        seasons.extend(self.num_players_on_court.starts());
+        seasons.extend(self.quarter_length.starts());
+        seasons.extend(self.disable_injuries.starts());
+        seasons.extend(self.pace.starts());
+        seasons.extend(self.fouls_until_bonus.starts());
+        seasons.extend(self.foul_rate_factor.starts());
+        seasons.extend(self.turnover_factor.starts());
+        seasons.extend(self.draft_type.starts());
+        seasons.extend(self.num_draft_rounds.starts());
+        seasons.extend(self.salary_cap.starts());
+        seasons.extend(self.salary_cap_type.starts());
+        seasons.extend(self.min_payroll.starts());
+        seasons.extend(self.luxury_payroll.starts());
+        seasons.extend(self.luxury_tax.starts());
+        seasons.extend(self.min_contract.starts());
+        seasons.extend(self.max_contract.starts());
+        seasons.extend(self.min_contract_length.starts());
+        seasons.extend(self.max_contract_length.starts());
+        seasons.extend(self.rookie_contract_lengths.starts());
+        seasons.extend(self.draft_pick_auto_contract_percent.starts());
+        seasons.extend(self.draft_pick_auto_contract_rounds.starts());
+        seasons.extend(self.max_roster_size.starts());
+        seasons.extend(self.min_roster_size.starts());
+        seasons.extend(self.trade_deadline.starts());
+        for season in seasons {
+            self.validate_season(season)?;
+        }
+        Ok(())
+    }
+
+    fn validate_season(&self, season: i32) -> Result<(), String> {
+        let fouls_needed_to_foul_out = *self.fouls_needed_to_foul_out.value_at(season);
+        let num_players_on_court = *self.num_players_on_court.value_at(season);
+        let quarter_length = *self.quarter_length.value_at(season);
+        let pace = *self.pace.value_at(season);
+        let fouls_until_bonus = self.fouls_until_bonus.value_at(season);
+        let foul_rate_factor = *self.foul_rate_factor.value_at(season);
+        let turnover_factor = *self.turnover_factor.value_at(season);
+        let draft_type = self.draft_type.value_at(season).as_str();
+        let num_draft_rounds = *self.num_draft_rounds.value_at(season);
+        let salary_cap = *self.salary_cap.value_at(season);
+        let salary_cap_type = self.salary_cap_type.value_at(season).as_str();
+        let min_payroll = *self.min_payroll.value_at(season);
+        let luxury_payroll = *self.luxury_payroll.value_at(season);
+        let luxury_tax = *self.luxury_tax.value_at(season);
+        let min_contract = *self.min_contract.value_at(season);
+        let max_contract = *self.max_contract.value_at(season);
+        let min_contract_length = *self.min_contract_length.value_at(season);
+        let max_contract_length = *self.max_contract_length.value_at(season);
+        let rookie_contract_lengths = self.rookie_contract_lengths.value_at(season);
+        let draft_pick_auto_contract_percent = *self.draft_pick_auto_contract_percent.value_at(season);
+        let draft_pick_auto_contract_rounds = *self.draft_pick_auto_contract_rounds.value_at(season);
+        let max_roster_size = *self.max_roster_size.value_at(season);
+        let min_roster_size = *self.min_roster_size.value_at(season);
+        let trade_deadline = *self.trade_deadline.value_at(season);
+
+        if num_players_on_court < 1 {
+            return Err(format!("numPlayersOnCourt: must be at least 1, got {}", num_players_on_court));
+        }
+        if fouls_needed_to_foul_out < 0 {
+            return Err(format!("foulsNeededToFoulOut: can't be negative, got {}", fouls_needed_to_foul_out));
+        }
+        if quarter_length <= 0.0 {
+            return Err(format!("quarterLength: must be positive, got {}", quarter_length));
+        }
+        if pace <= 0.0 {
+            return Err(format!("pace: must be positive, got {}", pace));
+        }
+        // Regulation, overtime and last 2 minutes
+        if fouls_until_bonus.len() < 3 {
+            return Err(format!("foulsUntilBonus: needs 3 entries, got {}", fouls_until_bonus.len()));
+        }
+        if foul_rate_factor < 0.0 {
+            return Err(format!("foulRateFactor: can't be negative, got {}", foul_rate_factor));
+        }
+        if !DRAFT_TYPES.contains(&draft_type) {
+            return Err(format!("draftType: unknown draft type \"{}\"", draft_type));
+        }
+        if num_draft_rounds < 0 {
+            return Err(format!("numDraftRounds: can't be negative, got {}", num_draft_rounds));
+        }
+        if turnover_factor < 0.0 {
+            return Err(format!("turnoverFactor: can't be negative, got {}", turnover_factor));
+        }
+        if salary_cap <= 0.0 {
+            return Err(format!("salaryCap: must be positive, got {}", salary_cap));
+        }
+        if !SALARY_CAP_TYPES.contains(&salary_cap_type) {
+            return Err(format!("salaryCapType: unknown salary cap type \"{}\"", salary_cap_type));
+        }
+        if min_payroll < 0.0 {
+            return Err(format!("minPayroll: can't be negative, got {}", min_payroll));
+        }
+        if luxury_payroll < 0.0 {
+            return Err(format!("luxuryPayroll: can't be negative, got {}", luxury_payroll));
+        }
+        if luxury_tax < 0.0 {
+            return Err(format!("luxuryTax: can't be negative, got {}", luxury_tax));
+        }
+        if min_contract <= 0.0 {
+            return Err(format!("minContract: must be positive, got {}", min_contract));
+        }
+        if max_contract < min_contract {
+            return Err(format!("maxContract: can't be less than minContract, got {}", max_contract));
+        }
+        if min_contract_length < 1 {
+            return Err(format!("minContractLength: must be at least 1, got {}", min_contract_length));
+        }
+        if max_contract_length < min_contract_length {
+            return Err(format!("maxContractLength: can't be less than minContractLength, got {}", max_contract_length));
+        }
+        if rookie_contract_lengths.is_empty() || rookie_contract_lengths.iter().any(|length| *length < 1) {
+            return Err("rookieContractLengths: needs at least one entry, and every entry must be at least 1".to_string());
+        }
+        if !(0.0..=100.0).contains(&draft_pick_auto_contract_percent) {
+            return Err(format!("draftPickAutoContractPercent: must be between 0 and 100, got {}", draft_pick_auto_contract_percent));
+        }
+        if draft_pick_auto_contract_rounds < 0 {
+            return Err(format!("draftPickAutoContractRounds: can't be negative, got {}", draft_pick_auto_contract_rounds));
+        }
+        if max_roster_size < num_players_on_court {
+            return Err(format!("maxRosterSize: can't be less than numPlayersOnCourt, got {}", max_roster_size));
+        }
+        if min_roster_size < num_players_on_court || min_roster_size > max_roster_size {
+            return Err(format!("minRosterSize: must be between numPlayersOnCourt and maxRosterSize, got {}", min_roster_size));
+        }
+        if !(0.0..=1.0).contains(&trade_deadline) {
+            return Err(format!("tradeDeadline: must be between 0 and 1, got {}", trade_deadline));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone)]
 pub struct GameAttributesNonLeague {
-    lid: Option<u8>
+    pub lid: Option<u8>
+}
+
+#[derive(Clone)]
+pub struct GameAttributeChange<T> {
+    pub start: i32,
+    pub value: T
 }
 
+// Timeline of a league setting. Each change applies from its start season until the next change. The first change
+// starts at i32::MIN so every season has a value.
+#[derive(Clone)]
 pub struct GameAttributesWithHistory<T> {
-    start: i32,
-    value: T
+    changes: Vec<GameAttributeChange<T>>
 }
 
+impl<T> GameAttributesWithHistory<T> {
+    pub fn new(value: T) -> Self {
+        GameAttributesWithHistory {
+            changes: vec![GameAttributeChange {
+                start: i32::MIN,
+                value
+            }]
+        }
+    }
+
+    pub fn value_at(&self, season: i32) -> &T {
+        let change = self.changes.iter().rev().find(|change| change.start <= season);
+        &change.unwrap_or(&self.changes[0]).value
+    }
+
+    // The value applies from season until the next change. A change already starting in season is replaced, later
+    // changes are kept.
+    pub fn set_from(&mut self, season: i32, value: T) {
+        let index = self.changes.iter().position(|change| change.start >= season);
+        if index.is_none() {
+            self.changes.push(GameAttributeChange {
+                start: season,
+                value
+            });
+            return;
+        }
+        let index = index.unwrap();
+        if self.changes[index].start == season {
+            self.changes[index].value = value;
+        } else {
+            self.changes.insert(index, GameAttributeChange {
+                start: season,
+                value
+            });
+        }
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, GameAttributeChange<T>> {
+        self.changes.iter()
+    }
+
+    pub fn starts(&self) -> impl Iterator<Item = i32> + '_ {
+        self.changes.iter().map(|change| change.start)
+    }
+}
+
+#[derive(Clone)]
 pub struct Conf {
-    cid: u8,
-    name: String,
+    pub cid: u8,
+    pub name: String,
 }
 
+#[derive(Clone)]
 pub struct Div {
-    did: u8,
-    cid: u8,
-    name: String,
+    pub did: u8,
+    pub cid: u8,
+    pub name: String,
 }
 
+#[derive(Clone)]
 pub struct GameAttributesLeagueWithHistory {
     pub game_attributes_league: GameAttributesLeague,
-    confs: GameAttributesWithHistory<Vec<Conf>>,
-    divs: GameAttributesWithHistory<Vec<Div>>,
-    numGamesPlayoffSeries: Vec<u8>,
-    numPlayoffByes: u8,
-    otl: bool,
-    playoffsNumTeamsDiv: u8,
-    pointsFormula: String,
-    tiebreakers: String,
-    ties: bool,
+    pub confs: GameAttributesWithHistory<Vec<Conf>>,
+    pub divs: GameAttributesWithHistory<Vec<Div>>,
+    // Regular season games per team
+    pub num_games: GameAttributesWithHistory<i32>,
+    pub num_games_playoff_series: GameAttributesWithHistory<Vec<u8>>,
+    pub num_playoff_byes: GameAttributesWithHistory<u8>,
+    pub otl: GameAttributesWithHistory<bool>,
+    pub playoffs_num_teams_div: GameAttributesWithHistory<u8>,
+    // "" for no play-in, "nba" for the 7-10 format where the 7v8 loser gets a second chance, or
+    // "singleElimination" where the last 4 teams play 2 knockout games for the last 2 seeds
+    pub play_in_type: GameAttributesWithHistory<String>,
+    pub points_formula: GameAttributesWithHistory<String>,
+    pub tiebreakers: GameAttributesWithHistory<String>,
+    pub ties: GameAttributesWithHistory<bool>,
+}
+
+impl GameAttributesLeagueWithHistory {
+    // tiebreakers is stored as a comma separated list of keys from TIEBREAKERS, in the order they are applied
+    pub fn get_tiebreakers(&self, season: i32) -> Vec<String> {
+        self.tiebreakers
+            .value_at(season)
+            .split(',')
+            .map(|key| key.trim().to_string())
+            .filter(|key| !key.is_empty())
+            .collect()
+    }
+
+    // None means standings are ordered by winning percentage rather than points
+    pub fn get_points_formula(&self, season: i32) -> Result<Option<PointsFormula>, String> {
+        let points_formula = self.points_formula.value_at(season);
+        if points_formula.trim().is_empty() {
+            return Ok(None);
+        }
+        PointsFormula::new(points_formula).map(Some)
+    }
+
+    // Every value in every timeline is checked, not just the current one
+    pub fn validate(&self) -> Result<(), String> {
+        let tiebreaker_names = TIEBREAKERS::new();
+        for change in self.tiebreakers.iter() {
+            for key in self.get_tiebreakers(change.start) {
+                if tiebreaker_names.get(&key).is_none() {
+                    return Err(format!("tiebreakers: unknown tiebreaker \"{}\"", key));
+                }
+            }
+        }
+
+        for change in self.divs.iter() {
+            let confs = self.confs.value_at(change.start);
+            for div in change.value.iter() {
+                if !confs.iter().any(|conf| conf.cid == div.cid) {
+                    return Err(format!("divs: division \"{}\" has cid {}, which is not in confs", div.name, div.cid));
+                }
+            }
+        }
+
+        for change in self.num_games.iter() {
+            if change.value < 1 {
+                return Err(format!("numGames: must be at least 1, got {}", change.value));
+            }
+        }
+
+        for change in self.num_games_playoff_series.iter() {
+            if change.value.contains(&0) {
+                return Err("numGamesPlayoffSeries: every round needs at least 1 game".to_string());
+            }
+            let even = change.value.iter().find(|num_games| *num_games % 2 == 0);
+            if let Some(even) = even {
+                return Err(format!("numGamesPlayoffSeries: every round needs an odd number of games, got {}", even));
+            }
+        }
+
+        for change in self.play_in_type.iter() {
+            if !PLAY_IN_TYPES.contains(&change.value.as_str()) {
+                return Err(format!("playInType: unknown play-in type \"{}\"", change.value));
+            }
+        }
+
+        for change in self.points_formula.iter() {
+            let points_formula = self.get_points_formula(change.start);
+            if points_formula.is_err() {
+                return Err(format!("pointsFormula: {}", points_formula.err().unwrap()));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct GameResultTeam {
+    pub tid: i32,
+    pub pts: i32
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct GameResult {
+    pub gid: i32,
+    pub season: i32,
+    pub playoffs: bool,
+    pub overtimes: i32,
+    // teams[0] is the home team, teams[1] is the away team
+    pub teams: [GameResultTeam; 2]
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TeamSeasonAttrs {
+    pub tid: i32,
+    pub season: i32,
+    pub cid: u8,
+    pub did: u8,
+    pub won: i32,
+    pub lost: i32,
+    pub tied: i32,
+    pub otl: i32,
+    pub pts: i32,
+    pub opp_pts: i32,
+    pub gp: i32,
+    // Set when the season's payroll is assessed, after the playoffs
+    #[serde(default)]
+    pub payroll_end_of_season: Option<f64>,
+    #[serde(default)]
+    pub luxury_tax: f64,
+    #[serde(default)]
+    pub min_payroll_penalty: f64,
+    // Part of the luxury tax paid by other teams
+    #[serde(default)]
+    pub luxury_tax_share: f64
+}
+
+impl TeamSeasonAttrs {
+    pub fn new(tid: i32, season: i32, cid: u8, did: u8) -> Self {
+        TeamSeasonAttrs {
+            tid,
+            season,
+            cid,
+            did,
+            won: 0,
+            lost: 0,
+            tied: 0,
+            otl: 0,
+            pts: 0,
+            opp_pts: 0,
+            gp: 0,
+            payroll_end_of_season: None,
+            luxury_tax: 0.0,
+            min_payroll_penalty: 0.0,
+            luxury_tax_share: 0.0
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayoffSeriesTeam {
+    pub tid: i32,
+    pub cid: u8,
+    pub seed: u8,
+    // Position in the league wide standings, used for home court when seeds are equal
+    pub league_rank: usize,
+    pub won: u8
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct PlayoffSeriesMatchup {
+    pub home: PlayoffSeriesTeam,
+    // None is a bye
+    pub away: Option<PlayoffSeriesTeam>
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayInGame {
+    pub home: PlayoffSeriesTeam,
+    pub away: PlayoffSeriesTeam,
+    pub winner_tid: Option<i32>
+}
+
+// Play-in for one bracket (a conference, or the whole league)
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayIn {
+    pub play_in_type: String,
+    pub cid: Option<u8>,
+    // Teams that qualified for the playoffs without the play-in, by seed
+    pub direct_teams: Vec<PlayoffSeriesTeam>,
+    // The 4 play-in teams, with the seeds they finished the regular season with
+    pub teams: Vec<PlayoffSeriesTeam>,
+    pub games: Vec<PlayInGame>
 }
 
+// A regular season game that has not been played yet
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduleGame {
+    pub gid: i32,
+    pub day: i32,
+    pub home_tid: i32,
+    pub away_tid: i32
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct BoxScorePlayer {
+    pub pid: i32,
+    pub name: String,
+    pub pos: String,
+    pub gs: bool,
+    pub min: f64,
+    pub pts: i32,
+    pub fg: i32,
+    pub fga: i32,
+    pub tp: i32,
+    pub tpa: i32,
+    pub ft: i32,
+    pub fta: i32,
+    pub orb: i32,
+    pub drb: i32,
+    pub ast: i32,
+    pub stl: i32,
+    pub blk: i32,
+    pub tov: i32,
+    pub pf: i32,
+    // Win probability added, see worker::core::game::win_probability
+    #[serde(default)]
+    pub wpa: f64,
+    // Injury suffered in this game, applied to the player when the game is saved
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub injury: Option<PlayerInjury>
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BoxScoreTeam {
+    pub tid: i32,
+    pub pts: i32,
+    // One entry per period, including overtimes
+    pub pts_qtrs: Vec<i32>,
+    pub players: Vec<BoxScorePlayer>
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct BoxScore {
+    pub gid: i32,
+    pub season: i32,
+    pub day: i32,
+    pub playoffs: bool,
+    pub overtimes: i32,
+    // teams[0] is the home team
+    pub teams: [BoxScoreTeam; 2]
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct PlayByPlayEvent {
+    // 1 indexed, overtimes continue after the last regulation period
+    pub period: i32,
+    // Minutes left in the period
+    pub t: f64,
+    pub tid: Option<i32>,
+    // Player the event is credited to, for win probability added
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pid: Option<i32>,
+    pub text: String,
+    // Home, away
+    pub score: [i32; 2],
+    // Home team's win probability after the event
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wp: Option<f64>
+}
+
+// A pick in a draft. tid is the team that owns it, and original_tid is the team whose record decides where it falls.
+// pick is 0 until the draft order is set.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DraftPick {
+    pub dpid: i32,
+    pub tid: i32,
+    pub original_tid: i32,
+    pub round: i32,
+    pub pick: i32,
+    pub season: i32
+}
+
+// One side of a trade: the team, and the players and draft picks it gives up
+#[derive(Clone, Default)]
+pub struct TradeTeam {
+    pub tid: i32,
+    pub pids: Vec<i32>,
+    pub dpids: Vec<i32>
+}
+
+// Something that happened in the league, like a trade, for the transaction log
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Event {
+    pub eid: i32,
+    // "trade"
+    #[serde(rename = "type")]
+    pub event_type: String,
+    pub season: i32,
+    pub text: String,
+    #[serde(default)]
+    pub tids: Vec<i32>,
+    #[serde(default)]
+    pub pids: Vec<i32>,
+    #[serde(default)]
+    pub dpids: Vec<i32>
+}
+
+// A player as they were when they won an award. Stats are per game, in the regular season or, for the Finals MVP, the
+// finals.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct AwardPlayer {
+    pub pid: i32,
+    pub name: String,
+    pub tid: i32,
+    #[serde(default)]
+    pub abbrev: String,
+    #[serde(default)]
+    pub pos: String,
+    #[serde(default)]
+    pub pts: f64,
+    #[serde(default)]
+    pub trb: f64,
+    #[serde(default)]
+    pub ast: f64,
+    #[serde(default)]
+    pub stl: f64,
+    #[serde(default)]
+    pub blk: f64,
+    // Regular season PER, see worker::core::season::adv_stats
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub per: Option<f64>
+}
+
+// One of the All-League or All-Defensive teams, like "First Team"
+#[derive(Clone, Serialize, Deserialize)]
+pub struct AwardTeam {
+    pub title: String,
+    pub players: Vec<AwardPlayer>
+}
+
+// Everything awarded after one season. An award is None when nobody was eligible.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SeasonAwards {
+    pub season: i32,
+    #[serde(default)]
+    pub mvp: Option<AwardPlayer>,
+    #[serde(default)]
+    pub dpoy: Option<AwardPlayer>,
+    #[serde(default)]
+    pub roy: Option<AwardPlayer>,
+    #[serde(default)]
+    pub smoy: Option<AwardPlayer>,
+    #[serde(default)]
+    pub mip: Option<AwardPlayer>,
+    #[serde(default)]
+    pub finals_mvp: Option<AwardPlayer>,
+    #[serde(default)]
+    pub all_league: Vec<AwardTeam>,
+    #[serde(default)]
+    pub all_defensive: Vec<AwardTeam>
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayoffSeries {
+    pub season: i32,
+    pub current_round: usize,
+    pub by_conf: bool,
+    // series is empty until the play-in is over
+    pub play_ins: Option<Vec<PlayIn>>,
+    pub series: Vec<Vec<PlayoffSeriesMatchup>>
+}
+
+#[derive(Clone)]
 pub struct GameAttributes {
-    pub nonLeague: Option<GameAttributesNonLeague>,
-    pub leagueWithHistory: Option<GameAttributesLeagueWithHistory>
+    pub non_league: Option<GameAttributesNonLeague>,
+    pub league_with_history: Option<GameAttributesLeagueWithHistory>
 }
 
 impl GameAttributes {
-    pub fn new(noHistory:Option<bool>) -> Self {
+    pub fn new(no_history:Option<bool>) -> Self {
         let mut game_attributes:GameAttributes = GameAttributes {
-            nonLeague: None,
-            leagueWithHistory: None
+            non_league: None,
+            league_with_history: None
         }; 
-        if (noHistory.is_some() && noHistory.unwrap()) {
-            game_attributes.nonLeague = Some(GameAttributesNonLeague {
+        if no_history.is_some() && no_history.unwrap() {
+            game_attributes.non_league = Some(GameAttributesNonLeague {
                 lid: None
             });
-            game_attributes.leagueWithHistory = None;
+            game_attributes.league_with_history = None;
             return game_attributes;
         } else {
-            game_attributes.leagueWithHistory = Some(GameAttributesLeagueWithHistory {
+            game_attributes.league_with_history = Some(GameAttributesLeagueWithHistory {
                 game_attributes_league: GameAttributesLeague {
-                    foulsNeededToFoulOut: 6,
-                    numPlayersOnCourt: 5
-                },
-                confs: GameAttributesWithHistory {
-                    start: 0,
-                    value: Vec::new()
+                    fouls_needed_to_foul_out: GameAttributesWithHistory::new(6),
+                    num_players_on_court: GameAttributesWithHistory::new(5),
+                    quarter_length: GameAttributesWithHistory::new(12.0),
+                    disable_injuries: GameAttributesWithHistory::new(false),
+                    pace: GameAttributesWithHistory::new(100.0),
+                    fouls_until_bonus: GameAttributesWithHistory::new(vec![5, 4, 2]),
+                    foul_rate_factor: GameAttributesWithHistory::new(1.0),
+                    turnover_factor: GameAttributesWithHistory::new(1.0),
+                    draft_type: GameAttributesWithHistory::new("nba2019".to_string()),
+                    num_draft_rounds: GameAttributesWithHistory::new(2),
+                    salary_cap: GameAttributesWithHistory::new(140000.0),
+                    salary_cap_type: GameAttributesWithHistory::new("soft".to_string()),
+                    min_payroll: GameAttributesWithHistory::new(105000.0),
+                    luxury_payroll: GameAttributesWithHistory::new(170000.0),
+                    luxury_tax: GameAttributesWithHistory::new(1.5),
+                    min_contract: GameAttributesWithHistory::new(1200.0),
+                    max_contract: GameAttributesWithHistory::new(49000.0),
+                    min_contract_length: GameAttributesWithHistory::new(1),
+                    max_contract_length: GameAttributesWithHistory::new(5),
+                    rookie_contract_lengths: GameAttributesWithHistory::new(vec![3, 2]),
+                    draft_pick_auto_contract_percent: GameAttributesWithHistory::new(25.0),
+                    draft_pick_auto_contract_rounds: GameAttributesWithHistory::new(1),
+                    max_roster_size: GameAttributesWithHistory::new(15),
+                    min_roster_size: GameAttributesWithHistory::new(13),
+                    trade_deadline: GameAttributesWithHistory::new(0.6)
                 },
-                divs: GameAttributesWithHistory {
-                    start: 0,
-                    value: Vec::new()
-                },
-                numGamesPlayoffSeries: Vec::new(),
-                numPlayoffByes: 0,
-                otl: false,
-                playoffsNumTeamsDiv: 0,
-                pointsFormula: "".to_string(),
-                tiebreakers: "".to_string(),
-                ties: false
+                confs: GameAttributesWithHistory::new(vec![
+                    Conf { cid: 0, name: "Eastern Conference".to_string() },
+                    Conf { cid: 1, name: "Western Conference".to_string() },
+                ]),
+                divs: GameAttributesWithHistory::new(vec![
+                    Div { did: 0, cid: 0, name: "Atlantic".to_string() },
+                    Div { did: 1, cid: 0, name: "Central".to_string() },
+                    Div { did: 2, cid: 0, name: "Southeast".to_string() },
+                    Div { did: 3, cid: 1, name: "Southwest".to_string() },
+                    Div { did: 4, cid: 1, name: "Northwest".to_string() },
+                    Div { did: 5, cid: 1, name: "Pacific".to_string() },
+                ]),
+                num_games: GameAttributesWithHistory::new(82),
+                num_games_playoff_series: GameAttributesWithHistory::new(vec![7, 7, 7, 7]),
+                num_playoff_byes: GameAttributesWithHistory::new(0),
+                otl: GameAttributesWithHistory::new(false),
+                playoffs_num_teams_div: GameAttributesWithHistory::new(0),
+                play_in_type: GameAttributesWithHistory::new("nba".to_string()),
+                points_formula: GameAttributesWithHistory::new("".to_string()),
+                tiebreakers: GameAttributesWithHistory::new(DEFAULT_TIEBREAKERS.join(",")),
+                ties: GameAttributesWithHistory::new(false)
             });
         }
-        return game_attributes;
+        game_attributes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_league_with_history() -> GameAttributesLeagueWithHistory {
+        GameAttributes::new(None).league_with_history.unwrap()
+    }
+
+    #[test]
+    fn value_at_follows_changes() {
+        let mut num_games = GameAttributesWithHistory::new(82);
+        num_games.set_from(2020, 72);
+        num_games.set_from(2022, 82);
+
+        assert_eq!(*num_games.value_at(1950), 82);
+        assert_eq!(*num_games.value_at(2019), 82);
+        assert_eq!(*num_games.value_at(2020), 72);
+        assert_eq!(*num_games.value_at(2021), 72);
+        assert_eq!(*num_games.value_at(2022), 82);
+        assert_eq!(*num_games.value_at(3000), 82);
+    }
+
+    #[test]
+    fn set_from_replaces_or_inserts_changes() {
+        let mut num_games = GameAttributesWithHistory::new(82);
+        num_games.set_from(2030, 60);
+        // Replaces the change already starting in 2030
+        num_games.set_from(2030, 50);
+        // Goes before it, and the 2030 change is kept
+        num_games.set_from(2025, 70);
+
+        let starts: Vec<i32> = num_games.iter().map(|change| change.start).collect();
+        assert_eq!(starts, vec![i32::MIN, 2025, 2030]);
+        assert_eq!(*num_games.value_at(2024), 82);
+        assert_eq!(*num_games.value_at(2029), 70);
+        assert_eq!(*num_games.value_at(2030), 50);
+    }
+
+    #[test]
+    fn validate_checks_every_change() {
+        assert!(get_league_with_history().validate().is_ok());
+
+        let mut league_with_history = get_league_with_history();
+        league_with_history.num_games_playoff_series.set_from(2030, vec![7, 6, 7, 7]);
+        assert_eq!(
+            league_with_history.validate().unwrap_err(),
+            "numGamesPlayoffSeries: every round needs an odd number of games, got 6"
+        );
+
+        let mut league_with_history = get_league_with_history();
+        league_with_history.num_games_playoff_series.set_from(2030, vec![0, 7]);
+        assert_eq!(league_with_history.validate().unwrap_err(), "numGamesPlayoffSeries: every round needs at least 1 game");
+
+        let mut league_with_history = get_league_with_history();
+        league_with_history.play_in_type.set_from(2030, "doubleElimination".to_string());
+        assert_eq!(league_with_history.validate().unwrap_err(), "playInType: unknown play-in type \"doubleElimination\"");
+
+        let mut league_with_history = get_league_with_history();
+        league_with_history.points_formula.set_from(2030, "2*W +".to_string());
+        assert_eq!(league_with_history.validate().unwrap_err(), "pointsFormula: Unexpected end of formula");
+    }
+
+    #[test]
+    fn validate_checks_settings_together_in_every_season() {
+        let mut league = get_league_with_history().game_attributes_league;
+        league.max_contract.set_from(2030, 30000.0);
+        assert!(league.validate().is_ok());
+        // Fine on its own, but in 2035 the minimum would be over the 2030 maximum
+        league.min_contract.set_from(2035, 40000.0);
+        assert_eq!(league.validate().unwrap_err(), "maxContract: can't be less than minContract, got 30000");
     }
 }
\ No newline at end of file
@@ -0,0 +1,2 @@
+pub mod common;
+pub mod worker;
\ No newline at end of file
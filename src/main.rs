@@ -1,8 +1,9 @@
-mod worker;
-mod common;
-
-use crate::worker::core::GameSim_basketball;
+mod cli;
 
 fn main() {
-    println!("Hello, world!");
-}
+    let argv: Vec<String> = std::env::args().skip(1).collect();
+    if let Err(error) = cli::run(&argv) {
+        eprintln!("Error: {}", error);
+        std::process::exit(1);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,10 @@
+// Chance of an injury on a possession, higher for older players and for players playing through an injury
+pub fn get_injury_rate(base_rate: f64, age: f64, playing_through_injury: Option<bool>) -> f64 {
+    let mut injury_rate = base_rate * 1.03_f64.powf(age.min(50.0) - 26.0);
+
+    if playing_through_injury.unwrap_or(false) {
+        injury_rate *= 1.5;
+    }
+
+    injury_rate
+}
\ No newline at end of file
@@ -1,143 +1,145 @@
-use crate::common::types::GameAttributesLeagueWithHistory;
+use crate::common::types::BoxScore;
+use crate::common::types::BoxScorePlayer;
+use crate::common::types::BoxScoreTeam;
+use crate::common::types::GameResult;
+use crate::common::types::GameResultTeam;
+use crate::common::types::PlayByPlayEvent;
 use crate::common::types::PlayerInjury;
+use crate::worker::core::game::fast_sim::get_fast_team_rating_from_composites;
+use crate::worker::core::game::fast_sim::FastModel;
+use crate::worker::core::game::fast_sim::FastTeamRating;
+use crate::worker::core::game::fast_sim::OVERTIME_LENGTH;
+use crate::worker::core::game::play::GameToSim;
+use crate::worker::core::game::play::SimGameResult;
+use crate::worker::core::game::win_probability::add_win_probability_added;
+use crate::worker::core::game::win_probability::WinProbability;
+use crate::worker::core::player::injury::gen_injury;
+use crate::worker::core::GameSim_basketball::get_injury_rate::get_injury_rate;
+use crate::worker::core::GameSim_basketball::synergy::get_skills_count;
+use crate::worker::core::GameSim_basketball::synergy::get_synergy;
+use crate::worker::core::GameSim_basketball::synergy::Synergy;
+use crate::worker::core::GameSim_basketball::synergy::SYNERGY_FACTOR;
 use crate::worker::util::g::G;
 use crate::worker::util::helpers;
-use rand::prelude::*;
-use core::num;
-use std::collections::HashSet;
+use crate::worker::util::random::gauss;
+use rand::rngs::StdRng;
+use rand::Rng;
+use rand::SeedableRng;
 use std::collections::HashMap;
-use std::cmp;
-use std::slice::SliceIndex;
-use crate::common::types::GameAttributes;
-use crate::worker::core::GameSim_basketball::getInjuryRate::getInjuryRate;
 
-const rng: ThreadRng = rand::thread_rng();
+#[derive(Clone, Copy, PartialEq)]
+enum ShotType {
+    AtRim,
+    LowPost,
+    MidRange,
+    ThreePointer
+}
+
+impl ShotType {
+    fn text(&self) -> &'static str {
+        match self {
+            ShotType::AtRim => "a shot at the rim",
+            ShotType::LowPost => "a low post shot",
+            ShotType::MidRange => "a mid-range shot",
+            ShotType::ThreePointer => "a three pointer"
+        }
+    }
+}
+
 enum PlayType {
-    Ast,
-    BlkAtRim,
-    BlkLowPost,
-    BlkMidRange,
-    BlkTp,
+    Blk(ShotType),
     Drb,
-    FgaAtRim,
-    FgaLowPost,
-    FgaMidRange,
-    FgaTp,
-    FgAtRim,
-    FgAtRimAndOne,
-    FgLowPost,
-    FgLowPostAndOne,
-    FgMidRange,
-    FgMidRangeAndOne,
+    Fg(ShotType),
+    FgAndOne(ShotType),
     FoulOut,
     Ft,
     GameOver,
     Injury,
     JumpBall,
-    MissAtRim,
+    Miss(ShotType),
     MissFt,
-    MissLowPost,
-    MissMidRange,
-    MissTp,
     Orb,
     Overtime,
     PfNonShooting,
     PfBonus,
-    PfFG,
-    PfTP,
+    PfFg,
+    PfTp,
     PfAndOne,
     Quarter,
     Stl,
     Sub,
     Tov,
-    Tp,
-    TpAndOne
-}
-
-struct Play {
-    play_type: PlayType,
-    team: TeamNum,
-    on: i32,
-    off: i32
-}
-
-impl Play {
-    fn new(play_type: PlayType, team: TeamNum, on: i32, off: i32) -> Self {
-        Play {
-            play_type,
-            team,
-            on,
-            off
-        }
-    }
-}
-
-struct Synergy {
-    def: f64,
-    off: f64,
-    reb: f64
-}
-
-enum ShotType {
-    AtRim,
-    Ft,
-    LowPost,
-    MidRange,
-    ThreePointer
+    Timeout
 }
 
 struct Stat {
-    ast: u16,
-    ba: u16,
-    benchTime: f64,
-    blk: u16,
-    courtTime: f64,
-    drb: u16,
+    ast: i32,
+    bench_time: f64,
+    blk: i32,
+    court_time: f64,
+    drb: i32,
     energy: f64,
-    fg: u16,
-    fgAtRim: u16,
-    fgLowPost: u16,
-    fgMidRange: u16,
-    fga: u16,
-    fgaAtRim: u16,
-    fgaLowPost: u16,
-    fgaMidRange: u16,
-    ft: u16,
-    fta: u16,
+    fg: i32,
+    fga: i32,
+    ft: i32,
+    fta: i32,
     gs: bool,
     min: f64,
-    orb: u16,
-    pf: u16,
-    pts: u16,
-    stl: u16,
-    tov: u16,
-    tp: u16,
-    tpa: u16
+    orb: i32,
+    pf: i32,
+    pts: i32,
+    stl: i32,
+    tov: i32,
+    tp: i32,
+    tpa: i32
 }
 
-type PlayerNumOnCourt = i32;
+impl Stat {
+    fn new() -> Self {
+        Stat {
+            ast: 0,
+            bench_time: 0.0,
+            blk: 0,
+            court_time: 0.0,
+            drb: 0,
+            energy: 1.0,
+            fg: 0,
+            fga: 0,
+            ft: 0,
+            fta: 0,
+            gs: false,
+            min: 0.0,
+            orb: 0,
+            pf: 0,
+            pts: 0,
+            stl: 0,
+            tov: 0,
+            tp: 0,
+            tpa: 0
+        }
+    }
+}
+
+type PlayerNumOnCourt = usize;
 
-type TeamNum = i32;
+// 0 for the home team, 1 for the away team
+pub type TeamNum = usize;
 
 struct TeamCompositeRating {
     ratings: HashMap<String, f64>
 }
 
 impl TeamCompositeRating {
-    fn insert(&self, key: String, value: f64) {
-        self.ratings.insert(key, value);
+    fn insert(&mut self, key: &str, value: f64) {
+        self.ratings.insert(key.to_string(), value);
     }
 
-    fn add(&self, key: String, value: f64) {
-        self.ratings.insert(key, self.ratings.get(&key).unwrap() + value);
+    fn add(&mut self, key: &str, value: f64) {
+        *self.ratings.entry(key.to_string()).or_insert(0.0) += value;
     }
 
-    fn mult(&self, key: String, value: f64) {
-        self.ratings.insert(key, self.ratings.get(&key).unwrap() * value);
-    }
-
-    fn get(&self, key: &str) -> Option<&f64> {
-        self.ratings.get(&key.to_string())
+    fn get(&self, key: &str) -> f64 {
+        self.ratings.get(key).copied().unwrap_or(0.0)
     }
 }
 
@@ -146,16 +148,8 @@ struct PlayerCompositeRating {
 }
 
 impl PlayerCompositeRating {
-    fn insert(&self, key: String, value: f64) {
-        self.ratings.insert(key, value);
-    }
-
-    fn add(&self, key: String, value: f64) {
-        self.ratings.insert(key, self.ratings.get(&key).unwrap() + value);
-    }
-
-    fn get(&self, key: &str) -> Option<&f64> {
-        self.ratings.get(&key.to_string())
+    fn get(&self, key: &str) -> f64 {
+        self.ratings.get(key).copied().unwrap_or(0.0)
     }
 }
 
@@ -164,7 +158,7 @@ struct Injury {
     playing_through: bool
 }
 
-struct PlayerGameSim {
+pub struct PlayerGameSim {
     id: i32,
     name: String,
     age: f64,
@@ -172,33 +166,52 @@ struct PlayerGameSim {
     value_no_pot: f64,
     stat: Stat,
     composite_rating: PlayerCompositeRating,
-    skills: Vec<String>,
     injured: bool,
     new_injury: bool,
     injury: Injury,
     pt_modifier: f64
 }
-struct LastScoringPlay {
-    team: i32,
-    player: i32,
-    shot_type: ShotType,
-    time: f32
-}
 
-struct ClutchPlay {
-    text: String,
-    show_notification: bool,
-    pids: Vec<i32>,
-    tids: Vec<i32>
+impl PlayerGameSim {
+    pub fn new(id: i32,
+               name: String,
+               age: f64,
+               pos: String,
+               value_no_pot: f64,
+               composite_rating: HashMap<String, f64>,
+               injury: PlayerInjury,
+               playing_through: bool,
+               pt_modifier: f64) -> Self {
+        let injured = injury.games_remaining > 0 && !playing_through;
+        PlayerGameSim {
+            id,
+            name,
+            age,
+            pos,
+            value_no_pot,
+            stat: Stat::new(),
+            composite_rating: PlayerCompositeRating {
+                ratings: composite_rating
+            },
+            injured,
+            new_injury: false,
+            injury: Injury {
+                injury,
+                playing_through
+            },
+            pt_modifier
+        }
+    }
 }
 
 struct TeamStat {
-    ptsQtrs: Vec<u8>,
-    pts: i32,
+    pts_qtrs: Vec<i32>,
+    pts: i32
 }
-struct TeamGameSim {
+
+pub struct TeamGameSim {
     id: i32,
-    pace: f32,
+    pace: f64,
     stat: TeamStat,
     composite_rating: TeamCompositeRating,
     player: Vec<PlayerGameSim>,
@@ -206,752 +219,1284 @@ struct TeamGameSim {
 }
 
 impl TeamGameSim {
-    fn insert(&mut self, key: String, value: f64) {
-        self.composite_rating.insert(key, value);
+    // players are in roster order, so the first num_players_on_court healthy ones start
+    pub fn new(id: i32, player: Vec<PlayerGameSim>, g: &G) -> Self {
+        // Possessions per 48 minutes, a bit faster than the league's pace with faster players
+        let rotation = &player[..player.len().min(10)];
+        let mut pace = g.pace();
+        if !rotation.is_empty() {
+            let pace_rating = rotation.iter().map(|p| p.composite_rating.get("pace")).sum::<f64>() / rotation.len() as f64;
+            pace += 15.0 * (pace_rating - 0.5);
+        }
+        TeamGameSim {
+            id,
+            pace,
+            stat: TeamStat {
+                pts_qtrs: Vec::new(),
+                pts: 0
+            },
+            composite_rating: TeamCompositeRating {
+                ratings: HashMap::new()
+            },
+            player,
+            synergy: Synergy {
+                def: 0.0,
+                off: 0.0,
+                reb: 0.0
+            }
+        }
     }
 }
 
 const TEAM_NUMS: [TeamNum; 2] = [0, 1];
 
-fn pick_player(mut ratios:Vec<f32>,
-               exempt:Option<PlayerNumOnCourt>) -> i32 {
-                if (exempt.is_some()) {
-                    ratios[exempt.unwrap() as usize] = 0.0;
-                }
-                let mut sum = 0.0;
-                for ratio in ratios.iter() {
-                    sum += ratio;
-                }
+const NUM_PERIODS: i32 = 4;
 
-                if (sum == 0.0) {
-                    let mut candidates: Vec<PlayerNumOnCourt> = Vec::new();
-                    for (i, ratio) in ratios.iter().enumerate() {
-                        if ratio > &0.0 {
-                            candidates.push(i as PlayerNumOnCourt);
-                        }
-                    }
-                    let index = rand::thread_rng().gen_range(0..candidates.len());
-                    return candidates[index];
-                }
+const NUM_TIMEOUTS: i32 = 7;
 
-                let mut rand = rand::thread_rng().gen_range(0.0..sum);
-                let mut running_sum = 0.0;
+// Possessions between the automatic substitution checks
+const SUBS_EVERY_N: i32 = 6;
 
-                for i in 0..ratios.len() {
-                    running_sum += ratios[i];
-                    if (rand < running_sum) {
-                        return i as i32;
-                    }
-                }
+// Energy lost per minute on the court by a player with no endurance
+const FATIGUE_FACTOR: f64 = 0.055;
+
+// Energy recovered per minute on the bench
+const BENCH_ENERGY: f64 = 0.094;
+
+// Energy recovered by the players on the court during a timeout, about a minute on the bench
+const TIMEOUT_ENERGY: f64 = BENCH_ENERGY;
+
+// Chance of an injury for each player on the court on each possession at pace 100, before the age adjustment
+const BASE_INJURY_RATE: f64 = 0.000125;
+
+// The home team's player composite ratings are raised by this fraction and the away team's lowered
+const HOME_COURT_ADVANTAGE: f64 = 0.01;
 
-                return 0;
+// Composite ratings that are summed over the players on the court into the team composite ratings
+const TEAM_COMPOSITE_RATINGS: [&str; 6] = [
+    "dribbling",
+    "passing",
+    "rebounding",
+    "defense",
+    "defensePerimeter",
+    "blocking"
+];
 
-               }
+fn pick_player(rng: &mut StdRng,
+               mut ratios: Vec<f64>,
+               exempt: Option<PlayerNumOnCourt>) -> PlayerNumOnCourt {
+    if let Some(exempt) = exempt {
+        ratios[exempt] = 0.0;
+    }
+    let sum: f64 = ratios.iter().sum();
 
-fn get_sorted_indexes(ovrs: Vec<f64>) -> Vec<i32> {
-    let mut ovrsSortedDesc = ovrs.clone();
-    ovrsSortedDesc.sort_by(|a, b| b.partial_cmp(a).unwrap());
-    let mut usedIndexes: HashSet<i32> = HashSet::new();
-    let mut sorted_indexes: Vec<i32> = ovrsSortedDesc.iter().map(|ovr| {
-        let mut index = ovrs.iter().position(|x| x == ovr).unwrap() as i32;
-        while (usedIndexes.contains(&index)) {
-            index += 1;
+    if sum <= 0.0 {
+        let candidates: Vec<PlayerNumOnCourt> = (0..ratios.len()).filter(|i| Some(*i) != exempt).collect();
+        if candidates.is_empty() {
+            return 0;
         }
-        usedIndexes.insert(index);
-        return index;
-    }).collect();
-    sorted_indexes.reverse();
-    return sorted_indexes;
+        return candidates[rng.gen_range(0..candidates.len())];
+    }
+
+    let rand = rng.gen_range(0.0..sum);
+    let mut running_sum = 0.0;
+    for (i, ratio) in ratios.iter().enumerate() {
+        running_sum += ratio;
+        if rand < running_sum {
+            return i;
+        }
+    }
+
+    ratios.len() - 1
 }
 
-fn boundProb(prob: f64) -> f64 {
-    helpers::bound(prob, 0.0, 1.0)
+// Indexes of ovrs from the lowest to the highest
+fn get_sorted_indexes(ovrs: &[f64]) -> Vec<usize> {
+    let mut indexes: Vec<usize> = (0..ovrs.len()).collect();
+    indexes.sort_by(|a, b| ovrs[*a].total_cmp(&ovrs[*b]));
+    indexes
 }
 
-struct SkillsCount {
-    three: f64,
-    A: f64,
-    B: f64,
-    Di: f64,
-    Dp: f64,
-    Po: f64,
-    Ps: f64,
-    R: f64
+fn bound_prob(prob: f64) -> f64 {
+    helpers::bound(prob, 0.0, 1.0)
 }
 
-impl SkillsCount {
-    pub fn new() -> Self {
-        SkillsCount {
-            three: 0.0,
-            A: 0.0,
-            B: 0.0,
-            Di: 0.0,
-            Dp: 0.0,
-            Po: 0.0,
-            Ps: 0.0,
-            R: 0.0
-        }
+// Players in foul trouble sit more
+fn get_foul_trouble_factor(pf: i32, foul_limit: i32) -> f64 {
+    if pf == foul_limit {
+        return 0.8;
+    }
+    if pf > foul_limit {
+        return 0.5;
     }
+    1.0
 }
 
-struct GameSim {
+fn ordinal(n: i32) -> String {
+    let suffix = match (n % 10, n % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th"
+    };
+    format!("{}{}", n, suffix)
+}
+
+pub struct GameSim {
     id: i32,
-    day: Option<i32>,
+    season: i32,
+    day: i32,
+    playoffs: bool,
     team: [TeamGameSim; 2],
-    playersOnCourt: [Vec<i32>; 2],
-    startersRecorded: bool,
-    subsEveryN: i32,
+    players_on_court: [Vec<usize>; 2],
+    starters_recorded: bool,
+    num_possessions: i32,
     overtimes: i32,
-    t: i32,
-    numPeriods: i32,
-    foulsThisQuarter: [i32; 2],
-    foulsLastTwoMinutes: [i32; 2],
-    averagePossessionLength: f32,
-    synergyFactor: f64,
-    last_scoring_play: Vec<LastScoringPlay>,
-    clutch_plays: Vec<ClutchPlay>,
-    o: Option<TeamNum>,
-    d: Option<TeamNum>,
-    play_by_play: Option<Vec<Play>>,
-    allStarGame: bool,
-    elam: bool,
-    elamActive: bool,
-    elamDone: bool,
-    elamTarget: i32,
-    fatigueFactor: f64,
-    numPlayersOnCourt: i32,
-    baseInjuryRate: f64,
-    gender: String
+    // Minutes left in the period
+    t: f64,
+    num_periods: i32,
+    // Regular season games with the ties setting end in a tie if it's still tied after an overtime, like in FastSim
+    allow_ties: bool,
+    fouls_this_quarter: [i32; 2],
+    fouls_last_two_minutes: [i32; 2],
+    average_possession_length: f64,
+    synergy_factor: f64,
+    // Team with the ball and team on defense. Possession switches at the start of every possession.
+    o: TeamNum,
+    d: TeamNum,
+    // Team that won the opening tip, which decides who starts the other regulation periods
+    jump_ball_winner: TeamNum,
+    play_by_play: Option<Vec<PlayByPlayEvent>>,
+    num_players_on_court: usize,
+    base_injury_rate: f64,
+    win_probability: WinProbability,
+    // Stepping state, see step
+    started: bool,
+    over: bool,
+    paused: bool,
+    timeouts_remaining: [i32; 2],
+    // When false, the team's players only change when someone on the court is injured or fouls out, and the caller
+    // makes every other substitution
+    auto_subs: [bool; 2],
+    // Every random number in a game comes from here, so a game can be replayed from its seed
+    rng: StdRng
 }
 
 impl GameSim {
-    pub fn new(mut self,
-               gid: i32, 
-               day: Option<i32>,
-               teams: [TeamGameSim; 2],
-               doPlayByPlay: Option<bool>,
-               homeCourtFactor: Option<f32>,
-               allStarGame: Option<bool>,
-               baseInjuryRate: f64,
-               disableHomeCourtAdvantage: Option<bool>) -> Self {
-                let g = GameAttributes::new(None);
-                let mut play_by_play: Option<Vec<Play>> = None;
-                if doPlayByPlay.is_some() {
-                    if doPlayByPlay.unwrap() {
-                        play_by_play = Some(Vec::new());
+    // teams[0] is the home team. Both need at least num_players_on_court players.
+    pub fn new(g: &G,
+               game: &GameToSim,
+               mut teams: [TeamGameSim; 2],
+               do_play_by_play: bool,
+               seed: u64,
+               fast_model: &FastModel) -> Self {
+        let num_players_on_court = g.num_players_on_court() as usize;
+
+        // From before the home court advantage, which the fast model has in home_court
+        let ratings: Vec<FastTeamRating> = teams.iter().map(|t| {
+            let composite_ratings: Vec<HashMap<String, f64>> = t.player
+                .iter()
+                .filter(|p| !p.injured)
+                .map(|p| p.composite_rating.ratings.clone())
+                .collect();
+            get_fast_team_rating_from_composites(&composite_ratings, num_players_on_court)
+        }).collect();
+        let win_probability = WinProbability::new(fast_model, &ratings[0], &ratings[1], NUM_PERIODS, g.quarter_length());
+
+        for t in TEAM_NUMS {
+            let factor = if t == 0 { 1.0 + HOME_COURT_ADVANTAGE } else { 1.0 / (1.0 + HOME_COURT_ADVANTAGE) };
+            for p in teams[t].player.iter_mut() {
+                for (rating, value) in p.composite_rating.ratings.iter_mut() {
+                    if rating != "endurance" {
+                        *value *= factor;
                     }
                 }
-                self.id = gid;
-                self.day = day;
-                self.team = teams;
-                self.baseInjuryRate = baseInjuryRate;
-                self.play_by_play = play_by_play;
-
-                let numPlayersOnCourt = g.leagueWithHistory.as_ref().unwrap().game_attributes_league.numPlayersOnCourt;
-
-                self.numPlayersOnCourt = numPlayersOnCourt;
-                let mut playersOnCourt = [Vec::new(), Vec::new()];
-                for i in 0..self.numPlayersOnCourt {
-                    playersOnCourt[0].push(i);
-                    playersOnCourt[1].push(i);
-                }
-                self.playersOnCourt = playersOnCourt;
+            }
+        }
 
-                self.startersRecorded = false;
+        // Offensive rebounds start a new possession here, so there are a few more possessions than the pace
+        let num_possessions = (teams[0].pace + teams[1].pace) / 2.0 * 1.1;
+
+        let mut game_sim = GameSim {
+            id: game.gid,
+            season: game.season,
+            day: game.day,
+            playoffs: game.playoffs,
+            team: teams,
+            players_on_court: [(0..num_players_on_court).collect(), (0..num_players_on_court).collect()],
+            starters_recorded: false,
+            num_possessions: 0,
+            overtimes: 0,
+            t: g.quarter_length(),
+            num_periods: NUM_PERIODS,
+            allow_ties: g.ties() && !game.playoffs,
+            fouls_this_quarter: [0, 0],
+            fouls_last_two_minutes: [0, 0],
+            average_possession_length: 48.0 / (2.0 * num_possessions),
+            synergy_factor: SYNERGY_FACTOR,
+            o: 0,
+            d: 1,
+            jump_ball_winner: 0,
+            play_by_play: if do_play_by_play { Some(Vec::new()) } else { None },
+            num_players_on_court,
+            base_injury_rate: BASE_INJURY_RATE,
+            win_probability,
+            started: false,
+            over: false,
+            paused: false,
+            timeouts_remaining: [NUM_TIMEOUTS; 2],
+            auto_subs: [true; 2],
+            rng: StdRng::seed_from_u64(seed)
+        };
+        game_sim.update_players_on_court(g, None);
+        game_sim.update_synergy();
+        game_sim
+    }
 
-                self.updatePlayersOnCourt(&g, None);
-                self.updateSynergy();
+    fn is_blowout(&self) -> bool {
+        let diff = (self.team[0].stat.pts - self.team[1].stat.pts).abs();
+        self.get_period() == self.num_periods &&
+            ((diff >= 30 && self.t < 12.0) ||
+                (diff >= 25 && self.t < 9.0) ||
+                (diff >= 20 && self.t < 7.0) ||
+                (diff >= 15 && self.t < 3.0) ||
+                (diff >= 10 && self.t < 1.0))
+    }
 
-                return self;
+    // How much the coach wants each player on the court right now, -Infinity for players who can't play
+    fn get_ovrs(&mut self, g: &G, t: TeamNum, include_fouled_out: bool, blowout: bool) -> Vec<f64> {
+        let late_game = self.is_late_game();
+        let fouls_needed_to_foul_out = g.fouls_needed_to_foul_out();
+        let foul_limit = self.get_foul_trouble_limit(g);
+
+        let mut ovrs = Vec::new();
+        for p in 0..self.team[t].player.len() {
+            let player = &self.team[t].player[p];
+            if player.injured ||
+                (!include_fouled_out && fouls_needed_to_foul_out > 0 && player.stat.pf >= fouls_needed_to_foul_out) {
+                ovrs.push(f64::NEG_INFINITY);
+                continue;
+            }
 
+            let pf = player.stat.pf;
+            let mut ovr = player.value_no_pot * self.fatigue(player.stat.energy) * player.pt_modifier;
+            if !late_game {
+                ovr *= self.rng.gen_range(0.9..1.1);
+            }
+            if blowout {
+                // Deep bench players get in at the end of blowouts
+                ovr *= (p as f64 + 1.0) / 10.0;
+            } else {
+                ovr *= get_foul_trouble_factor(pf, foul_limit);
+            }
+            ovrs.push(ovr);
+        }
+        ovrs
+    }
 
-               }
+    // Requires 2 Gs (or 1 PG) and 2 Fs (or 1 C) on the court after bench player b replaces the player at pp
+    fn has_positions(&self, t: TeamNum, pp: PlayerNumOnCourt, b: usize) -> bool {
+        let mut pos: Vec<&str> = self.players_on_court[t]
+            .iter()
+            .enumerate()
+            .filter(|(j, _)| *j != pp)
+            .map(|(_, p)| self.team[t].player[*p].pos.as_str())
+            .collect();
+        pos.push(self.team[t].player[b].pos.as_str());
+
+        let num_g = pos.iter().filter(|pos| pos.contains('G')).count();
+        let num_pg = pos.iter().filter(|pos| pos.contains("PG")).count();
+        let num_f = pos.iter().filter(|pos| pos.contains('F')).count();
+        let num_c = pos.iter().filter(|pos| pos.contains('C')).count();
+
+        let cutoff = if self.num_players_on_court >= 5 {
+            2
+        } else if self.num_players_on_court >= 3 {
+            1
+        } else {
+            0
+        };
+        !((num_g < cutoff && num_pg == 0) || (num_f < cutoff && num_c == 0))
+    }
 
-    fn updatePlayersOnCourt(&self, g:&GameAttributes, shooter:Option<PlayerNumOnCourt>) -> bool {
-        let substitutions = false;
-        let mut blowout = false;
-        let lateGame = self.isLateGame();
+    // Puts bench player b in for the player at pp
+    fn swap_players(&mut self, t: TeamNum, pp: PlayerNumOnCourt, b: usize) {
+        let p = self.players_on_court[t][pp];
+        self.players_on_court[t][pp] = b;
+        for i in [b, p] {
+            self.team[t].player[i].stat.court_time = self.rng.gen_range(-2.0..2.0);
+            self.team[t].player[i].stat.bench_time = self.rng.gen_range(-2.0..2.0);
+        }
 
-        let foulsNeededToFoulOut = g.leagueWithHistory.as_ref().unwrap().game_attributes_league.foulsNeededToFoulOut;
+        if self.starters_recorded {
+            let names = [self.team[t].player[b].name.clone(), self.team[t].player[p].name.clone()];
+            self.record_play(PlayType::Sub, Some(t), Some(b), &names);
+        }
+    }
 
-        if self.o != None && self.d != None {
-            let o = self.o.as_ref().unwrap();
-            let o = *o as usize;
-            let d = self.d.as_ref().unwrap();
-            let d = *d as usize;
-            let diff = (&(self.team[d].stat.pts)
-            - &(self.team[o].stat.pts))
-            .abs();
+    // Automatic substitutions, from the worst player on the court to the best. shooter is a player on offense who stays
+    // on the court for their free throws. Returns whether anyone was substituted.
+    fn update_players_on_court(&mut self, g: &G, shooter: Option<PlayerNumOnCourt>) -> bool {
+        let mut substitutions = false;
+        let blowout = self.is_blowout();
 
-            let quarter = self.team[o].stat.ptsQtrs.len();
-            if self.elamActive {
-                let ptsToTarget = 
-                    self.elamTarget -
-                    cmp::max(self.team[d].stat.pts, self.team[o].stat.pts);
-                blowout = diff >= 20 && ptsToTarget < diff;
-            } else {
-                blowout =
-                    quarter == self.numPeriods as usize &&
-                    ((diff >= 30 && self.t < 12) ||
-                        (diff >= 25 && self.t < 9) ||
-                        (diff >= 20 && self.t < 7) ||
-                        (diff >= 15 && self.t < 3) ||
-                        (diff >= 10 && self.t < 1));
+        for t in TEAM_NUMS {
+            let mut ovrs = self.get_ovrs(g, t, false, blowout);
+            let num_eligible_players = ovrs.iter().filter(|ovr| **ovr > f64::NEG_INFINITY).count();
+            if num_eligible_players < self.num_players_on_court {
+                ovrs = self.get_ovrs(g, t, true, blowout);
             }
-        }
 
-        let foulLimit = self.getFoulTroubleLimit(g);
-
-        for t in TEAM_NUMS.to_vec() {
-            let t = t as usize;
-            let getOvrs = |includeFouledOut: bool| {
-                let ovrs: Vec<f64>;
-
-                for p in 0..self.team[t].player.len() {
-                    if self.team[t].player[p].injured ||
-                        (!includeFouledOut &&
-                            foulsNeededToFoulOut > 0 &&
-                        self.team[t].player[p].stat.pf as i32 >= foulsNeededToFoulOut) {
-                        ovrs[p] = f64::NEG_INFINITY;
-                    } else {
-                        let mut rng = rand::thread_rng();
-                        ovrs[p] =
-                            self.team[t].player[p].value_no_pot *
-                            self.fatigue(self.team[t].player[p].stat.energy) *
-                            if !lateGame {
-                                rng.gen_range(0.9..1.1)
-                            } else {
-                                1.0
-                            };
-
-                        if !self.allStarGame {
-                            ovrs[p] *= self.team[t].player[p].pt_modifier;
-                        }
+            let ovrs_on_court: Vec<f64> = self.players_on_court[t].iter().map(|p| ovrs[*p]).collect();
+            for pp in get_sorted_indexes(&ovrs_on_court) {
+                let p = self.players_on_court[t][pp];
+                let on_court_is_ineligible = ovrs[p] == f64::NEG_INFINITY;
 
-                        if blowout {
-                            ovrs[p] *= (p as f64 + 1.0) / 10.0;
-                        } else {
-                            let foulTroubleFactor = self.getFoulTroubleFactor(
-                                self.team[t].player[p],
-                                foulLimit,
-                            );
-                            ovrs[p] *= foulTroubleFactor;
-                        }
+                if self.started && t == self.o && shooter == Some(pp) {
+                    continue;
+                }
+                if !self.auto_subs[t] && !on_court_is_ineligible {
+                    continue;
+                }
 
+                for b in 0..self.team[t].player.len() {
+                    if self.players_on_court[t].contains(&b) {
+                        continue;
                     }
-                }
 
-                return ovrs;
-            };
+                    let bench_is_valid_and_better = self.team[t].player[p].stat.court_time > 2.0 &&
+                        self.team[t].player[b].stat.bench_time > 2.0 &&
+                        ovrs[b] > ovrs[p];
+                    let bench_is_eligible = ovrs[b] != f64::NEG_INFINITY;
+                    let can_sub = bench_is_valid_and_better || (on_court_is_ineligible && bench_is_eligible);
+                    if !can_sub {
+                        continue;
+                    }
 
-            let numEligiblePlayers = |ovrs: Vec<f64>| {
-                let count = 0;
-                for ovr in ovrs {
-                    if ovr > f64::NEG_INFINITY {
-                        count += 1;
+                    // A tired player comes out even if it leaves the lineup short at a position
+                    if !on_court_is_ineligible &&
+                        !self.has_positions(t, pp, b) &&
+                        self.fatigue(self.team[t].player[p].stat.energy) > 0.728 {
+                        continue;
                     }
+
+                    self.swap_players(t, pp, b);
+                    substitutions = true;
+                    break;
                 }
+            }
+        }
 
-                return count;
-            };
+        if !self.starters_recorded {
+            for t in TEAM_NUMS {
+                for p in self.players_on_court[t].clone() {
+                    self.team[t].player[p].stat.gs = true;
+                }
+            }
+            self.starters_recorded = true;
+        }
 
-            let ovrs = getOvrs(false);
+        substitutions
+    }
 
-            if numEligiblePlayers(ovrs) < self.numPlayersOnCourt {
-                ovrs = getOvrs(true);
-            }
+    fn get_foul_trouble_limit(&self, g: &G) -> i32 {
+        let fouls_needed_to_foul_out = g.fouls_needed_to_foul_out();
 
-            let ovrsOnCourt:Vec<f64> = self.playersOnCourt[t]
-                .iter()
-                .map(|p| ovrs[*p as usize])
-                .collect();
+        let quarter = self.get_period();
+        if self.overtimes > 0 || (quarter == self.num_periods && self.t < 8.0) {
+            return fouls_needed_to_foul_out;
+        }
 
-            for pp in get_sorted_indexes(ovrsOnCourt) {
-                let pp = pp as usize;
-                let p = self.playersOnCourt[t][pp] as usize;
-                let onCourtIsIneligible = ovrs[p] == f64::NEG_INFINITY;
-                self.playersOnCourt[t][pp] = p as i32;
+        let game_completion_fraction = (quarter as f64 - self.t / g.quarter_length()) / self.num_periods as f64;
 
-                if t as i32 == self.o.unwrap() && pp as i32 == shooter.unwrap() {
-                    continue;
-                }
+        let mut foul_limit = (game_completion_fraction * fouls_needed_to_foul_out as f64).ceil() as i32;
+        if foul_limit < 2 {
+            foul_limit = 2;
+        } else if foul_limit >= fouls_needed_to_foul_out {
+            foul_limit = fouls_needed_to_foul_out - 1;
+        }
 
-                for b in 0..self.team[t].player.len() {
-                    let b = b as i32;
-                    if self.playersOnCourt[t].contains(&b) {
-                        continue;
-                    }
+        foul_limit
+    }
 
-                    let benchIsValidAndBetter =
-                        self.team[t].player[p].stat.courtTime > 2.0 &&
-                        self.team[t].player[b as usize].stat.courtTime > 2.0 &&
-                        ovrs[b as usize] > ovrs[p];
-                    let benchIsEligible =
-                        ovrs[b as usize] != f64::NEG_INFINITY;
-
-                    if benchIsValidAndBetter ||
-                        (onCourtIsIneligible && benchIsEligible)
-                        {
-                        let pos:Vec<String> = Vec::new();
-
-                        for j in 0..self.playersOnCourt[t].len() {
-                            if (j != pp) {
-                                pos.push(self.team[t].player[self.playersOnCourt[t][j] as usize].pos);
-                            }
-                        }
+    fn is_late_game(&self) -> bool {
+        self.get_period() >= self.num_periods && self.t < 6.0
+    }
 
-                        pos.push(self.team[t].player[b as usize].pos);
-
-                        // Requre 2 Gs (or 1 PG) and 2 Fs (or 1 C)
-                        let numG = 0;
-                        let numPG = 0;
-                        let numF = 0;
-                        let numC = 0;
-
-                        for j in 0..pos.len() {
-                            if (pos[j].contains("G")) {
-                                numG += 1;
-                            }
-                            if (pos[j].contains("PG")) {
-                                numPG += 1;
-                            }
-                            if (pos[j].contains("F")) {
-                                numF += 1;
-                            }
-                            if (pos[j].contains("C")) {
-                                numC += 1;
-                            }
-                        }
+    // Late in games, or in overtime, fatigue matters less
+    fn fatigue(&self, energy: f64) -> f64 {
+        let energy = (energy + 0.016).min(1.0);
+        if self.is_late_game() {
+            let factor = 6.0 - self.t;
+            return (energy + factor) / (1.0 + factor);
+        }
+        energy
+    }
 
-                        let cutoff =
-                            if self.numPlayersOnCourt >= 5 {
-                                2
-                            } else {
-                                if self.numPlayersOnCourt >= 3 {
-                                    1
-                                } else {
-                                    0
-                                }
-                            };
-                        
-                        if (numG < cutoff && numPG == 0) ||
-                            (numF < cutoff && numC == 0) {
-                                if (
-                                    self.fatigue(self.team[t].player[p].stat.energy > 0.728 &&
-                                    !onCourtIsIneligible)) {
-                                        continue;
-                                    }
-                            }
-                        
-                        substitutions = true;
-
-                        self.playersOnCourt[t][pp] = b;
-                        self.team[t].player[b as usize].stat.courtTime = rng.gen_range(-2.0..2.0);
-                        self.team[t].player[b as usize].stat.benchTime = rng.gen_range(-2.0..2.0);
-                        self.team[t].player[p].stat.courtTime = rng.gen_range(-2.0..2.0);
-                        self.team[t].player[p].stat.benchTime = rng.gen_range(-2.0..2.0);
-
-                        if self.play_by_play.is_some() {
-                            let play = Play::new(
-                                PlayType::Sub,
-                                t as i32,
-                                self.team[t].player[b as usize].id,
-                                self.team[t].player[p].id,
-                            );
-                            self.play_by_play.unwrap().push(play);
-                        }
+    // Home team's chance of winning before the next possession
+    fn get_win_probability(&self) -> f64 {
+        let possession = if self.started && !self.over { Some(self.d) } else { None };
+        self.win_probability.get(
+            [self.team[0].stat.pts, self.team[1].stat.pts],
+            self.get_period(),
+            self.t,
+            possession
+        )
+    }
 
-                        if (self.startersRecorded) {
-                            self.recordPlay("sub", t, [
-                                &self.team[t].player[b as usize].name,
-                                &self.team[t].player[p].name,
-                            ]);
-                        }
+    fn update_synergy(&mut self) {
+        for t in TEAM_NUMS {
+            let composite_ratings: Vec<&HashMap<String, f64>> = self.players_on_court[t]
+                .iter()
+                .map(|p| &self.team[t].player[*p].composite_rating.ratings)
+                .collect();
+            self.team[t].synergy = get_synergy(&get_skills_count(&composite_ratings));
+        }
+    }
+
+    fn update_team_composite_ratings(&mut self, g: &G) {
+        let foul_limit = self.get_foul_trouble_limit(g);
+
+        for t in TEAM_NUMS {
+            let diff = self.team[t].stat.pts - self.team[1 - t].stat.pts;
+            // Teams ease up with a big lead and try harder when behind
+            let perf_factor = 1.0 - 0.2 * (diff as f64 / 60.0).tanh();
 
-                        break;
+            for rating in TEAM_COMPOSITE_RATINGS {
+                let mut sum = 0.0;
+                for p in self.players_on_court[t].iter() {
+                    let player = &self.team[t].player[*p];
+
+                    // Players in foul trouble play softer defense
+                    let mut foul_limit_factor = 1.0;
+                    if rating == "defense" || rating == "defensePerimeter" || rating == "blocking" {
+                        if player.stat.pf == foul_limit {
+                            foul_limit_factor = 0.9;
+                        } else if player.stat.pf > foul_limit {
+                            foul_limit_factor = 0.75;
+                        }
                     }
+
+                    sum += player.composite_rating.get(rating) *
+                        self.fatigue(player.stat.energy) *
+                        perf_factor *
+                        foul_limit_factor;
                 }
+                self.team[t].composite_rating.insert(rating, sum / self.num_players_on_court as f64);
             }
 
+            let off = self.synergy_factor * self.team[t].synergy.off;
+            let def = self.synergy_factor * self.team[t].synergy.def;
+            let reb = self.synergy_factor * self.team[t].synergy.reb;
+            let composite_rating = &mut self.team[t].composite_rating;
+            composite_rating.add("dribbling", off);
+            composite_rating.add("passing", off);
+            composite_rating.add("rebounding", reb);
+            composite_rating.add("defense", def);
+            composite_rating.add("defensePerimeter", def);
+            composite_rating.add("blocking", def);
         }
+    }
 
-        if (!self.startersRecorded) {
-            for t in TEAM_NUMS.to_vec() {
-                for p in 0..self.team[t as usize].player.len() {
-                    let p = p as i32;
-                    if self.playersOnCourt[t as usize].contains(&p) {
-                        self.recordStat(t, p, "gs");
-                    }
+    fn update_playing_time(&mut self, possession_length: f64) {
+        for t in TEAM_NUMS {
+            for p in 0..self.team[t].player.len() {
+                let on_court = self.players_on_court[t].contains(&p);
+                let endurance = self.team[t].player[p].composite_rating.get("endurance");
+                let stat = &mut self.team[t].player[p].stat;
+                if on_court {
+                    stat.min += possession_length;
+                    stat.court_time += possession_length;
+                    stat.energy = (stat.energy - possession_length * FATIGUE_FACTOR * (1.0 - endurance)).max(0.0);
+                } else {
+                    stat.bench_time += possession_length;
+                    stat.energy = (stat.energy + possession_length * BENCH_ENERGY).min(1.0);
                 }
             }
+        }
+    }
 
-            self.startersRecorded = true;
+    fn injuries(&mut self, g: &G) {
+        if g.disable_injuries() {
+            return;
         }
 
-        return substitutions;
+        // The rate is per possession, so faster leagues get the same number of injuries per game
+        let base_rate = self.base_injury_rate * 100.0 / g.pace();
 
+        let mut new_injury = false;
+        for t in TEAM_NUMS {
+            for p in self.players_on_court[t].clone() {
+                let player = &self.team[t].player[p];
+                let injury_rate = get_injury_rate(base_rate, player.age, Some(player.injury.playing_through));
+                if self.rng.gen_range(0.0..1.0) < injury_rate {
+                    let injury = gen_injury(&mut self.rng);
+                    let names = [self.team[t].player[p].name.clone(), injury.injury_type.clone()];
+                    let player = &mut self.team[t].player[p];
+                    player.injured = true;
+                    player.new_injury = true;
+                    player.injury.injury = injury;
+                    self.record_play(PlayType::Injury, Some(t), Some(p), &names);
+                    new_injury = true;
+                }
+            }
+        }
 
+        if new_injury && self.update_players_on_court(g, None) {
+            self.update_synergy();
+        }
+    }
+
+    fn get_num_fouls_until_bonus(&self, g: &G) -> i32 {
+        let fouls_until_bonus = g.fouls_until_bonus();
+        if self.t <= 2.0 {
+            return fouls_until_bonus[2] - self.fouls_last_two_minutes[self.d];
+        }
+        if self.overtimes >= 1 {
+            return fouls_until_bonus[1] - self.fouls_this_quarter[self.d];
+        }
+        fouls_until_bonus[0] - self.fouls_this_quarter[self.d]
     }
 
-    fn getFoulTroubleLimit(&self, g:&GameAttributes) -> i32 {
-        let foulsNeededToFoulOut = g.leagueWithHistory.as_ref().unwrap().game_attributes_league.foulsNeededToFoulOut;
+    // Trailing late in the game, the defense fouls to stop the clock
+    fn is_intentional_foul(&self) -> bool {
+        let diff = self.team[self.o].stat.pts - self.team[self.d].stat.pts;
+        self.get_period() >= self.num_periods && self.t > 0.0 && self.t < 0.4 && diff > 0 && diff <= 6
+    }
 
-        let quarter = self.team[0].stat.ptsQtrs.len();
-        if (
-            self.overtimes > 0 ||
-            self.elamActive ||
-            (quarter == self.numPeriods.try_into().unwrap() && self.t < 8)
-        ) {
-            return foulsNeededToFoulOut;
+    fn get_possession_outcome(&mut self, g: &G, possession_length: f64, intentional_foul: bool) -> String {
+        // If winning at the end of the game, just run out the clock
+        if self.t <= 0.0 &&
+            self.get_period() >= self.num_periods &&
+            self.team[self.o].stat.pts > self.team[self.d].stat.pts {
+            return "endOfQuarter".to_string();
         }
 
-        let quarterLength = g.leagueWithHistory.as_ref().unwrap().game_attributes_league.quarterLength;
+        // With not much time on the clock at the end of a period, the possession might end with the clock running out
+        if self.t <= 0.0 &&
+            possession_length < 6.0 / 60.0 &&
+            self.rng.gen_range(0.0..1.0) > (possession_length / (8.0 / 60.0)).powf(1.0 / 4.0) {
+            return "endOfQuarter".to_string();
+        }
 
-        let gameCompletionFraction =
-            (quarter as f64 - self.t as f64 / quarterLength) / self.numPeriods as f64;
+        if self.rng.gen_range(0.0..1.0) < self.prob_tov(g) {
+            return self.do_tov();
+        }
 
-        let mut foulLimit = (gameCompletionFraction * foulsNeededToFoulOut as f64).ceil() as i32;
+        let ratios = self.rating_array("usage", self.o, 1.25);
+        let shooter = pick_player(&mut self.rng, ratios, None);
 
-        if (foulLimit < 2) {
-            foulLimit = 2;
-        } else if (foulLimit >= foulsNeededToFoulOut) {
-            foulLimit = foulsNeededToFoulOut - 1;
+        if intentional_foul || self.rng.gen_range(0.0..1.0) < 0.08 * g.foul_rate_factor() {
+            let in_bonus = self.get_num_fouls_until_bonus(g) <= 1;
+            if in_bonus {
+                self.do_pf(g, self.d, PlayType::PfBonus, Some(shooter));
+                return self.do_ft(shooter, 2);
+            }
+
+            self.do_pf(g, self.d, PlayType::PfNonShooting, Some(shooter));
+            return "nonShootingFoul".to_string();
         }
 
-        return foulLimit;
+        self.do_shot(g, shooter)
     }
 
-    fn isLateGame(&self) -> bool {
-        let quarter = self.team[0].stat.ptsQtrs.len();
-        let lateGame:bool;
-        if (self.elamActive) {
-            let ptsToTarget = 
-                self.elamTarget -
-                cmp::max(self.team[self.d.unwrap() as usize].stat.pts, self.team[self.o.unwrap() as usize].stat.pts);
-            lateGame = ptsToTarget <= 15;
-        } else {
-            lateGame = quarter >= self.numPeriods.try_into().unwrap() && self.t < 6;
+    fn prob_tov(&self, g: &G) -> f64 {
+        let o = &self.team[self.o].composite_rating;
+        let d = &self.team[self.d].composite_rating;
+        bound_prob(
+            g.turnover_factor() * 0.14 * d.get("defense") / (0.5 * (o.get("dribbling") + o.get("passing")))
+        )
+    }
+
+    fn prob_stl(&self) -> f64 {
+        let o = &self.team[self.o].composite_rating;
+        let d = &self.team[self.d].composite_rating;
+        bound_prob(0.55 * d.get("defensePerimeter") / (0.5 * (o.get("dribbling") + o.get("passing"))))
+    }
+
+    fn prob_ast(&self) -> f64 {
+        bound_prob(
+            0.6 * (2.0 + self.team[self.o].composite_rating.get("passing")) /
+                (2.0 + self.team[self.d].composite_rating.get("defense"))
+        )
+    }
+
+    fn prob_blk(&self) -> f64 {
+        bound_prob(0.2 * self.team[self.d].composite_rating.get("blocking").powi(2))
+    }
+
+    // One entry per player on the court of team t, for pick_player
+    fn rating_array(&self, rating: &str, t: TeamNum, power: f64) -> Vec<f64> {
+        self.players_on_court[t]
+            .iter()
+            .map(|p| {
+                let player = &self.team[t].player[*p];
+                (player.composite_rating.get(rating) * self.fatigue(player.stat.energy)).powf(power)
+            })
+            .collect()
+    }
+
+    fn get_name(&self, t: TeamNum, p: usize) -> String {
+        self.team[t].player[p].name.clone()
+    }
+
+    fn add_pts(&mut self, t: TeamNum, p: usize, pts: i32) {
+        self.team[t].player[p].stat.pts += pts;
+        self.team[t].stat.pts += pts;
+        *self.team[t].stat.pts_qtrs.last_mut().unwrap() += pts;
+    }
+
+    fn do_tov(&mut self) -> String {
+        let ratios = self.rating_array("turnovers", self.o, 0.5);
+        let p = self.players_on_court[self.o][pick_player(&mut self.rng, ratios, None)];
+        self.team[self.o].player[p].stat.tov += 1;
+
+        if self.prob_stl() > self.rng.gen_range(0.0..1.0) {
+            return self.do_stl(p);
         }
 
-        return lateGame;
+        let names = [self.get_name(self.o, p)];
+        self.record_play(PlayType::Tov, Some(self.o), Some(p), &names);
+        "tov".to_string()
     }
 
-    fn updateSynergy(&mut self) {
-        for t in TEAM_NUMS {
-            let mut skillsCount = SkillsCount::new();
-            for i in 0..self.numPlayersOnCourt {
-                let p = self.playersOnCourt[t as usize][i as usize];
-
-                skillsCount.three += helpers::sigmoid(
-                    *self.team[t as usize].player[p as usize].composite_rating.get("shootingThreePointer").unwrap(),
-                    15.0,
-                    0.59
-                );
-                skillsCount.A += helpers::sigmoid(
-                    *self.team[t as usize].player[p as usize].composite_rating.get("athleticism").unwrap(),
-                    15.0,
-                    0.63
-                );
-                skillsCount.B += helpers::sigmoid(
-                    *self.team[t as usize].player[p as usize].composite_rating.get("dribbling").unwrap(),
-                    15.0,
-                    0.68
-                );
-                skillsCount.Di += helpers::sigmoid(
-                    *self.team[t as usize].player[p as usize].composite_rating.get("defenseInterior").unwrap(),
-                    15.0,
-                    0.57
-                );
-                skillsCount.Dp += helpers::sigmoid(
-                    *self.team[t as usize].player[p as usize].composite_rating.get("defensePerimeter").unwrap(),
-                    15.0,
-                    0.61
-                );
-                skillsCount.Po += helpers::sigmoid(
-                    *self.team[t as usize].player[p as usize].composite_rating.get("shootingLowPost").unwrap(),
-                    15.0,
-                    0.61
-                );
-                skillsCount.Ps += helpers::sigmoid(
-                    *self.team[t as usize].player[p as usize].composite_rating.get("passing").unwrap(),
-                    15.0,
-                    0.63
-                );
-                skillsCount.R += helpers::sigmoid(
-                    *self.team[t as usize].player[p as usize].composite_rating.get("rebounding").unwrap(),
-                    15.0,
-                    0.61
-                );
+    // p_stole_from is the player on offense who turned it over
+    fn do_stl(&mut self, p_stole_from: usize) -> String {
+        let ratios = self.rating_array("stealing", self.d, 1.0);
+        let p = self.players_on_court[self.d][pick_player(&mut self.rng, ratios, None)];
+        self.team[self.d].player[p].stat.stl += 1;
+
+        let names = [self.get_name(self.d, p), self.get_name(self.o, p_stole_from)];
+        self.record_play(PlayType::Stl, Some(self.d), Some(p), &names);
+        "stl".to_string()
+    }
+
+    fn do_shot(&mut self, g: &G, shooter: PlayerNumOnCourt) -> String {
+        let o = self.o;
+        let d = self.d;
+        let p = self.players_on_court[o][shooter];
+        let current_fatigue = self.fatigue(self.team[o].player[p].stat.energy);
+
+        // Is this an assisted attempt, where an assist is recorded if it's made
+        let mut passer = None;
+        if self.num_players_on_court > 1 && self.prob_ast() > self.rng.gen_range(0.0..1.0) {
+            let ratios = self.rating_array("passing", o, 10.0);
+            passer = Some(pick_player(&mut self.rng, ratios, Some(shooter)));
+        }
+
+        let synergy_diff = self.synergy_factor * (self.team[o].synergy.off - self.team[d].synergy.def);
+        let rating = |name: &str| self.team[o].player[p].composite_rating.get(name);
+        let shooting_three_pointer = rating("shootingThreePointer");
+        let shooting_at_rim = rating("shootingAtRim");
+        let shooting_low_post = rating("shootingLowPost");
+        let shooting_mid_range = rating("shootingMidRange");
+
+        // Pick the type of shot and store the success rate (with no defense) in prob_make and the probability of an
+        // and-one in prob_and_one
+        let shot_type;
+        let mut prob_make;
+        let prob_and_one;
+        if shooting_three_pointer > 0.35 && self.rng.gen_range(0.0..1.0) < 0.67 * shooting_three_pointer {
+            shot_type = ShotType::ThreePointer;
+            prob_make = shooting_three_pointer * 0.3 + 0.36;
+            prob_and_one = 0.01;
+        } else {
+            let r1 = self.rng.gen_range(0.0..1.0) * shooting_mid_range;
+            let r2 = self.rng.gen_range(0.0..1.0) * (shooting_at_rim + synergy_diff);
+            let r3 = self.rng.gen_range(0.0..1.0) * (shooting_low_post + synergy_diff);
+            if r1 > r2 && r1 > r3 {
+                shot_type = ShotType::MidRange;
+                prob_make = shooting_mid_range * 0.32 + 0.46;
+                prob_and_one = 0.05;
+            } else if r2 > r3 {
+                shot_type = ShotType::AtRim;
+                prob_make = shooting_at_rim * 0.41 + 0.62;
+                prob_and_one = 0.25;
+            } else {
+                shot_type = ShotType::LowPost;
+                prob_make = shooting_low_post * 0.32 + 0.42;
+                prob_and_one = 0.15;
             }
+        }
 
-            self.team[t as usize].synergy.off = 0.0;
-            self.team[t as usize].synergy.off += 5.0 * helpers::sigmoid(skillsCount.three, 3.0, 2.0);
-
-            self.team[t as usize].synergy.off +=
-                3.0 * helpers::sigmoid(skillsCount.B, 15.0, 0.75) +
-                helpers::sigmoid(skillsCount.B, 5.0, 1.75);
-
-            self.team[t as usize].synergy.off +=
-                3.0 * helpers::sigmoid(skillsCount.Ps, 15.0, 0.75) +
-                helpers::sigmoid(skillsCount.Ps, 5.0, 1.75)  +
-                helpers::sigmoid(skillsCount.Ps, 5.0, 2.75);
-            
-            self.team[t as usize].synergy.off += helpers::sigmoid(skillsCount.Po, 15.0, 0.75);
-
-            self.team[t as usize].synergy.off +=
-                helpers::sigmoid(skillsCount.A, 15.0, 1.75) +
-                helpers::sigmoid(skillsCount.A, 5.0, 2.75);
-            
-            self.team[t as usize].synergy.off /= 17.0;
-
-            let perim_factor =
-                helpers::bound(
-                    (1.0 + skillsCount.B + skillsCount.Ps + skillsCount.three).sqrt() - 1.0,
-                    0.0,
-                    2.0
-                ) / 2.0;
-
-            self.team[t as usize].synergy.off *= 0.5 + 0.5 * perim_factor;
-
-            self.team[t as usize].synergy.def = 0.0;
-            self.team[t as usize].synergy.def += helpers::sigmoid(skillsCount.Dp, 15.0, 0.75);
-            self.team[t as usize].synergy.def += 2.0 * helpers::sigmoid(skillsCount.Di, 15.0, 0.75);
-            
-            self.team[t as usize].synergy.def +=
-                helpers::sigmoid(skillsCount.A, 5.0, 2.0) +
-                helpers::sigmoid(skillsCount.A, 5.0, 3.25);
-
-            self.team[t as usize].synergy.def /= 6.0;
-
-            self.team[t as usize].synergy.reb = 0.0;
-            self.team[t as usize].synergy.reb +=
-                helpers::sigmoid(skillsCount.R, 15.0, 0.75) +
-                helpers::sigmoid(skillsCount.R, 5.0, 1.75);
-
-            self.team[t as usize].synergy.reb /= 4.0;
-
-        }
-    }
-
-    fn updateTeamCompositeRatings(&self, g:&GameAttributes) {
-        let toUpdate = [
-            "dribbling",
-            "passing",
-            "rebounding",
-            "defense",
-            "defensePerimeter",
-            "blocking"
-        ];
-
-        let foulLimit = self.getFoulTroubleLimit(g);
-
-        for k in 0..TEAM_NUMS.len() {
-            let t = TEAM_NUMS[k] as usize;
-            let oppT = TEAM_NUMS[1 - k];
-            let diff = self.team[t].stat.pts - self.team[oppT as usize].stat.pts;
-
-            let perfFactor = 1.0 - 0.2 * (diff as f64 / 60.0).tanh();
-
-            for j in 0..toUpdate.len() {
-                let rating = toUpdate[j];
-                self.team[t].composite_rating.insert(rating.to_string(), 0.0);
-
-                for i in 0..self.numPlayersOnCourt {
-                    let p = self.playersOnCourt[t][i as usize];
-
-                    let mut foulLimitFactor = 1.0;
-                    if (
-                        rating.to_string() == "defense" ||
-                        rating.to_string() == "defensePerimeter" ||
-                        rating.to_string() == "blocking"
-                    ) {
-                        let pf = self.team[t].player[p as usize].stat.pf;
-                        if (pf as i32 == foulLimit) {
-                            foulLimitFactor *= 0.9;
-                        } else if (pf as i32 > foulLimit) {
-                            foulLimitFactor *= 0.75;
-                        }
-                    }
+        prob_make = (prob_make - 0.25 * self.team[d].composite_rating.get("defense") + synergy_diff) * current_fatigue;
+        if passer.is_some() {
+            prob_make += 0.025;
+        }
 
-                    self.team[t].composite_rating.add(
-                        rating.to_string(),
-                        self.team[t].player[p as usize].composite_rating.ratings.get(rating).unwrap() *
-                        self.fatigue(self.team[t].player[p].stat.energy) *
-                        perfFactor *
-                        foulLimitFactor
-                    );
-                }
+        if self.prob_blk() > self.rng.gen_range(0.0..1.0) {
+            return self.do_blk(shooter, shot_type);
+        }
+
+        if prob_make > self.rng.gen_range(0.0..1.0) {
+            let and_one = prob_and_one > self.rng.gen_range(0.0..1.0);
+            return self.do_fg(g, shooter, shot_type, passer, and_one);
+        }
 
-                self.team[t].composite_rating.mult(rating.to_string(), 1.0/5.0);
+        // Miss, but fouled
+        let prob_miss_and_foul = if shot_type == ShotType::ThreePointer { 0.03 } else { 0.16 } * g.foul_rate_factor();
+        if prob_miss_and_foul > self.rng.gen_range(0.0..1.0) {
+            if shot_type == ShotType::ThreePointer {
+                self.do_pf(g, d, PlayType::PfTp, Some(shooter));
+                return self.do_ft(shooter, 3);
             }
+            self.do_pf(g, d, PlayType::PfFg, Some(shooter));
+            return self.do_ft(shooter, 2);
+        }
 
-            self.team[t].composite_rating.add(
-                "dribbling".to_string(),
-                self.synergyFactor * self.team[t].synergy.off
-            );
-            self.team[t].composite_rating.add(
-                "passing".to_string(),
-                self.synergyFactor * self.team[t].synergy.off
-            );
-            self.team[t].composite_rating.add(
-                "rebounding".to_string(),
-                self.synergyFactor * self.team[t].synergy.reb
-            );
-            self.team[t].composite_rating.add(
-                "defense".to_string(),
-                self.synergyFactor * self.team[t].synergy.def
-            );
-            self.team[t].composite_rating.add(
-                "defensePerimeter".to_string(),
-                self.synergyFactor * self.team[t].synergy.def
-            );
-            self.team[t].composite_rating.add(
-                "blocking".to_string(),
-                self.synergyFactor * self.team[t].synergy.def
-            );
-        }
-    }
-
-    fn updatePlayingTime(&self, possessionLength: f64) {
-        for t in TEAM_NUMS.to_vec() {
-            let t = t as usize;
-                for p in 0..self.team[t].player.len() {
-                    if self.playersOnCourt[t].contains(p) {
-                        self.recordStat(t, p, "min", possessionLength);
-                        self.recordStat(t, p, "courtTime", possessionLength);
-
-                        self.recordStat(
-                            t,
-                            p,
-                            "energy",
-                            -possessionLength *
-                                self.fatigueFactor *
-                                (1.0 - self.team[t].player[p].composite_rating.get("endurance").unwrap()),
-                        );
-
-                        if self.team[t].player[p].stat.energy < 0.0 {
-                            self.team[t].player[p].stat.energy = 0.0;
-                        }
-                    } else {
-                        self.recordStat(t, p, "benchTime", possessionLength);
-                        self.recordStat(t, p, "energy", possessionLength * 0.094);
+        // Miss
+        let stat = &mut self.team[o].player[p].stat;
+        stat.fga += 1;
+        if shot_type == ShotType::ThreePointer {
+            stat.tpa += 1;
+        }
+        let names = [self.get_name(o, p)];
+        self.record_play(PlayType::Miss(shot_type), Some(o), Some(p), &names);
+        self.do_reb()
+    }
 
-                        if self.team[t].player[p].stat.energy > 1.0 {
-                            self.team[t].player[p].stat.energy = 1.0;
-                        }
-                    }
-                }   
+    fn do_blk(&mut self, shooter: PlayerNumOnCourt, shot_type: ShotType) -> String {
+        let p = self.players_on_court[self.o][shooter];
+        let stat = &mut self.team[self.o].player[p].stat;
+        stat.fga += 1;
+        if shot_type == ShotType::ThreePointer {
+            stat.tpa += 1;
+        }
+
+        let ratios = self.rating_array("blocking", self.d, 10.0);
+        let b = self.players_on_court[self.d][pick_player(&mut self.rng, ratios, None)];
+        self.team[self.d].player[b].stat.blk += 1;
+
+        let names = [self.get_name(self.d, b), self.get_name(self.o, p)];
+        self.record_play(PlayType::Blk(shot_type), Some(self.d), Some(b), &names);
+        self.do_reb()
+    }
+
+    fn do_fg(&mut self,
+            g: &G,
+            shooter: PlayerNumOnCourt,
+            shot_type: ShotType,
+            passer: Option<PlayerNumOnCourt>,
+            and_one: bool) -> String {
+        let o = self.o;
+        let p = self.players_on_court[o][shooter];
+        let stat = &mut self.team[o].player[p].stat;
+        stat.fga += 1;
+        stat.fg += 1;
+        if shot_type == ShotType::ThreePointer {
+            stat.tpa += 1;
+            stat.tp += 1;
+            self.add_pts(o, p, 3);
+        } else {
+            self.add_pts(o, p, 2);
+        }
 
+        let mut names = vec![self.get_name(o, p)];
+        if let Some(passer) = passer {
+            let passer = self.players_on_court[o][passer];
+            self.team[o].player[passer].stat.ast += 1;
+            names.push(self.get_name(o, passer));
         }
+        let play_type = if and_one { PlayType::FgAndOne(shot_type) } else { PlayType::Fg(shot_type) };
+        self.record_play(play_type, Some(o), Some(p), &names);
+
+        if and_one {
+            self.do_pf(g, self.d, PlayType::PfAndOne, Some(shooter));
+            return self.do_ft(shooter, 1);
+        }
+        "fg".to_string()
     }
 
-    fn injuries(&self, g: &GameAttributes) {
-        if g.leagueWithHistory.as_ref().unwrap().game_attributes_league.disableInjuries {
-            return;
+    // Free throws, with a rebound after a missed last one
+    fn do_ft(&mut self, shooter: PlayerNumOnCourt, amount: i32) -> String {
+        let o = self.o;
+        let p = self.players_on_court[o][shooter];
+        let prob_make = self.team[o].player[p].composite_rating.get("shootingFT") * 0.3 + 0.6;
+
+        let mut made_last = false;
+        for _ in 0..amount {
+            self.team[o].player[p].stat.fta += 1;
+            made_last = prob_make > self.rng.gen_range(0.0..1.0);
+            let names = [self.get_name(o, p)];
+            if made_last {
+                self.team[o].player[p].stat.ft += 1;
+                self.add_pts(o, p, 1);
+                self.record_play(PlayType::Ft, Some(o), Some(p), &names);
+            } else {
+                self.record_play(PlayType::MissFt, Some(o), Some(p), &names);
+            }
         }
 
-        let newInjury = false;
-        let mut baseRate = self.baseInjuryRate;
+        if made_last {
+            return "fg".to_string();
+        }
+        self.do_reb()
+    }
 
-        baseRate *= 100.0 / g.leagueWithHistory.as_ref().unwrap().game_attributes_league.pace;
+    // shooter is the player on offense who was fouled, if any
+    fn do_pf(&mut self, g: &G, t: TeamNum, play_type: PlayType, shooter: Option<PlayerNumOnCourt>) {
+        let ratios = self.rating_array("fouling", t, 2.0);
+        let p = self.players_on_court[t][pick_player(&mut self.rng, ratios, None)];
+        self.team[t].player[p].stat.pf += 1;
 
-        for t in TEAM_NUMS.to_vec() {
-            let t = t as usize;
-            for p in 0..self.team[t].player.len() {
-                let p = p as i32;
-                if self.playersOnCourt[t].contains(&p) {
-                    let injuryRate = getInjuryRate(
-                        baseRate,
-                        self.team[t].player[p as usize].age,
-                        Some(self.team[t].player[p as usize].injury.playing_through),
-                    );
-
-                    if rng.gen_range(0.0..1.0) < injuryRate {
-                        self.team[t].player[p as usize].injured = true;
-                        self.team[t].player[p as usize].new_injury = true;
-                        newInjury = true;
-                        let injuredPID = self.team[t].player[p as usize].id;
-                        self.recordPlay("injury",
-                            t,
-                            [self.team[t].player[p as usize].name],
-                            Some(injuredPID),    
-                        );
-                    }
-                }
+        let mut names = vec![self.get_name(t, p)];
+        if let Some(shooter) = shooter {
+            names.push(self.get_name(1 - t, self.players_on_court[1 - t][shooter]));
+        }
+        self.record_play(play_type, Some(t), Some(p), &names);
+
+        // Fouls for the bonus
+        self.fouls_this_quarter[t] += 1;
+        if self.t <= 2.0 {
+            self.fouls_last_two_minutes[t] += 1;
+        }
+
+        let fouls_needed_to_foul_out = g.fouls_needed_to_foul_out();
+        if fouls_needed_to_foul_out > 0 && self.team[t].player[p].stat.pf >= fouls_needed_to_foul_out {
+            let names = [self.get_name(t, p)];
+            self.record_play(PlayType::FoulOut, Some(t), Some(p), &names);
+            if self.update_players_on_court(g, shooter) {
+                self.update_synergy();
             }
         }
+    }
+
+    fn do_reb(&mut self) -> String {
+        // Some missed shots go out of bounds or are grabbed by nobody in particular, and the defense gets the ball
+        if 0.15 > self.rng.gen_range(0.0..1.0) {
+            return "drb".to_string();
+        }
+
+        let prob_drb = 0.75 * (2.0 + self.team[self.d].composite_rating.get("rebounding")) /
+            (2.0 + self.team[self.o].composite_rating.get("rebounding"));
+        if prob_drb > self.rng.gen_range(0.0..1.0) {
+            let ratios = self.rating_array("rebounding", self.d, 3.0);
+            let p = self.players_on_court[self.d][pick_player(&mut self.rng, ratios, None)];
+            self.team[self.d].player[p].stat.drb += 1;
+            let names = [self.get_name(self.d, p)];
+            self.record_play(PlayType::Drb, Some(self.d), Some(p), &names);
+            return "drb".to_string();
+        }
 
-        if (newInjury) {
-            self.updatePlayersOnCourt(g, None);
+        let ratios = self.rating_array("rebounding", self.o, 3.0);
+        let p = self.players_on_court[self.o][pick_player(&mut self.rng, ratios, None)];
+        self.team[self.o].player[p].stat.orb += 1;
+        let names = [self.get_name(self.o, p)];
+        self.record_play(PlayType::Orb, Some(self.o), Some(p), &names);
+        "orb".to_string()
+    }
+
+    fn get_play_text(&self, play_type: &PlayType, names: &[String]) -> String {
+        let name = |i: usize| names.get(i).map(|name| name.as_str()).unwrap_or("");
+        match play_type {
+            PlayType::Blk(shot_type) => format!("{} blocked {} by {}", name(0), shot_type.text(), name(1)),
+            PlayType::Drb => format!("{} grabbed the defensive rebound", name(0)),
+            PlayType::Fg(shot_type) | PlayType::FgAndOne(shot_type) => {
+                let mut text = format!("{} made {}", name(0), shot_type.text());
+                if matches!(play_type, PlayType::FgAndOne(_)) {
+                    text.push_str(", and one");
+                }
+                if names.len() > 1 {
+                    text.push_str(&format!(" ({} assisted)", name(1)));
+                }
+                text
+            },
+            PlayType::FoulOut => format!("{} fouled out", name(0)),
+            PlayType::Ft => format!("{} made a free throw", name(0)),
+            PlayType::GameOver => "End of game".to_string(),
+            PlayType::Injury => format!("{} was injured! ({})", name(0), name(1)),
+            PlayType::JumpBall => format!("{} won the jump ball", name(0)),
+            PlayType::Miss(shot_type) => format!("{} missed {}", name(0), shot_type.text()),
+            PlayType::MissFt => format!("{} missed a free throw", name(0)),
+            PlayType::Orb => format!("{} grabbed the offensive rebound", name(0)),
+            PlayType::Overtime => {
+                if self.overtimes == 1 {
+                    "Start of overtime".to_string()
+                } else {
+                    format!("Start of {} overtime", ordinal(self.overtimes))
+                }
+            },
+            PlayType::PfNonShooting => format!("Non-shooting foul on {}", name(0)),
+            PlayType::PfBonus => format!("Non-shooting foul on {}, in the bonus: two free throws for {}", name(0), name(1)),
+            PlayType::PfFg => format!("Shooting foul on {}, two free throws for {}", name(0), name(1)),
+            PlayType::PfTp => format!("Shooting foul on {}, three free throws for {}", name(0), name(1)),
+            PlayType::PfAndOne => format!("Foul on {}, one free throw for {}", name(0), name(1)),
+            PlayType::Quarter => format!("Start of {} quarter", ordinal(self.get_period())),
+            PlayType::Stl => format!("{} stole the ball from {}", name(0), name(1)),
+            PlayType::Sub => format!("Substitution: {} for {}", name(0), name(1)),
+            PlayType::Tov => format!("{} turned the ball over", name(0)),
+            PlayType::Timeout => "Timeout".to_string()
         }
     }
 
-    fn getNumFoulsUntilBonus(&self, g: &GameAttributes) -> i32 {
-        let foulsUntilBonus = g.leagueWithHistory.as_ref().unwrap().game_attributes_league.foulsUntilBonus;
-        if (self.t <= 2) {
-            return foulsUntilBonus[2] - self.foulsLastTwoMinutes[self.d.unwrap() as usize];
+    // t is the team of the first player in names, None for plays that aren't by a team, and p is that player, who gets
+    // credit for the play's change in win probability
+    fn record_play(&mut self, play_type: PlayType, t: Option<TeamNum>, p: Option<usize>, names: &[String]) {
+        if self.play_by_play.is_none() {
+            return;
         }
-        if (self.overtimes >= 1) {
-            return foulsUntilBonus[1] - self.foulsThisQuarter[self.d.unwrap() as usize];
+        let event = PlayByPlayEvent {
+            period: self.get_period(),
+            t: self.t,
+            tid: t.map(|t| self.team[t].id),
+            pid: t.zip(p).map(|(t, p)| self.team[t].player[p].id),
+            text: self.get_play_text(&play_type, names),
+            score: [self.team[0].stat.pts, self.team[1].stat.pts],
+            wp: None
+        };
+        self.play_by_play.as_mut().unwrap().push(event);
+    }
+
+    fn get_box_score_team(&self, t: TeamNum) -> BoxScoreTeam {
+        let mut players: Vec<BoxScorePlayer> = self.team[t].player.iter().map(|p| BoxScorePlayer {
+            pid: p.id,
+            name: p.name.clone(),
+            pos: p.pos.clone(),
+            gs: p.stat.gs,
+            min: p.stat.min,
+            pts: p.stat.pts,
+            fg: p.stat.fg,
+            fga: p.stat.fga,
+            tp: p.stat.tp,
+            tpa: p.stat.tpa,
+            ft: p.stat.ft,
+            fta: p.stat.fta,
+            orb: p.stat.orb,
+            drb: p.stat.drb,
+            ast: p.stat.ast,
+            stl: p.stat.stl,
+            blk: p.stat.blk,
+            tov: p.stat.tov,
+            pf: p.stat.pf,
+            wpa: 0.0,
+            injury: if p.new_injury { Some(p.injury.injury.clone()) } else { None }
+        }).collect();
+        // Starters first, then by minutes
+        players.sort_by(|a, b| b.gs.cmp(&a.gs).then(b.min.total_cmp(&a.min)));
+        BoxScoreTeam {
+            tid: self.team[t].id,
+            pts: self.team[t].stat.pts,
+            pts_qtrs: self.team[t].stat.pts_qtrs.clone(),
+            players
         }
-        return foulsUntilBonus[0] - self.foulsThisQuarter[self.d.unwrap() as usize];
     }
 
-    fn getPossessionOutcome(&self, g: &GameAttributes, possessionLength: f64, intentionalFoul: bool) -> String {
-        if (
-            self.t <= 0 &&
-            self.team[self.o.unwrap() as usize].stat.ptsQtrs.len() >= self.numPeriods &&
-            self.team[self.o.unwrap() as usize].stat.pts > self.team[self.d.unwrap() as usize].stat.pts &&
-            !self.elamActive
-        ) {
-            return "endOfQuarter".to_string();
+    // The finished game, with its box score and play-by-play
+    pub fn get_result(&mut self) -> SimGameResult {
+        let mut box_score = BoxScore {
+            gid: self.id,
+            season: self.season,
+            day: self.day,
+            playoffs: self.playoffs,
+            overtimes: self.overtimes,
+            teams: TEAM_NUMS.map(|t| self.get_box_score_team(t))
+        };
+        let mut play_by_play = self.play_by_play.take();
+        if let Some(events) = &mut play_by_play {
+            self.win_probability.add_to_play_by_play(events);
+            add_win_probability_added(&mut box_score, events, self.win_probability.get_pregame());
+        }
+        SimGameResult {
+            result: GameResult {
+                gid: self.id,
+                season: self.season,
+                playoffs: self.playoffs,
+                overtimes: self.overtimes,
+                teams: TEAM_NUMS.map(|t| GameResultTeam {
+                    tid: self.team[t].id,
+                    pts: self.team[t].stat.pts
+                })
+            },
+            box_score: Some(box_score),
+            play_by_play
         }
+    }
+}
 
-        if self.t <= 0 && possessionLength < 6.0 / 60.0 && !self.elamActive {
-            if (rng.gen_range(0.0..1.0) > (possessionLength / (8.0 / 60.0)).powf(1.0/4.0)) {
-                return "endOfQuarter".to_string();
-            }
+// One possession, as returned by GameSim::step
+pub struct PossessionResult {
+    pub period: i32,
+    // Minutes left in the period after the possession
+    pub t: f64,
+    // Team that had the ball
+    pub o: TeamNum,
+    pub outcome: String,
+    // Home, away
+    pub score: [i32; 2],
+    // Home team's chance of winning after the possession
+    pub win_probability: f64
+}
+
+pub enum StepResult {
+    Possession(PossessionResult),
+    // Nothing was played, call resume first
+    Paused,
+    Over
+}
+
+// Iterator over a game's possessions, see GameSim::possessions. The last item is Paused or Over, whichever stopped it.
+pub struct Possessions<'a> {
+    game_sim: &'a mut GameSim,
+    g: &'a G,
+    done: bool
+}
+
+impl<'a> Iterator for Possessions<'a> {
+    type Item = StepResult;
+
+    fn next(&mut self) -> Option<StepResult> {
+        if self.done {
+            return None;
         }
+        let result = self.game_sim.step(self.g);
+        self.done = !matches!(result, StepResult::Possession(_));
+        Some(result)
+    }
+}
+
+// A player's line so far, for callers that coach a team
+pub struct PlayerStatus {
+    pub pid: i32,
+    pub name: String,
+    pub pos: String,
+    pub on_court: bool,
+    pub energy: f64,
+    pub min: f64,
+    pub pts: i32,
+    pub pf: i32,
+    pub injured: bool
+}
+
+// Stepping API. A game is played by calling step until it returns Over, and between steps the caller can pause,
+// substitute or call a timeout. run plays the whole game at once.
+impl GameSim {
+    // step does nothing until resume is called
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
 
-        if (rng.gen_range(0.0..1.0) < self.probTov(g)) {
-            return self.doTov();
+    pub fn possessions<'a>(&'a mut self, g: &'a G) -> Possessions<'a> {
+        Possessions {
+            game_sim: self,
+            g,
+            done: false
         }
+    }
 
-        let ratios = self.ratingArray("usage", self.o.unwrap(), 1.25);
-        let shooter = pick_player(ratios, None);
+    pub fn run(&mut self, g: &G) {
+        self.resume();
+        for _ in self.possessions(g) {}
+    }
 
-        if (rng.gen_range(0.0..1.0) < 0.08 * g.leagueWithHistory.as_ref().unwrap().game_attributes_league.foulRateFactor ||
-            intentionalFoul) {
-            let numFoulsUntilBonus = self.getNumFoulsUntilBonus(g);
-            let inBonus = numFoulsUntilBonus <= 1;
+    fn get_period(&self) -> i32 {
+        self.team[0].stat.pts_qtrs.len() as i32
+    }
 
-            if (inBonus) {
-                self.doPf(self.d.unwrap(), "pfBonus", shooter);
+    // The best jumper on the court for each team contests the tip
+    fn jump_ball(&mut self) -> TeamNum {
+        let ratings: Vec<f64> = TEAM_NUMS.iter().map(|t| {
+            self.players_on_court[*t]
+                .iter()
+                .map(|p| self.team[*t].player[*p].composite_rating.get("jumpBall"))
+                .fold(0.0, f64::max)
+        }).collect();
+        let total = ratings[0].powi(2) + ratings[1].powi(2);
+        let prob_team0 = if total > 0.0 { ratings[0].powi(2) / total } else { 0.5 };
+        let winner = if self.rng.gen_range(0.0..1.0) < prob_team0 { 0 } else { 1 };
+
+        let jumper = self.players_on_court[winner]
+            .iter()
+            .copied()
+            .max_by(|a, b| {
+                let a = self.team[winner].player[*a].composite_rating.get("jumpBall");
+                let b = self.team[winner].player[*b].composite_rating.get("jumpBall");
+                a.total_cmp(&b)
+            })
+            .unwrap();
+        let names = [self.get_name(winner, jumper)];
+        self.record_play(PlayType::JumpBall, Some(winner), Some(jumper), &names);
+        winner
+    }
+
+    fn start_period(&mut self, g: &G) {
+        for t in TEAM_NUMS {
+            self.team[t].stat.pts_qtrs.push(0);
+        }
+        self.fouls_this_quarter = [0, 0];
+        self.fouls_last_two_minutes = [0, 0];
+
+        // The team that starts on offense. The other regulation periods go to the team that lost the opening tip, then
+        // the one that won it, and overtimes have their own tip.
+        let period = self.get_period();
+        let starter;
+        if period > self.num_periods {
+            self.overtimes += 1;
+            self.t = OVERTIME_LENGTH;
+            self.record_play(PlayType::Overtime, None, None, &[]);
+            starter = self.jump_ball();
+        } else {
+            self.t = g.quarter_length();
+            if period == 1 {
+                self.jump_ball_winner = self.jump_ball();
+                starter = self.jump_ball_winner;
             } else {
-                self.doPf(self.d.unwrap(), "pfNonShooting");
+                self.record_play(PlayType::Quarter, None, None, &[]);
+                starter = if period == self.num_periods { self.jump_ball_winner } else { 1 - self.jump_ball_winner };
             }
+        }
 
-            if (inBonus) {
-                return self.doFt(shooter, 2);
-            }
+        // sim_possession switches possession first
+        self.o = 1 - starter;
+        self.d = starter;
+    }
+
+    // Plays the next possession, starting a new period first if needed
+    pub fn step(&mut self, g: &G) -> StepResult {
+        if self.over {
+            return StepResult::Over;
+        }
+        if self.paused {
+            return StepResult::Paused;
+        }
 
-            return "nonShootingFoul";
+        if !self.started {
+            self.started = true;
+            self.start_period(g);
+        } else if self.t <= 0.0 {
+            let tied = self.team[0].stat.pts == self.team[1].stat.pts;
+            if self.get_period() >= self.num_periods && (!tied || (self.allow_ties && self.overtimes > 0)) {
+                self.over = true;
+                self.record_play(PlayType::GameOver, None, None, &[]);
+                return StepResult::Over;
+            }
+            self.start_period(g);
         }
 
-        return self.doShot(shooter, possessionLength);
+        StepResult::Possession(self.sim_possession(g))
     }
 
-    fn probTov(&self, g: &GameAttributes) -> f64 {
-        return boundProb(
-            (g.leagueWithHistory.as_ref().unwrap().game_attributes_league.turnoverFactor) *
-                (0.14 * self.team[self.d.unwrap() as usize].composite_rating.get("defense").unwrap()) /
-                (0.5 *
-                    self.team[self.o.unwrap() as usize].composite_rating.get("dribbling").unwrap() +
-                        self.team[self.o.unwrap() as usize].composite_rating.get("passing").unwrap()),
+    fn sim_possession(&mut self, g: &G) -> PossessionResult {
+        let o = 1 - self.o;
+        self.o = o;
+        self.d = 1 - o;
+        self.update_team_composite_ratings(g);
+
+        let possession_length = helpers::bound(
+            gauss(&mut self.rng, self.average_possession_length, 0.1),
+            0.0,
+            self.t
         );
+        self.t -= possession_length;
+
+        let intentional_foul = self.is_intentional_foul();
+        let outcome = self.get_possession_outcome(g, possession_length, intentional_foul);
+
+        // Swap o and d so that o gets the ball again when possession switches at the start of the next one
+        if outcome == "orb" || outcome == "nonShootingFoul" {
+            self.o = 1 - o;
+            self.d = o;
+        }
+
+        self.update_playing_time(possession_length);
+        self.injuries(g);
+        self.num_possessions += 1;
+        if self.num_possessions % SUBS_EVERY_N == 0 && self.update_players_on_court(g, None) {
+            self.update_synergy();
+        }
+
+        PossessionResult {
+            period: self.get_period(),
+            t: self.t,
+            o,
+            outcome,
+            score: [self.team[0].stat.pts, self.team[1].stat.pts],
+            win_probability: self.get_win_probability()
+        }
+    }
+
+    fn get_player_index(&self, t: TeamNum, pid: i32) -> Result<usize, String> {
+        let p = self.team[t].player.iter().position(|p| p.id == pid);
+        if p.is_none() {
+            return Err(format!("Player {} is not on team {}", pid, self.team[t].id));
+        }
+        Ok(p.unwrap())
+    }
+
+    // Puts pid_on in for pid_off. Only allowed between possessions.
+    pub fn substitute(&mut self, g: &G, t: TeamNum, pid_on: i32, pid_off: i32) -> Result<(), String> {
+        if self.over {
+            return Err("The game is over".to_string());
+        }
+        let on = self.get_player_index(t, pid_on)?;
+        let off = self.get_player_index(t, pid_off)?;
+        let pp = self.players_on_court[t].iter().position(|p| *p == off);
+        if pp.is_none() {
+            return Err(format!("Player {} is not on the court", pid_off));
+        }
+        if self.players_on_court[t].contains(&on) {
+            return Err(format!("Player {} is already on the court", pid_on));
+        }
+        let fouls_needed_to_foul_out = g.fouls_needed_to_foul_out();
+        let player = &self.team[t].player[on];
+        if player.injured {
+            return Err(format!("Player {} is injured", pid_on));
+        }
+        if fouls_needed_to_foul_out > 0 && player.stat.pf >= fouls_needed_to_foul_out {
+            return Err(format!("Player {} fouled out", pid_on));
+        }
+
+        self.swap_players(t, pp.unwrap(), on);
+        self.update_synergy();
+        Ok(())
     }
 
-    fn doTov(&self) -> String {
-        let ratios = self.ratingArray("turnovers", self.)
+    pub fn get_player_statuses(&self, t: TeamNum) -> Vec<PlayerStatus> {
+        self.team[t].player.iter().enumerate().map(|(p, player)| PlayerStatus {
+            pid: player.id,
+            name: player.name.clone(),
+            pos: player.pos.clone(),
+            on_court: self.players_on_court[t].contains(&p),
+            energy: player.stat.energy,
+            min: player.stat.min,
+            pts: player.stat.pts,
+            pf: player.stat.pf,
+            injured: player.injured
+        }).collect()
+    }
+
+    pub fn set_auto_subs(&mut self, t: TeamNum, auto_subs: bool) {
+        self.auto_subs[t] = auto_subs;
+    }
+
+    pub fn get_timeouts_remaining(&self, t: TeamNum) -> i32 {
+        self.timeouts_remaining[t]
+    }
+
+    // Stops the clock so the players on the court catch their breath, and gives the auto subs a chance to run
+    pub fn call_timeout(&mut self, g: &G, t: TeamNum) -> Result<(), String> {
+        if self.over {
+            return Err("The game is over".to_string());
+        }
+        if self.timeouts_remaining[t] <= 0 {
+            return Err(format!("Team {} has no timeouts left", self.team[t].id));
+        }
+        self.timeouts_remaining[t] -= 1;
+
+        for t2 in TEAM_NUMS {
+            for p in self.players_on_court[t2].clone() {
+                let stat = &mut self.team[t2].player[p].stat;
+                stat.energy = (stat.energy + TIMEOUT_ENERGY).min(1.0);
+            }
+        }
+        self.record_play(PlayType::Timeout, Some(t), None, &[]);
+        if self.update_players_on_court(g, None) {
+            self.update_synergy();
+        }
+        Ok(())
     }
 }
\ No newline at end of file
@@ -1,2 +1,3 @@
 pub mod index;
-pub mod getInjuryRate;
\ No newline at end of file
+pub mod get_injury_rate;
+pub mod synergy;
\ No newline at end of file
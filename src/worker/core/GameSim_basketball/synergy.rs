@@ -0,0 +1,81 @@
+use crate::worker::util::helpers;
+use std::collections::HashMap;
+
+// How much synergy adds to the team composite ratings
+pub const SYNERGY_FACTOR: f64 = 0.1;
+
+pub struct Synergy {
+    pub def: f64,
+    pub off: f64,
+    pub reb: f64
+}
+
+#[derive(Default)]
+pub struct SkillsCount {
+    pub three: f64,
+    pub a: f64,
+    pub b: f64,
+    pub di: f64,
+    pub dp: f64,
+    pub po: f64,
+    pub ps: f64,
+    pub r: f64
+}
+
+fn get_rating(composite_rating: &HashMap<String, f64>, key: &str) -> f64 {
+    *composite_rating.get(key).unwrap()
+}
+
+// Soft count of the skills of the players on the court, from their composite ratings
+pub fn get_skills_count(composite_ratings: &[&HashMap<String, f64>]) -> SkillsCount {
+    let mut skills_count = SkillsCount::default();
+    for composite_rating in composite_ratings.iter() {
+        skills_count.three += helpers::sigmoid(get_rating(composite_rating, "shootingThreePointer"), 15.0, 0.59);
+        skills_count.a += helpers::sigmoid(get_rating(composite_rating, "athleticism"), 15.0, 0.63);
+        skills_count.b += helpers::sigmoid(get_rating(composite_rating, "dribbling"), 15.0, 0.68);
+        skills_count.di += helpers::sigmoid(get_rating(composite_rating, "defenseInterior"), 15.0, 0.57);
+        skills_count.dp += helpers::sigmoid(get_rating(composite_rating, "defensePerimeter"), 15.0, 0.61);
+        skills_count.po += helpers::sigmoid(get_rating(composite_rating, "shootingLowPost"), 15.0, 0.61);
+        skills_count.ps += helpers::sigmoid(get_rating(composite_rating, "passing"), 15.0, 0.63);
+        skills_count.r += helpers::sigmoid(get_rating(composite_rating, "rebounding"), 15.0, 0.61);
+    }
+    skills_count
+}
+
+pub fn get_synergy(skills_count: &SkillsCount) -> Synergy {
+    let mut off = 0.0;
+    off += 5.0 * helpers::sigmoid(skills_count.three, 3.0, 2.0);
+    off += 3.0 * helpers::sigmoid(skills_count.b, 15.0, 0.75) + helpers::sigmoid(skills_count.b, 5.0, 1.75);
+    off +=
+        3.0 * helpers::sigmoid(skills_count.ps, 15.0, 0.75) +
+        helpers::sigmoid(skills_count.ps, 5.0, 1.75) +
+        helpers::sigmoid(skills_count.ps, 5.0, 2.75);
+    off += helpers::sigmoid(skills_count.po, 15.0, 0.75);
+    off += helpers::sigmoid(skills_count.a, 15.0, 1.75) + helpers::sigmoid(skills_count.a, 5.0, 2.75);
+    off /= 17.0;
+
+    // Punish teams for not having multiple perimeter skills
+    let perim_factor =
+        helpers::bound(
+            (1.0 + skills_count.b + skills_count.ps + skills_count.three).sqrt() - 1.0,
+            0.0,
+            2.0
+        ) / 2.0;
+    off *= 0.5 + 0.5 * perim_factor;
+
+    let mut def = 0.0;
+    def += helpers::sigmoid(skills_count.dp, 15.0, 0.75);
+    def += 2.0 * helpers::sigmoid(skills_count.di, 15.0, 0.75);
+    def += helpers::sigmoid(skills_count.a, 5.0, 2.0) + helpers::sigmoid(skills_count.a, 5.0, 3.25);
+    def /= 6.0;
+
+    let mut reb = 0.0;
+    reb += helpers::sigmoid(skills_count.r, 15.0, 0.75) + helpers::sigmoid(skills_count.r, 5.0, 1.75);
+    reb /= 4.0;
+
+    Synergy {
+        def,
+        off,
+        reb
+    }
+}
\ No newline at end of file
@@ -0,0 +1,33 @@
+use crate::common::constants::PLAYER_FREE_AGENT;
+use crate::common::constants::PLAYER_UNDRAFTED;
+use crate::worker::core::draft::gen_picks::gen_picks;
+use crate::worker::core::draft::gen_players::gen_players;
+use crate::worker::core::draft::gen_players::get_num_draft_prospects;
+use crate::worker::core::player::gen_contract::gen_contract;
+use crate::worker::core::player::gen_ratings::TalentDistribution;
+use crate::worker::db::Tx;
+
+// Once every pick in season's draft is made, the prospects nobody took become free agents asking for contracts that
+// start next season, and next year's draft class is generated, if there isn't one already
+pub fn after_picks(tx: &Tx, season: i32) -> Result<(), String> {
+    let g = tx.get_g()?;
+    let mut has_next_class = false;
+    for mut p in tx.get_players_by_tid(PLAYER_UNDRAFTED)? {
+        if p.draft.year == season {
+            p.tid = PLAYER_FREE_AGENT;
+            p.contract = gen_contract(&p, &g, season + 1);
+            tx.put_player(&p)?;
+        } else if p.draft.year == season + 1 {
+            has_next_class = true;
+        }
+    }
+    tx.put_last_draft_season(season)?;
+
+    if !has_next_class {
+        let num_players = get_num_draft_prospects(tx.get_teams()?.len(), g.num_draft_rounds());
+        for p in gen_players(season + 1, &g, num_players, tx.get_next_pid()?, &TalentDistribution::default(), tx.get_seed()?)? {
+            tx.put_player(&p)?;
+        }
+    }
+    gen_picks(tx)
+}
\ No newline at end of file
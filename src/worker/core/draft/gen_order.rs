@@ -0,0 +1,193 @@
+use crate::common::constants::PLAYER_UNDRAFTED;
+use crate::worker::core::draft::gen_players::gen_players;
+use crate::worker::core::player::gen_ratings::TalentDistribution;
+use crate::worker::core::season::get_standings::get_standings;
+use crate::worker::db::Tx;
+use crate::worker::util::random::coin_flip_seed;
+use crate::worker::util::random::lottery_seed;
+use crate::worker::util::random::rng_from_seed;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+// Chances out of 1000 for the 14 lottery teams, worst record first. Teams past the 14th have no chance.
+const NBA_2019_CHANCES: [f64; 14] = [140.0, 140.0, 140.0, 125.0, 105.0, 90.0, 75.0, 60.0, 45.0, 30.0, 20.0, 15.0, 10.0, 5.0];
+const NBA_2019_NUM_DRAWN: usize = 4;
+
+pub struct DraftLotteryTeam {
+    pub original_tid: i32,
+    // Chance of the first pick, None for teams that aren't in the lottery
+    pub chance: Option<f64>,
+    pub pick: i32
+}
+
+// Chance of each lottery team, worst record first, of winning any one drawing
+pub fn get_lottery_chances(draft_type: &str, num_teams: usize) -> Vec<f64> {
+    match draft_type {
+        "nba2019" => (0..num_teams).map(|i| NBA_2019_CHANCES.get(i).copied().unwrap_or(0.0)).collect(),
+        "randomLottery" => vec![1.0; num_teams],
+        _ => vec![0.0; num_teams],
+    }
+}
+
+// Order of the lottery teams in the first round, from their order by record, worst first
+pub fn run_lottery(draft_type: &str, tids: &[i32], rng: &mut StdRng) -> Vec<i32> {
+    if draft_type == "randomLottery" {
+        let mut order = tids.to_vec();
+        order.shuffle(rng);
+        return order;
+    }
+    if draft_type != "nba2019" {
+        return tids.to_vec();
+    }
+
+    let mut chances = get_lottery_chances(draft_type, tids.len());
+    let mut order = Vec::new();
+    for _ in 0..NBA_2019_NUM_DRAWN.min(tids.len()) {
+        let total: f64 = chances.iter().sum();
+        if total <= 0.0 {
+            break;
+        }
+        let mut r = rng.gen::<f64>() * total;
+        let mut winner = chances.iter().rposition(|chance| *chance > 0.0).unwrap();
+        for (i, chance) in chances.iter().enumerate() {
+            if r < *chance {
+                winner = i;
+                break;
+            }
+            r -= chance;
+        }
+        order.push(tids[winner]);
+        chances[winner] = 0.0;
+    }
+    for tid in tids.iter() {
+        if !order.contains(tid) {
+            order.push(*tid);
+        }
+    }
+    order
+}
+
+// Sets the pick numbers of g.season()'s draft picks, running the lottery if there is one. Teams are ordered worst first
+// by the regular season standings. Also tops up the draft class if there aren't enough prospects for every pick.
+pub fn gen_order(tx: &Tx) -> Result<Vec<DraftLotteryTeam>, String> {
+    let g = tx.get_g()?;
+    let mut draft_picks = tx.get_draft_picks(g.season())?;
+    if draft_picks.is_empty() {
+        return Err(format!("No draft picks for {}", g.season()));
+    }
+    if draft_picks.iter().all(|dp| dp.pick > 0) {
+        return Err(format!("The {} draft order is already set", g.season()));
+    }
+
+    let seed = tx.get_seed()?;
+    let team_seasons = tx.get_team_seasons(g.season())?;
+    let standings = get_standings(&team_seasons, &tx.get_games(g.season())?, &g, coin_flip_seed(seed, g.season()));
+    let worst_first: Vec<i32> = standings.league.teams.iter().rev().map(|entry| entry.season_attrs.tid).collect();
+
+    let mut playoff_tids = Vec::new();
+    let playoff_series = tx.get_playoff_series(g.season())?;
+    if let Some(playoff_series) = playoff_series {
+        for round in playoff_series.series.iter() {
+            for matchup in round.iter() {
+                playoff_tids.push(matchup.home.tid);
+                if let Some(away) = &matchup.away {
+                    playoff_tids.push(away.tid);
+                }
+            }
+        }
+    }
+    let lottery_tids: Vec<i32> = worst_first.iter().copied().filter(|tid| !playoff_tids.contains(tid)).collect();
+    let chances = get_lottery_chances(g.draft_type(), lottery_tids.len());
+    let total_chance: f64 = chances.iter().sum();
+
+    let mut rng = rng_from_seed(lottery_seed(seed, g.season()));
+    let mut first_round = run_lottery(g.draft_type(), &lottery_tids, &mut rng);
+    first_round.extend(worst_first.iter().copied().filter(|tid| playoff_tids.contains(tid)));
+
+    let mut lottery = Vec::new();
+    for dp in draft_picks.iter_mut() {
+        let order = if dp.round == 1 { &first_round } else { &worst_first };
+        let index = order.iter().position(|tid| *tid == dp.original_tid);
+        if index.is_none() {
+            continue;
+        }
+        dp.pick = index.unwrap() as i32 + 1;
+        tx.put_draft_pick(dp)?;
+
+        if dp.round == 1 {
+            let lottery_index = lottery_tids.iter().position(|tid| *tid == dp.original_tid);
+            lottery.push(DraftLotteryTeam {
+                original_tid: dp.original_tid,
+                chance: if total_chance > 0.0 { lottery_index.map(|i| chances[i] / total_chance) } else { None },
+                pick: dp.pick
+            });
+        }
+    }
+    lottery.sort_by_key(|t| t.pick);
+
+    let num_prospects = tx.get_players_by_tid(PLAYER_UNDRAFTED)?.iter().filter(|p| p.draft.year == g.season()).count();
+    if num_prospects < draft_picks.len() {
+        for p in gen_players(g.season(), &g, draft_picks.len() - num_prospects, tx.get_next_pid()?, &TalentDistribution::default(), seed)? {
+            tx.put_player(&p)?;
+        }
+    }
+    Ok(lottery)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NUM_RUNS: usize = 20000;
+
+    // How often each of 16 lottery teams, worst first, ends up with each pick
+    fn get_pick_counts(draft_type: &str) -> Vec<Vec<usize>> {
+        let tids: Vec<i32> = (0..16).collect();
+        let mut counts = vec![vec![0; tids.len()]; tids.len()];
+        let mut rng = rng_from_seed(1);
+        for _ in 0..NUM_RUNS {
+            for (pick, tid) in run_lottery(draft_type, &tids, &mut rng).iter().enumerate() {
+                counts[*tid as usize][pick] += 1;
+            }
+        }
+        counts
+    }
+
+    #[test]
+    fn nba2019_first_pick_odds() {
+        let counts = get_pick_counts("nba2019");
+        for (i, chance) in NBA_2019_CHANCES.iter().enumerate() {
+            let expected = chance / 1000.0;
+            let actual = counts[i][0] as f64 / NUM_RUNS as f64;
+            assert!((actual - expected).abs() < 0.01, "team {} got the first pick {:.3} of the time, expected {:.3}", i, actual, expected);
+        }
+        // Teams past the 14th are never drawn
+        assert_eq!(counts[14][..NBA_2019_NUM_DRAWN].iter().sum::<usize>(), 0);
+        assert_eq!(counts[15][..NBA_2019_NUM_DRAWN].iter().sum::<usize>(), 0);
+    }
+
+    #[test]
+    fn nba2019_teams_drop_at_most_four_spots() {
+        let counts = get_pick_counts("nba2019");
+        for (i, team_counts) in counts.iter().enumerate() {
+            let worst_pick = team_counts.iter().rposition(|count| *count > 0).unwrap();
+            assert!(worst_pick <= i + NBA_2019_NUM_DRAWN, "team {} picked as late as {}", i, worst_pick + 1);
+        }
+        // The worst team keeps a top 5 pick
+        assert_eq!(counts[0][..5].iter().sum::<usize>(), NUM_RUNS);
+    }
+
+    #[test]
+    fn random_lottery_and_no_lottery() {
+        let counts = get_pick_counts("randomLottery");
+        for team_counts in counts.iter() {
+            let actual = team_counts[0] as f64 / NUM_RUNS as f64;
+            assert!((actual - 1.0 / 16.0).abs() < 0.01);
+        }
+
+        let tids = [5, 3, 9];
+        assert_eq!(run_lottery("noLottery", &tids, &mut rng_from_seed(1)), vec![5, 3, 9]);
+        assert_eq!(get_lottery_chances("noLottery", 3), vec![0.0; 3]);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,36 @@
+use crate::common::types::DraftPick;
+use crate::worker::db::Tx;
+
+// Seasons of picks teams own at a time, counting the next draft
+pub const NUM_SEASONS_WITH_PICKS: i32 = 4;
+
+// Creates every team's missing picks for the next NUM_SEASONS_WITH_PICKS drafts. Picks that were traded keep their
+// owner, and a finished draft doesn't get its picks back.
+pub fn gen_picks(tx: &Tx) -> Result<(), String> {
+    let g = tx.get_g()?;
+    let teams = tx.get_teams()?;
+    let last_draft_season = tx.get_last_draft_season()?;
+    let first_season = match last_draft_season {
+        Some(last_draft_season) => g.season().max(last_draft_season + 1),
+        None => g.season(),
+    };
+    for season in first_season..(g.season() + NUM_SEASONS_WITH_PICKS) {
+        let draft_picks = tx.get_draft_picks(season)?;
+        for t in teams.iter() {
+            for round in 1..=g.num_draft_rounds() {
+                if draft_picks.iter().any(|dp| dp.original_tid == t.tid && dp.round == round) {
+                    continue;
+                }
+                tx.put_draft_pick(&DraftPick {
+                    dpid: tx.get_next_dpid()?,
+                    tid: t.tid,
+                    original_tid: t.tid,
+                    round,
+                    pick: 0,
+                    season
+                })?;
+            }
+        }
+    }
+    Ok(())
+}
\ No newline at end of file
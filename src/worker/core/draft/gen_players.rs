@@ -0,0 +1,35 @@
+use crate::common::constants::PLAYER_UNDRAFTED;
+use crate::common::types::Player;
+use crate::worker::core::player::generate::generate;
+use crate::worker::core::player::gen_ratings::TalentDistribution;
+use crate::worker::util::g::G;
+use crate::worker::util::random::draft_class_seed;
+use crate::worker::util::random::rng_from_seed;
+use rand::Rng;
+
+// Enough prospects that the last picks still have a choice
+pub fn get_num_draft_prospects(num_teams: usize, num_draft_rounds: i32) -> usize {
+    num_draft_rounds.max(1) as usize * num_teams * 7 / 6
+}
+
+// Prospects for the draft after draft_year's playoffs, as of g.season(), with pids from first_pid. Most are 19 or 20 in
+// the draft season, some stay in school longer.
+pub fn gen_players(draft_year: i32, g: &G, num_players: usize, first_pid: i32, talent: &TalentDistribution, seed: u64) -> Result<Vec<Player>, String> {
+    let mut rng = rng_from_seed(draft_class_seed(seed, draft_year, first_pid));
+    let mut players = Vec::new();
+    for i in 0..num_players {
+        let r: f64 = rng.gen();
+        let draft_age = if r < 0.4 {
+            19
+        } else if r < 0.7 {
+            20
+        } else if r < 0.87 {
+            21
+        } else {
+            22
+        };
+        let age = draft_age - (draft_year - g.season());
+        players.push(generate(first_pid + i as i32, PLAYER_UNDRAFTED, age, draft_year, g, talent, &mut rng)?);
+    }
+    Ok(players)
+}
\ No newline at end of file
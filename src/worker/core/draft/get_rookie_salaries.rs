@@ -0,0 +1,37 @@
+use crate::common::types::DraftPick;
+use crate::common::types::PlayerContract;
+use crate::worker::util::g::G;
+
+// Salaries of the picks in the first draft_pick_auto_contract_rounds rounds, in order. The first pick gets
+// draft_pick_auto_contract_percent of max_contract, falling off geometrically to min_contract at the last of those picks.
+pub fn get_rookie_salaries(g: &G, num_teams: usize) -> Vec<f64> {
+    let num_picks = g.draft_pick_auto_contract_rounds().max(0) as usize * num_teams;
+    let top = (g.max_contract() * g.draft_pick_auto_contract_percent() / 100.0).max(g.min_contract());
+    let mut salaries = Vec::new();
+    for i in 0..num_picks {
+        let fraction = if num_picks > 1 { i as f64 / (num_picks - 1) as f64 } else { 0.0 };
+        let salary = top * (g.min_contract() / top).powf(fraction);
+        salaries.push(((salary / 10.0).round() * 10.0).max(g.min_contract()));
+    }
+    salaries
+}
+
+pub fn get_rookie_contract_length(g: &G, round: i32) -> i32 {
+    let lengths = g.rookie_contract_lengths();
+    let index = ((round - 1).max(0) as usize).min(lengths.len() - 1);
+    lengths[index]
+}
+
+// Contract for the player taken with dp, starting the season after the draft. First round picks have a team option
+// on their last season.
+pub fn get_rookie_contract(g: &G, dp: &DraftPick, num_teams: usize) -> PlayerContract {
+    let index = (dp.round - 1) as usize * num_teams + (dp.pick - 1).max(0) as usize;
+    let amount = get_rookie_salaries(g, num_teams).get(index).copied().unwrap_or(g.min_contract());
+    let years = get_rookie_contract_length(g, dp.round);
+    PlayerContract {
+        amount,
+        exp: dp.season + years,
+        rookie: true,
+        option: if dp.round == 1 && years > 1 { "team".to_string() } else { String::new() }
+    }
+}
\ No newline at end of file
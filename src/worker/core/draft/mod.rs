@@ -0,0 +1,7 @@
+pub mod gen_players;
+pub mod gen_picks;
+pub mod gen_order;
+pub mod select_player;
+pub mod after_picks;
+pub mod run_picks;
+pub mod get_rookie_salaries;
\ No newline at end of file
@@ -0,0 +1,71 @@
+use crate::common::constants::PLAYER_UNDRAFTED;
+use crate::common::types::DraftPick;
+use crate::common::types::Player;
+use crate::worker::core::draft::select_player::select_player;
+use crate::worker::db::Tx;
+
+// Players a 13 man roster wants at each position, counting a "GF" as both a guard and a forward
+const POSITION_TARGETS: [(char, usize); 3] = [('G', 5), ('F', 5), ('C', 3)];
+
+// Value added to prospects at a position the team is short at, so needs only break near ties in talent
+const NEED_BONUS: f64 = 3.0;
+
+// Positions where the roster has fewer players than POSITION_TARGETS
+pub fn get_team_needs(roster: &[Player]) -> Vec<char> {
+    let mut needs = Vec::new();
+    for (position, target) in POSITION_TARGETS.iter() {
+        let count = roster.iter().filter(|p| p.ratings.last().unwrap().pos.contains(*position)).count();
+        if count < *target {
+            needs.push(*position);
+        }
+    }
+    needs
+}
+
+// The prospect an AI team takes: best value, with a bonus at the positions it needs
+pub fn get_best_available<'a>(prospects: &'a [Player], roster: &[Player]) -> Option<&'a Player> {
+    let needs = get_team_needs(roster);
+    let score = |p: &Player| {
+        let pos = &p.ratings.last().unwrap().pos;
+        if needs.iter().any(|position| pos.contains(*position)) {
+            return p.value + NEED_BONUS;
+        }
+        p.value
+    };
+    prospects.iter().max_by(|a, b| score(a).total_cmp(&score(b)).then(b.pid.cmp(&a.pid)))
+}
+
+pub fn get_prospects(tx: &Tx, season: i32) -> Result<Vec<Player>, String> {
+    let mut prospects: Vec<Player> = tx.get_players_by_tid(PLAYER_UNDRAFTED)?.into_iter().filter(|p| p.draft.year == season).collect();
+    prospects.sort_by(|a, b| b.value.total_cmp(&a.value));
+    Ok(prospects)
+}
+
+// AI teams make picks in order until the draft is over, or until until_tid is on the clock. Returns the picks made
+// and who was taken with each.
+pub fn run_picks(tx: &Tx, season: i32, until_tid: Option<i32>) -> Result<Vec<(DraftPick, Player)>, String> {
+    let mut picks = Vec::new();
+    loop {
+        let draft_picks = tx.get_draft_picks(season)?;
+        if draft_picks.is_empty() {
+            break;
+        }
+        let dp = &draft_picks[0];
+        if dp.pick == 0 {
+            return Err(format!("The {} draft order isn't set yet", season));
+        }
+        if until_tid == Some(dp.tid) {
+            break;
+        }
+
+        let prospects = get_prospects(tx, season)?;
+        let best = get_best_available(&prospects, &tx.get_players_by_tid(dp.tid)?);
+        if best.is_none() {
+            return Err(format!("No prospects left for pick {} of round {}", dp.pick, dp.round));
+        }
+        let pid = best.unwrap().pid;
+        select_player(tx, dp, pid)?;
+        picks.push((dp.clone(), tx.get_player(pid)?.unwrap()));
+    }
+    Ok(picks)
+}
\ No newline at end of file
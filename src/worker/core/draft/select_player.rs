@@ -0,0 +1,42 @@
+use crate::common::constants::PHASE_DRAFT;
+use crate::common::constants::PHASE_RESIGN_PLAYERS;
+use crate::common::constants::PLAYER_UNDRAFTED;
+use crate::common::types::DraftPick;
+use crate::common::types::PlayerDraft;
+use crate::worker::core::draft::after_picks::after_picks;
+use crate::worker::core::draft::get_rookie_salaries::get_rookie_contract;
+use crate::worker::core::phase::get_phase::check_phase;
+use crate::worker::core::phase::new_phase::new_phase;
+use crate::worker::db::Tx;
+
+// The team that owns dp drafts pid, who signs a rookie contract. The pick is used up, and when it's the last one the
+// draft is over and teams can re-sign their players.
+pub fn select_player(tx: &Tx, dp: &DraftPick, pid: i32) -> Result<(), String> {
+    check_phase(tx, &[PHASE_DRAFT], "draft players")?;
+    let p = tx.get_player(pid)?;
+    if p.is_none() {
+        return Err(format!("pid: no player {}", pid));
+    }
+    let mut p = p.unwrap();
+    if p.tid != PLAYER_UNDRAFTED || p.draft.year != dp.season {
+        return Err(format!("pid: {} is not in the {} draft", p.name(), dp.season));
+    }
+
+    p.tid = dp.tid;
+    p.draft = PlayerDraft {
+        round: dp.round,
+        pick: dp.pick,
+        tid: dp.tid,
+        original_tid: dp.original_tid,
+        year: dp.season
+    };
+    p.contract = get_rookie_contract(&tx.get_g()?, dp, tx.get_teams()?.len());
+    tx.put_player(&p)?;
+    tx.delete_draft_pick(dp.dpid)?;
+
+    if tx.get_draft_picks(dp.season)?.is_empty() {
+        after_picks(tx, dp.season)?;
+        new_phase(tx, PHASE_RESIGN_PLAYERS)?;
+    }
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,40 @@
+use crate::common::constants::PHASE_DRAFT_LOTTERY;
+use crate::common::constants::PHASE_EXPANSION_DRAFT;
+use crate::common::types::Team;
+use crate::worker::core::phase::get_phase::check_phase;
+use crate::worker::db::Tx;
+
+// Adds an expansion team to the division with the fewest teams. It has no players until the expansion draft and no
+// team season until the next one starts.
+pub fn add_team(tx: &Tx, abbrev: &str, region: &str, name: &str, pop: f64) -> Result<Team, String> {
+    check_phase(tx, &[PHASE_DRAFT_LOTTERY, PHASE_EXPANSION_DRAFT], "add expansion teams")?;
+    if abbrev.is_empty() || region.is_empty() || name.is_empty() {
+        return Err("An expansion team needs an abbrev, a region and a name".to_string());
+    }
+    if pop <= 0.0 {
+        return Err(format!("pop: must be positive, got {}", pop));
+    }
+    let teams = tx.get_teams()?;
+    if teams.iter().any(|t| t.abbrev.eq_ignore_ascii_case(abbrev)) {
+        return Err(format!("abbrev: {} is already taken", abbrev));
+    }
+
+    let g = tx.get_g()?;
+    let div = g.divs().iter().min_by_key(|div| (teams.iter().filter(|t| t.did == div.did).count(), div.did));
+    if div.is_none() {
+        return Err("divs: there are no divisions to put an expansion team in".to_string());
+    }
+    let div = div.unwrap();
+    let t = Team {
+        tid: teams.iter().map(|t| t.tid + 1).max().unwrap_or(0),
+        cid: div.cid,
+        did: div.did,
+        region: region.to_string(),
+        name: name.to_string(),
+        abbrev: abbrev.to_string(),
+        pop,
+        coaching_rank: None
+    };
+    tx.put_team(&t)?;
+    Ok(t)
+}
\ No newline at end of file
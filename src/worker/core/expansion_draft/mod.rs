@@ -0,0 +1,2 @@
+pub mod add_team;
+pub mod run_picks;
\ No newline at end of file
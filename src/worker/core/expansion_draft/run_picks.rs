@@ -0,0 +1,97 @@
+use crate::common::types::Event;
+use crate::common::types::Player;
+use crate::common::types::Team;
+use crate::worker::core::draft::gen_picks::gen_picks;
+use crate::worker::db::Tx;
+
+// Players each existing team keeps out of the expansion draft, best first by value
+pub const NUM_PROTECTED_PLAYERS: usize = 8;
+
+// Teams added for the expansion draft, which have no team season for g.season()
+pub fn get_expansion_teams(tx: &Tx) -> Result<Vec<Team>, String> {
+    let g = tx.get_g()?;
+    let team_seasons = tx.get_team_seasons(g.season())?;
+    Ok(tx.get_teams()?.into_iter().filter(|t| !team_seasons.iter().any(|ts| ts.tid == t.tid)).collect())
+}
+
+// Unprotected players on existing teams, best first
+pub fn get_available_players(tx: &Tx, expansion_tids: &[i32]) -> Result<Vec<Player>, String> {
+    let mut available = Vec::new();
+    for t in tx.get_teams()? {
+        if expansion_tids.contains(&t.tid) {
+            continue;
+        }
+        let mut roster = tx.get_players_by_tid(t.tid)?;
+        roster.sort_by(|a, b| b.value.total_cmp(&a.value).then(a.pid.cmp(&b.pid)));
+        available.extend(roster.into_iter().skip(NUM_PROTECTED_PLAYERS));
+    }
+    available.sort_by(|a, b| b.value.total_cmp(&a.value).then(a.pid.cmp(&b.pid)));
+    Ok(available)
+}
+
+// Expansion teams take turns picking the best available player, keeping their contracts, until each has
+// min_roster_size players. An existing team loses at most one player per expansion team. Afterwards the expansion teams
+// get draft picks from next season's draft on. Returns the picks.
+pub fn run_picks(tx: &Tx) -> Result<Vec<Event>, String> {
+    let g = tx.get_g()?;
+    let teams = tx.get_teams()?;
+    let expansion_tids: Vec<i32> = get_expansion_teams(tx)?.iter().map(|t| t.tid).collect();
+    let max_lost_per_team = expansion_tids.len();
+    let mut available = get_available_players(tx, &expansion_tids)?;
+    let mut num_lost: Vec<(i32, usize)> = Vec::new();
+    let mut roster_sizes: Vec<usize> = Vec::new();
+    for tid in expansion_tids.iter() {
+        roster_sizes.push(tx.get_players_by_tid(*tid)?.len());
+    }
+
+    let abbrev = |tid: i32| teams.iter().find(|t| t.tid == tid).map(|t| t.abbrev.clone()).unwrap_or(tid.to_string());
+    let mut events = Vec::new();
+    loop {
+        let mut picked = false;
+        for (i, tid) in expansion_tids.iter().enumerate() {
+            if roster_sizes[i] >= g.min_roster_size() as usize {
+                continue;
+            }
+            let index = available.iter().position(|p| {
+                num_lost.iter().find(|(other_tid, _)| *other_tid == p.tid).map(|(_, n)| *n).unwrap_or(0) < max_lost_per_team
+            });
+            if index.is_none() {
+                continue;
+            }
+            let mut p = available.remove(index.unwrap());
+            let from_tid = p.tid;
+            match num_lost.iter_mut().find(|(other_tid, _)| *other_tid == from_tid) {
+                Some((_, n)) => *n += 1,
+                None => num_lost.push((from_tid, 1)),
+            }
+            p.tid = *tid;
+            tx.put_player(&p)?;
+            roster_sizes[i] += 1;
+            picked = true;
+
+            let event = Event {
+                eid: tx.get_next_eid()?,
+                event_type: "expansionDraft".to_string(),
+                season: g.season(),
+                text: format!("{} took {} from {} in the expansion draft", abbrev(*tid), p.name(), abbrev(from_tid)),
+                tids: vec![*tid, from_tid],
+                pids: vec![p.pid],
+                dpids: Vec::new()
+            };
+            tx.put_event(&event)?;
+            events.push(event);
+        }
+        if !picked {
+            break;
+        }
+    }
+
+    // gen_picks also gives them picks in this season's draft, but they have no record to place them by
+    gen_picks(tx)?;
+    for dp in tx.get_draft_picks(g.season())? {
+        if expansion_tids.contains(&dp.original_tid) {
+            tx.delete_draft_pick(dp.dpid)?;
+        }
+    }
+    Ok(events)
+}
\ No newline at end of file
@@ -0,0 +1,27 @@
+use crate::worker::core::finances::get_luxury_tax::get_luxury_tax;
+use crate::worker::core::finances::get_luxury_tax::get_min_payroll_penalty;
+use crate::worker::core::team::get_payroll::get_payroll;
+use crate::worker::db::Tx;
+
+// Charges each team for its payroll at the end of season: the luxury tax, which is split evenly among the teams that
+// don't pay it, and the minimum payroll penalty. Assessing a season again replaces the earlier amounts.
+pub fn assess_payroll_min_luxury(tx: &Tx, season: i32) -> Result<(), String> {
+    let mut g = tx.get_g()?;
+    g.set_season(season);
+    let mut team_seasons = tx.get_team_seasons(season)?;
+    let mut total_luxury_tax = 0.0;
+    for ts in team_seasons.iter_mut() {
+        let payroll = get_payroll(tx, ts.tid)?;
+        ts.payroll_end_of_season = Some(payroll);
+        ts.luxury_tax = get_luxury_tax(&g, payroll);
+        ts.min_payroll_penalty = get_min_payroll_penalty(&g, payroll);
+        total_luxury_tax += ts.luxury_tax;
+    }
+
+    let num_sharing = team_seasons.iter().filter(|ts| ts.luxury_tax == 0.0).count();
+    for ts in team_seasons.iter_mut() {
+        ts.luxury_tax_share = if ts.luxury_tax == 0.0 { total_luxury_tax / num_sharing as f64 } else { 0.0 };
+        tx.put_team_season(ts)?;
+    }
+    Ok(())
+}
\ No newline at end of file
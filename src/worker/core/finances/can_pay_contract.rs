@@ -0,0 +1,56 @@
+use crate::common::helpers::format_currency;
+use crate::worker::util::g::G;
+
+// Whether a team with payroll can add a contract for amount. re_signing is for a player already on the team whose
+// contract is expiring, so their current contract should not be counted in payroll.
+pub fn can_pay_contract(g: &G, payroll: f64, amount: f64, re_signing: bool) -> Result<(), String> {
+    let payroll_after = payroll + amount;
+    if g.salary_cap_type() == "none" || payroll_after <= g.salary_cap() {
+        return Ok(());
+    }
+    if g.salary_cap_type() == "soft" && (re_signing || amount <= g.min_contract()) {
+        return Ok(());
+    }
+    Err(format!(
+        "A payroll of {} would be over the {} salary cap of {}",
+        format_currency(payroll_after),
+        g.salary_cap_type(),
+        format_currency(g.salary_cap())
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::league_settings::from_toml;
+
+    fn get_g(salary_cap_type: &str) -> G {
+        G::new(from_toml(&format!("salaryCapType = \"{}\"", salary_cap_type)).unwrap(), 2025).unwrap()
+    }
+
+    #[test]
+    fn soft_cap() {
+        // $140M cap and $1.2M minimum contract
+        let g = get_g("soft");
+        assert!(can_pay_contract(&g, 130000.0, 10000.0, false).is_ok());
+        assert_eq!(
+            can_pay_contract(&g, 130000.0, 10010.0, false).unwrap_err(),
+            "A payroll of $140.0M would be over the soft salary cap of $140.0M"
+        );
+        assert!(can_pay_contract(&g, 150000.0, 1200.0, false).is_ok());
+        assert!(can_pay_contract(&g, 150000.0, 30000.0, true).is_ok());
+    }
+
+    #[test]
+    fn hard_cap() {
+        let g = get_g("hard");
+        assert!(can_pay_contract(&g, 130000.0, 10000.0, false).is_ok());
+        assert!(can_pay_contract(&g, 139000.0, 1200.0, false).is_err());
+        assert!(can_pay_contract(&g, 130000.0, 30000.0, true).is_err());
+    }
+
+    #[test]
+    fn no_cap() {
+        assert!(can_pay_contract(&get_g("none"), 300000.0, 49000.0, false).is_ok());
+    }
+}
\ No newline at end of file
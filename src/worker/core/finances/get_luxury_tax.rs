@@ -0,0 +1,36 @@
+use crate::worker::util::g::G;
+
+pub fn get_luxury_tax(g: &G, payroll: f64) -> f64 {
+    if payroll <= g.luxury_payroll() {
+        return 0.0;
+    }
+    g.luxury_tax() * (payroll - g.luxury_payroll())
+}
+
+// A team below the minimum payroll pays the difference
+pub fn get_min_payroll_penalty(g: &G, payroll: f64) -> f64 {
+    (g.min_payroll() - payroll).max(0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn luxury_tax_only_on_the_excess() {
+        // 1.5 over a luxury payroll of $170M
+        let g = G::from_defaults(2025).unwrap();
+        assert_eq!(get_luxury_tax(&g, 150000.0), 0.0);
+        assert_eq!(get_luxury_tax(&g, 170000.0), 0.0);
+        assert_eq!(get_luxury_tax(&g, 180000.0), 15000.0);
+    }
+
+    #[test]
+    fn min_payroll_penalty_makes_up_the_difference() {
+        // Minimum payroll of $105M
+        let g = G::from_defaults(2025).unwrap();
+        assert_eq!(get_min_payroll_penalty(&g, 100000.0), 5000.0);
+        assert_eq!(get_min_payroll_penalty(&g, 105000.0), 0.0);
+        assert_eq!(get_min_payroll_penalty(&g, 120000.0), 0.0);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,3 @@
+pub mod can_pay_contract;
+pub mod get_luxury_tax;
+pub mod assess_payroll_min_luxury;
\ No newline at end of file
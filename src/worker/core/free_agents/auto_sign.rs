@@ -0,0 +1,73 @@
+use crate::common::constants::PLAYER_FREE_AGENT;
+use crate::common::types::Event;
+use crate::common::types::PlayerContract;
+use crate::worker::core::finances::can_pay_contract::can_pay_contract;
+use crate::worker::core::player::mood_info::mood_info;
+use crate::worker::core::player::mood_info::MoodContext;
+use crate::worker::core::player::sign::sign;
+use crate::worker::core::team::get_payroll::sum_contracts;
+use crate::worker::db::Tx;
+use crate::worker::util::random::free_agency_seed;
+use crate::worker::util::random::rng_from_seed;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+// Chance that a team with a full enough roster looks for an upgrade on a given day
+const UPGRADE_PROBABILITY: f64 = 0.3;
+
+// One day of AI signings. Teams go in a random order and sign at most one player each: the best free agent who is
+// willing to sign with them and fits under the cap. Teams below min_roster_size always sign someone, and the others
+// only sometimes, for a player better than the worst one they have.
+pub fn auto_sign(tx: &Tx, day: i32) -> Result<Vec<Event>, String> {
+    let g = tx.get_g()?;
+    let mut rng = rng_from_seed(free_agency_seed(tx.get_seed()?, g.season(), day));
+    let mut ctx = MoodContext::load(tx)?;
+    let mut free_agents = tx.get_players_by_tid(PLAYER_FREE_AGENT)?;
+    free_agents.sort_by(|a, b| b.value.total_cmp(&a.value).then(a.pid.cmp(&b.pid)));
+
+    let mut tids: Vec<i32> = tx.get_teams()?.iter().map(|t| t.tid).collect();
+    tids.shuffle(&mut rng);
+
+    let mut events = Vec::new();
+    for tid in tids {
+        let roster = ctx.roster(tid);
+        if roster.len() >= g.max_roster_size() as usize {
+            continue;
+        }
+        let needs_players = roster.len() < g.min_roster_size() as usize;
+        if !needs_players && rng.gen::<f64>() >= UPGRADE_PROBABILITY {
+            continue;
+        }
+        let worst_value = roster.iter().map(|p| p.value).fold(f64::INFINITY, f64::min);
+        let payroll = sum_contracts(roster);
+
+        let mut signed = None;
+        for (i, p) in free_agents.iter().enumerate() {
+            if !needs_players && p.value <= worst_value {
+                // Sorted by value, so nobody after this is an upgrade either
+                break;
+            }
+            let mood = mood_info(p, tid, &ctx, &g);
+            if mood.willing && can_pay_contract(&g, payroll, mood.contract_amount, false).is_ok() {
+                signed = Some((i, mood.contract_amount));
+                break;
+            }
+        }
+        if signed.is_none() {
+            continue;
+        }
+
+        let (i, amount) = signed.unwrap();
+        let p = free_agents.remove(i);
+        let contract = PlayerContract {
+            amount,
+            exp: p.contract.exp,
+            rookie: false,
+            option: String::new()
+        };
+        let event = sign(tx, &p, tid, contract, "freeAgent")?;
+        ctx.add_player(&p, tid);
+        events.push(event);
+    }
+    Ok(events)
+}
\ No newline at end of file
@@ -0,0 +1,16 @@
+use crate::common::constants::PLAYER_FREE_AGENT;
+use crate::worker::db::Tx;
+
+// Share of their asking amount that unsigned free agents give up each day
+const DAILY_DECREASE: f64 = 0.03;
+
+// Free agents who are still unsigned ask for a bit less, down to the minimum contract
+pub fn decrease_demands(tx: &Tx) -> Result<(), String> {
+    let g = tx.get_g()?;
+    for mut p in tx.get_players_by_tid(PLAYER_FREE_AGENT)? {
+        let amount = (p.contract.amount * (1.0 - DAILY_DECREASE) / 10.0).round() * 10.0;
+        p.contract.amount = f64::max(amount, g.min_contract());
+        tx.put_player(&p)?;
+    }
+    Ok(())
+}
\ No newline at end of file
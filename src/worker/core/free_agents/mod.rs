@@ -0,0 +1,5 @@
+pub mod start_free_agency;
+pub mod auto_sign;
+pub mod decrease_demands;
+pub mod play;
+pub mod negotiate;
\ No newline at end of file
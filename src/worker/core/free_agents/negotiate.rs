@@ -0,0 +1,118 @@
+use crate::common::constants::PHASE_AFTER_TRADE_DEADLINE;
+use crate::common::constants::PHASE_FREE_AGENCY;
+use crate::common::constants::PHASE_PRESEASON;
+use crate::common::constants::PHASE_REGULAR_SEASON;
+use crate::common::constants::PHASE_RESIGN_PLAYERS;
+use crate::common::constants::PLAYER_FREE_AGENT;
+use crate::common::helpers::bound;
+use crate::common::helpers::format_currency;
+use crate::common::types::Event;
+use crate::common::types::Player;
+use crate::common::types::PlayerContract;
+use crate::worker::core::finances::can_pay_contract::can_pay_contract;
+use crate::worker::core::phase::get_phase::check_phase;
+use crate::worker::core::phase::get_phase::get_phase;
+use crate::worker::core::phase::get_phase::is_offseason;
+use crate::worker::core::player::gen_contract::gen_contract;
+use crate::worker::core::player::mood_info::is_re_signing;
+use crate::worker::core::player::mood_info::mood_info;
+use crate::worker::core::player::mood_info::MoodContext;
+use crate::worker::core::player::sign::sign;
+use crate::worker::db::Tx;
+use crate::worker::util::g::G;
+
+// Players ask for this much more per season away from the length they want
+const AMOUNT_PER_YEAR_OFF: f64 = 0.05;
+
+// The contract p wants from tid: an amount from their mood and a length from what they asked for, or would ask for
+// on the open market when re-signing. It starts in first_season.
+pub fn asking_contract(p: &Player, tid: i32, ctx: &MoodContext, g: &G, first_season: i32) -> PlayerContract {
+    let exp = if is_re_signing(p, tid, g) {
+        gen_contract(p, g, first_season).exp
+    } else {
+        i32::max(p.contract.exp, first_season)
+    };
+    PlayerContract {
+        amount: mood_info(p, tid, ctx, g).contract_amount,
+        exp,
+        rookie: false,
+        option: String::new()
+    }
+}
+
+// What p asks for a contract through exp, given what they asked for at their preferred length
+pub fn amount_for_exp(asking: &PlayerContract, exp: i32, g: &G) -> f64 {
+    let amount = asking.amount * (1.0 + AMOUNT_PER_YEAR_OFF * (exp - asking.exp).abs() as f64);
+    bound((amount / 10.0).round() * 10.0, g.min_contract(), g.max_contract())
+}
+
+// The first season a contract signed now would cover: next season once this one's playoffs are over
+pub fn get_first_contract_season(tx: &Tx) -> Result<i32, String> {
+    let g = tx.get_g()?;
+    if is_offseason(get_phase(tx)?) {
+        return Ok(g.season() + 1);
+    }
+    Ok(g.season())
+}
+
+// tid offers p a contract for amount through exp, each defaulting to what p asks for. Free agents can be signed
+// in the preseason, regular season and free agency. Players with expiring contracts can be re-signed by their team
+// in the re-sign players phase.
+pub fn negotiate(tx: &Tx, pid: i32, tid: i32, amount: Option<f64>, exp: Option<i32>) -> Result<Event, String> {
+    let g = tx.get_g()?;
+    let p = tx.get_player(pid)?;
+    if p.is_none() {
+        return Err(format!("No player with pid {}", pid));
+    }
+    let p = p.unwrap();
+    if !tx.get_teams()?.iter().any(|t| t.tid == tid) {
+        return Err(format!("No team with tid {}", tid));
+    }
+
+    let re_signing = is_re_signing(&p, tid, &g);
+    if re_signing {
+        check_phase(tx, &[PHASE_RESIGN_PLAYERS], "re-sign players")?;
+    } else if p.tid != PLAYER_FREE_AGENT {
+        return Err(format!("{} isn't a free agent", p.name()));
+    } else {
+        check_phase(tx, &[PHASE_PRESEASON, PHASE_REGULAR_SEASON, PHASE_AFTER_TRADE_DEADLINE, PHASE_FREE_AGENCY], "sign free agents")?;
+    }
+
+    let ctx = MoodContext::load(tx)?;
+    let mood = mood_info(&p, tid, &ctx, &g);
+    if !mood.willing {
+        return Err(format!("{} isn't willing to sign with this team (mood {:+.1})", p.name(), mood.sum_components));
+    }
+
+    let first_season = get_first_contract_season(tx)?;
+    let asking = asking_contract(&p, tid, &ctx, &g, first_season);
+    let exp = exp.unwrap_or(asking.exp);
+    let min_exp = first_season + g.min_contract_length() - 1;
+    let max_exp = first_season + g.max_contract_length() - 1;
+    if exp < min_exp || exp > max_exp {
+        return Err(format!("exp: must be between {} and {}, got {}", min_exp, max_exp, exp));
+    }
+    let asking_amount = amount_for_exp(&asking, exp, &g);
+    let amount = amount.unwrap_or(asking_amount);
+    if amount > g.max_contract() {
+        return Err(format!("amount: can't be more than the max contract of {}", format_currency(g.max_contract())));
+    }
+    if amount < asking_amount {
+        return Err(format!("{} wants at least {} for a contract through {}", p.name(), format_currency(asking_amount), exp));
+    }
+
+    let roster = ctx.roster(tid);
+    if !re_signing && roster.len() >= g.max_roster_size() as usize {
+        return Err(format!("Rosters can't have more than {} players", g.max_roster_size()));
+    }
+    let payroll: f64 = roster.iter().filter(|other| other.contract.exp >= first_season).map(|other| other.contract.amount).sum();
+    can_pay_contract(&g, payroll, amount, re_signing)?;
+
+    let contract = PlayerContract {
+        amount: (amount / 10.0).round() * 10.0,
+        exp,
+        rookie: false,
+        option: String::new()
+    };
+    sign(tx, &p, tid, contract, if re_signing { "reSigned" } else { "freeAgent" })
+}
\ No newline at end of file
@@ -0,0 +1,30 @@
+use crate::common::constants::PHASE_FREE_AGENCY;
+use crate::common::types::Event;
+use crate::worker::core::free_agents::auto_sign::auto_sign;
+use crate::worker::core::free_agents::decrease_demands::decrease_demands;
+use crate::worker::core::free_agents::start_free_agency::FREE_AGENCY_DAYS;
+use crate::worker::core::phase::get_phase::check_phase;
+use crate::worker::db::Tx;
+
+// Plays up to num_days days of free agency, each with a round of AI signings and then lower demands from the players
+// left. Returns the signings.
+pub fn play(tx: &Tx, num_days: i32) -> Result<Vec<Event>, String> {
+    check_phase(tx, &[PHASE_FREE_AGENCY], "play free agency days")?;
+    let g = tx.get_g()?;
+    let mut days_left = tx.get_free_agency_days_left()?.unwrap_or(0);
+    if days_left <= 0 {
+        return Err(format!("Free agency after {} is over", g.season()));
+    }
+
+    let mut events = Vec::new();
+    for _ in 0..num_days {
+        if days_left <= 0 {
+            break;
+        }
+        events.extend(auto_sign(tx, FREE_AGENCY_DAYS - days_left)?);
+        decrease_demands(tx)?;
+        days_left -= 1;
+    }
+    tx.put_free_agency_days_left(Some(days_left))?;
+    Ok(events)
+}
\ No newline at end of file
@@ -0,0 +1,60 @@
+use crate::common::constants::PLAYER_FREE_AGENT;
+use crate::common::types::Event;
+use crate::common::types::PlayerContract;
+use crate::worker::core::finances::can_pay_contract::can_pay_contract;
+use crate::worker::core::player::gen_contract::gen_contract;
+use crate::worker::core::player::mood_info::is_re_signing;
+use crate::worker::core::player::mood_info::mood_info;
+use crate::worker::core::player::mood_info::MoodContext;
+use crate::worker::core::player::sign::sign;
+use crate::worker::db::Tx;
+
+pub const FREE_AGENCY_DAYS: i32 = 30;
+
+// AI teams only re-sign players who are among this many of their best
+const NUM_PLAYERS_KEPT: usize = 10;
+
+// Starts free agency after g.season()'s draft, when the phase moves on from re-signing players. Teams re-sign the expiring players they want who are willing to stay,
+// the rest become free agents, and every free agent asks for a contract that starts next season. Returns the
+// re-signings.
+pub fn start_free_agency(tx: &Tx) -> Result<Vec<Event>, String> {
+    let g = tx.get_g()?;
+    let ctx = MoodContext::load(tx)?;
+    let mut events = Vec::new();
+    for t in tx.get_teams()? {
+        let mut roster = ctx.roster(t.tid).to_vec();
+        roster.sort_by(|a, b| b.value.total_cmp(&a.value));
+        let mut payroll: f64 = roster.iter().filter(|p| !is_re_signing(p, t.tid, &g)).map(|p| p.contract.amount).sum();
+        for (i, p) in roster.iter().enumerate() {
+            if !is_re_signing(p, t.tid, &g) {
+                continue;
+            }
+            let market = gen_contract(p, &g, g.season() + 1);
+            let mood = mood_info(p, t.tid, &ctx, &g);
+            if i < NUM_PLAYERS_KEPT && mood.willing && can_pay_contract(&g, payroll, mood.contract_amount, true).is_ok() {
+                let contract = PlayerContract {
+                    amount: mood.contract_amount,
+                    exp: market.exp,
+                    rookie: false,
+                    option: String::new()
+                };
+                payroll += contract.amount;
+                events.push(sign(tx, p, t.tid, contract, "reSigned")?);
+            } else {
+                let mut p = p.clone();
+                p.tid = PLAYER_FREE_AGENT;
+                p.contract = market;
+                tx.put_player(&p)?;
+            }
+        }
+    }
+
+    for mut p in tx.get_players_by_tid(PLAYER_FREE_AGENT)? {
+        if p.contract.exp <= g.season() {
+            p.contract = gen_contract(&p, &g, g.season() + 1);
+            tx.put_player(&p)?;
+        }
+    }
+    tx.put_free_agency_days_left(Some(FREE_AGENCY_DAYS))?;
+    Ok(events)
+}
\ No newline at end of file
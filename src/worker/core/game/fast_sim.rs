@@ -0,0 +1,352 @@
+use crate::common::types::GameResult;
+use crate::common::types::GameResultTeam;
+use crate::common::types::Player;
+use crate::worker::core::game::play::GameToSim;
+use crate::worker::core::game::play::SimGameResult;
+use crate::worker::core::player::composite_rating::get_composite_ratings;
+use crate::worker::core::GameSim_basketball::synergy::get_skills_count;
+use crate::worker::core::GameSim_basketball::synergy::get_synergy;
+use crate::worker::core::GameSim_basketball::synergy::SYNERGY_FACTOR;
+use crate::worker::db::Tx;
+use crate::worker::util::g::G;
+use crate::worker::util::random::gauss;
+use rand::rngs::StdRng;
+use serde::Deserialize;
+use serde::Serialize;
+use std::collections::HashMap;
+
+// Minutes out of 240 for the best 10 healthy players, like a typical GameSim rotation
+const ROTATION_MINUTES: [f64; 10] = [34.0, 33.0, 32.0, 30.0, 28.0, 24.0, 20.0, 16.0, 13.0, 10.0];
+
+// Spread of the combined score of both teams around its expected value
+const TOTAL_PTS_SD: f64 = 16.0;
+
+pub const OVERTIME_LENGTH: f64 = 5.0;
+
+// Fewer games than this don't say much about margin_per_rating
+const MIN_CALIBRATION_GAMES: usize = 50;
+
+// Outcome model that plays a game from the two teams' ratings alone, instead of possession by possession like GameSim.
+// The expected home margin is linear in the rating difference and the actual margin is normally distributed around it.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FastModel {
+    // Expected home margin for each point of FastTeamRating::ovr difference
+    pub margin_per_rating: f64,
+    // Expected home margin between equal teams
+    pub home_court: f64,
+    pub margin_sd: f64,
+    // Average points per team in regulation, at pace 100
+    pub pts: f64,
+    // Number of games the model was calibrated on, 0 for the defaults
+    pub num_games: usize
+}
+
+impl Default for FastModel {
+    // Rough values for untuned GameSim leagues, to use until calibrate has been run
+    fn default() -> Self {
+        FastModel {
+            margin_per_rating: 60.0,
+            home_court: 2.5,
+            margin_sd: 12.5,
+            pts: 108.0,
+            num_games: 0
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct FastTeamRating {
+    // Team composite ratings plus synergy, on the 0-1 scale of player composite ratings
+    pub off: f64,
+    pub def: f64
+}
+
+impl FastTeamRating {
+    pub fn ovr(&self) -> f64 {
+        self.off + self.def
+    }
+}
+
+// Rates a team from its best healthy players, weighted by the minutes they would play. The starters' skills decide
+// synergy, which is added the same way GameSim adds it to the team composite ratings. A team with no players gets the
+// average rating of 0.5.
+pub fn get_fast_team_rating(players: &[&Player], season: i32, num_players_on_court: usize) -> Result<FastTeamRating, String> {
+    let mut rotation: Vec<&Player> = players
+        .iter()
+        .filter(|p| !p.is_injured() && !p.ratings.is_empty())
+        .copied()
+        .collect();
+    rotation.sort_by(|a, b| b.value_no_pot.total_cmp(&a.value_no_pot));
+    rotation.truncate(ROTATION_MINUTES.len());
+
+    let composite_ratings: Vec<HashMap<String, f64>> = rotation
+        .iter()
+        .map(|p| get_composite_ratings(p.ratings_for_season(season)))
+        .collect::<Result<_, _>>()?;
+    Ok(get_fast_team_rating_from_composites(&composite_ratings, num_players_on_court))
+}
+
+// Same as get_fast_team_rating, for players that already have composite ratings, from the best player down. Players past
+// the 10th are ignored.
+pub fn get_fast_team_rating_from_composites(composite_ratings: &[HashMap<String, f64>], num_players_on_court: usize) -> FastTeamRating {
+    let composite_ratings = &composite_ratings[..composite_ratings.len().min(ROTATION_MINUTES.len())];
+    if composite_ratings.is_empty() {
+        return FastTeamRating {
+            off: 0.5,
+            def: 0.5
+        };
+    }
+
+    let total_minutes: f64 = ROTATION_MINUTES[..composite_ratings.len()].iter().sum();
+    let weighted_rating = |key: &str| {
+        let mut rating = 0.0;
+        for (i, composite_rating) in composite_ratings.iter().enumerate() {
+            rating += ROTATION_MINUTES[i] * composite_rating.get(key).unwrap();
+        }
+        rating / total_minutes
+    };
+
+    let starters: Vec<&HashMap<String, f64>> = composite_ratings.iter().take(num_players_on_court).collect();
+    let synergy = get_synergy(&get_skills_count(&starters));
+
+    let shooting = (
+        weighted_rating("shootingAtRim") +
+        weighted_rating("shootingLowPost") +
+        weighted_rating("shootingMidRange") +
+        weighted_rating("shootingThreePointer")
+    ) / 4.0;
+    let dribbling = weighted_rating("dribbling") + SYNERGY_FACTOR * synergy.off;
+    let passing = weighted_rating("passing") + SYNERGY_FACTOR * synergy.off;
+    let rebounding = weighted_rating("rebounding") + SYNERGY_FACTOR * synergy.reb;
+    let defense = weighted_rating("defense") + SYNERGY_FACTOR * synergy.def;
+    let defense_perimeter = weighted_rating("defensePerimeter") + SYNERGY_FACTOR * synergy.def;
+    let blocking = weighted_rating("blocking") + SYNERGY_FACTOR * synergy.def;
+
+    FastTeamRating {
+        off: 0.45 * shooting + 0.2 * dribbling + 0.2 * passing + 0.15 * rebounding,
+        def: 0.4 * defense + 0.3 * defense_perimeter + 0.15 * blocking + 0.15 * rebounding
+    }
+}
+
+pub fn get_fast_team_ratings(tids: &[i32], players: &[Player], season: i32, num_players_on_court: usize) -> Result<HashMap<i32, FastTeamRating>, String> {
+    let mut ratings = HashMap::new();
+    for tid in tids.iter() {
+        let team_players: Vec<&Player> = players.iter().filter(|p| p.tid == *tid).collect();
+        ratings.insert(*tid, get_fast_team_rating(&team_players, season, num_players_on_court)?);
+    }
+    Ok(ratings)
+}
+
+// Standard normal CDF, from the Abramowitz and Stegun approximation of erf (error below 1.5e-7)
+pub fn normal_cdf(x: f64) -> f64 {
+    let z = x.abs() / std::f64::consts::SQRT_2;
+    let t = 1.0 / (1.0 + 0.3275911 * z);
+    let poly = t * (0.254829592 + t * (-0.284496736 + t * (1.421413741 + t * (-1.453152027 + t * 1.061405429))));
+    let erf = 1.0 - poly * (-z * z).exp();
+    if x >= 0.0 {
+        return 0.5 * (1.0 + erf);
+    }
+    0.5 * (1.0 - erf)
+}
+
+impl FastModel {
+    pub fn expected_margin(&self, home: &FastTeamRating, away: &FastTeamRating) -> f64 {
+        self.home_court + self.margin_per_rating * (home.ovr() - away.ovr())
+    }
+
+    // Probability that the home team wins, counting a tie after regulation as a coin flip
+    pub fn win_probability(&self, home: &FastTeamRating, away: &FastTeamRating) -> f64 {
+        normal_cdf(self.expected_margin(home, away) / self.margin_sd)
+    }
+
+    // Fits the model to games played by GameSim. ratings are keyed by (season, tid), and games between teams without
+    // ratings are skipped. The margin is fit by least squares on the rating difference, and pts comes from the games
+    // that ended in regulation.
+    pub fn calibrate(games: &[GameResult], ratings: &HashMap<(i32, i32), FastTeamRating>, g: &G) -> Result<FastModel, String> {
+        let mut samples = Vec::new();
+        let mut pts_sum = 0.0;
+        let mut num_pts = 0;
+        for game in games.iter() {
+            let home = ratings.get(&(game.season, game.teams[0].tid));
+            let away = ratings.get(&(game.season, game.teams[1].tid));
+            if home.is_none() || away.is_none() {
+                continue;
+            }
+            samples.push((home.unwrap().ovr() - away.unwrap().ovr(), (game.teams[0].pts - game.teams[1].pts) as f64));
+            if game.overtimes == 0 {
+                pts_sum += (game.teams[0].pts + game.teams[1].pts) as f64;
+                num_pts += 2;
+            }
+        }
+        if samples.len() < MIN_CALIBRATION_GAMES || num_pts == 0 {
+            return Err(format!(
+                "Need at least {} GameSim games between rated teams to calibrate, found {}",
+                MIN_CALIBRATION_GAMES,
+                samples.len()
+            ));
+        }
+
+        let n = samples.len() as f64;
+        let mean_x = samples.iter().map(|(x, _)| x).sum::<f64>() / n;
+        let mean_y = samples.iter().map(|(_, y)| y).sum::<f64>() / n;
+        let sxx: f64 = samples.iter().map(|(x, _)| (x - mean_x) * (x - mean_x)).sum();
+        let sxy: f64 = samples.iter().map(|(x, y)| (x - mean_x) * (y - mean_y)).sum();
+        // Without any spread in ratings, only the home court advantage can be fit
+        let margin_per_rating = if sxx > 0.0 { sxy / sxx } else { FastModel::default().margin_per_rating };
+        let home_court = mean_y - margin_per_rating * mean_x;
+        let sse: f64 = samples.iter().map(|(x, y)| {
+            let residual = y - home_court - margin_per_rating * x;
+            residual * residual
+        }).sum();
+
+        Ok(FastModel {
+            margin_per_rating,
+            home_court,
+            margin_sd: (sse / (n - 2.0)).sqrt(),
+            pts: pts_sum / num_pts as f64 * 100.0 / g.pace(),
+            num_games: samples.len()
+        })
+    }
+}
+
+// Plays games with a FastModel. It can be used anywhere a SimGame is expected, and it never returns a box score or
+// play-by-play.
+pub struct FastSim {
+    pub model: FastModel,
+    pub ratings: HashMap<i32, FastTeamRating>
+}
+
+impl FastSim {
+    pub fn new(model: FastModel, ratings: HashMap<i32, FastTeamRating>) -> Self {
+        FastSim {
+            model,
+            ratings
+        }
+    }
+
+    fn get_rating(&self, tid: i32) -> Result<&FastTeamRating, String> {
+        let rating = self.ratings.get(&tid);
+        if rating.is_none() {
+            return Err(format!("No fast sim rating for team {}", tid));
+        }
+        Ok(rating.unwrap())
+    }
+
+    pub fn sim_game(&self, g: &G, game: &GameToSim, rng: &mut StdRng) -> Result<SimGameResult, String> {
+        let home = self.get_rating(game.home_tid)?;
+        let away = self.get_rating(game.away_tid)?;
+        let expected_margin = self.model.expected_margin(home, away);
+        let pts = self.model.pts * g.pace() / 100.0;
+
+        let margin = gauss(rng, expected_margin, self.model.margin_sd);
+        let total = gauss(rng, 2.0 * pts, TOTAL_PTS_SD);
+        let mut home_pts = ((total + margin) / 2.0).round().max(0.0) as i32;
+        let mut away_pts = ((total - margin) / 2.0).round().max(0.0) as i32;
+
+        // Overtimes are shorter games, with everything scaled by their length
+        let fraction = OVERTIME_LENGTH / (4.0 * g.quarter_length());
+        let mut overtimes = 0;
+        while home_pts == away_pts && !(!game.playoffs && g.ties() && overtimes > 0) {
+            overtimes += 1;
+            let margin = gauss(rng, fraction * expected_margin, fraction.sqrt() * self.model.margin_sd);
+            let total = gauss(rng, 2.0 * fraction * pts, fraction.sqrt() * TOTAL_PTS_SD);
+            home_pts += ((total + margin) / 2.0).round().max(0.0) as i32;
+            away_pts += ((total - margin) / 2.0).round().max(0.0) as i32;
+        }
+
+        Ok(SimGameResult {
+            result: GameResult {
+                gid: game.gid,
+                season: game.season,
+                playoffs: game.playoffs,
+                overtimes,
+                teams: [
+                    GameResultTeam {
+                        tid: game.home_tid,
+                        pts: home_pts
+                    },
+                    GameResultTeam {
+                        tid: game.away_tid,
+                        pts: away_pts
+                    }
+                ]
+            },
+            box_score: None,
+            play_by_play: None
+        })
+    }
+}
+
+// FastSim with the league's fast model and every team's current roster
+pub fn load_fast_sim(tx: &Tx) -> Result<FastSim, String> {
+    let g = tx.get_g()?;
+    let tids: Vec<i32> = tx.get_teams()?.iter().map(|t| t.tid).collect();
+    let ratings = get_fast_team_ratings(&tids, &tx.get_players()?, g.season(), g.num_players_on_court() as usize)?;
+    Ok(FastSim::new(tx.get_fast_model()?, ratings))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::worker::util::random::rng_from_seed;
+
+    // Plays num_games between 10 teams of different strengths with model, in season 2025
+    fn sim_games(model: &FastModel, num_games: i32) -> (Vec<GameResult>, HashMap<(i32, i32), FastTeamRating>) {
+        let g = G::from_defaults(2025).unwrap();
+        let ratings: HashMap<i32, FastTeamRating> = (0..10).map(|tid| (tid, FastTeamRating {
+            off: 0.4 + 0.01 * tid as f64,
+            def: 0.45 + 0.005 * tid as f64
+        })).collect();
+        let fast_sim = FastSim::new(model.clone(), ratings.clone());
+        let mut rng = rng_from_seed(1);
+        let games = (0..num_games).map(|gid| {
+            let game = GameToSim {
+                gid,
+                season: 2025,
+                day: gid,
+                home_tid: gid % 10,
+                away_tid: (gid / 10 + gid + 1) % 10,
+                playoffs: false
+            };
+            fast_sim.sim_game(&g, &game, &mut rng).unwrap().result
+        }).filter(|game| game.teams[0].tid != game.teams[1].tid).collect();
+        (games, ratings.into_iter().map(|(tid, rating)| ((2025, tid), rating)).collect())
+    }
+
+    #[test]
+    fn calibrate_recovers_the_model() {
+        let model = FastModel {
+            margin_per_rating: 80.0,
+            home_court: 3.0,
+            margin_sd: 11.0,
+            pts: 110.0,
+            num_games: 0
+        };
+        let (games, ratings) = sim_games(&model, 5000);
+        let calibrated = FastModel::calibrate(&games, &ratings, &G::from_defaults(2025).unwrap()).unwrap();
+        assert_eq!(calibrated.num_games, games.len());
+        assert!((calibrated.margin_per_rating - 80.0).abs() < 8.0, "{}", calibrated.margin_per_rating);
+        assert!((calibrated.home_court - 3.0).abs() < 0.5, "{}", calibrated.home_court);
+        assert!((calibrated.margin_sd - 11.0).abs() < 0.5, "{}", calibrated.margin_sd);
+        assert!((calibrated.pts - 110.0).abs() < 1.0, "{}", calibrated.pts);
+    }
+
+    #[test]
+    fn calibrate_needs_enough_games() {
+        let (games, ratings) = sim_games(&FastModel::default(), 40);
+        let result = FastModel::calibrate(&games, &ratings, &G::from_defaults(2025).unwrap());
+        assert_eq!(result.err().unwrap(), format!("Need at least 50 GameSim games between rated teams to calibrate, found {}", games.len()));
+    }
+
+    #[test]
+    fn win_probability_follows_the_margin() {
+        let model = FastModel::default();
+        let even = FastTeamRating { off: 0.5, def: 0.5 };
+        let better = FastTeamRating { off: 0.55, def: 0.5 };
+        let home_court = model.win_probability(&even, &even);
+        assert!(home_court > 0.5 && home_court < 0.7);
+        assert!(model.win_probability(&better, &even) > home_court);
+        assert!(model.win_probability(&even, &better) < 0.5);
+    }
+}
\ No newline at end of file
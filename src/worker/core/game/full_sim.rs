@@ -0,0 +1,156 @@
+use crate::common::types::Player;
+use crate::worker::core::game::fast_sim::FastModel;
+use crate::worker::core::game::play::GameToSim;
+use crate::worker::core::game::play::SimGameResult;
+use crate::worker::core::player::to_player_game_sim::to_player_game_sim;
+use crate::worker::core::GameSim_basketball::index::GameSim;
+use crate::worker::core::GameSim_basketball::index::TeamGameSim;
+use crate::worker::db::Tx;
+use crate::worker::util::g::G;
+use rand::rngs::StdRng;
+use rand::Rng;
+use std::collections::HashMap;
+
+// Plays games possession by possession with GameSim. Like FastSim, it can be used anywhere a SimGame is expected, and it
+// returns a box score and play-by-play for every game.
+pub struct FullSim {
+    // For the win probability in the play-by-play
+    pub model: FastModel,
+    // Every team's players in roster order
+    pub players: HashMap<i32, Vec<Player>>
+}
+
+// Healthy players from the best to the worst, then the injured ones
+fn get_roster_order(mut players: Vec<Player>) -> Vec<Player> {
+    players.sort_by(|a, b| a.is_injured().cmp(&b.is_injured()).then(b.value_no_pot.total_cmp(&a.value_no_pot)));
+    players
+}
+
+impl FullSim {
+    pub fn new(model: FastModel, players: &[Player]) -> Self {
+        let mut players_by_tid: HashMap<i32, Vec<Player>> = HashMap::new();
+        for p in players.iter().filter(|p| p.tid >= 0 && !p.ratings.is_empty()) {
+            players_by_tid.entry(p.tid).or_default().push(p.clone());
+        }
+        FullSim {
+            model,
+            players: players_by_tid.into_iter().map(|(tid, players)| (tid, get_roster_order(players))).collect()
+        }
+    }
+
+    fn get_team(&self, g: &G, tid: i32, season: i32) -> Result<TeamGameSim, String> {
+        let players = self.players.get(&tid).map(|players| players.as_slice()).unwrap_or(&[]);
+        if players.len() < g.num_players_on_court() as usize {
+            return Err(format!("Team {} has {} players, needs at least {} to play a game", tid, players.len(), g.num_players_on_court()));
+        }
+        let players = players.iter().map(|p| to_player_game_sim(p, season)).collect::<Result<_, _>>()?;
+        Ok(TeamGameSim::new(tid, players, g))
+    }
+
+    // A game that hasn't started yet, for callers that step through it
+    pub fn new_game_sim(&self, g: &G, game: &GameToSim, rng: &mut StdRng) -> Result<GameSim, String> {
+        let teams = [
+            self.get_team(g, game.home_tid, game.season)?,
+            self.get_team(g, game.away_tid, game.season)?
+        ];
+        Ok(GameSim::new(g, game, teams, true, rng.gen(), &self.model))
+    }
+
+    pub fn sim_game(&self, g: &G, game: &GameToSim, rng: &mut StdRng) -> Result<SimGameResult, String> {
+        let mut game_sim = self.new_game_sim(g, game, rng)?;
+        game_sim.run(g);
+        Ok(game_sim.get_result())
+    }
+}
+
+// FullSim with the league's fast model and every team's current roster
+pub fn load_full_sim(tx: &Tx) -> Result<FullSim, String> {
+    Ok(FullSim::new(tx.get_fast_model()?, &tx.get_players()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::types::Team;
+    use crate::worker::core::GameSim_basketball::index::StepResult;
+    use crate::worker::core::league::gen_random_players::gen_random_players;
+    use crate::worker::core::player::gen_ratings::TalentDistribution;
+    use crate::worker::util::random::rng_from_seed;
+
+    fn new_game_sim(g: &G) -> GameSim {
+        let teams: Vec<Team> = (0..2).map(|tid| Team {
+            tid,
+            cid: 0,
+            did: 0,
+            region: format!("Region {}", tid),
+            name: format!("Team {}", tid),
+            abbrev: format!("T{}", tid),
+            pop: 1.0,
+            coaching_rank: None
+        }).collect();
+        let players = gen_random_players(&teams, g, &TalentDistribution::default(), 1).unwrap();
+        let game = GameToSim {
+            gid: 0,
+            season: g.season(),
+            day: 0,
+            home_tid: 0,
+            away_tid: 1,
+            playoffs: false
+        };
+        FullSim::new(FastModel::default(), &players).new_game_sim(g, &game, &mut rng_from_seed(1)).unwrap()
+    }
+
+    fn get_score(game_sim: &mut GameSim) -> [i32; 2] {
+        let result = game_sim.get_result().result;
+        [result.teams[0].pts, result.teams[1].pts]
+    }
+
+    #[test]
+    fn stepping_plays_the_same_game() {
+        let g = G::from_defaults(2025).unwrap();
+        let mut ran = new_game_sim(&g);
+        ran.run(&g);
+
+        let mut stepped = new_game_sim(&g);
+        let mut num_possessions = 0;
+        while let StepResult::Possession(_) = stepped.step(&g) {
+            num_possessions += 1;
+            // Pausing in the middle changes nothing
+            if num_possessions == 50 {
+                stepped.pause();
+                assert!(matches!(stepped.step(&g), StepResult::Paused));
+                stepped.resume();
+            }
+        }
+        assert!(num_possessions > 150);
+        assert!(matches!(stepped.step(&g), StepResult::Over));
+        assert_eq!(get_score(&mut stepped), get_score(&mut ran));
+    }
+
+    #[test]
+    fn substitutions_between_possessions() {
+        let g = G::from_defaults(2025).unwrap();
+        let mut game_sim = new_game_sim(&g);
+        game_sim.set_auto_subs(0, false);
+        for _ in 0..10 {
+            game_sim.step(&g);
+        }
+
+        let statuses = game_sim.get_player_statuses(0);
+        let off = statuses.iter().find(|p| p.on_court).unwrap().pid;
+        let on = statuses.iter().find(|p| !p.on_court && !p.injured).unwrap().pid;
+        assert_eq!(game_sim.substitute(&g, 0, off, on).unwrap_err(), format!("Player {} is not on the court", on));
+        game_sim.substitute(&g, 0, on, off).unwrap();
+        let statuses = game_sim.get_player_statuses(0);
+        assert!(statuses.iter().find(|p| p.pid == on).unwrap().on_court);
+        assert!(!statuses.iter().find(|p| p.pid == off).unwrap().on_court);
+        assert_eq!(statuses.iter().filter(|p| p.on_court).count(), 5);
+
+        let timeouts = game_sim.get_timeouts_remaining(0);
+        game_sim.call_timeout(&g, 0).unwrap();
+        assert_eq!(game_sim.get_timeouts_remaining(0), timeouts - 1);
+
+        game_sim.run(&g);
+        assert_eq!(game_sim.substitute(&g, 0, off, on).unwrap_err(), "The game is over");
+    }
+}
\ No newline at end of file
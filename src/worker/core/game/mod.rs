@@ -0,0 +1,5 @@
+pub mod play;
+pub mod sim_games;
+pub mod fast_sim;
+pub mod win_probability;
+pub mod full_sim;
\ No newline at end of file
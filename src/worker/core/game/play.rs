@@ -0,0 +1,325 @@
+use crate::common::constants::PHASE_AFTER_TRADE_DEADLINE;
+use crate::common::constants::PHASE_DRAFT_LOTTERY;
+use crate::common::constants::PHASE_PLAYOFFS;
+use crate::common::constants::PHASE_REGULAR_SEASON;
+use crate::common::constants::PLAYER_FREE_AGENT;
+use crate::common::types::BoxScore;
+use crate::common::types::GameResult;
+use crate::common::types::PlayByPlayEvent;
+use crate::common::types::PlayerInjury;
+use crate::common::types::ScheduleGame;
+use crate::common::types::TeamSeasonAttrs;
+use crate::worker::core::game::sim_games::sim_games;
+use crate::worker::core::draft::gen_picks::gen_picks;
+use crate::worker::core::player::add_game_stats::add_game_stats;
+use crate::worker::core::player::value::update_values;
+use crate::worker::core::game::sim_games::SimGame;
+use crate::worker::core::phase::get_phase::check_phase;
+use crate::worker::core::phase::new_phase::new_phase;
+use crate::worker::core::season::get_standings::update_season_attrs;
+use crate::worker::core::season::new_schedule::new_schedule;
+use crate::worker::core::season::new_schedule_playoffs_day::get_champion;
+use crate::worker::core::season::new_schedule_playoffs_day::get_next_games;
+use crate::worker::core::season::new_schedule_playoffs_day::new_schedule_playoffs_day;
+use crate::worker::core::season::new_schedule_playoffs_day::record_playoff_game;
+use crate::worker::db::LeagueDb;
+use crate::worker::db::Tx;
+use crate::worker::util::g::G;
+use crate::worker::util::random::rng_from_seed;
+use crate::worker::util::random::schedule_seed;
+
+// Everything sim_game needs to know about the game it is asked to play
+pub struct GameToSim {
+    pub gid: i32,
+    pub season: i32,
+    pub day: i32,
+    pub home_tid: i32,
+    pub away_tid: i32,
+    pub playoffs: bool
+}
+
+pub struct SimGameResult {
+    pub result: GameResult,
+    pub box_score: Option<BoxScore>,
+    pub play_by_play: Option<Vec<PlayByPlayEvent>>
+}
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum SeasonState {
+    RegularSeason,
+    Playoffs,
+    // Playoffs are over, with the champion's tid
+    Done(i32)
+}
+
+// Creates the team seasons, the regular season schedule for g.season() and the teams' draft picks, unless they already
+// exist, and drops the play-by-play from past seasons
+pub fn start_regular_season(tx: &Tx) -> Result<(), String> {
+    let g = tx.get_g()?;
+    let teams = tx.get_teams()?;
+    if teams.len() < 2 {
+        return Err("A league needs at least 2 teams".to_string());
+    }
+
+    let team_seasons = tx.get_team_seasons(g.season())?;
+    for t in teams.iter() {
+        if !team_seasons.iter().any(|ts| ts.tid == t.tid) {
+            tx.put_team_season(&TeamSeasonAttrs::new(t.tid, g.season(), t.cid, t.did))?;
+        }
+    }
+
+    if tx.get_schedule()?.is_empty() && tx.get_games(g.season())?.is_empty() {
+        let tids: Vec<i32> = teams.iter().map(|t| t.tid).collect();
+        let mut rng = rng_from_seed(schedule_seed(tx.get_seed()?, g.season()));
+        for game in new_schedule(&tids, g.num_games(), tx.get_next_gid()?, &mut rng) {
+            tx.put_schedule_game(&game)?;
+        }
+        tx.put_day(0)?;
+    }
+    tx.delete_play_by_play_before(g.season())?;
+    gen_picks(tx)
+}
+
+// State of g.season()'s games, which is all get_phase has to go on for leagues from before phases were stored
+pub fn get_season_state(tx: &Tx) -> Result<SeasonState, String> {
+    if !tx.get_schedule()?.is_empty() {
+        return Ok(SeasonState::RegularSeason);
+    }
+    let g = tx.get_g()?;
+    let playoff_series = tx.get_playoff_series(g.season())?;
+    if playoff_series.is_none() {
+        // The schedule is done, but the playoffs haven't been created yet
+        return Ok(SeasonState::RegularSeason);
+    }
+    let champion = get_champion(playoff_series.as_ref().unwrap(), g.num_games_playoff_series());
+    if let Some(champion) = champion {
+        return Ok(SeasonState::Done(champion));
+    }
+    Ok(SeasonState::Playoffs)
+}
+
+// Saves a game with its box score and the stats and injuries in it. Returns the players who got hurt in the game.
+// --fast games have no box score, so they record no stats or injuries.
+fn save_game(tx: &Tx, sim_game_result: &SimGameResult) -> Result<Vec<i32>, String> {
+    let mut injured = Vec::new();
+    tx.put_game(&sim_game_result.result)?;
+    if let Some(box_score) = &sim_game_result.box_score {
+        tx.put_box_score(box_score)?;
+        for t in box_score.teams.iter() {
+            for line in t.players.iter() {
+                let p = tx.get_player(line.pid)?;
+                if let Some(mut p) = p {
+                    add_game_stats(&mut p, t.tid, box_score.season, box_score.playoffs, line);
+                    update_values(&mut p, box_score.season);
+                    if line.injury.is_some() {
+                        p.injury = line.injury.clone().unwrap();
+                        injured.push(p.pid);
+                    }
+                    tx.put_player(&p)?;
+                }
+            }
+        }
+    }
+    if let Some(play_by_play) = &sim_game_result.play_by_play {
+        tx.put_play_by_play(sim_game_result.result.gid, play_by_play)?;
+    }
+    Ok(injured)
+}
+
+// Every injured player on a team or in free agency is a day closer to healthy, whether they played or not, except the
+// ones hurt today
+fn heal_injuries(tx: &Tx, injured_today: &[i32]) -> Result<(), String> {
+    for mut p in tx.get_players()? {
+        if p.tid < PLAYER_FREE_AGENT || p.injury.games_remaining == 0 || injured_today.contains(&p.pid) {
+            continue;
+        }
+        p.injury.games_remaining -= 1;
+        if p.injury.games_remaining == 0 {
+            p.injury = PlayerInjury::healthy();
+        }
+        tx.put_player(&p)?;
+    }
+    Ok(())
+}
+
+fn play_regular_season_day(tx: &Tx, g: &G, day: i32, games: Vec<ScheduleGame>, sim_game: &SimGame, num_threads: usize) -> Result<Vec<GameResult>, String> {
+    let games_to_sim: Vec<GameToSim> = games.iter().map(|game| GameToSim {
+        gid: game.gid,
+        season: g.season(),
+        day,
+        home_tid: game.home_tid,
+        away_tid: game.away_tid,
+        playoffs: false
+    }).collect();
+
+    let mut results = Vec::new();
+    let mut injured = Vec::new();
+    for sim_game_result in sim_games(g, &games_to_sim, tx.get_seed()?, num_threads, sim_game)? {
+        injured.extend(save_game(tx, &sim_game_result)?);
+        tx.delete_schedule_game(sim_game_result.result.gid)?;
+        results.push(sim_game_result.result);
+    }
+    heal_injuries(tx, &injured)?;
+
+    let mut team_seasons = tx.get_team_seasons(g.season())?;
+    update_season_attrs(&mut team_seasons, &tx.get_games(g.season())?, g.otl());
+    for team_season in team_seasons.iter() {
+        tx.put_team_season(team_season)?;
+    }
+    Ok(results)
+}
+
+fn play_playoffs_day(tx: &Tx, g: &G, day: i32, sim_game: &SimGame, num_threads: usize) -> Result<Vec<GameResult>, String> {
+    let playoff_series = tx.get_playoff_series(g.season())?;
+    if playoff_series.is_none() {
+        return Err(format!("The {} playoffs haven't started", g.season()));
+    }
+    let mut playoff_series = playoff_series.unwrap();
+
+    let num_games_playoff_series = g.num_games_playoff_series();
+    let mut results = Vec::new();
+    let mut injured = Vec::new();
+    if !new_schedule_playoffs_day(&mut playoff_series, num_games_playoff_series) {
+        let first_gid = tx.get_next_gid()?;
+        let games_to_sim: Vec<GameToSim> = get_next_games(&playoff_series, num_games_playoff_series)
+            .iter()
+            .enumerate()
+            .map(|(i, game)| GameToSim {
+                gid: first_gid + i as i32,
+                season: g.season(),
+                day,
+                home_tid: game.home_tid,
+                away_tid: game.away_tid,
+                playoffs: true
+            })
+            .collect();
+        for sim_game_result in sim_games(g, &games_to_sim, tx.get_seed()?, num_threads, sim_game)? {
+            record_playoff_game(&mut playoff_series, &sim_game_result.result)?;
+            injured.extend(save_game(tx, &sim_game_result)?);
+            results.push(sim_game_result.result);
+        }
+    }
+    heal_injuries(tx, &injured)?;
+    tx.put_playoff_series(&playoff_series)?;
+    if get_champion(&playoff_series, num_games_playoff_series).is_some() {
+        new_phase(tx, PHASE_DRAFT_LOTTERY)?;
+    }
+    Ok(results)
+}
+
+// Past the trade deadline once trade_deadline of the regular season's games are played, unless it's 1
+fn is_past_trade_deadline(tx: &Tx, g: &G) -> Result<bool, String> {
+    if g.trade_deadline() >= 1.0 {
+        return Ok(false);
+    }
+    let num_played = tx.get_games(g.season())?.iter().filter(|game| !game.playoffs).count();
+    let num_left = tx.get_schedule()?.len();
+    Ok(num_played as f64 >= g.trade_deadline() * (num_played + num_left) as f64)
+}
+
+// Plays the next day of games in a single transaction, so a failure or crash midway leaves the league as it was
+// before the day. The phase moves on to after the trade deadline, the playoffs and the draft lottery as the days are
+// played. Returns the games played. The day's games are played on up to num_threads threads, with results that don't
+// depend on num_threads.
+pub fn play_day(db: &mut LeagueDb, sim_game: &SimGame, num_threads: usize) -> Result<Vec<GameResult>, String> {
+    db.transaction(|tx| {
+        let phase = check_phase(tx, &[PHASE_REGULAR_SEASON, PHASE_AFTER_TRADE_DEADLINE, PHASE_PLAYOFFS], "play games")?;
+        let g = tx.get_g()?;
+        let day = tx.get_day()?;
+        let results = if phase == PHASE_PLAYOFFS {
+            play_playoffs_day(tx, &g, day, sim_game, num_threads)?
+        } else {
+            let schedule = tx.get_schedule()?;
+            let results = if schedule.is_empty() {
+                Vec::new()
+            } else {
+                let schedule_day = schedule[0].day;
+                let games = schedule.into_iter().filter(|game| game.day == schedule_day).collect();
+                play_regular_season_day(tx, &g, day, games, sim_game, num_threads)?
+            };
+            if phase == PHASE_REGULAR_SEASON && is_past_trade_deadline(tx, &g)? {
+                new_phase(tx, PHASE_AFTER_TRADE_DEADLINE)?;
+            }
+            if tx.get_schedule()?.is_empty() {
+                new_phase(tx, PHASE_PLAYOFFS)?;
+            }
+            results
+        };
+        tx.put_day(day + 1)?;
+        Ok(results)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::types::Team;
+    use crate::worker::core::game::fast_sim::load_fast_sim;
+    use crate::worker::core::league::gen_random_players::gen_random_players;
+    use crate::worker::core::league::League;
+    use crate::worker::core::player::gen_ratings::TalentDistribution;
+    use rand::rngs::StdRng;
+
+    // Two teams at the start of the regular season, with a few free agents
+    fn new_league_db() -> LeagueDb {
+        let g = G::from_defaults(2025).unwrap();
+        let teams: Vec<Team> = (0..2).map(|tid| Team {
+            tid,
+            cid: 0,
+            did: 0,
+            region: format!("Region {}", tid),
+            name: format!("Team {}", tid),
+            abbrev: format!("T{}", tid),
+            pop: 1.0,
+            coaching_rank: None
+        }).collect();
+        let players = gen_random_players(&teams, &g, &TalentDistribution::default(), 1).unwrap();
+        let mut db = LeagueDb::open_in_memory().unwrap();
+        db.transaction(|tx| {
+            tx.put_league(&League {
+                g,
+                starting_season: 2025,
+                phase: PHASE_REGULAR_SEASON,
+                teams,
+                players,
+                games: Vec::new(),
+                team_seasons: Vec::new(),
+                draft_picks: Vec::new(),
+                events: Vec::new(),
+                awards: Vec::new()
+            })?;
+            tx.put_seed(1)?;
+            start_regular_season(tx)
+        }).unwrap();
+        db
+    }
+
+    fn injure(db: &mut LeagueDb, pid: i32, games_remaining: u8) {
+        db.transaction(|tx| {
+            let mut p = tx.get_player(pid)?.unwrap();
+            p.injury = PlayerInjury { games_remaining, injury_type: "Sprained Ankle".to_string(), score: None };
+            tx.put_player(&p)
+        }).unwrap();
+    }
+
+    #[test]
+    fn injuries_heal_without_playing() {
+        let mut db = new_league_db();
+        let players = db.transaction(|tx| tx.get_players()).unwrap();
+        let rostered = players.iter().find(|p| p.tid == 0).unwrap().pid;
+        let free_agent = players.iter().find(|p| p.tid == PLAYER_FREE_AGENT).unwrap().pid;
+        injure(&mut db, rostered, 1);
+        injure(&mut db, free_agent, 2);
+
+        // --fast games have no box scores, so nobody is in one
+        let fast_sim = db.transaction(load_fast_sim).unwrap();
+        let sim_game = |g: &G, game: &GameToSim, rng: &mut StdRng| fast_sim.sim_game(g, game, rng);
+        assert_eq!(play_day(&mut db, &sim_game, 1).unwrap().len(), 1);
+
+        let rostered = db.transaction(|tx| tx.get_player(rostered)).unwrap().unwrap();
+        assert_eq!(rostered.injury.games_remaining, 0);
+        assert_eq!(rostered.injury.injury_type, "Healthy");
+        let free_agent = db.transaction(|tx| tx.get_player(free_agent)).unwrap().unwrap();
+        assert_eq!(free_agent.injury.games_remaining, 1);
+    }
+}
\ No newline at end of file
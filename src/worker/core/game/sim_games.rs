@@ -0,0 +1,70 @@
+use crate::worker::core::game::play::GameToSim;
+use crate::worker::core::game::play::SimGameResult;
+use crate::worker::util::g::G;
+use crate::worker::util::parallel::parallel_map;
+use crate::worker::util::random::game_seed;
+use crate::worker::util::random::rng_from_seed;
+use rand::rngs::StdRng;
+
+// Plays one game, drawing every random number from rng
+pub type SimGame<'a> = dyn Fn(&G, &GameToSim, &mut StdRng) -> Result<SimGameResult, String> + Sync + 'a;
+
+// Plays games that don't depend on each other, like one day of the schedule, on up to num_threads threads. Each game
+// gets its own RNG seeded from seed, season and gid, so the results are the same for any num_threads. Results are in
+// the same order as games.
+pub fn sim_games(g: &G, games: &[GameToSim], seed: u64, num_threads: usize, sim_game: &SimGame) -> Result<Vec<SimGameResult>, String> {
+    let results = parallel_map(games.len(), num_threads, |i| {
+        let game = &games[i];
+        let mut rng = rng_from_seed(game_seed(seed, game.season, game.gid));
+        sim_game(g, game, &mut rng)
+    });
+    results.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::types::GameResult;
+    use crate::common::types::GameResultTeam;
+    use rand::Rng;
+
+    // A game decided by nothing but rng
+    fn sim_game(_: &G, game: &GameToSim, rng: &mut StdRng) -> Result<SimGameResult, String> {
+        Ok(SimGameResult {
+            result: GameResult {
+                gid: game.gid,
+                season: game.season,
+                playoffs: game.playoffs,
+                overtimes: 0,
+                teams: [
+                    GameResultTeam { tid: game.home_tid, pts: rng.gen_range(80..120) },
+                    GameResultTeam { tid: game.away_tid, pts: rng.gen_range(80..120) }
+                ]
+            },
+            box_score: None,
+            play_by_play: None
+        })
+    }
+
+    fn get_scores(num_threads: usize) -> Vec<(i32, i32, i32)> {
+        let g = G::from_defaults(2025).unwrap();
+        let games: Vec<GameToSim> = (0..50).map(|gid| GameToSim {
+            gid,
+            season: 2025,
+            day: 0,
+            home_tid: 0,
+            away_tid: 1,
+            playoffs: false
+        }).collect();
+        let results = sim_games(&g, &games, 7, num_threads, &sim_game).unwrap();
+        results.iter().map(|r| (r.result.gid, r.result.teams[0].pts, r.result.teams[1].pts)).collect()
+    }
+
+    #[test]
+    fn results_dont_depend_on_num_threads() {
+        let scores = get_scores(1);
+        assert_eq!(scores.iter().map(|score| score.0).collect::<Vec<i32>>(), (0..50).collect::<Vec<i32>>());
+        assert_eq!(get_scores(4), scores);
+        assert_eq!(get_scores(16), scores);
+    }
+}
\ No newline at end of file
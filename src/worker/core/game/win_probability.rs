@@ -0,0 +1,170 @@
+use crate::common::types::BoxScore;
+use crate::common::types::PlayByPlayEvent;
+use crate::worker::core::game::fast_sim::normal_cdf;
+use crate::worker::core::game::fast_sim::FastModel;
+use crate::worker::core::game::fast_sim::FastTeamRating;
+use crate::worker::core::game::fast_sim::OVERTIME_LENGTH;
+use std::collections::HashMap;
+
+// Having the ball is worth about half a possession more than the other team
+const POSSESSION_VALUE: f64 = 0.55;
+
+// Home team's chance of winning at any point in a game. The rest of the game is treated like a shorter fast model game:
+// the final margin is the current one plus the pregame expected margin scaled by the time left, with a spread that
+// shrinks with the square root of the time left.
+pub struct WinProbability {
+    // Pregame expected home margin over regulation
+    expected_margin: f64,
+    margin_sd: f64,
+    num_periods: i32,
+    quarter_length: f64
+}
+
+impl WinProbability {
+    pub fn new(model: &FastModel, home: &FastTeamRating, away: &FastTeamRating, num_periods: i32, quarter_length: f64) -> Self {
+        WinProbability {
+            expected_margin: model.expected_margin(home, away),
+            margin_sd: model.margin_sd,
+            num_periods,
+            quarter_length
+        }
+    }
+
+    fn get_regulation_length(&self) -> f64 {
+        self.num_periods as f64 * self.quarter_length
+    }
+
+    // A tie at the end of a period goes to another overtime, which is a coin flip weighted by the expected margin
+    fn get_overtime_win_probability(&self) -> f64 {
+        let fraction = OVERTIME_LENGTH / self.get_regulation_length();
+        normal_cdf(fraction * self.expected_margin / (fraction.sqrt() * self.margin_sd))
+    }
+
+    // score is [home, away], period is 1 indexed with overtimes after the last regulation period, t is minutes left in
+    // the period, and possession is 0 when the home team has the ball and 1 when the away team does
+    pub fn get(&self, score: [i32; 2], period: i32, t: f64, possession: Option<usize>) -> f64 {
+        let diff = (score[0] - score[1]) as f64;
+        let minutes_left = if period > self.num_periods {
+            t.max(0.0)
+        } else {
+            (self.num_periods - period) as f64 * self.quarter_length + t.max(0.0)
+        };
+        if minutes_left <= 0.0 {
+            if diff > 0.0 {
+                return 1.0;
+            }
+            if diff < 0.0 {
+                return 0.0;
+            }
+            return self.get_overtime_win_probability();
+        }
+
+        let fraction = minutes_left / self.get_regulation_length();
+        let mut mean = diff + fraction * self.expected_margin;
+        if possession == Some(0) {
+            mean += POSSESSION_VALUE;
+        } else if possession == Some(1) {
+            mean -= POSSESSION_VALUE;
+        }
+        normal_cdf(mean / (fraction.sqrt() * self.margin_sd))
+    }
+
+    pub fn get_pregame(&self) -> f64 {
+        self.get([0, 0], 1, self.quarter_length, None)
+    }
+
+    // Sets wp on every event, from the score and clock after it. The possession is unknown, so it's left out.
+    pub fn add_to_play_by_play(&self, events: &mut [PlayByPlayEvent]) {
+        for event in events.iter_mut() {
+            event.wp = Some(self.get(event.score, event.period, event.t, None));
+        }
+    }
+}
+
+// Change in the home team's win probability from each event, credited to the event's player: positive when a home
+// player raises it or an away player lowers it. Events without wp or without a player are skipped, but still count as
+// the starting point for the next change.
+pub fn get_win_probability_added(events: &[PlayByPlayEvent], home_tid: i32, pregame: f64) -> HashMap<i32, f64> {
+    let mut wpa = HashMap::new();
+    let mut prev = pregame;
+    for event in events.iter() {
+        if event.wp.is_none() {
+            continue;
+        }
+        let wp = event.wp.unwrap();
+        if let (Some(pid), Some(tid)) = (event.pid, event.tid) {
+            let delta = if tid == home_tid { wp - prev } else { prev - wp };
+            *wpa.entry(pid).or_insert(0.0) += delta;
+        }
+        prev = wp;
+    }
+    wpa
+}
+
+// Fills in wpa for the players in the box score
+pub fn add_win_probability_added(box_score: &mut BoxScore, events: &[PlayByPlayEvent], pregame: f64) {
+    let wpa = get_win_probability_added(events, box_score.teams[0].tid, pregame);
+    for t in box_score.teams.iter_mut() {
+        for p in t.players.iter_mut() {
+            p.wpa = wpa.get(&p.pid).copied().unwrap_or(0.0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(period: i32, t: f64, tid: i32, pid: Option<i32>, score: [i32; 2]) -> PlayByPlayEvent {
+        PlayByPlayEvent {
+            period,
+            t,
+            tid: Some(tid),
+            pid,
+            text: String::new(),
+            score,
+            wp: None
+        }
+    }
+
+    fn new_win_probability() -> WinProbability {
+        let home = FastTeamRating { off: 0.52, def: 0.5 };
+        let away = FastTeamRating { off: 0.5, def: 0.5 };
+        WinProbability::new(&FastModel::default(), &home, &away, 4, 12.0)
+    }
+
+    #[test]
+    fn final_score_decides_it() {
+        let wp = new_win_probability();
+        assert!(wp.get_pregame() > 0.5 && wp.get_pregame() < 1.0);
+        assert_eq!(wp.get([100, 99], 4, 0.0, None), 1.0);
+        assert_eq!(wp.get([99, 100], 5, 0.0, None), 0.0);
+        // A tie at the end of regulation favors the better team in overtime
+        let overtime = wp.get([100, 100], 4, 0.0, None);
+        assert!(overtime > 0.5 && overtime < wp.get_pregame());
+        // Having the ball helps
+        assert!(wp.get([50, 50], 3, 5.0, Some(0)) > wp.get([50, 50], 3, 5.0, Some(1)));
+    }
+
+    #[test]
+    fn win_probability_added_sums_to_the_change() {
+        let wp = new_win_probability();
+        let mut events = vec![
+            event(1, 11.5, 0, Some(1), [2, 0]),
+            event(1, 11.0, 1, Some(20), [2, 3]),
+            event(2, 6.0, 0, Some(2), [40, 38]),
+            event(3, 1.0, 1, Some(20), [60, 66]),
+            event(4, 2.0, 0, Some(1), [95, 96]),
+            event(4, 0.0, 1, Some(21), [97, 99])
+        ];
+        wp.add_to_play_by_play(&mut events);
+        assert_eq!(events.last().unwrap().wp, Some(0.0));
+
+        let wpa = get_win_probability_added(&events, 0, wp.get_pregame());
+        let home: f64 = [1, 2].iter().map(|pid| wpa[pid]).sum();
+        let away: f64 = [20, 21].iter().map(|pid| wpa[pid]).sum();
+        // Home players' gains are away players' losses, so together they account for the whole game
+        assert!((home - away - (0.0 - wp.get_pregame())).abs() < 1e-9);
+        assert!(wpa[&21] > 0.0);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,329 @@
+use crate::common::league_settings::LeagueSettingsFile;
+use crate::common::types::Player;
+use crate::worker::core::league::import_league::map_history_value;
+use crate::worker::core::league::league_file::LeagueFile;
+use crate::worker::core::league::league_file::LeagueFileAwards;
+use crate::worker::core::league::league_file::LeagueFileBorn;
+use crate::worker::core::league::league_file::LeagueFileContract;
+use crate::worker::core::league::league_file::LeagueFileDraft;
+use crate::worker::core::league::league_file::LeagueFileDraftPick;
+use crate::worker::core::league::league_file::LeagueFileEvent;
+use crate::worker::core::league::league_file::LeagueFileGame;
+use crate::worker::core::league::league_file::LeagueFileGameTeam;
+use crate::worker::core::league::league_file::LeagueFilePlayer;
+use crate::worker::core::league::league_file::LeagueFilePlayerStats;
+use crate::worker::core::league::league_file::LeagueFileTeam;
+use crate::worker::core::league::league_file::LeagueFileTeamSeason;
+use crate::worker::core::league::League;
+use serde_json::json;
+use serde_json::Map;
+use serde_json::Value;
+
+// Settings that are stored with history, named as in LeagueSettingsFile
+const HISTORY_KEYS: [&str; 11] = [
+    "confs",
+    "divs",
+    "numGames",
+    "numGamesPlayoffSeries",
+    "numPlayoffByes",
+    "otl",
+    "playoffsNumTeamsDiv",
+    "playInType",
+    "pointsFormula",
+    "tiebreakers",
+    "ties",
+];
+
+// LeagueSettingsFile leaves out start for the first change, and writes a single change as a plain value
+fn is_settings_changes(value: &Value) -> bool {
+    let changes = value.as_array();
+    if changes.is_none() || changes.unwrap().is_empty() {
+        return false;
+    }
+    changes.unwrap().iter().all(|change| {
+        change.is_object()
+            && change.get("value").is_some()
+            && change.as_object().unwrap().keys().all(|key| key == "start" || key == "value")
+    })
+}
+
+// BBGM always has [{start, value}] for these, with start null for the first value
+fn to_history_value(value: Value) -> Value {
+    if !is_settings_changes(&value) {
+        return json!([{ "start": null, "value": value }]);
+    }
+    let mut changes = Vec::new();
+    for mut change in value.as_array().unwrap().iter().cloned() {
+        let change_object = change.as_object_mut().unwrap();
+        if !change_object.contains_key("start") {
+            change_object.insert("start".to_string(), Value::Null);
+        }
+        changes.push(change);
+    }
+    Value::Array(changes)
+}
+
+// Value of a list of changes from LeagueSettingsFile in season
+fn value_at(changes: Value, season: i32) -> Value {
+    let mut value = Value::Null;
+    for change in changes.as_array().unwrap().iter() {
+        let start = change.get("start").and_then(|start| start.as_i64());
+        if value.is_null() || start.is_none() || start.unwrap() <= season as i64 {
+            value = change.get("value").unwrap().clone();
+        }
+    }
+    value
+}
+
+fn tiebreakers_to_list(value: Value) -> Result<Value, String> {
+    let keys: Vec<Value> = value
+        .as_str()
+        .unwrap_or("")
+        .split(',')
+        .map(|key| key.trim())
+        .filter(|key| !key.is_empty())
+        .map(|key| Value::String(key.to_string()))
+        .collect();
+    Ok(Value::Array(keys))
+}
+
+fn play_in_type_to_bool(value: Value) -> Result<Value, String> {
+    Ok(Value::Bool(!value.as_str().unwrap_or("").is_empty()))
+}
+
+fn export_game_attributes(league: &League, warnings: &mut Vec<String>) -> Map<String, Value> {
+    let settings = serde_json::to_value(LeagueSettingsFile::from_game_attributes(league.g.game_attributes())).unwrap();
+    let mut game_attributes = Map::new();
+    for (key, mut value) in settings.as_object().unwrap().clone().into_iter() {
+        if HISTORY_KEYS.contains(&key.as_str()) {
+            value = to_history_value(value);
+        } else if is_settings_changes(&value) {
+            warnings.push(format!("gameAttributes.{}: BBGM has no history for this setting, exporting the {} value", key, league.g.season()));
+            value = value_at(value, league.g.season());
+        }
+        match key.as_str() {
+            "disableInjuries" => {
+                if value.as_bool() == Some(true) {
+                    warnings.push("gameAttributes.disableInjuries: not supported by BBGM, not exported".to_string());
+                }
+            }
+            "tiebreakers" => {
+                game_attributes.insert(key, map_history_value(value, &tiebreakers_to_list).unwrap());
+            }
+            "playInType" => {
+                let single_elimination = league.g.get_league_with_history().play_in_type.iter().any(|change| change.value == "singleElimination");
+                if single_elimination {
+                    warnings.push("gameAttributes.playInType: singleElimination is exported as the NBA play-in".to_string());
+                }
+                game_attributes.insert("playIn".to_string(), map_history_value(value, &play_in_type_to_bool).unwrap());
+            }
+            _ => {
+                game_attributes.insert(key, value);
+            }
+        }
+    }
+    game_attributes.insert("season".to_string(), json!(league.g.season()));
+    game_attributes.insert("startingSeason".to_string(), json!(league.starting_season));
+    game_attributes.insert("phase".to_string(), json!(league.phase));
+    game_attributes
+}
+
+fn to_league_file_player(p: &Player) -> LeagueFilePlayer {
+    LeagueFilePlayer {
+        pid: p.pid,
+        tid: p.tid,
+        first_name: p.first_name.clone(),
+        last_name: p.last_name.clone(),
+        born: LeagueFileBorn {
+            year: p.born.year,
+            loc: p.born.loc.clone()
+        },
+        hgt: p.hgt,
+        weight: p.weight,
+        college: p.college.clone(),
+        draft: Some(LeagueFileDraft {
+            round: p.draft.round,
+            pick: p.draft.pick,
+            tid: p.draft.tid,
+            original_tid: p.draft.original_tid,
+            year: p.draft.year
+        }),
+        ratings: p.ratings.clone(),
+        injury: Some(p.injury.clone()),
+        value: Some(p.value),
+        value_no_pot: Some(p.value_no_pot),
+        contract: Some(LeagueFileContract {
+            amount: p.contract.amount,
+            exp: p.contract.exp,
+            rookie: if p.contract.rookie { Some(true) } else { None },
+            extra: Map::new()
+        }),
+        stats: p.stats.iter().map(|ps| LeagueFilePlayerStats {
+            season: ps.season,
+            tid: ps.tid,
+            playoffs: ps.playoffs,
+            gp: ps.gp,
+            gs: ps.gs,
+            min: ps.min,
+            pts: ps.pts,
+            fg: ps.fg,
+            fga: ps.fga,
+            tp: ps.tp,
+            tpa: ps.tpa,
+            ft: ps.ft,
+            fta: ps.fta,
+            orb: ps.orb,
+            drb: ps.drb,
+            ast: ps.ast,
+            stl: ps.stl,
+            blk: ps.blk,
+            tov: ps.tov,
+            pf: ps.pf,
+            extra: Map::new()
+        }).collect(),
+        pt_modifier: Some(p.pt_modifier),
+        awards: p.awards.clone(),
+        extra: Map::new()
+    }
+}
+
+// Writes the league in the BBGM JSON format. Returns the JSON and warnings about anything that BBGM can't represent.
+pub fn export_league(league: &League) -> (String, Vec<String>) {
+    let mut warnings = Vec::new();
+    let game_attributes = export_game_attributes(league, &mut warnings);
+    let num_options = league.players.iter().filter(|p| !p.contract.option.is_empty()).count();
+    if num_options > 0 {
+        warnings.push(format!("players.contract.option: not supported by BBGM, {} contract options not exported", num_options));
+    }
+
+    let file = LeagueFile {
+        version: None,
+        starting_season: Some(league.starting_season),
+        game_attributes: Some(Value::Object(game_attributes)),
+        teams: league.teams.iter().map(|t| LeagueFileTeam {
+            tid: t.tid,
+            cid: t.cid,
+            did: t.did,
+            region: t.region.clone(),
+            name: t.name.clone(),
+            abbrev: t.abbrev.clone(),
+            pop: t.pop,
+            extra: Map::new()
+        }).collect(),
+        players: league.players.iter().map(to_league_file_player).collect(),
+        games: league.games.iter().map(|game| LeagueFileGame {
+            gid: game.gid,
+            season: game.season,
+            playoffs: game.playoffs,
+            overtimes: game.overtimes,
+            teams: game.teams.iter().map(|t| LeagueFileGameTeam {
+                tid: t.tid,
+                pts: t.pts,
+                extra: Map::new()
+            }).collect(),
+            extra: Map::new()
+        }).collect(),
+        team_seasons: league.team_seasons.iter().map(|ts| LeagueFileTeamSeason {
+            tid: ts.tid,
+            season: ts.season,
+            won: ts.won,
+            lost: ts.lost,
+            tied: ts.tied,
+            otl: ts.otl,
+            cid: Some(ts.cid),
+            did: Some(ts.did),
+            extra: Map::new()
+        }).collect(),
+        draft_picks: league.draft_picks.iter().map(|dp| LeagueFileDraftPick {
+            dpid: dp.dpid,
+            tid: dp.tid,
+            original_tid: dp.original_tid,
+            round: dp.round,
+            pick: dp.pick,
+            season: json!(dp.season),
+            extra: Map::new()
+        }).collect(),
+        events: league.events.iter().map(|event| LeagueFileEvent {
+            eid: event.eid,
+            event_type: event.event_type.clone(),
+            season: event.season,
+            text: event.text.clone(),
+            tids: event.tids.clone(),
+            pids: event.pids.clone(),
+            dpids: event.dpids.clone(),
+            extra: Map::new()
+        }).collect(),
+        awards: league.awards.iter().map(|awards| LeagueFileAwards {
+            season: awards.season,
+            mvp: awards.mvp.clone(),
+            dpoy: awards.dpoy.clone(),
+            roy: awards.roy.clone(),
+            smoy: awards.smoy.clone(),
+            mip: awards.mip.clone(),
+            finals_mvp: awards.finals_mvp.clone(),
+            all_league: awards.all_league.clone(),
+            all_defensive: awards.all_defensive.clone(),
+            extra: Map::new()
+        }).collect(),
+        extra: Map::new()
+    };
+
+    (serde_json::to_string_pretty(&file).unwrap(), warnings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::constants::PHASE_REGULAR_SEASON;
+    use crate::common::types::Team;
+    use crate::worker::core::league::gen_random_players::gen_random_players;
+    use crate::worker::core::league::import_league::import_league;
+    use crate::worker::core::player::gen_ratings::TalentDistribution;
+    use crate::worker::util::g::G;
+
+    fn new_league() -> League {
+        let mut g = G::from_defaults(2025).unwrap();
+        g.set_num_games_playoff_series(2026, vec![5, 7, 7, 7]).unwrap();
+        let teams: Vec<Team> = (0..2).map(|tid| Team {
+            tid,
+            cid: 0,
+            did: 0,
+            region: format!("Region {}", tid),
+            name: format!("Team {}", tid),
+            abbrev: format!("T{}", tid),
+            pop: 1.0,
+            coaching_rank: None
+        }).collect();
+        let players = gen_random_players(&teams, &g, &TalentDistribution::default(), 1).unwrap();
+        League {
+            g,
+            starting_season: 2025,
+            phase: PHASE_REGULAR_SEASON,
+            teams,
+            players,
+            games: Vec::new(),
+            team_seasons: Vec::new(),
+            draft_picks: Vec::new(),
+            events: Vec::new(),
+            awards: Vec::new()
+        }
+    }
+
+    #[test]
+    fn exported_leagues_import_the_same() {
+        let (json, warnings) = export_league(&new_league());
+        assert!(warnings.is_empty(), "{:?}", warnings);
+        let (league, warnings) = import_league(&json).unwrap();
+        assert!(warnings.is_empty(), "{:?}", warnings);
+        assert_eq!(export_league(&league).0, json);
+    }
+
+    #[test]
+    fn unsupported_ratings_are_reported() {
+        let (json, _) = export_league(&new_league());
+        let mut file: Value = serde_json::from_str(&json).unwrap();
+        file["players"][0]["ratings"][0]["fuzz"] = json!(1.5);
+        let (_, warnings) = import_league(&file.to_string()).unwrap();
+        assert_eq!(warnings, vec!["players.ratings: unsupported fields ignored: fuzz"]);
+    }
+}
\ No newline at end of file
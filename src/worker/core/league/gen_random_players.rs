@@ -0,0 +1,99 @@
+use crate::common::constants::PLAYER_FREE_AGENT;
+use crate::common::types::Player;
+use crate::common::types::Team;
+use crate::worker::core::draft::gen_players::gen_players;
+use crate::worker::core::draft::gen_players::get_num_draft_prospects;
+use crate::worker::core::player::generate::generate;
+use crate::worker::core::player::gen_ratings::TalentDistribution;
+use crate::worker::util::g::G;
+use crate::worker::util::helpers;
+use crate::worker::util::random::gauss;
+use crate::worker::util::random::random_players_seed;
+use crate::worker::util::random::rng_from_seed;
+use rand::Rng;
+
+pub const NUM_PLAYERS_PER_TEAM: usize = 13;
+
+// Players left over for free agency, per team
+const NUM_FREE_AGENTS_PER_TEAM: f64 = 0.5;
+
+// Rosters for a new random league, plus free agents and the draft class for g.season(). Teams pick from the pool in a
+// snake draft by value, so they start out about even. Contracts on the rosters end at random points within the
+// length the players ask for, so they don't all expire together.
+pub fn gen_random_players(teams: &[Team], g: &G, talent: &TalentDistribution, seed: u64) -> Result<Vec<Player>, String> {
+    let season = g.season();
+    let mut rng = rng_from_seed(random_players_seed(seed));
+    let num_players = teams.len() * NUM_PLAYERS_PER_TEAM + (teams.len() as f64 * NUM_FREE_AGENTS_PER_TEAM).round() as usize;
+    let mut players = Vec::new();
+    for pid in 0..num_players {
+        let age = helpers::bound(gauss(&mut rng, 26.0, 4.0).round(), 19.0, 36.0) as i32;
+        // Drafted the year they turned 19
+        let draft_year = season - (age - 19);
+        players.push(generate(pid as i32, PLAYER_FREE_AGENT, age, draft_year, g, talent, &mut rng)?);
+    }
+
+    players.sort_by(|a, b| b.value.total_cmp(&a.value));
+    for (i, p) in players.iter_mut().take(teams.len() * NUM_PLAYERS_PER_TEAM).enumerate() {
+        let round = i / teams.len();
+        let index = if round.is_multiple_of(2) { i % teams.len() } else { teams.len() - 1 - i % teams.len() };
+        p.tid = teams[index].tid;
+        p.contract.exp = rng.gen_range(season..=p.contract.exp);
+    }
+    players.sort_by_key(|p| p.pid);
+
+    let draft_class = gen_players(season, g, get_num_draft_prospects(teams.len(), g.num_draft_rounds()), num_players as i32, talent, seed)?;
+    players.extend(draft_class);
+    Ok(players)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::constants::PLAYER_UNDRAFTED;
+
+    fn new_teams(num_teams: i32) -> Vec<Team> {
+        (0..num_teams).map(|tid| Team {
+            tid,
+            cid: 0,
+            did: 0,
+            region: format!("Region {}", tid),
+            name: format!("Team {}", tid),
+            abbrev: format!("T{}", tid),
+            pop: 1.0,
+            coaching_rank: None
+        }).collect()
+    }
+
+    #[test]
+    fn rosters_free_agents_and_draft_class() {
+        let g = G::from_defaults(2025).unwrap();
+        let teams = new_teams(4);
+        let players = gen_random_players(&teams, &g, &TalentDistribution::default(), 1).unwrap();
+
+        for t in teams.iter() {
+            assert_eq!(players.iter().filter(|p| p.tid == t.tid).count(), NUM_PLAYERS_PER_TEAM);
+        }
+        assert_eq!(players.iter().filter(|p| p.tid == PLAYER_FREE_AGENT).count(), 2);
+        let draft_class: Vec<&Player> = players.iter().filter(|p| p.tid == PLAYER_UNDRAFTED).collect();
+        assert_eq!(draft_class.len(), get_num_draft_prospects(4, g.num_draft_rounds()));
+        assert!(draft_class.iter().all(|p| p.draft.year == 2025 && p.age(2025) >= 19 && p.age(2025) <= 22));
+
+        for (i, p) in players.iter().enumerate() {
+            assert_eq!(p.pid, i as i32);
+            assert!(!p.first_name.is_empty() && !p.last_name.is_empty());
+            let ratings = &p.ratings[0];
+            assert!(ratings.ovr > 0.0 && ratings.pot >= ratings.ovr && !ratings.pos.is_empty());
+            assert!((19..=36).contains(&p.age(2025)));
+            assert!(p.contract.exp >= 2025);
+        }
+    }
+
+    #[test]
+    fn same_seed_same_players() {
+        let g = G::from_defaults(2025).unwrap();
+        let teams = new_teams(2);
+        let to_json = |seed: u64| serde_json::to_string(&gen_random_players(&teams, &g, &TalentDistribution::default(), seed).unwrap()).unwrap();
+        assert_eq!(to_json(3), to_json(3));
+        assert_ne!(to_json(3), to_json(4));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,495 @@
+use crate::common::constants::DRAFT_TYPES;
+use crate::common::constants::PHASES;
+use crate::common::constants::PHASE_PLAYOFFS;
+use crate::common::constants::PHASE_PRESEASON;
+use crate::common::constants::PHASE_REGULAR_SEASON;
+use crate::common::constants::PHASE_RESIGN_PLAYERS;
+use crate::common::constants::POSITIONS;
+use crate::common::constants::SALARY_CAP_TYPES;
+use crate::common::league_settings::LeagueSettingsFile;
+use crate::common::types::DraftPick;
+use crate::common::types::Event;
+use crate::common::types::GameResult;
+use crate::common::types::GameResultTeam;
+use crate::common::types::Player;
+use crate::common::types::PlayerBorn;
+use crate::common::types::PlayerContract;
+use crate::common::types::PlayerDraft;
+use crate::common::types::PlayerInjury;
+use crate::common::types::PlayerRatings;
+use crate::common::types::PlayerStats;
+use crate::common::types::SeasonAwards;
+use crate::common::types::Team;
+use crate::common::types::TeamSeasonAttrs;
+use crate::worker::core::league::league_file::LeagueFile;
+use crate::worker::core::league::league_file::LeagueFilePlayer;
+use crate::worker::core::league::League;
+use crate::worker::core::player::ovr::ovr;
+use crate::worker::core::player::pos::pos;
+use crate::worker::core::player::pot_estimator::pot_estimator;
+use crate::worker::core::player::skills::skills;
+use crate::worker::core::player::gen_contract::gen_contract;
+use crate::worker::core::player::value::value;
+use crate::worker::util::g::G;
+use serde_json::Map;
+use serde_json::Value;
+use std::collections::BTreeSet;
+
+// gameAttributes keys that map directly onto a LeagueSettingsFile key
+const SETTINGS_KEYS: [&str; 34] = [
+    "lid",
+    "foulsNeededToFoulOut",
+    "numPlayersOnCourt",
+    "quarterLength",
+    "pace",
+    "foulsUntilBonus",
+    "foulRateFactor",
+    "turnoverFactor",
+    "draftType",
+    "numDraftRounds",
+    "salaryCap",
+    "salaryCapType",
+    "minPayroll",
+    "luxuryPayroll",
+    "luxuryTax",
+    "minContract",
+    "maxContract",
+    "minContractLength",
+    "maxContractLength",
+    "rookieContractLengths",
+    "draftPickAutoContractPercent",
+    "draftPickAutoContractRounds",
+    "maxRosterSize",
+    "minRosterSize",
+    "tradeDeadline",
+    "confs",
+    "divs",
+    "numGames",
+    "numGamesPlayoffSeries",
+    "numPlayoffByes",
+    "otl",
+    "playoffsNumTeamsDiv",
+    "pointsFormula",
+    "ties",
+];
+
+// BBGM's phase numbers match the PHASE constants, except for the phases that don't exist here. A league in the
+// playoffs goes back to the end of the regular season, since playoff series aren't imported.
+fn to_phase(value: &Value, warnings: &mut Vec<String>) -> i32 {
+    let phase = value.as_i64().map(|phase| phase as i32);
+    match phase {
+        Some(-1) => {
+            warnings.push("gameAttributes.phase: the fantasy draft is not supported, starting in the preseason".to_string());
+            PHASE_PRESEASON
+        }
+        Some(6) => PHASE_RESIGN_PLAYERS,
+        Some(PHASE_PLAYOFFS) => {
+            warnings.push("gameAttributes.phase: playoff series are not imported, the playoffs start over".to_string());
+            PHASE_REGULAR_SEASON
+        }
+        Some(phase) if PHASES.contains(&phase) => phase,
+        _ => {
+            warnings.push(format!("gameAttributes.phase: {} is not a phase, starting in the regular season", value));
+            PHASE_REGULAR_SEASON
+        }
+    }
+}
+
+// BBGM stores settings with history as [{start, value}], where the first start is null
+pub fn is_history_value(value: &Value) -> bool {
+    let changes = value.as_array();
+    if changes.is_none() || changes.unwrap().is_empty() {
+        return false;
+    }
+    changes.unwrap().iter().all(|change| {
+        change.is_object() && change.get("value").is_some() && change.get("start").is_some()
+    })
+}
+
+// Applies convert to every value of a setting, whether or not it has history
+pub fn map_history_value(value: Value, convert: &dyn Fn(Value) -> Result<Value, String>) -> Result<Value, String> {
+    if !is_history_value(&value) {
+        return convert(value);
+    }
+    let mut changes = Vec::new();
+    for mut change in value.as_array().unwrap().iter().cloned() {
+        let converted = convert(change.get("value").unwrap().clone())?;
+        change.as_object_mut().unwrap().insert("value".to_string(), converted);
+        changes.push(change);
+    }
+    Ok(Value::Array(changes))
+}
+
+// Every value of a setting, whether or not it has history
+fn history_values(value: &Value) -> Vec<&Value> {
+    if !is_history_value(value) {
+        return vec![value];
+    }
+    value.as_array().unwrap().iter().map(|change| change.get("value").unwrap()).collect()
+}
+
+fn tiebreakers_to_string(value: Value) -> Result<Value, String> {
+    let keys = value.as_array();
+    if keys.is_none() {
+        return Err("gameAttributes.tiebreakers: expected a list of tiebreakers".to_string());
+    }
+    let keys: Vec<String> = keys.unwrap().iter().filter_map(|key| key.as_str().map(|key| key.to_string())).collect();
+    Ok(Value::String(keys.join(",")))
+}
+
+fn play_in_to_type(value: Value) -> Result<Value, String> {
+    let play_in = value.as_bool();
+    if play_in.is_none() {
+        return Err("gameAttributes.playIn: expected true or false".to_string());
+    }
+    if play_in.unwrap() {
+        return Ok(Value::String("nba".to_string()));
+    }
+    Ok(Value::String("".to_string()))
+}
+
+// Older files have gameAttributes as [{key, value}]
+fn normalize_game_attributes(game_attributes: Value) -> Result<Map<String, Value>, String> {
+    if game_attributes.is_object() {
+        return Ok(game_attributes.as_object().unwrap().clone());
+    }
+    if game_attributes.is_array() {
+        let mut map = Map::new();
+        for entry in game_attributes.as_array().unwrap().iter() {
+            let key = entry.get("key").and_then(|key| key.as_str());
+            let value = entry.get("value");
+            if key.is_none() || value.is_none() {
+                return Err("gameAttributes: expected entries like {\"key\": ..., \"value\": ...}".to_string());
+            }
+            map.insert(key.unwrap().to_string(), value.unwrap().clone());
+        }
+        return Ok(map);
+    }
+    Err("gameAttributes: expected an object".to_string())
+}
+
+fn summarize_extra_fields(name: &str, extras: Vec<&Map<String, Value>>, warnings: &mut Vec<String>) {
+    let keys: BTreeSet<&String> = extras.iter().flat_map(|extra| extra.keys()).collect();
+    if !keys.is_empty() {
+        let keys: Vec<&str> = keys.iter().map(|key| key.as_str()).collect();
+        warnings.push(format!("{}: unsupported fields ignored: {}", name, keys.join(", ")));
+    }
+}
+
+// Fills in what BBGM computes from the raw ratings when the file leaves it out
+fn fill_ratings(ratings: &mut PlayerRatings, age: i32) -> Result<(), String> {
+    if ratings.ovr == 0.0 && ratings.pot == 0.0 {
+        ratings.ovr = ovr(ratings, None);
+        ratings.pot = pot_estimator(ratings, age);
+        ratings.skills = skills(ratings)?;
+    }
+    if ratings.ovrs.is_empty() {
+        for position in POSITIONS.iter() {
+            ratings.ovrs.insert(position.to_string(), ovr(ratings, Some(position)));
+        }
+    }
+    if ratings.pos.is_empty() {
+        ratings.pos = pos(ratings);
+    }
+    Ok(())
+}
+
+// Value is computed when it's missing, and players without a contract get the one they would ask for
+fn to_player(mut p: LeagueFilePlayer, g: &G) -> Result<Player, String> {
+    let season = g.season();
+    for ratings in p.ratings.iter_mut() {
+        fill_ratings(ratings, ratings.season - p.born.year)?;
+    }
+    let mut player = Player {
+        pid: p.pid,
+        tid: p.tid,
+        first_name: p.first_name,
+        last_name: p.last_name,
+        born: PlayerBorn {
+            year: p.born.year,
+            loc: p.born.loc
+        },
+        hgt: p.hgt,
+        weight: p.weight,
+        college: p.college,
+        draft: match p.draft {
+            Some(draft) => PlayerDraft {
+                round: draft.round,
+                pick: draft.pick,
+                tid: draft.tid,
+                original_tid: draft.original_tid,
+                year: draft.year
+            },
+            None => PlayerDraft::default(),
+        },
+        ratings: p.ratings,
+        contract: PlayerContract {
+            amount: g.min_contract(),
+            exp: season,
+            rookie: false,
+            option: String::new()
+        },
+        injury: p.injury.unwrap_or_else(PlayerInjury::healthy),
+        stats: p.stats.into_iter().map(|ps| PlayerStats {
+            season: ps.season,
+            tid: ps.tid,
+            playoffs: ps.playoffs,
+            gp: ps.gp,
+            gs: ps.gs,
+            min: ps.min,
+            pts: ps.pts,
+            fg: ps.fg,
+            fga: ps.fga,
+            tp: ps.tp,
+            tpa: ps.tpa,
+            ft: ps.ft,
+            fta: ps.fta,
+            orb: ps.orb,
+            drb: ps.drb,
+            ast: ps.ast,
+            stl: ps.stl,
+            blk: ps.blk,
+            tov: ps.tov,
+            pf: ps.pf
+        }).collect(),
+        value: 0.0,
+        value_no_pot: 0.0,
+        pt_modifier: p.pt_modifier.unwrap_or(1.0),
+        awards: p.awards
+    };
+    player.value = p.value.unwrap_or_else(|| value(&player, season, false));
+    player.value_no_pot = p.value_no_pot.unwrap_or_else(|| value(&player, season, true));
+    player.contract = match p.contract {
+        Some(contract) => PlayerContract {
+            amount: contract.amount,
+            exp: contract.exp,
+            rookie: contract.rookie.unwrap_or(false),
+            option: String::new()
+        },
+        None => gen_contract(&player, g, season),
+    };
+    Ok(player)
+}
+
+pub fn import_league(text: &str) -> Result<(League, Vec<String>), String> {
+    let file: Result<LeagueFile, serde_json::Error> = serde_json::from_str(text);
+    if file.is_err() {
+        return Err(format!("Invalid league file: {}", file.err().unwrap()));
+    }
+    let file = file.unwrap();
+    let mut warnings = Vec::new();
+
+    for key in file.extra.keys() {
+        warnings.push(format!("{}: not supported, ignored", key));
+    }
+    summarize_extra_fields("teams", file.teams.iter().map(|t| &t.extra).collect(), &mut warnings);
+    summarize_extra_fields("players", file.players.iter().map(|p| &p.extra).collect(), &mut warnings);
+    summarize_extra_fields("players.ratings", file.players.iter().flat_map(|p| p.ratings.iter().map(|ratings| &ratings.extra)).collect(), &mut warnings);
+    summarize_extra_fields("players.contract", file.players.iter().filter_map(|p| p.contract.as_ref().map(|c| &c.extra)).collect(), &mut warnings);
+    summarize_extra_fields("players.stats", file.players.iter().flat_map(|p| p.stats.iter().map(|ps| &ps.extra)).collect(), &mut warnings);
+    summarize_extra_fields("games", file.games.iter().map(|game| &game.extra).collect(), &mut warnings);
+    summarize_extra_fields("games.teams", file.games.iter().flat_map(|game| game.teams.iter().map(|t| &t.extra)).collect(), &mut warnings);
+    summarize_extra_fields("teamSeasons", file.team_seasons.iter().map(|ts| &ts.extra).collect(), &mut warnings);
+    summarize_extra_fields("draftPicks", file.draft_picks.iter().map(|dp| &dp.extra).collect(), &mut warnings);
+    summarize_extra_fields("events", file.events.iter().map(|event| &event.extra).collect(), &mut warnings);
+    summarize_extra_fields("awards", file.awards.iter().map(|awards| &awards.extra).collect(), &mut warnings);
+
+    let game_attributes = match file.game_attributes {
+        Some(game_attributes) => normalize_game_attributes(game_attributes)?,
+        None => Map::new(),
+    };
+
+    let mut settings = Map::new();
+    let mut season = None;
+    let mut starting_season = file.starting_season;
+    let mut phase = PHASE_REGULAR_SEASON;
+    for (key, value) in game_attributes.into_iter() {
+        match key.as_str() {
+            "season" => {
+                season = value.as_i64().map(|season| season as i32);
+            }
+            "startingSeason" => {
+                starting_season = value.as_i64().map(|season| season as i32);
+            }
+            "phase" => {
+                phase = to_phase(&value, &mut warnings);
+            }
+            "tiebreakers" => {
+                settings.insert(key, map_history_value(value, &tiebreakers_to_string)?);
+            }
+            "playIn" => {
+                settings.insert("playInType".to_string(), map_history_value(value, &play_in_to_type)?);
+            }
+            "draftType" => {
+                if history_values(&value).iter().all(|draft_type| draft_type.as_str().map(|draft_type| DRAFT_TYPES.contains(&draft_type)) == Some(true)) {
+                    settings.insert(key, value);
+                } else {
+                    warnings.push(format!("gameAttributes.draftType: {} is not supported, using nba2019", value));
+                }
+            }
+            "salaryCapType" => {
+                if history_values(&value).iter().all(|salary_cap_type| salary_cap_type.as_str().map(|salary_cap_type| SALARY_CAP_TYPES.contains(&salary_cap_type)) == Some(true)) {
+                    settings.insert(key, value);
+                } else {
+                    warnings.push(format!("gameAttributes.salaryCapType: {} is not supported, using soft", value));
+                }
+            }
+            _ => {
+                if SETTINGS_KEYS.contains(&key.as_str()) {
+                    settings.insert(key, value);
+                } else {
+                    warnings.push(format!("gameAttributes.{}: not supported, ignored", key));
+                }
+            }
+        }
+    }
+
+    let settings_file: Result<LeagueSettingsFile, serde_json::Error> = serde_json::from_value(Value::Object(settings));
+    if settings_file.is_err() {
+        return Err(format!("gameAttributes: {}", settings_file.err().unwrap()));
+    }
+    let game_attributes = settings_file.unwrap().to_game_attributes()?;
+
+    if season.is_none() {
+        season = file.team_seasons.iter().map(|ts| ts.season).max();
+    }
+    if season.is_none() {
+        season = starting_season;
+    }
+    if season.is_none() {
+        return Err("gameAttributes.season: missing, and it can't be determined from teamSeasons".to_string());
+    }
+    let season = season.unwrap();
+    let g = G::new(game_attributes, season)?;
+
+    let teams: Vec<Team> = file.teams.iter().map(|t| Team {
+        tid: t.tid,
+        cid: t.cid,
+        did: t.did,
+        region: t.region.clone(),
+        name: t.name.clone(),
+        abbrev: t.abbrev.clone(),
+        pop: t.pop,
+        coaching_rank: None
+    }).collect();
+
+    let mut games = Vec::new();
+    for game in file.games.iter() {
+        if game.teams.len() != 2 {
+            warnings.push(format!("games: game {} does not have 2 teams, ignored", game.gid));
+            continue;
+        }
+        games.push(GameResult {
+            gid: game.gid,
+            season: game.season,
+            playoffs: game.playoffs,
+            overtimes: game.overtimes,
+            teams: [
+                GameResultTeam {
+                    tid: game.teams[0].tid,
+                    pts: game.teams[0].pts
+                },
+                GameResultTeam {
+                    tid: game.teams[1].tid,
+                    pts: game.teams[1].pts
+                }
+            ]
+        });
+    }
+
+    let mut team_seasons = Vec::new();
+    for ts in file.team_seasons.iter() {
+        let t = teams.iter().find(|t| t.tid == ts.tid);
+        if t.is_none() && (ts.cid.is_none() || ts.did.is_none()) {
+            warnings.push(format!("teamSeasons: unknown team {} in {}, ignored", ts.tid, ts.season));
+            continue;
+        }
+        let cid = ts.cid.unwrap_or_else(|| t.unwrap().cid);
+        let did = ts.did.unwrap_or_else(|| t.unwrap().did);
+        let mut team_season = TeamSeasonAttrs::new(ts.tid, ts.season, cid, did);
+        team_season.won = ts.won;
+        team_season.lost = ts.lost;
+        team_season.tied = ts.tied;
+        team_season.otl = ts.otl;
+        team_season.gp = ts.won + ts.lost + ts.tied + ts.otl;
+        // Old games are usually deleted from BBGM leagues, so points are only known for recent seasons
+        for game in games.iter() {
+            if game.season != ts.season || game.playoffs {
+                continue;
+            }
+            for t in 0..2 {
+                if game.teams[t].tid == ts.tid {
+                    team_season.pts += game.teams[t].pts;
+                    team_season.opp_pts += game.teams[1 - t].pts;
+                }
+            }
+        }
+        team_seasons.push(team_season);
+    }
+
+    let mut players = Vec::new();
+    for p in file.players.into_iter() {
+        if p.ratings.is_empty() {
+            warnings.push(format!("players: player {} has no ratings, ignored", p.pid));
+            continue;
+        }
+        players.push(to_player(p, &g)?);
+    }
+
+    let mut draft_picks = Vec::new();
+    for dp in file.draft_picks.iter() {
+        if !dp.season.is_i64() {
+            warnings.push(format!("draftPicks: pick {} is for a fantasy draft, ignored", dp.dpid));
+            continue;
+        }
+        if !teams.iter().any(|t| t.tid == dp.tid) || !teams.iter().any(|t| t.tid == dp.original_tid) {
+            warnings.push(format!("draftPicks: pick {} belongs to an unknown team, ignored", dp.dpid));
+            continue;
+        }
+        draft_picks.push(DraftPick {
+            dpid: dp.dpid,
+            tid: dp.tid,
+            original_tid: dp.original_tid,
+            round: dp.round,
+            pick: dp.pick,
+            season: dp.season.as_i64().unwrap() as i32
+        });
+    }
+
+    let events = file.events.iter().map(|event| Event {
+        eid: event.eid,
+        event_type: event.event_type.clone(),
+        season: event.season,
+        text: event.text.clone(),
+        tids: event.tids.clone(),
+        pids: event.pids.clone(),
+        dpids: event.dpids.clone()
+    }).collect();
+
+    let awards = file.awards.iter().map(|awards| SeasonAwards {
+        season: awards.season,
+        mvp: awards.mvp.clone(),
+        dpoy: awards.dpoy.clone(),
+        roy: awards.roy.clone(),
+        smoy: awards.smoy.clone(),
+        mip: awards.mip.clone(),
+        finals_mvp: awards.finals_mvp.clone(),
+        all_league: awards.all_league.clone(),
+        all_defensive: awards.all_defensive.clone()
+    }).collect();
+
+    let league = League {
+        g,
+        starting_season: starting_season.unwrap_or(season),
+        phase,
+        teams,
+        players,
+        games,
+        team_seasons,
+        draft_picks,
+        events,
+        awards
+    };
+
+    Ok((league, warnings))
+}
\ No newline at end of file
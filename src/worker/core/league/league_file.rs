@@ -0,0 +1,278 @@
+use crate::common::types::AwardPlayer;
+use crate::common::types::AwardTeam;
+use crate::common::types::PlayerAward;
+use crate::common::types::PlayerInjury;
+use crate::common::types::PlayerRatings;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Map;
+use serde_json::Value;
+
+// The parts of the original BBGM JSON league format that are supported. Anything else in the file ends up in the
+// extra maps, so the importer can report it.
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct LeagueFileTeam {
+    pub tid: i32,
+    pub cid: u8,
+    pub did: u8,
+    #[serde(default)]
+    pub region: String,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub abbrev: String,
+    #[serde(default)]
+    pub pop: f64,
+    #[serde(flatten, skip_serializing)]
+    pub extra: Map<String, Value>
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct LeagueFileBorn {
+    pub year: i32,
+    #[serde(default)]
+    pub loc: String
+}
+
+// BBGM also stores the ratings at the time of the draft here, which are left out
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LeagueFileDraft {
+    #[serde(default)]
+    pub round: i32,
+    #[serde(default)]
+    pub pick: i32,
+    #[serde(default = "default_draft_tid")]
+    pub tid: i32,
+    #[serde(default = "default_draft_tid")]
+    pub original_tid: i32,
+    pub year: i32
+}
+
+fn default_draft_tid() -> i32 {
+    -1
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct LeagueFileContract {
+    pub amount: f64,
+    pub exp: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rookie: Option<bool>,
+    #[serde(flatten, skip_serializing)]
+    pub extra: Map<String, Value>
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct LeagueFilePlayerStats {
+    pub season: i32,
+    pub tid: i32,
+    #[serde(default)]
+    pub playoffs: bool,
+    #[serde(default)]
+    pub gp: i32,
+    #[serde(default)]
+    pub gs: i32,
+    #[serde(default)]
+    pub min: f64,
+    #[serde(default)]
+    pub pts: i32,
+    #[serde(default)]
+    pub fg: i32,
+    #[serde(default)]
+    pub fga: i32,
+    #[serde(default)]
+    pub tp: i32,
+    #[serde(default)]
+    pub tpa: i32,
+    #[serde(default)]
+    pub ft: i32,
+    #[serde(default)]
+    pub fta: i32,
+    #[serde(default)]
+    pub orb: i32,
+    #[serde(default)]
+    pub drb: i32,
+    #[serde(default)]
+    pub ast: i32,
+    #[serde(default)]
+    pub stl: i32,
+    #[serde(default)]
+    pub blk: i32,
+    #[serde(default)]
+    pub tov: i32,
+    #[serde(default)]
+    pub pf: i32,
+    #[serde(flatten, skip_serializing)]
+    pub extra: Map<String, Value>
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LeagueFilePlayer {
+    pub pid: i32,
+    pub tid: i32,
+    #[serde(default)]
+    pub first_name: String,
+    #[serde(default)]
+    pub last_name: String,
+    pub born: LeagueFileBorn,
+    // Inches
+    #[serde(default)]
+    pub hgt: f64,
+    // Pounds
+    #[serde(default)]
+    pub weight: f64,
+    #[serde(default)]
+    pub college: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub draft: Option<LeagueFileDraft>,
+    pub ratings: Vec<PlayerRatings>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub injury: Option<PlayerInjury>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value_no_pot: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contract: Option<LeagueFileContract>,
+    #[serde(default)]
+    pub stats: Vec<LeagueFilePlayerStats>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pt_modifier: Option<f64>,
+    #[serde(default)]
+    pub awards: Vec<PlayerAward>,
+    #[serde(flatten, skip_serializing)]
+    pub extra: Map<String, Value>
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct LeagueFileGameTeam {
+    pub tid: i32,
+    pub pts: i32,
+    #[serde(flatten, skip_serializing)]
+    pub extra: Map<String, Value>
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct LeagueFileGame {
+    pub gid: i32,
+    pub season: i32,
+    #[serde(default)]
+    pub playoffs: bool,
+    #[serde(default)]
+    pub overtimes: i32,
+    // teams[0] is the home team
+    pub teams: Vec<LeagueFileGameTeam>,
+    #[serde(flatten, skip_serializing)]
+    pub extra: Map<String, Value>
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct LeagueFileTeamSeason {
+    pub tid: i32,
+    pub season: i32,
+    #[serde(default)]
+    pub won: i32,
+    #[serde(default)]
+    pub lost: i32,
+    #[serde(default)]
+    pub tied: i32,
+    #[serde(default)]
+    pub otl: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cid: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub did: Option<u8>,
+    #[serde(flatten, skip_serializing)]
+    pub extra: Map<String, Value>
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LeagueFileDraftPick {
+    pub dpid: i32,
+    pub tid: i32,
+    pub original_tid: i32,
+    pub round: i32,
+    #[serde(default)]
+    pub pick: i32,
+    // BBGM uses "fantasy" for fantasy drafts, which aren't supported
+    pub season: Value,
+    #[serde(flatten, skip_serializing)]
+    pub extra: Map<String, Value>
+}
+
+// BBGM's text has links to players and teams in HTML
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LeagueFileEvent {
+    pub eid: i32,
+    #[serde(rename = "type")]
+    pub event_type: String,
+    pub season: i32,
+    #[serde(default)]
+    pub text: String,
+    #[serde(default)]
+    pub tids: Vec<i32>,
+    #[serde(default)]
+    pub pids: Vec<i32>,
+    #[serde(default)]
+    pub dpids: Vec<i32>,
+    #[serde(flatten, skip_serializing)]
+    pub extra: Map<String, Value>
+}
+
+// BBGM also has awards like bestRecord and allRookie, which aren't supported
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LeagueFileAwards {
+    pub season: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mvp: Option<AwardPlayer>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dpoy: Option<AwardPlayer>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub roy: Option<AwardPlayer>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub smoy: Option<AwardPlayer>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mip: Option<AwardPlayer>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub finals_mvp: Option<AwardPlayer>,
+    #[serde(default)]
+    pub all_league: Vec<AwardTeam>,
+    #[serde(default)]
+    pub all_defensive: Vec<AwardTeam>,
+    #[serde(flatten, skip_serializing)]
+    pub extra: Map<String, Value>
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LeagueFile {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub starting_season: Option<i32>,
+    // Either {key: value} or, in older files, [{key, value}]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub game_attributes: Option<Value>,
+    #[serde(default)]
+    pub teams: Vec<LeagueFileTeam>,
+    #[serde(default)]
+    pub players: Vec<LeagueFilePlayer>,
+    #[serde(default)]
+    pub games: Vec<LeagueFileGame>,
+    #[serde(default)]
+    pub team_seasons: Vec<LeagueFileTeamSeason>,
+    #[serde(default)]
+    pub draft_picks: Vec<LeagueFileDraftPick>,
+    #[serde(default)]
+    pub events: Vec<LeagueFileEvent>,
+    #[serde(default)]
+    pub awards: Vec<LeagueFileAwards>,
+    #[serde(flatten, skip_serializing)]
+    pub extra: Map<String, Value>
+}
\ No newline at end of file
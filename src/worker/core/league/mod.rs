@@ -0,0 +1,28 @@
+pub mod league_file;
+pub mod import_league;
+pub mod export_league;
+pub mod gen_random_players;
+
+use crate::common::types::DraftPick;
+use crate::common::types::Event;
+use crate::common::types::SeasonAwards;
+use crate::common::types::GameResult;
+use crate::common::types::Player;
+use crate::common::types::Team;
+use crate::common::types::TeamSeasonAttrs;
+use crate::worker::util::g::G;
+
+// Everything in a league that is not a setting. Season data is kept for every season, not just g.season().
+pub struct League {
+    pub g: G,
+    pub starting_season: i32,
+    // One of the PHASE constants
+    pub phase: i32,
+    pub teams: Vec<Team>,
+    pub players: Vec<Player>,
+    pub games: Vec<GameResult>,
+    pub team_seasons: Vec<TeamSeasonAttrs>,
+    pub draft_picks: Vec<DraftPick>,
+    pub events: Vec<Event>,
+    pub awards: Vec<SeasonAwards>
+}
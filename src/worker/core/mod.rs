@@ -1 +1,14 @@
-pub mod GameSim_basketball;
\ No newline at end of file
+// Ported from zengm's GameSim.basketball, keeping its folder name and its long argument lists
+#[allow(non_snake_case, clippy::too_many_arguments)]
+pub mod GameSim_basketball;
+pub mod season;
+pub mod player;
+pub mod league;
+pub mod game;
+pub mod draft;
+pub mod finances;
+pub mod team;
+pub mod trade;
+pub mod free_agents;
+pub mod phase;
+pub mod expansion_draft;
\ No newline at end of file
@@ -0,0 +1,47 @@
+use crate::common::constants::phase_text;
+use crate::common::constants::PHASE_DRAFT;
+use crate::common::constants::PHASE_DRAFT_LOTTERY;
+use crate::common::constants::PHASE_FREE_AGENCY;
+use crate::common::constants::PHASE_PLAYOFFS;
+use crate::common::constants::PHASE_REGULAR_SEASON;
+use crate::common::constants::PHASE_RESIGN_PLAYERS;
+use crate::worker::core::game::play::get_season_state;
+use crate::worker::core::game::play::SeasonState;
+use crate::worker::db::Tx;
+
+// The stored phase, or for leagues from before phases were stored, the phase their schedule, playoffs, draft and free
+// agency add up to
+pub fn get_phase(tx: &Tx) -> Result<i32, String> {
+    let phase = tx.get_phase()?;
+    if let Some(phase) = phase {
+        return Ok(phase);
+    }
+    let g = tx.get_g()?;
+    Ok(match get_season_state(tx)? {
+        SeasonState::RegularSeason => PHASE_REGULAR_SEASON,
+        SeasonState::Playoffs => PHASE_PLAYOFFS,
+        SeasonState::Done(_) => {
+            if tx.get_last_draft_season()? == Some(g.season()) {
+                if tx.get_free_agency_days_left()?.is_some() { PHASE_FREE_AGENCY } else { PHASE_RESIGN_PLAYERS }
+            } else if tx.get_draft_picks(g.season())?.iter().any(|dp| dp.pick > 0) {
+                PHASE_DRAFT
+            } else {
+                PHASE_DRAFT_LOTTERY
+            }
+        }
+    })
+}
+
+// Errors unless the league is in one of phases, with action saying what can't be done, like "make trades"
+pub fn check_phase(tx: &Tx, phases: &[i32], action: &str) -> Result<i32, String> {
+    let phase = get_phase(tx)?;
+    if !phases.contains(&phase) {
+        return Err(format!("Can't {} in the current phase ({})", action, phase_text(phase)));
+    }
+    Ok(phase)
+}
+
+// After the playoffs, contracts that are signed start next season
+pub fn is_offseason(phase: i32) -> bool {
+    !(0..=PHASE_PLAYOFFS).contains(&phase)
+}
\ No newline at end of file
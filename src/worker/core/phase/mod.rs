@@ -0,0 +1,3 @@
+pub mod get_phase;
+pub mod new_phase;
+pub mod new_phase_preseason;
\ No newline at end of file
@@ -0,0 +1,231 @@
+use crate::common::constants::phase_text;
+use crate::common::constants::PHASE_AFTER_TRADE_DEADLINE;
+use crate::common::constants::PHASE_DRAFT;
+use crate::common::constants::PHASE_DRAFT_LOTTERY;
+use crate::common::constants::PHASE_EXPANSION_DRAFT;
+use crate::common::constants::PHASE_FREE_AGENCY;
+use crate::common::constants::PHASE_PLAYOFFS;
+use crate::common::constants::PHASE_PRESEASON;
+use crate::common::constants::PHASE_REGULAR_SEASON;
+use crate::common::constants::PHASE_RESIGN_PLAYERS;
+use crate::common::types::Event;
+use crate::worker::core::draft::gen_order::gen_order;
+use crate::worker::core::expansion_draft::run_picks::get_expansion_teams;
+use crate::worker::core::expansion_draft::run_picks::run_picks;
+use crate::worker::core::finances::assess_payroll_min_luxury::assess_payroll_min_luxury;
+use crate::worker::core::free_agents::start_free_agency::start_free_agency;
+use crate::worker::core::game::play::start_regular_season;
+use crate::worker::core::phase::get_phase::get_phase;
+use crate::worker::core::phase::new_phase_preseason::new_phase_preseason;
+use crate::worker::core::player::decide_options::decide_options;
+use crate::worker::core::season::do_awards::do_awards;
+use crate::worker::core::season::gen_playoff_series::gen_playoff_series;
+use crate::worker::core::season::get_standings::get_standings;
+use crate::worker::core::season::new_schedule_playoffs_day::get_champion;
+use crate::worker::db::Tx;
+use crate::worker::util::random::coin_flip_seed;
+
+// Every allowed (from, to) pair. The expansion draft happens between the playoffs and the draft.
+const TRANSITIONS: [(i32, i32); 11] = [
+    (PHASE_PRESEASON, PHASE_REGULAR_SEASON),
+    (PHASE_REGULAR_SEASON, PHASE_AFTER_TRADE_DEADLINE),
+    (PHASE_REGULAR_SEASON, PHASE_PLAYOFFS),
+    (PHASE_AFTER_TRADE_DEADLINE, PHASE_PLAYOFFS),
+    (PHASE_PLAYOFFS, PHASE_DRAFT_LOTTERY),
+    (PHASE_DRAFT_LOTTERY, PHASE_EXPANSION_DRAFT),
+    (PHASE_EXPANSION_DRAFT, PHASE_DRAFT_LOTTERY),
+    (PHASE_DRAFT_LOTTERY, PHASE_DRAFT),
+    (PHASE_DRAFT, PHASE_RESIGN_PLAYERS),
+    (PHASE_RESIGN_PLAYERS, PHASE_FREE_AGENCY),
+    (PHASE_FREE_AGENCY, PHASE_PRESEASON),
+];
+
+pub fn can_transition(from: i32, to: i32) -> bool {
+    TRANSITIONS.contains(&(from, to))
+}
+
+// Where --next goes from phase, if anywhere. The regular season, playoffs and draft end on their own once every game
+// or pick is done.
+pub fn get_next_phase(phase: i32) -> Option<i32> {
+    match phase {
+        PHASE_PRESEASON => Some(PHASE_REGULAR_SEASON),
+        PHASE_DRAFT_LOTTERY => Some(PHASE_DRAFT),
+        PHASE_EXPANSION_DRAFT => Some(PHASE_DRAFT_LOTTERY),
+        PHASE_RESIGN_PLAYERS => Some(PHASE_FREE_AGENCY),
+        PHASE_FREE_AGENCY => Some(PHASE_PRESEASON),
+        _ => None,
+    }
+}
+
+// The playoff series are seeded from the final standings
+fn new_phase_playoffs(tx: &Tx) -> Result<(), String> {
+    if !tx.get_schedule()?.is_empty() {
+        return Err("The playoffs start once every regular season game is played".to_string());
+    }
+    let g = tx.get_g()?;
+    if tx.get_playoff_series(g.season())?.is_none() {
+        let standings = get_standings(&tx.get_team_seasons(g.season())?, &tx.get_games(g.season())?, &g, coin_flip_seed(tx.get_seed()?, g.season()));
+        tx.put_playoff_series(&gen_playoff_series(&standings, &g)?)?;
+    }
+    Ok(())
+}
+
+// Once the title is decided, the season's awards are given out, payrolls are assessed and the options on next
+// season's contracts are decided
+fn new_phase_draft_lottery(tx: &Tx) -> Result<(), String> {
+    let g = tx.get_g()?;
+    let playoff_series = tx.get_playoff_series(g.season())?;
+    if playoff_series.is_none() || get_champion(playoff_series.as_ref().unwrap(), g.num_games_playoff_series()).is_none() {
+        return Err(format!("The {} playoffs aren't over", g.season()));
+    }
+    do_awards(tx)?;
+    assess_payroll_min_luxury(tx, g.season())?;
+    decide_options(tx, g.season())?;
+    Ok(())
+}
+
+// Sets the draft order, unless the lottery already did
+fn new_phase_draft(tx: &Tx) -> Result<(), String> {
+    let g = tx.get_g()?;
+    if tx.get_draft_picks(g.season())?.iter().any(|dp| dp.pick == 0) {
+        gen_order(tx)?;
+    }
+    Ok(())
+}
+
+fn new_phase_resign_players(tx: &Tx) -> Result<(), String> {
+    let g = tx.get_g()?;
+    if tx.get_last_draft_season()? != Some(g.season()) {
+        return Err(format!("The {} draft isn't over", g.season()));
+    }
+    Ok(())
+}
+
+fn new_phase_expansion_draft(tx: &Tx) -> Result<(), String> {
+    if get_expansion_teams(tx)?.is_empty() {
+        return Err("The expansion draft needs at least one expansion team".to_string());
+    }
+    Ok(())
+}
+
+// Moves the league to phase, running what happens on the way: development in the preseason, the schedule for the
+// regular season, the playoff series, awards, payrolls and options after the playoffs, the draft order, the expansion picks,
+// and expiring contracts when free agency starts. Returns the transactions made along the way.
+pub fn new_phase(tx: &Tx, phase: i32) -> Result<Vec<Event>, String> {
+    let from = get_phase(tx)?;
+    if !can_transition(from, phase) {
+        return Err(format!("Can't go from the {} phase to the {} phase", phase_text(from), phase_text(phase)));
+    }
+
+    let mut events = Vec::new();
+    match phase {
+        PHASE_PRESEASON => {
+            events = new_phase_preseason(tx)?;
+        }
+        PHASE_REGULAR_SEASON => {
+            start_regular_season(tx)?;
+        }
+        PHASE_PLAYOFFS => {
+            new_phase_playoffs(tx)?;
+        }
+        PHASE_DRAFT_LOTTERY => {
+            if from == PHASE_EXPANSION_DRAFT {
+                events = run_picks(tx)?;
+            } else {
+                new_phase_draft_lottery(tx)?;
+            }
+        }
+        PHASE_DRAFT => {
+            new_phase_draft(tx)?;
+        }
+        PHASE_RESIGN_PLAYERS => {
+            new_phase_resign_players(tx)?;
+        }
+        PHASE_FREE_AGENCY => {
+            events = start_free_agency(tx)?;
+        }
+        PHASE_EXPANSION_DRAFT => {
+            new_phase_expansion_draft(tx)?;
+        }
+        _ => {}
+    }
+    tx.put_phase(phase)?;
+    Ok(events)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::constants::PHASES;
+    use crate::common::types::Team;
+    use crate::worker::core::league::gen_random_players::gen_random_players;
+    use crate::worker::core::league::League;
+    use crate::worker::core::player::gen_ratings::TalentDistribution;
+    use crate::worker::db::LeagueDb;
+    use crate::worker::util::g::G;
+
+    // Two teams at the start of the regular season
+    fn new_league_db() -> LeagueDb {
+        let g = G::from_defaults(2025).unwrap();
+        let teams: Vec<Team> = (0..2).map(|tid| Team {
+            tid,
+            cid: 0,
+            did: 0,
+            region: format!("Region {}", tid),
+            name: format!("Team {}", tid),
+            abbrev: format!("T{}", tid),
+            pop: 1.0,
+            coaching_rank: None
+        }).collect();
+        let players = gen_random_players(&teams, &g, &TalentDistribution::default(), 1).unwrap();
+        let mut db = LeagueDb::open_in_memory().unwrap();
+        db.transaction(|tx| {
+            tx.put_league(&League {
+                g,
+                starting_season: 2025,
+                phase: PHASE_REGULAR_SEASON,
+                teams,
+                players,
+                games: Vec::new(),
+                team_seasons: Vec::new(),
+                draft_picks: Vec::new(),
+                events: Vec::new(),
+                awards: Vec::new()
+            })?;
+            tx.put_seed(1)?;
+            start_regular_season(tx)
+        }).unwrap();
+        db
+    }
+
+    #[test]
+    fn next_phases_are_allowed() {
+        for phase in PHASES.iter() {
+            let next = get_next_phase(*phase);
+            if let Some(next) = next {
+                assert!(can_transition(*phase, next), "{}", phase_text(*phase));
+            }
+        }
+        assert!(!can_transition(PHASE_PRESEASON, PHASE_PLAYOFFS));
+        assert!(!can_transition(PHASE_DRAFT, PHASE_DRAFT_LOTTERY));
+        assert!(!can_transition(PHASE_FREE_AGENCY, PHASE_REGULAR_SEASON));
+    }
+
+    #[test]
+    fn illegal_transitions_are_rejected() {
+        let mut db = new_league_db();
+        for phase in [PHASE_PRESEASON, PHASE_DRAFT_LOTTERY, PHASE_DRAFT, PHASE_RESIGN_PLAYERS, PHASE_FREE_AGENCY, PHASE_EXPANSION_DRAFT] {
+            let error = db.transaction(|tx| new_phase(tx, phase)).err().unwrap();
+            assert_eq!(error, format!("Can't go from the regular season phase to the {} phase", phase_text(phase)));
+        }
+        // Allowed, but not until the regular season is over
+        let error = db.transaction(|tx| new_phase(tx, PHASE_PLAYOFFS)).err().unwrap();
+        assert_eq!(error, "The playoffs start once every regular season game is played");
+        assert_eq!(db.transaction(get_phase).unwrap(), PHASE_REGULAR_SEASON);
+
+        db.transaction(|tx| new_phase(tx, PHASE_AFTER_TRADE_DEADLINE)).unwrap();
+        assert_eq!(db.transaction(get_phase).unwrap(), PHASE_AFTER_TRADE_DEADLINE);
+        let error = db.transaction(|tx| new_phase(tx, PHASE_REGULAR_SEASON)).err().unwrap();
+        assert_eq!(error, "Can't go from the regular season, after trade deadline phase to the regular season phase");
+    }
+}
\ No newline at end of file
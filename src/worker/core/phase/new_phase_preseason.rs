@@ -0,0 +1,24 @@
+use crate::common::types::Event;
+use crate::worker::core::draft::gen_picks::gen_picks;
+use crate::worker::core::free_agents::play::play;
+use crate::worker::core::player::develop::develop_players;
+use crate::worker::db::Tx;
+
+// Starts the next season: the days left in free agency are played out, then everyone ages a year and develops, and
+// teams get picks in the draft NUM_SEASONS_WITH_PICKS years away. Returns the signings from the rest of free agency.
+pub fn new_phase_preseason(tx: &Tx) -> Result<Vec<Event>, String> {
+    let mut events = Vec::new();
+    let days_left = tx.get_free_agency_days_left()?.unwrap_or(0);
+    if days_left > 0 {
+        events = play(tx, days_left)?;
+    }
+
+    let mut g = tx.get_g()?;
+    g.set_season(g.season() + 1);
+    tx.put_g(&g)?;
+    tx.put_day(0)?;
+    tx.put_free_agency_days_left(None)?;
+    develop_players(tx, g.season())?;
+    gen_picks(tx)?;
+    Ok(events)
+}
\ No newline at end of file
@@ -0,0 +1,110 @@
+use crate::common::types::BoxScorePlayer;
+use crate::common::types::Player;
+use crate::common::types::PlayerStats;
+
+// Adds a box score line to the player's stats for that season, team and part of the season. A player who didn't get
+// in the game doesn't get a game played.
+pub fn add_game_stats(p: &mut Player, tid: i32, season: i32, playoffs: bool, line: &BoxScorePlayer) {
+    let index = p.stats.iter().position(|ps| ps.season == season && ps.tid == tid && ps.playoffs == playoffs);
+    let index = match index {
+        Some(index) => index,
+        None => {
+            p.stats.push(PlayerStats::new(season, tid, playoffs));
+            p.stats.len() - 1
+        }
+    };
+
+    add_box_score_line(&mut p.stats[index], line);
+}
+
+pub fn add_box_score_line(ps: &mut PlayerStats, line: &BoxScorePlayer) {
+    if line.min > 0.0 {
+        ps.gp += 1;
+    }
+    if line.gs {
+        ps.gs += 1;
+    }
+    ps.min += line.min;
+    ps.pts += line.pts;
+    ps.fg += line.fg;
+    ps.fga += line.fga;
+    ps.tp += line.tp;
+    ps.tpa += line.tpa;
+    ps.ft += line.ft;
+    ps.fta += line.fta;
+    ps.orb += line.orb;
+    ps.drb += line.drb;
+    ps.ast += line.ast;
+    ps.stl += line.stl;
+    ps.blk += line.blk;
+    ps.tov += line.tov;
+    ps.pf += line.pf;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_player() -> Player {
+        serde_json::from_str(r#"{
+            "pid": 0,
+            "tid": 3,
+            "firstName": "First",
+            "lastName": "Last",
+            "born": { "year": 2000, "loc": "USA" },
+            "hgt": 78,
+            "weight": 210,
+            "college": "",
+            "ratings": [],
+            "contract": { "amount": 1000, "exp": 2026 }
+        }"#).unwrap()
+    }
+
+    fn line(min: f64, pts: i32) -> BoxScorePlayer {
+        BoxScorePlayer {
+            pid: 0,
+            name: "First Last".to_string(),
+            pos: "G".to_string(),
+            gs: min > 0.0,
+            min,
+            pts,
+            fg: pts / 2,
+            fga: pts,
+            tp: 0,
+            tpa: 0,
+            ft: 0,
+            fta: 0,
+            orb: 0,
+            drb: 1,
+            ast: 2,
+            stl: 0,
+            blk: 0,
+            tov: 1,
+            pf: 2,
+            wpa: 0.0,
+            injury: None
+        }
+    }
+
+    #[test]
+    fn stats_are_kept_by_season_team_and_playoffs() {
+        let mut p = new_player();
+        assert!(!p.is_injured());
+        assert_eq!(p.age(2025), 25);
+
+        add_game_stats(&mut p, 3, 2025, false, &line(30.0, 20));
+        add_game_stats(&mut p, 3, 2025, false, &line(20.0, 10));
+        // Didn't get in the game
+        add_game_stats(&mut p, 3, 2025, false, &line(0.0, 0));
+        add_game_stats(&mut p, 3, 2025, true, &line(35.0, 25));
+        add_game_stats(&mut p, 4, 2025, false, &line(10.0, 4));
+
+        assert_eq!(p.stats.len(), 3);
+        let regular_season = &p.stats[0];
+        assert_eq!((regular_season.tid, regular_season.playoffs), (3, false));
+        assert_eq!((regular_season.gp, regular_season.gs, regular_season.pts, regular_season.fg), (2, 2, 30, 15));
+        assert_eq!(regular_season.min, 50.0);
+        assert_eq!((p.stats[1].playoffs, p.stats[1].gp, p.stats[1].pts), (true, 1, 25));
+        assert_eq!((p.stats[2].tid, p.stats[2].gp, p.stats[2].pts), (4, 1, 4));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,8 @@
+use crate::common::types::Player;
+
+// Copies the latest ratings to a new row for season, for develop to change over the offseason
+pub fn add_ratings_row(p: &mut Player, season: i32) {
+    let mut ratings = p.ratings.last().unwrap().clone();
+    ratings.season = season;
+    p.ratings.push(ratings);
+}
\ No newline at end of file
@@ -0,0 +1,46 @@
+use crate::common::constants::COMPOSITE_WEIGHTS;
+use crate::common::types::PlayerRatings;
+use std::collections::HashMap;
+
+// Weighted average of raw ratings, scaled to 0-1. Components that aren't ratings have to be numbers.
+pub fn composite_rating(ratings: &PlayerRatings, components: &[&str], weights: &[f64]) -> Result<f64, String> {
+    let mut rating = 0.0;
+    let mut divisor = 0.0;
+    for i in 0..components.len() {
+        let value = match ratings.get(components[i]) {
+            Some(value) => value,
+            None => components[i]
+                .parse::<f64>()
+                .map_err(|_| format!("Composite rating component \"{}\" is not a rating or a number", components[i]))?,
+        };
+        rating += weights[i] * value;
+        divisor += weights[i].abs();
+    }
+    Ok(rating / divisor / 100.0)
+}
+
+pub fn get_composite_ratings(ratings: &PlayerRatings) -> Result<HashMap<String, f64>, String> {
+    let mut composite_ratings = HashMap::new();
+    for (name, components, weights) in COMPOSITE_WEIGHTS.iter() {
+        composite_ratings.insert(name.to_string(), composite_rating(ratings, components, weights)?);
+    }
+    Ok(composite_ratings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::worker::core::player::gen_ratings::gen_ratings;
+    use crate::worker::core::player::gen_ratings::TalentDistribution;
+    use crate::worker::util::random::rng_from_seed;
+
+    #[test]
+    fn components_are_ratings_or_numbers() {
+        let (ratings, _) = gen_ratings(2025, &TalentDistribution::default(), &mut rng_from_seed(1));
+        assert_eq!(composite_rating(&ratings, &["hgt", "50"], &[1.0, 1.0]).unwrap(), (ratings.hgt + 50.0) / 200.0);
+        assert_eq!(
+            composite_rating(&ratings, &["height"], &[1.0]).unwrap_err(),
+            "Composite rating component \"height\" is not a rating or a number"
+        );
+    }
+}
\ No newline at end of file
@@ -0,0 +1,35 @@
+use crate::common::types::Player;
+use crate::worker::core::player::gen_contract::gen_contract;
+use crate::worker::db::Tx;
+use crate::worker::util::g::G;
+
+// Whether the option season of p's contract is kept. Teams keep players who would ask for at least as much on a new
+// contract, and players stay unless they would get more as free agents.
+pub fn keeps_option(p: &Player, g: &G) -> bool {
+    let asking_amount = gen_contract(p, g, g.season() + 1).amount;
+    if p.contract.option == "player" {
+        return asking_amount <= p.contract.amount;
+    }
+    asking_amount >= p.contract.amount
+}
+
+// After season, decides the options on contracts that end next season. A declined option makes season the last
+// one of the contract. Returns the players with options and whether each one was kept.
+pub fn decide_options(tx: &Tx, season: i32) -> Result<Vec<(Player, bool)>, String> {
+    let mut g = tx.get_g()?;
+    g.set_season(season);
+    let mut decisions = Vec::new();
+    for mut p in tx.get_players()? {
+        if p.tid < 0 || p.contract.option.is_empty() || p.contract.exp != season + 1 {
+            continue;
+        }
+        let kept = keeps_option(&p, &g);
+        if !kept {
+            p.contract.exp = season;
+        }
+        p.contract.option = String::new();
+        tx.put_player(&p)?;
+        decisions.push((p, kept));
+    }
+    Ok(decisions)
+}
\ No newline at end of file
@@ -0,0 +1,372 @@
+use crate::common::constants::PLAYER_RETIRED;
+use crate::common::constants::POSITIONS;
+use crate::common::types::Player;
+use crate::common::types::PlayerRatings;
+use crate::worker::core::player::add_ratings_row::add_ratings_row;
+use crate::worker::core::player::ovr::ovr;
+use crate::worker::core::player::pos::pos;
+use crate::worker::core::player::pot_estimator::pot_estimator;
+use crate::worker::core::player::skills::skills;
+use crate::worker::core::player::value::update_values;
+use crate::worker::db::Tx;
+use crate::worker::util::helpers;
+use crate::worker::util::random::gauss;
+use crate::worker::util::random::progression_seed;
+use crate::worker::util::random::rng_from_seed;
+use rand::rngs::StdRng;
+use rand::Rng;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+
+struct RatingFormula {
+    key: &'static str,
+    // Added to the base change, so athletic ratings start declining earlier than skills
+    age_modifier: fn(i32) -> f64,
+    change_limits: fn(i32) -> (f64, f64)
+}
+
+fn no_age_modifier(_age: i32) -> f64 {
+    0.0
+}
+
+fn no_change_limits(_age: i32) -> (f64, f64) {
+    (f64::NEG_INFINITY, f64::INFINITY)
+}
+
+fn spd_age_modifier(age: i32) -> f64 {
+    if age <= 27 {
+        return 0.0;
+    }
+    if age <= 31 {
+        return -2.0;
+    }
+    -3.0
+}
+
+fn jmp_age_modifier(age: i32) -> f64 {
+    if age <= 27 {
+        return 0.0;
+    }
+    if age <= 31 {
+        return -3.0;
+    }
+    -4.0
+}
+
+fn athletic_change_limits(_age: i32) -> (f64, f64) {
+    (-12.0, 2.0)
+}
+
+fn endu_age_modifier(age: i32) -> f64 {
+    if age <= 23 {
+        return 2.0;
+    }
+    if age <= 30 {
+        return 0.0;
+    }
+    if age <= 35 {
+        return -2.0;
+    }
+    if age <= 40 {
+        return -4.0;
+    }
+    -8.0
+}
+
+fn endu_change_limits(_age: i32) -> (f64, f64) {
+    (-11.0, 19.0)
+}
+
+fn shooting_age_modifier(age: i32) -> f64 {
+    if age <= 27 {
+        return 0.0;
+    }
+    if age <= 29 {
+        return 0.5;
+    }
+    1.0
+}
+
+fn shooting_change_limits(_age: i32) -> (f64, f64) {
+    (-3.0, 13.0)
+}
+
+// Basketball IQ keeps improving after the body starts to decline
+fn iq_age_modifier(age: i32) -> f64 {
+    if age <= 21 {
+        return 2.0;
+    }
+    if age <= 23 {
+        return 1.0;
+    }
+    if age <= 27 {
+        return 0.0;
+    }
+    if age <= 29 {
+        return 0.5;
+    }
+    1.0
+}
+
+fn iq_change_limits(age: i32) -> (f64, f64) {
+    if age > 24 {
+        return (-3.0, 9.0);
+    }
+    (-3.0, 7.0 + 5.0 * (24 - age) as f64)
+}
+
+fn skill_age_modifier(age: i32) -> f64 {
+    if age <= 27 {
+        return 0.0;
+    }
+    if age <= 29 {
+        return 0.5;
+    }
+    1.5
+}
+
+fn skill_change_limits(_age: i32) -> (f64, f64) {
+    (-2.0, 5.0)
+}
+
+const RATING_FORMULAS: [RatingFormula; 14] = [
+    RatingFormula { key: "stre", age_modifier: no_age_modifier, change_limits: no_change_limits },
+    RatingFormula { key: "spd", age_modifier: spd_age_modifier, change_limits: athletic_change_limits },
+    RatingFormula { key: "jmp", age_modifier: jmp_age_modifier, change_limits: athletic_change_limits },
+    RatingFormula { key: "endu", age_modifier: endu_age_modifier, change_limits: endu_change_limits },
+    RatingFormula { key: "dnk", age_modifier: shooting_age_modifier, change_limits: shooting_change_limits },
+    RatingFormula { key: "ins", age_modifier: shooting_age_modifier, change_limits: shooting_change_limits },
+    RatingFormula { key: "ft", age_modifier: shooting_age_modifier, change_limits: shooting_change_limits },
+    RatingFormula { key: "fg", age_modifier: shooting_age_modifier, change_limits: shooting_change_limits },
+    RatingFormula { key: "tp", age_modifier: shooting_age_modifier, change_limits: shooting_change_limits },
+    RatingFormula { key: "oiq", age_modifier: iq_age_modifier, change_limits: iq_change_limits },
+    RatingFormula { key: "diq", age_modifier: iq_age_modifier, change_limits: iq_change_limits },
+    RatingFormula { key: "drb", age_modifier: skill_age_modifier, change_limits: skill_change_limits },
+    RatingFormula { key: "pss", age_modifier: skill_age_modifier, change_limits: skill_change_limits },
+    RatingFormula { key: "reb", age_modifier: skill_age_modifier, change_limits: skill_change_limits },
+];
+
+fn limit_rating(rating: f64) -> f64 {
+    helpers::bound(rating.round(), 0.0, 100.0)
+}
+
+// Change for every rating at this age before noise and coaching
+fn get_age_base_change(age: i32) -> f64 {
+    if age <= 21 {
+        return 2.0;
+    }
+    if age <= 25 {
+        return 1.0;
+    }
+    if age <= 27 {
+        return 0.0;
+    }
+    if age <= 29 {
+        return -1.0;
+    }
+    if age <= 31 {
+        return -2.0;
+    }
+    if age <= 34 {
+        return -3.0;
+    }
+    if age <= 40 {
+        return -4.0;
+    }
+    if age <= 43 {
+        return -5.0;
+    }
+    -6.0
+}
+
+// Average of the bounded noise added in calc_base_change, which is positive because the bounds cut off more bad luck
+// than good
+fn get_mean_noise(age: i32) -> f64 {
+    if age <= 25 {
+        return 0.6;
+    }
+    0.33
+}
+
+// Change for every rating at this age, plus noise that's bigger for young players, scaled up for good coaching and
+// down for bad. coaching_rank is 1 for the best coaching and num_teams for the worst.
+fn calc_base_change(age: i32, coaching_rank: f64, num_teams: usize, rng: &mut StdRng) -> f64 {
+    let mut val = get_age_base_change(age);
+
+    if age <= 23 {
+        val += helpers::bound(gauss(rng, 0.0, 5.0), -4.0, 20.0);
+    } else if age <= 25 {
+        val += helpers::bound(gauss(rng, 0.0, 5.0), -4.0, 10.0);
+    } else {
+        val += helpers::bound(gauss(rng, 0.0, 3.0), -2.0, 4.0);
+    }
+
+    let coaching_fraction = if num_teams > 1 { (coaching_rank - 1.0) / (num_teams - 1) as f64 } else { 0.5 };
+    if val >= 0.0 {
+        val *= 1.25 - 0.5 * coaching_fraction;
+    } else {
+        val *= 0.75 + 0.5 * coaching_fraction;
+    }
+    val
+}
+
+// One offseason of development for ratings of a player who is age during the new season
+pub fn develop_season(ratings: &mut PlayerRatings, age: i32, coaching_rank: f64, num_teams: usize, rng: &mut StdRng) {
+    // In young players, height can sometimes increase
+    if age <= 21 {
+        let height_rand: f64 = rng.gen();
+        if height_rand > 0.99 && age <= 20 && ratings.hgt <= 99.0 {
+            ratings.hgt += 1.0;
+        }
+        if height_rand > 0.999 && ratings.hgt <= 99.0 {
+            ratings.hgt += 1.0;
+        }
+    }
+
+    let base_change = calc_base_change(age, coaching_rank, num_teams, rng);
+    for formula in RATING_FORMULAS.iter() {
+        let (min_change, max_change) = (formula.change_limits)(age);
+        let change = helpers::bound((base_change + (formula.age_modifier)(age)) * rng.gen_range(0.4..1.4), min_change, max_change);
+        let rating = ratings.get(formula.key).unwrap();
+        ratings.set(formula.key, limit_rating(rating + change));
+    }
+}
+
+// Average development with average coaching, without rounding, for estimating potential
+pub fn develop_season_expected(ratings: &mut PlayerRatings, age: i32) {
+    let base_change = get_age_base_change(age) + get_mean_noise(age);
+    for formula in RATING_FORMULAS.iter() {
+        let (min_change, max_change) = (formula.change_limits)(age);
+        // 0.9 is the average of the random factor in develop_season
+        let change = helpers::bound((base_change + (formula.age_modifier)(age)) * 0.9, min_change, max_change);
+        let rating = ratings.get(formula.key).unwrap();
+        ratings.set(formula.key, helpers::bound(rating + change, 0.0, 100.0));
+    }
+}
+
+// Recomputes everything derived from the raw ratings of a player who is age that season
+pub fn update_ratings(ratings: &mut PlayerRatings, age: i32) -> Result<(), String> {
+    ratings.ovr = ovr(ratings, None);
+    ratings.ovrs = BTreeMap::new();
+    for pos in POSITIONS.iter() {
+        ratings.ovrs.insert(pos.to_string(), ovr(ratings, Some(pos)));
+    }
+    ratings.pot = pot_estimator(ratings, age);
+    ratings.pos = pos(ratings);
+    ratings.skills = skills(ratings)?;
+    Ok(())
+}
+
+// Develops the player's latest ratings by years seasons. For a new league, the ratings are for a player who is already
+// that age, so the seasons being developed are the ones before it. Otherwise the latest ratings are a copy of last
+// season's made for the new season, see add_ratings_row, and they develop over the offseason before it.
+pub fn develop(p: &mut Player, years: i32, new_league: bool, coaching_rank: f64, num_teams: usize, rng: &mut StdRng) -> Result<(), String> {
+    let born_year = p.born.year;
+    let ratings = p.ratings.last_mut().unwrap();
+    for i in 0..years {
+        let age = if new_league {
+            ratings.season - born_year - (years - 1 - i)
+        } else {
+            ratings.season - born_year + i
+        };
+        develop_season(ratings, age, coaching_rank, num_teams, rng);
+    }
+    update_ratings(ratings, ratings.season - born_year)
+}
+
+// Offseason development for every player who isn't retired, before season. Players whose ratings are already for
+// season are skipped, so running it twice doesn't develop anyone twice. Free agents and draft prospects get average
+// coaching.
+pub fn develop_players(tx: &Tx, season: i32) -> Result<(), String> {
+    let teams = tx.get_teams()?;
+    let num_teams = teams.len();
+    let middle_rank = (num_teams as f64 + 1.0) / 2.0;
+    let mut coaching_ranks = HashMap::new();
+    for t in teams.iter() {
+        coaching_ranks.insert(t.tid, t.coaching_rank.map(|rank| rank as f64).unwrap_or(middle_rank));
+    }
+
+    let seed = tx.get_seed()?;
+    for mut p in tx.get_players()? {
+        if p.tid == PLAYER_RETIRED || p.ratings.is_empty() || p.ratings.last().unwrap().season >= season {
+            continue;
+        }
+        let coaching_rank = coaching_ranks.get(&p.tid).copied().unwrap_or(middle_rank);
+        let mut rng = rng_from_seed(progression_seed(seed, season, p.pid));
+        add_ratings_row(&mut p, season);
+        develop(&mut p, 1, false, coaching_rank, num_teams, &mut rng)?;
+        update_values(&mut p, season);
+        tx.put_player(&p)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_ratings(rating: f64) -> PlayerRatings {
+        let mut ratings: PlayerRatings = serde_json::from_str(r#"{
+            "season": 2025, "hgt": 0, "stre": 0, "spd": 0, "jmp": 0, "endu": 0, "ins": 0, "dnk": 0, "ft": 0, "fg": 0,
+            "tp": 0, "oiq": 0, "diq": 0, "drb": 0, "pss": 0, "reb": 0
+        }"#).unwrap();
+        for formula in RATING_FORMULAS.iter() {
+            ratings.set(formula.key, rating);
+        }
+        ratings.hgt = rating;
+        ratings
+    }
+
+    fn get_total(ratings: &PlayerRatings) -> f64 {
+        RATING_FORMULAS.iter().map(|formula| ratings.get(formula.key).unwrap()).sum()
+    }
+
+    // Average change in the sum of the ratings over many players developed for a season
+    fn get_average_change(age: i32, coaching_rank: f64) -> f64 {
+        let mut total = 0.0;
+        for seed in 0..200 {
+            let mut ratings = new_ratings(50.0);
+            develop_season(&mut ratings, age, coaching_rank, 30, &mut rng_from_seed(seed));
+            total += get_total(&ratings) - get_total(&new_ratings(50.0));
+        }
+        total / 200.0
+    }
+
+    #[test]
+    fn young_players_improve_and_old_ones_decline() {
+        assert!(get_average_change(20, 15.5) > 10.0);
+        assert!(get_average_change(27, 15.5).abs() < get_average_change(20, 15.5));
+        assert!(get_average_change(34, 15.5) < -10.0);
+    }
+
+    #[test]
+    fn coaching_helps() {
+        assert!(get_average_change(20, 1.0) > get_average_change(20, 30.0));
+        // Good coaching slows the decline too
+        assert!(get_average_change(34, 1.0) > get_average_change(34, 30.0));
+    }
+
+    #[test]
+    fn ratings_stay_between_zero_and_hundred() {
+        let mut rng = rng_from_seed(1);
+        let mut young = new_ratings(99.0);
+        let mut old = new_ratings(1.0);
+        for _ in 0..10 {
+            develop_season(&mut young, 19, 1.0, 30, &mut rng);
+            develop_season(&mut old, 40, 30.0, 30, &mut rng);
+        }
+        for formula in RATING_FORMULAS.iter() {
+            assert!(young.get(formula.key).unwrap() <= 100.0);
+            assert!(old.get(formula.key).unwrap() >= 0.0);
+        }
+    }
+
+    #[test]
+    fn same_seed_same_development() {
+        let mut a = new_ratings(50.0);
+        let mut b = new_ratings(50.0);
+        develop_season(&mut a, 22, 10.0, 30, &mut rng_from_seed(5));
+        develop_season(&mut b, 22, 10.0, 30, &mut rng_from_seed(5));
+        assert_eq!(serde_json::to_string(&a).unwrap(), serde_json::to_string(&b).unwrap());
+    }
+}
\ No newline at end of file
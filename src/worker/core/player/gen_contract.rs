@@ -0,0 +1,33 @@
+use crate::common::helpers::bound;
+use crate::common::types::Player;
+use crate::common::types::PlayerContract;
+use crate::worker::util::g::G;
+
+// Value that asks for the minimum, and how fast the asking amount grows above it as a share of the range from
+// min_contract to max_contract per point of value
+pub const MIN_CONTRACT_VALUE: f64 = 46.0;
+pub const AMOUNT_PER_VALUE: f64 = 0.033;
+
+// Players this age or older only want one season at a time
+const MAX_CONTRACT_AGE: i32 = 34;
+
+// What p asks for on a new contract starting in season: an amount from value, rounded to $10k, and a length from
+// age, since young players want long deals and old ones want short ones
+pub fn gen_contract(p: &Player, g: &G, season: i32) -> PlayerContract {
+    let share = (p.value - MIN_CONTRACT_VALUE) * AMOUNT_PER_VALUE;
+    let amount = g.min_contract() + share * (g.max_contract() - g.min_contract());
+    let amount = bound((amount / 10.0).round() * 10.0, g.min_contract(), g.max_contract());
+
+    let years = bound(
+        ((MAX_CONTRACT_AGE - p.age(season)) as f64 / 2.0).ceil(),
+        g.min_contract_length() as f64,
+        g.max_contract_length() as f64
+    ) as i32;
+
+    PlayerContract {
+        amount,
+        exp: season + years - 1,
+        rookie: false,
+        option: String::new()
+    }
+}
\ No newline at end of file
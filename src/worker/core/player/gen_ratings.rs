@@ -0,0 +1,125 @@
+use crate::common::types::PlayerRatings;
+use crate::worker::util::helpers;
+use crate::worker::util::random::gauss;
+use rand::rngs::StdRng;
+use rand::Rng;
+use serde_json::Map;
+use std::collections::BTreeMap;
+
+// Spread of talent among generated players, as a factor on every rating before development. The default gives
+// draft classes like BBGM's.
+pub struct TalentDistribution {
+    pub mean: f64,
+    pub sd: f64
+}
+
+impl Default for TalentDistribution {
+    fn default() -> Self {
+        TalentDistribution {
+            mean: 1.0,
+            sd: 0.07
+        }
+    }
+}
+
+// Ratings of a typical 19 year old prospect, before any factors
+const BASE_RATINGS: [(&str, f64); 14] = [
+    ("stre", 42.0),
+    ("spd", 45.0),
+    ("jmp", 45.0),
+    ("endu", 24.0),
+    ("ins", 32.0),
+    ("dnk", 34.0),
+    ("ft", 37.0),
+    ("fg", 37.0),
+    ("tp", 37.0),
+    ("oiq", 29.0),
+    ("diq", 29.0),
+    ("drb", 42.0),
+    ("pss", 42.0),
+    ("reb", 42.0),
+];
+
+// Ratings that are correlated with each other, so that some players are elite at one thing and not another
+const RATING_GROUPS: [&[&str]; 4] = [
+    &["stre", "spd", "jmp", "endu", "dnk"],
+    &["ft", "fg", "tp"],
+    &["oiq", "diq", "drb", "pss", "reb"],
+    &["ins"],
+];
+
+const TYPE_FACTORS: [(&str, &[(&str, f64)]); 3] = [
+    ("point", &[("spd", 1.15), ("jmp", 1.05), ("drb", 1.3), ("pss", 1.4), ("ft", 1.1), ("fg", 1.1), ("tp", 1.1), ("oiq", 1.1), ("ins", 0.8), ("reb", 0.8), ("stre", 0.9)]),
+    ("wing", &[("spd", 1.1), ("jmp", 1.1), ("dnk", 1.1), ("ft", 1.1), ("fg", 1.1), ("tp", 1.2), ("drb", 1.1)]),
+    ("big", &[("stre", 1.2), ("ins", 1.4), ("dnk", 1.3), ("reb", 1.4), ("diq", 1.1), ("spd", 0.9), ("drb", 0.8), ("pss", 0.85), ("tp", 0.8), ("ft", 0.9)]),
+];
+
+// Tall players are more likely to be bigs and short players to be points, but any type is possible at any height
+fn pick_type(hgt: f64, rng: &mut StdRng) -> &'static str {
+    let prob_big = 0.9 * helpers::sigmoid(hgt, 0.15, 60.0);
+    let prob_point = 0.9 * (1.0 - helpers::sigmoid(hgt, 0.15, 35.0));
+    let r: f64 = rng.gen();
+    if r < prob_big {
+        return "big";
+    }
+    if r < prob_big + prob_point {
+        return "point";
+    }
+    "wing"
+}
+
+fn get_type_factor(player_type: &str, key: &str) -> f64 {
+    let factors = TYPE_FACTORS.iter().find(|(t, _)| *t == player_type).unwrap().1;
+    factors.iter().find(|(k, _)| *k == key).map(|(_, factor)| *factor).unwrap_or(1.0)
+}
+
+// Converts a height rating to inches, where 0 is 5'4" and 100 is 7'9"
+pub fn hgt_to_inches(hgt: f64) -> f64 {
+    64.0 + hgt * (93.0 - 64.0) / 100.0
+}
+
+// Raw ratings for a 19 year old prospect in season, and their height in inches
+pub fn gen_ratings(season: i32, talent: &TalentDistribution, rng: &mut StdRng) -> (PlayerRatings, f64) {
+    let hgt = helpers::bound(gauss(rng, 48.0, 11.0), 0.0, 100.0).round();
+    let inches = (hgt_to_inches(hgt) + gauss(rng, 0.0, 0.75)).round();
+    let player_type = pick_type(hgt, rng);
+    let talent_factor = helpers::bound(gauss(rng, talent.mean, talent.sd), 0.2, 2.0);
+    let group_factors: Vec<f64> = RATING_GROUPS.iter().map(|_| helpers::bound(gauss(rng, 1.0, 0.12), 0.5, 1.3)).collect();
+
+    let mut ratings = PlayerRatings {
+        season,
+        hgt,
+        stre: 0.0,
+        spd: 0.0,
+        jmp: 0.0,
+        endu: 0.0,
+        ins: 0.0,
+        dnk: 0.0,
+        ft: 0.0,
+        fg: 0.0,
+        tp: 0.0,
+        oiq: 0.0,
+        diq: 0.0,
+        drb: 0.0,
+        pss: 0.0,
+        reb: 0.0,
+        pos: String::new(),
+        skills: Vec::new(),
+        ovr: 0.0,
+        pot: 0.0,
+        ovrs: BTreeMap::new(),
+        extra: Map::new()
+    };
+    for (key, base) in BASE_RATINGS.iter() {
+        let group = RATING_GROUPS.iter().position(|group| group.contains(key)).unwrap();
+        // Height makes up for a lack of other ratings inside, and makes it harder to handle the ball
+        let hgt_factor = match *key {
+            "ins" | "dnk" | "reb" => 1.0 + (hgt - 48.0) / 150.0,
+            "spd" | "drb" => 1.0 - (hgt - 48.0) / 300.0,
+            _ => 1.0,
+        };
+        let rating = base * get_type_factor(player_type, key) * group_factors[group] * talent_factor * hgt_factor + gauss(rng, 0.0, 3.0);
+        ratings.set(key, helpers::bound(rating.round(), 0.0, 100.0));
+    }
+    (ratings, inches)
+}
\ No newline at end of file
@@ -0,0 +1,92 @@
+use crate::common::names::CountryNames;
+use crate::common::names::COLLEGES;
+use crate::common::names::NAMES;
+use crate::common::types::Player;
+use crate::common::types::PlayerBorn;
+use crate::common::types::PlayerContract;
+use crate::worker::core::player::gen_contract::gen_contract;
+use crate::common::types::PlayerDraft;
+use crate::common::types::PlayerInjury;
+use crate::worker::core::player::develop::develop;
+use crate::worker::core::player::gen_ratings::gen_ratings;
+use crate::worker::core::player::gen_ratings::TalentDistribution;
+use crate::worker::core::player::value::update_values;
+use crate::worker::util::g::G;
+use crate::worker::util::random::gauss;
+use rand::rngs::StdRng;
+use rand::Rng;
+
+// Age of generated ratings, before they are developed to the player's age
+const BASE_AGE: i32 = 19;
+
+// Share of players from the USA and Canada who went to college
+const COLLEGE_RATE: f64 = 0.9;
+
+fn pick_country(rng: &mut StdRng) -> &'static CountryNames {
+    let total: f64 = NAMES.iter().map(|names| names.weight).sum();
+    let mut r = rng.gen::<f64>() * total;
+    for names in NAMES.iter() {
+        if r < names.weight {
+            return names;
+        }
+        r -= names.weight;
+    }
+    &NAMES[0]
+}
+
+fn pick<'a>(values: &[&'a str], rng: &mut StdRng) -> &'a str {
+    values[rng.gen_range(0..values.len())]
+}
+
+// A random player who is age in g.season(), with ratings developed from a 19 year old prospect's and the contract they
+// would ask for. draft_year is the season of the draft they were or will be in.
+pub fn generate(pid: i32, tid: i32, age: i32, draft_year: i32, g: &G, talent: &TalentDistribution, rng: &mut StdRng) -> Result<Player, String> {
+    let season = g.season();
+    let names = pick_country(rng);
+    let college = if (names.country == "USA" || names.country == "Canada") && rng.gen::<f64>() < COLLEGE_RATE {
+        pick(&COLLEGES, rng).to_string()
+    } else {
+        String::new()
+    };
+
+    let (ratings, hgt) = gen_ratings(season, talent, rng);
+    // Heavier for taller and stronger players
+    let weight = (gauss(rng, 215.0, 12.0) + 7.0 * (hgt - 78.0) + 0.5 * (ratings.stre - 37.0)).round();
+
+    let mut p = Player {
+        pid,
+        tid,
+        first_name: pick(names.first, rng).to_string(),
+        last_name: pick(names.last, rng).to_string(),
+        born: PlayerBorn {
+            year: season - age,
+            loc: names.country.to_string()
+        },
+        hgt,
+        weight,
+        college,
+        draft: PlayerDraft {
+            year: draft_year,
+            ..PlayerDraft::default()
+        },
+        ratings: vec![ratings],
+        contract: PlayerContract {
+            amount: g.min_contract(),
+            exp: season,
+            rookie: false,
+            option: String::new()
+        },
+        injury: PlayerInjury::healthy(),
+        stats: Vec::new(),
+        value: 0.0,
+        value_no_pot: 0.0,
+        pt_modifier: 1.0,
+        awards: Vec::new()
+    };
+
+    // With average coaching
+    develop(&mut p, (age - BASE_AGE).max(0), true, 1.0, 1, rng)?;
+    update_values(&mut p, season);
+    p.contract = gen_contract(&p, g, season);
+    Ok(p)
+}
\ No newline at end of file
@@ -0,0 +1,51 @@
+use crate::common::types::Player;
+use crate::common::types::PlayerStats;
+
+// Stats in season, in the regular season or the playoffs, summed over every team the player was on. tid is the last
+// of those teams.
+pub fn get_season_stats(p: &Player, season: i32, playoffs: bool) -> Option<PlayerStats> {
+    let rows: Vec<&PlayerStats> = p.stats.iter().filter(|ps| ps.season == season && ps.playoffs == playoffs).collect();
+    if rows.is_empty() {
+        return None;
+    }
+    let mut total = PlayerStats::new(season, rows.last().unwrap().tid, playoffs);
+    for ps in rows.iter() {
+        add_stats(&mut total, ps);
+    }
+    Some(total)
+}
+
+pub fn add_stats(total: &mut PlayerStats, ps: &PlayerStats) {
+    total.gp += ps.gp;
+    total.gs += ps.gs;
+    total.min += ps.min;
+    total.pts += ps.pts;
+    total.fg += ps.fg;
+    total.fga += ps.fga;
+    total.tp += ps.tp;
+    total.tpa += ps.tpa;
+    total.ft += ps.ft;
+    total.fta += ps.fta;
+    total.orb += ps.orb;
+    total.drb += ps.drb;
+    total.ast += ps.ast;
+    total.stl += ps.stl;
+    total.blk += ps.blk;
+    total.tov += ps.tov;
+    total.pf += ps.pf;
+}
+
+// John Hollinger's Game Score, a box score estimate of production
+pub fn get_game_score(ps: &PlayerStats) -> f64 {
+    ps.pts as f64 + 0.4 * ps.fg as f64 - 0.7 * ps.fga as f64 - 0.4 * (ps.fta - ps.ft) as f64 +
+        0.7 * ps.orb as f64 + 0.3 * ps.drb as f64 + ps.stl as f64 + 0.7 * ps.ast as f64 + 0.7 * ps.blk as f64 -
+        0.4 * ps.pf as f64 - ps.tov as f64
+}
+
+// Per game average of a total
+pub fn per_game(total: f64, gp: i32) -> f64 {
+    if gp <= 0 {
+        return 0.0;
+    }
+    total / gp as f64
+}
\ No newline at end of file
@@ -0,0 +1,43 @@
+use crate::common::types::PlayerInjury;
+use rand::rngs::StdRng;
+use rand::Rng;
+
+// Injuries a player can get in a game, with how often each happens relative to the others and the average number of
+// games it takes to heal
+const INJURIES: [(&str, f64, f64); 14] = [
+    ("Sprained Ankle", 25.0, 4.0),
+    ("Sore Knee", 12.0, 3.0),
+    ("Back Spasms", 10.0, 3.0),
+    ("Bruised Thigh", 8.0, 2.0),
+    ("Hamstring Strain", 8.0, 6.0),
+    ("Sprained Wrist", 6.0, 4.0),
+    ("Groin Strain", 6.0, 6.0),
+    ("Calf Strain", 5.0, 7.0),
+    ("Concussion", 4.0, 5.0),
+    ("Broken Nose", 3.0, 3.0),
+    ("Sprained Knee", 3.0, 12.0),
+    ("Broken Hand", 2.0, 25.0),
+    ("Torn Meniscus", 1.0, 35.0),
+    ("Torn ACL", 0.5, 75.0)
+];
+
+// Picks the injury a player just suffered and how long it keeps them out
+pub fn gen_injury(rng: &mut StdRng) -> PlayerInjury {
+    let total: f64 = INJURIES.iter().map(|(_, frequency, _)| frequency).sum();
+    let mut r = rng.gen_range(0.0..total);
+    let mut injury = INJURIES[0];
+    for candidate in INJURIES.iter() {
+        if r < candidate.1 {
+            injury = *candidate;
+            break;
+        }
+        r -= candidate.1;
+    }
+
+    let games_remaining = (injury.2 * rng.gen_range(0.5..1.5)).round().clamp(1.0, 255.0) as u8;
+    PlayerInjury {
+        games_remaining,
+        injury_type: injury.0.to_string(),
+        score: None
+    }
+}
\ No newline at end of file
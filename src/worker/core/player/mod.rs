@@ -0,0 +1,18 @@
+pub mod composite_rating;
+pub mod to_player_game_sim;
+pub mod add_game_stats;
+pub mod add_ratings_row;
+pub mod develop;
+pub mod ovr;
+pub mod pos;
+pub mod pot_estimator;
+pub mod value;
+pub mod skills;
+pub mod gen_ratings;
+pub mod generate;
+pub mod gen_contract;
+pub mod decide_options;
+pub mod mood_info;
+pub mod sign;
+pub mod get_season_stats;
+pub mod injury;
\ No newline at end of file
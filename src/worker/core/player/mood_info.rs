@@ -0,0 +1,238 @@
+use crate::common::helpers::bound;
+use crate::common::helpers::calc_winp;
+use crate::common::types::Player;
+use crate::common::types::Team;
+use crate::common::types::TeamSeasonAttrs;
+use crate::worker::core::player::gen_contract::gen_contract;
+use crate::worker::db::Tx;
+use crate::worker::util::g::G;
+use std::collections::HashMap;
+
+// Players sign anywhere their mood adds up to more than this
+const WILLING_THRESHOLD: f64 = -1.5;
+
+// Each point of mood takes this much off the asking amount, within the bounds
+const AMOUNT_PER_MOOD: f64 = 0.05;
+const MIN_AMOUNT_FACTOR: f64 = 0.75;
+const MAX_AMOUNT_FACTOR: f64 = 1.25;
+
+// How a player feels about playing for a team, each part around 0 for an average team. Positive is good.
+#[derive(Clone, Debug, Default)]
+pub struct MoodComponents {
+    // +1 for the biggest market down to -1 for the smallest
+    pub market_size: f64,
+    // From the team's winning percentage this season, between -2 and +2
+    pub team_performance: f64,
+    // From where the player would rank on the roster, +1 for a starter down to -2 for the end of the bench
+    pub playing_time: f64,
+    // Players coming off a rookie contract like to stay with the team that drafted them
+    pub rookie_contract: f64
+}
+
+impl MoodComponents {
+    pub fn sum(&self) -> f64 {
+        self.market_size + self.team_performance + self.playing_time + self.rookie_contract
+    }
+}
+
+pub struct MoodInfo {
+    pub components: MoodComponents,
+    pub sum_components: f64,
+    pub willing: bool,
+    // What the player asks this team for, before any change to the contract length
+    pub contract_amount: f64
+}
+
+// Everything mood_info needs to know about the league, loaded once for many players
+pub struct MoodContext {
+    teams: Vec<Team>,
+    team_seasons: Vec<TeamSeasonAttrs>,
+    rosters: HashMap<i32, Vec<Player>>
+}
+
+impl MoodContext {
+    pub fn load(tx: &Tx) -> Result<Self, String> {
+        let g = tx.get_g()?;
+        let teams = tx.get_teams()?;
+        let mut rosters = HashMap::new();
+        for p in tx.get_players()? {
+            if p.tid >= 0 {
+                rosters.entry(p.tid).or_insert_with(Vec::new).push(p);
+            }
+        }
+        Ok(MoodContext {
+            teams,
+            team_seasons: tx.get_team_seasons(g.season())?,
+            rosters
+        })
+    }
+
+    pub fn roster(&self, tid: i32) -> &[Player] {
+        self.rosters.get(&tid).map(|roster| roster.as_slice()).unwrap_or(&[])
+    }
+
+    // Keeps the rosters up to date after p signs with tid
+    pub fn add_player(&mut self, p: &Player, tid: i32) {
+        self.rosters.entry(tid).or_default().push(p.clone());
+    }
+}
+
+// Whether p is on tid with a contract that ends after g.season(), so a new contract with tid would be a re-signing
+pub fn is_re_signing(p: &Player, tid: i32, g: &G) -> bool {
+    p.tid == tid && p.contract.exp <= g.season()
+}
+
+pub fn mood_components(p: &Player, tid: i32, ctx: &MoodContext, g: &G) -> MoodComponents {
+    let mut components = MoodComponents::default();
+
+    let num_teams = ctx.teams.len();
+    let t = ctx.teams.iter().find(|t| t.tid == tid);
+    if let Some(t) = t.filter(|_| num_teams > 1) {
+        let pop = t.pop;
+        let rank = ctx.teams.iter().filter(|other| other.pop > pop).count();
+        components.market_size = 1.0 - 2.0 * rank as f64 / (num_teams - 1) as f64;
+    }
+
+    if let Some(ts) = ctx.team_seasons.iter().find(|ts| ts.tid == tid && ts.gp > 0) {
+        components.team_performance = bound((calc_winp(ts.won, ts.lost, ts.tied, ts.otl) - 0.5) * 5.0, -2.0, 2.0);
+    }
+
+    let rank = ctx.roster(tid).iter().filter(|other| other.pid != p.pid && other.value_no_pot > p.value_no_pot).count() as f64;
+    components.playing_time = if rank < 5.0 {
+        1.0
+    } else if rank < 8.0 {
+        0.0
+    } else {
+        f64::max(-2.0, -0.5 * (rank - 7.0))
+    };
+
+    if p.contract.rookie && is_re_signing(p, tid, g) {
+        components.rookie_contract = 2.0;
+    }
+    components
+}
+
+// How p feels about signing with tid and what they would ask for. Free agents start from the demands in their
+// contract, and players re-signing from what they would ask for on the open market. A good mood knocks up to 25% off
+// and a bad one adds up to 25%.
+pub fn mood_info(p: &Player, tid: i32, ctx: &MoodContext, g: &G) -> MoodInfo {
+    let components = mood_components(p, tid, ctx, g);
+    let sum_components = components.sum();
+    let base_amount = if is_re_signing(p, tid, g) {
+        gen_contract(p, g, g.season() + 1).amount
+    } else {
+        p.contract.amount
+    };
+    let factor = bound(1.0 - AMOUNT_PER_MOOD * sum_components, MIN_AMOUNT_FACTOR, MAX_AMOUNT_FACTOR);
+    let contract_amount = bound((base_amount * factor / 10.0).round() * 10.0, g.min_contract(), g.max_contract());
+    MoodInfo {
+        components,
+        sum_components,
+        willing: sum_components > WILLING_THRESHOLD,
+        contract_amount
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::worker::core::player::generate::generate;
+    use crate::worker::core::player::gen_ratings::TalentDistribution;
+    use crate::worker::util::random::rng_from_seed;
+
+    // Three teams from the biggest market to the smallest, and from the best record to the worst
+    fn get_context(g: &G) -> MoodContext {
+        let pops = [20.0, 5.0, 1.0];
+        let teams = (0..3).map(|tid| Team {
+            tid,
+            cid: 0,
+            did: 0,
+            region: format!("Region {}", tid),
+            name: format!("Team {}", tid),
+            abbrev: format!("T{}", tid),
+            pop: pops[tid as usize],
+            coaching_rank: None
+        }).collect();
+        let records = [(40, 0), (20, 20), (0, 40)];
+        let team_seasons = (0..3).map(|tid| {
+            let mut team_season = TeamSeasonAttrs::new(tid, g.season(), 0, 0);
+            (team_season.won, team_season.lost) = records[tid as usize];
+            team_season.gp = 40;
+            team_season
+        }).collect();
+        MoodContext {
+            teams,
+            team_seasons,
+            rosters: HashMap::new()
+        }
+    }
+
+    fn get_free_agent(g: &G, amount: f64) -> Player {
+        let mut p = generate(1, -1, 27, g.season() - 6, g, &TalentDistribution::default(), &mut rng_from_seed(1)).unwrap();
+        p.contract.amount = amount;
+        p.contract.exp = g.season() + 2;
+        p
+    }
+
+    #[test]
+    fn components_from_market_record_and_role() {
+        let g = G::from_defaults(2025).unwrap();
+        let mut ctx = get_context(&g);
+        let p = get_free_agent(&g, 10000.0);
+
+        let best = mood_components(&p, 0, &ctx, &g);
+        assert_eq!((best.market_size, best.team_performance, best.playing_time, best.rookie_contract), (1.0, 2.0, 1.0, 0.0));
+        let middle = mood_components(&p, 1, &ctx, &g);
+        assert_eq!((middle.market_size, middle.team_performance), (0.0, 0.0));
+
+        // Nine better players on the roster push p to the end of the bench
+        for pid in 10..19 {
+            let mut other = get_free_agent(&g, 10000.0);
+            other.pid = pid;
+            other.value_no_pot = p.value_no_pot + 1.0;
+            ctx.add_player(&other, 2);
+        }
+        let worst = mood_components(&p, 2, &ctx, &g);
+        assert_eq!((worst.market_size, worst.team_performance, worst.playing_time), (-1.0, -2.0, -1.0));
+    }
+
+    #[test]
+    fn asking_amount_follows_mood() {
+        let g = G::from_defaults(2025).unwrap();
+        let ctx = get_context(&g);
+        let p = get_free_agent(&g, 10000.0);
+
+        // Mood of +4 takes 20% off
+        let best = mood_info(&p, 0, &ctx, &g);
+        assert_eq!(best.sum_components, 4.0);
+        assert!(best.willing);
+        assert_eq!(best.contract_amount, 8000.0);
+
+        // Mood of -2 adds 10%, and isn't enough to sign at all
+        let worst = mood_info(&p, 2, &ctx, &g);
+        assert_eq!(worst.sum_components, -2.0);
+        assert!(!worst.willing);
+        assert_eq!(worst.contract_amount, 11000.0);
+
+        // Never below the minimum contract
+        assert_eq!(mood_info(&get_free_agent(&g, 1300.0), 0, &ctx, &g).contract_amount, g.min_contract());
+    }
+
+    #[test]
+    fn rookies_like_re_signing() {
+        let g = G::from_defaults(2025).unwrap();
+        let ctx = get_context(&g);
+        let mut p = get_free_agent(&g, 3000.0);
+        p.tid = 1;
+        p.contract.rookie = true;
+        p.contract.exp = g.season();
+
+        let info = mood_info(&p, 1, &ctx, &g);
+        assert_eq!(info.components.rookie_contract, 2.0);
+        // Re-signing starts from what they'd ask for on the open market, not the rookie contract
+        let factor = 1.0 - AMOUNT_PER_MOOD * info.sum_components;
+        let expected = (gen_contract(&p, &g, g.season() + 1).amount * factor / 10.0).round() * 10.0;
+        assert_eq!(info.contract_amount, bound(expected, g.min_contract(), g.max_contract()));
+        assert_eq!(mood_components(&p, 0, &ctx, &g).rookie_contract, 0.0);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,137 @@
+use crate::common::types::PlayerRatings;
+use crate::worker::util::helpers;
+
+// (rating, weight, league average) from a regression of team performance on player ratings
+const OVR_WEIGHTS: [(&str, f64, f64); 15] = [
+    ("hgt", 0.159, 47.5),
+    ("stre", 0.0777, 50.2),
+    ("spd", 0.123, 50.8),
+    ("jmp", 0.051, 48.7),
+    ("endu", 0.0632, 39.9),
+    ("ins", 0.0126, 42.4),
+    ("dnk", 0.0286, 49.5),
+    ("ft", 0.0202, 47.0),
+    ("fg", 0.01, 47.0),
+    ("tp", 0.0726, 47.1),
+    ("oiq", 0.133, 46.8),
+    ("diq", 0.159, 46.7),
+    ("drb", 0.059, 54.8),
+    ("pss", 0.062, 51.3),
+    ("reb", 0.01, 51.4),
+];
+
+// How much more or less each rating matters at a position, relative to OVR_WEIGHTS. Every position's weights are
+// scaled back to the same total, so ovrs at different positions are on the same scale.
+const POSITION_MULTIPLIERS: [(&str, &[(&str, f64)]); 5] = [
+    ("PG", &[("hgt", 0.6), ("drb", 2.0), ("pss", 2.0), ("tp", 1.3), ("reb", 0.5), ("ins", 0.5)]),
+    ("SG", &[("hgt", 0.7), ("drb", 1.5), ("pss", 1.2), ("tp", 1.5), ("fg", 1.5)]),
+    ("SF", &[("spd", 1.2), ("jmp", 1.2), ("tp", 1.2), ("diq", 1.1)]),
+    ("PF", &[("hgt", 1.2), ("stre", 1.5), ("reb", 3.0), ("ins", 2.0), ("drb", 0.6), ("pss", 0.7)]),
+    ("C", &[("hgt", 1.4), ("stre", 1.5), ("reb", 4.0), ("ins", 3.0), ("dnk", 1.5), ("drb", 0.4), ("pss", 0.5), ("tp", 0.5)]),
+];
+
+fn get_multiplier(pos: Option<&str>, key: &str) -> f64 {
+    if pos.is_none() {
+        return 1.0;
+    }
+    let multipliers = POSITION_MULTIPLIERS.iter().find(|(p, _)| *p == pos.unwrap());
+    if multipliers.is_none() {
+        return 1.0;
+    }
+    multipliers.unwrap().1.iter().find(|(k, _)| *k == key).map(|(_, multiplier)| *multiplier).unwrap_or(1.0)
+}
+
+// Overall rating, for a player at pos or for any position if pos is None
+pub fn ovr(ratings: &PlayerRatings, pos: Option<&str>) -> f64 {
+    let total_weight: f64 = OVR_WEIGHTS.iter().map(|(_, weight, _)| weight).sum();
+    let pos_weight: f64 = OVR_WEIGHTS.iter().map(|(key, weight, _)| weight * get_multiplier(pos, key)).sum();
+
+    let mut r = 48.5;
+    for (key, weight, mean) in OVR_WEIGHTS.iter() {
+        let weight = weight * get_multiplier(pos, key) * total_weight / pos_weight;
+        r += weight * (ratings.get(key).unwrap() - mean);
+    }
+
+    // Spreads out the ratings, so the best players are in the 70s and the worst are in the 30s
+    let fudge_factor = if r >= 68.0 {
+        8.0
+    } else if r >= 50.0 {
+        4.0 + (r - 50.0) * (4.0 / 18.0)
+    } else if r >= 42.0 {
+        -5.0 + (r - 42.0) * (9.0 / 8.0)
+    } else if r >= 31.0 {
+        -5.0 - (42.0 - r) * (5.0 / 11.0)
+    } else {
+        -10.0
+    };
+
+    helpers::bound((r + fudge_factor).round(), 0.0, f64::INFINITY)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::worker::core::player::pos::pos;
+    use crate::worker::core::player::pot_estimator::pot_estimator;
+
+    fn new_ratings(rating: f64) -> PlayerRatings {
+        let mut ratings: PlayerRatings = serde_json::from_str(r#"{
+            "season": 2025, "hgt": 0, "stre": 0, "spd": 0, "jmp": 0, "endu": 0, "ins": 0, "dnk": 0, "ft": 0, "fg": 0,
+            "tp": 0, "oiq": 0, "diq": 0, "drb": 0, "pss": 0, "reb": 0
+        }"#).unwrap();
+        for (key, _, _) in OVR_WEIGHTS.iter() {
+            ratings.set(key, rating);
+        }
+        ratings
+    }
+
+    #[test]
+    fn better_ratings_better_ovr() {
+        let average = ovr(&new_ratings(47.0), None);
+        assert!(average > 40.0 && average < 55.0, "{}", average);
+        assert!(ovr(&new_ratings(70.0), None) > average);
+        assert!(ovr(&new_ratings(30.0), None) < average);
+        assert_eq!(ovr(&new_ratings(0.0), None), 0.0);
+    }
+
+    #[test]
+    fn positions_weigh_their_skills() {
+        let mut passer = new_ratings(50.0);
+        passer.drb = 80.0;
+        passer.pss = 80.0;
+        assert!(ovr(&passer, Some("PG")) > ovr(&passer, Some("C")));
+        let mut big = new_ratings(50.0);
+        big.reb = 80.0;
+        big.ins = 80.0;
+        assert!(ovr(&big, Some("C")) > ovr(&big, Some("PG")));
+        // Every position is on the same scale, so a league average player is just as good anywhere
+        let mut average = new_ratings(0.0);
+        for (key, _, mean) in OVR_WEIGHTS.iter() {
+            average.set(key, *mean);
+        }
+        for position in ["PG", "SG", "SF", "PF", "C"] {
+            assert_eq!(ovr(&average, Some(position)), ovr(&average, None));
+        }
+    }
+
+    #[test]
+    fn position_from_height_and_skills() {
+        let mut ratings = new_ratings(50.0);
+        ratings.hgt = 10.0;
+        assert_eq!(pos(&ratings), "PG");
+        ratings.hgt = 70.0;
+        assert_eq!(pos(&ratings), "C");
+        // A tall ball handler plays further from the basket
+        ratings.drb = 90.0;
+        ratings.pss = 90.0;
+        assert_ne!(pos(&ratings), "C");
+    }
+
+    #[test]
+    fn potential_is_at_least_ovr() {
+        let ratings = new_ratings(45.0);
+        let current = ovr(&ratings, None);
+        assert!(pot_estimator(&ratings, 19) > current);
+        assert_eq!(pot_estimator(&ratings, 30), current);
+    }
+}
\ No newline at end of file
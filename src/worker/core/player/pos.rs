@@ -0,0 +1,40 @@
+use crate::common::types::PlayerRatings;
+use crate::worker::util::helpers;
+
+// Position from height and skills: bigs are forwards or centers, unless they handle the ball like guards. Players
+// between two positions get both, like "G" or "GF".
+pub fn pos(ratings: &PlayerRatings) -> String {
+    let guard_skills = (ratings.drb + ratings.pss) / 2.0;
+    let big_skills = (ratings.reb + ratings.ins + ratings.stre) / 3.0;
+
+    // Height on a scale of positions, from 0 for a PG to 4 for a C, moved towards the skills the player has
+    let mut score = (ratings.hgt - 27.0) / 10.0;
+    score += (big_skills - guard_skills) / 25.0;
+    let score = helpers::bound(score, 0.0, 4.0);
+
+    if score < 0.75 {
+        return "PG".to_string();
+    }
+    if score < 1.25 {
+        return "G".to_string();
+    }
+    if score < 1.75 {
+        return "SG".to_string();
+    }
+    if score < 2.25 {
+        return "GF".to_string();
+    }
+    if score < 2.75 {
+        return "SF".to_string();
+    }
+    if score < 3.0 {
+        return "F".to_string();
+    }
+    if score < 3.5 {
+        return "PF".to_string();
+    }
+    if score < 3.75 {
+        return "FC".to_string();
+    }
+    "C".to_string()
+}
\ No newline at end of file
@@ -0,0 +1,19 @@
+use crate::common::types::PlayerRatings;
+use crate::worker::core::player::develop::develop_season_expected;
+use crate::worker::core::player::ovr::ovr;
+
+// Age when players stop improving on average
+const PEAK_AGE: i32 = 29;
+
+// Best ovr the player is expected to reach, from average development until their peak. Players at or past it have
+// no potential beyond their ovr.
+pub fn pot_estimator(ratings: &PlayerRatings, age: i32) -> f64 {
+    let current = ovr(ratings, None);
+    let mut pot = current;
+    let mut future = ratings.clone();
+    for future_age in (age + 1)..=PEAK_AGE {
+        develop_season_expected(&mut future, future_age);
+        pot = pot.max(ovr(&future, None));
+    }
+    pot
+}
\ No newline at end of file
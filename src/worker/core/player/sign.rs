@@ -0,0 +1,39 @@
+use crate::common::helpers::format_currency;
+use crate::common::types::Event;
+use crate::common::types::Player;
+use crate::common::types::PlayerContract;
+use crate::worker::db::Tx;
+
+// Puts p on tid with contract and logs it as event_type, "freeAgent" for a free agent or "reSigned" for a player who
+// stays with their team
+pub fn sign(tx: &Tx, p: &Player, tid: i32, contract: PlayerContract, event_type: &str) -> Result<Event, String> {
+    let g = tx.get_g()?;
+    let teams = tx.get_teams()?;
+    let abbrev = teams.iter().find(|t| t.tid == tid).map(|t| t.abbrev.clone()).unwrap_or(tid.to_string());
+    let verb = if event_type == "reSigned" { "re-signed" } else { "signed" };
+    let text = format!(
+        "{} {} {} for {} through {}",
+        abbrev,
+        verb,
+        p.name(),
+        format_currency(contract.amount),
+        contract.exp
+    );
+
+    let mut p = p.clone();
+    p.tid = tid;
+    p.contract = contract;
+    tx.put_player(&p)?;
+
+    let event = Event {
+        eid: tx.get_next_eid()?,
+        event_type: event_type.to_string(),
+        season: g.season(),
+        text,
+        tids: vec![tid],
+        pids: vec![p.pid],
+        dpids: Vec::new()
+    };
+    tx.put_event(&event)?;
+    Ok(event)
+}
\ No newline at end of file
@@ -0,0 +1,24 @@
+use crate::common::types::PlayerRatings;
+use crate::worker::core::player::composite_rating::get_composite_ratings;
+
+// Composite rating a player needs for each skill label. They are the same cutoffs GameSim uses for synergy.
+const SKILL_CUTOFFS: [(&str, &str, f64); 9] = [
+    ("3", "shootingThreePointer", 0.59),
+    ("A", "athleticism", 0.63),
+    ("B", "dribbling", 0.68),
+    ("Di", "defenseInterior", 0.57),
+    ("Dp", "defensePerimeter", 0.61),
+    ("Po", "shootingLowPost", 0.61),
+    ("Ps", "passing", 0.63),
+    ("R", "rebounding", 0.61),
+    ("V", "usage", 0.61),
+];
+
+pub fn skills(ratings: &PlayerRatings) -> Result<Vec<String>, String> {
+    let composite_ratings = get_composite_ratings(ratings)?;
+    Ok(SKILL_CUTOFFS
+        .iter()
+        .filter(|(_, composite, cutoff)| composite_ratings[*composite] > *cutoff)
+        .map(|(skill, _, _)| skill.to_string())
+        .collect())
+}
\ No newline at end of file
@@ -0,0 +1,19 @@
+use crate::common::types::Player;
+use crate::worker::core::player::composite_rating::get_composite_ratings;
+use crate::worker::core::GameSim_basketball::index::PlayerGameSim;
+
+// The player as GameSim sees them in one game, with the latest ratings from before or during season
+pub fn to_player_game_sim(p: &Player, season: i32) -> Result<PlayerGameSim, String> {
+    let ratings = p.ratings_for_season(season);
+    Ok(PlayerGameSim::new(
+        p.pid,
+        p.name(),
+        p.age(season) as f64,
+        ratings.pos.clone(),
+        p.value_no_pot,
+        get_composite_ratings(ratings)?,
+        p.injury.clone(),
+        false,
+        p.pt_modifier,
+    ))
+}
\ No newline at end of file
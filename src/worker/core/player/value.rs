@@ -0,0 +1,86 @@
+use crate::common::types::Player;
+use crate::worker::core::player::get_season_stats::get_game_score;
+use crate::worker::core::player::get_season_stats::get_season_stats;
+
+// Minutes of stats it takes to trust them over the ratings
+const MIN_TRUSTED: f64 = 2000.0;
+
+// Game Score per 36 minutes of a typical player, with an ovr of about OVR_MEAN, and ovr points per point above it
+const GMSC_MEAN: f64 = 11.0;
+const OVR_MEAN: f64 = 47.0;
+const OVR_PER_GMSC: f64 = 2.5;
+
+// Production over the two most recent seasons up to season on the ovr scale, and the minutes it's based on
+fn get_stats_ovr(p: &Player, season: i32) -> Option<(f64, f64)> {
+    let mut seasons: Vec<i32> = p.stats.iter().filter(|ps| ps.season <= season && !ps.playoffs).map(|ps| ps.season).collect();
+    seasons.sort();
+    seasons.dedup();
+    let mut game_score = 0.0;
+    let mut min = 0.0;
+    for s in seasons.iter().rev().take(2) {
+        let ps = get_season_stats(p, *s, false).unwrap();
+        game_score += get_game_score(&ps);
+        min += ps.min;
+        // One full season is enough
+        if min >= MIN_TRUSTED {
+            break;
+        }
+    }
+    if min <= 0.0 {
+        return None;
+    }
+    Some((OVR_MEAN + OVR_PER_GMSC * (36.0 * game_score / min - GMSC_MEAN), min))
+}
+
+// How good the player is as of season on the ovr scale. With no_pot, only how good they are now: their ovr, moved
+// towards their recent production the more minutes it's based on. Otherwise young players get credit for their
+// potential and old players lose some for their coming decline.
+pub fn value(p: &Player, season: i32, no_pot: bool) -> f64 {
+    let ratings = p.ratings_for_season(season);
+    let mut current = ratings.ovr;
+    let stats_ovr = get_stats_ovr(p, season);
+    if let Some(stats_ovr) = stats_ovr {
+        let (stats_ovr, min) = stats_ovr;
+        let fraction = (min / MIN_TRUSTED).min(1.0);
+        current = fraction * stats_ovr + (1.0 - fraction) * ratings.ovr;
+        // Include some part of the ratings
+        current = 0.1 * ratings.ovr + 0.9 * current;
+    }
+
+    if no_pot {
+        return current;
+    }
+
+    let age = p.age(season);
+    // If performance is already exceeding predicted potential, just use that
+    let mut potential = ratings.pot;
+    if current >= potential && age < 29 {
+        potential = current;
+    }
+
+    let potential_weight = match age {
+        ..=19 => 0.8,
+        20 => 0.7,
+        21 => 0.5,
+        22 => 0.3,
+        23 => 0.15,
+        24 => 0.1,
+        _ => 0.0,
+    };
+    let age_factor = match age {
+        ..=26 => 1.0,
+        27 => 0.975,
+        28 => 0.95,
+        29 => 0.9,
+        30 => 0.85,
+        31 => 0.8,
+        32 => 0.75,
+        _ => 0.7,
+    };
+    age_factor * (potential_weight * potential + (1.0 - potential_weight) * current)
+}
+
+pub fn update_values(p: &mut Player, season: i32) {
+    p.value = value(p, season, false);
+    p.value_no_pot = value(p, season, true);
+}
\ No newline at end of file
@@ -0,0 +1,128 @@
+use crate::common::types::Player;
+use crate::common::types::PlayerStats;
+use crate::common::types::TeamSeasonAttrs;
+use crate::worker::core::player::get_season_stats::add_stats;
+use crate::worker::core::player::get_season_stats::get_season_stats;
+use std::collections::HashMap;
+
+// League average PER, and the PER of a replacement level player for EWA
+const PER_MEAN: f64 = 15.0;
+const PER_REPLACEMENT: f64 = 11.0;
+
+// Regular season advanced stats for one player
+#[derive(Clone, Copy)]
+pub struct AdvStats {
+    // John Hollinger's Player Efficiency Rating, without the pace adjustment since team possessions aren't tracked
+    pub per: f64,
+    // Estimated Wins Added over a replacement level player
+    pub ewa: f64,
+    // Defensive points saved: the defensive part of Game Score over the league rate for the minutes played, plus a
+    // share of the points the team saved against the league average by minutes
+    pub dps: f64
+}
+
+fn defensive_score(ps: &PlayerStats) -> f64 {
+    0.3 * ps.drb as f64 + ps.stl as f64 + 0.7 * ps.blk as f64
+}
+
+// League wide factors of the PER formula
+struct PerFactors {
+    factor: f64,
+    vop: f64,
+    drbp: f64,
+    ft_per_pf: f64,
+    fta_per_pf: f64
+}
+
+impl PerFactors {
+    fn new(lg: &PlayerStats) -> Option<Self> {
+        let trb = (lg.orb + lg.drb) as f64;
+        let possessions = (lg.fga - lg.orb + lg.tov) as f64 + 0.44 * lg.fta as f64;
+        if lg.fg == 0 || lg.ft == 0 || lg.pf == 0 || trb == 0.0 || possessions <= 0.0 {
+            return None;
+        }
+        Some(PerFactors {
+            factor: 2.0 / 3.0 - (0.5 * lg.ast as f64 / lg.fg as f64) / (2.0 * lg.fg as f64 / lg.ft as f64),
+            vop: lg.pts as f64 / possessions,
+            drbp: lg.drb as f64 / trb,
+            ft_per_pf: lg.ft as f64 / lg.pf as f64,
+            fta_per_pf: lg.fta as f64 / lg.pf as f64
+        })
+    }
+
+    // Unadjusted PER times minutes, for one row of stats with a team that assisted on ast_per_fg of its field goals
+    fn u_per_times_min(&self, ps: &PlayerStats, ast_per_fg: f64) -> f64 {
+        let vop = self.vop;
+        let drbp = self.drbp;
+        ps.tp as f64 + 2.0 / 3.0 * ps.ast as f64 + (2.0 - self.factor * ast_per_fg) * ps.fg as f64 +
+            ps.ft as f64 * 0.5 * (1.0 + (1.0 - ast_per_fg) + 2.0 / 3.0 * ast_per_fg) - vop * ps.tov as f64 -
+            vop * drbp * (ps.fga - ps.fg) as f64 - vop * 0.44 * (0.44 + 0.56 * drbp) * (ps.fta - ps.ft) as f64 +
+            vop * (1.0 - drbp) * ps.drb as f64 + vop * drbp * ps.orb as f64 + vop * ps.stl as f64 +
+            vop * drbp * ps.blk as f64 - ps.pf as f64 * (self.ft_per_pf - 0.44 * self.fta_per_pf * vop)
+    }
+}
+
+// Advanced stats by pid for everyone who played in the regular season. Empty if the season has no stats.
+pub fn get_adv_stats(players: &[Player], team_seasons: &[TeamSeasonAttrs], season: i32) -> HashMap<i32, AdvStats> {
+    let rows: Vec<&PlayerStats> = players
+        .iter()
+        .flat_map(|p| p.stats.iter())
+        .filter(|ps| ps.season == season && !ps.playoffs)
+        .collect();
+    let mut lg = PlayerStats::new(season, -1, false);
+    let mut teams: HashMap<i32, PlayerStats> = HashMap::new();
+    for ps in rows.iter() {
+        add_stats(&mut lg, ps);
+        add_stats(teams.entry(ps.tid).or_insert_with(|| PlayerStats::new(season, ps.tid, false)), ps);
+    }
+    let factors = PerFactors::new(&lg);
+    if factors.is_none() || lg.min <= 0.0 {
+        return HashMap::new();
+    }
+    let factors = factors.unwrap();
+    let ast_per_fg = |tid: i32| -> f64 {
+        let t = &teams[&tid];
+        if t.fg > 0 { t.ast as f64 / t.fg as f64 } else { 0.0 }
+    };
+
+    // Scaled so the average minute has a PER of PER_MEAN
+    let lg_u_per = rows.iter().map(|ps| factors.u_per_times_min(ps, ast_per_fg(ps.tid))).sum::<f64>() / lg.min;
+    if lg_u_per <= 0.0 {
+        return HashMap::new();
+    }
+    let lg_defensive_rate = defensive_score(&lg) / lg.min;
+    let lg_gp: i32 = team_seasons.iter().map(|ts| ts.gp).sum();
+    let lg_opp_pts_per_game = if lg_gp > 0 { team_seasons.iter().map(|ts| ts.opp_pts).sum::<i32>() as f64 / lg_gp as f64 } else { 0.0 };
+    let team_pts_saved = |tid: i32| -> f64 {
+        let ts = team_seasons.iter().find(|ts| ts.tid == tid);
+        if ts.is_none() || ts.unwrap().gp == 0 {
+            return 0.0;
+        }
+        let ts = ts.unwrap();
+        lg_opp_pts_per_game * ts.gp as f64 - ts.opp_pts as f64
+    };
+
+    let mut adv_stats = HashMap::new();
+    for p in players.iter() {
+        let ps = get_season_stats(p, season, false);
+        if ps.is_none() || ps.as_ref().unwrap().min <= 0.0 {
+            continue;
+        }
+        let ps = ps.unwrap();
+        let mut u_per_times_min = 0.0;
+        let mut team_part = 0.0;
+        for row in p.stats.iter().filter(|row| row.season == season && !row.playoffs) {
+            u_per_times_min += factors.u_per_times_min(row, ast_per_fg(row.tid));
+            if row.min > 0.0 {
+                team_part += team_pts_saved(row.tid) * row.min / teams[&row.tid].min;
+            }
+        }
+        let per = PER_MEAN * u_per_times_min / ps.min / lg_u_per;
+        adv_stats.insert(p.pid, AdvStats {
+            per,
+            ewa: ps.min * (per - PER_REPLACEMENT) / 67.0 / 30.0,
+            dps: defensive_score(&ps) - lg_defensive_rate * ps.min + team_part
+        });
+    }
+    adv_stats
+}
\ No newline at end of file
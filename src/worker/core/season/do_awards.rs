@@ -0,0 +1,344 @@
+use crate::common::helpers::calc_winp;
+use crate::common::types::AwardPlayer;
+use crate::common::types::AwardTeam;
+use crate::common::types::Player;
+use crate::common::types::PlayerAward;
+use crate::common::types::PlayerStats;
+use crate::common::types::SeasonAwards;
+use crate::common::types::Team;
+use crate::common::types::TeamSeasonAttrs;
+use crate::worker::core::player::add_game_stats::add_box_score_line;
+use crate::worker::core::player::get_season_stats::get_game_score;
+use crate::worker::core::player::get_season_stats::get_season_stats;
+use crate::worker::core::player::get_season_stats::per_game;
+use crate::worker::core::season::adv_stats::get_adv_stats;
+use crate::worker::core::season::adv_stats::AdvStats;
+use crate::worker::core::season::new_schedule_playoffs_day::get_champion;
+use crate::worker::db::Tx;
+use std::collections::HashMap;
+
+// Share of the regular season's games a player needs for any award but the Finals MVP
+const MIN_GAMES_FRACTION: f64 = 0.7;
+
+// EWA the MVP gets for their team's winning percentage above .500, so 5 for an undefeated team
+const MVP_WIN_FACTOR: f64 = 10.0;
+
+const TEAM_TITLES: [&str; 3] = ["First Team", "Second Team", "Third Team"];
+const TEAM_POSITIONS: [&str; 5] = ["G", "G", "F", "F", "C"];
+
+struct Candidate<'a> {
+    p: &'a Player,
+    stats: PlayerStats,
+    adv: AdvStats,
+    winp: f64
+}
+
+// Spots on the All-League and All-Defensive teams a player can take, players between two positions can take either
+fn team_positions(pos: &str) -> &'static [&'static str] {
+    match pos {
+        "PG" | "SG" | "G" => &["G"],
+        "GF" => &["G", "F"],
+        "SF" | "PF" | "F" => &["F"],
+        "FC" => &["F", "C"],
+        "C" => &["C"],
+        _ => &["G", "F", "C"],
+    }
+}
+
+fn to_award_player(p: &Player, ps: &PlayerStats, per: Option<f64>, teams: &[Team]) -> AwardPlayer {
+    AwardPlayer {
+        pid: p.pid,
+        name: p.name(),
+        tid: ps.tid,
+        abbrev: teams.iter().find(|t| t.tid == ps.tid).map(|t| t.abbrev.clone()).unwrap_or_default(),
+        pos: p.ratings_for_season(ps.season).pos.clone(),
+        pts: per_game(ps.pts as f64, ps.gp),
+        trb: per_game((ps.orb + ps.drb) as f64, ps.gp),
+        ast: per_game(ps.ast as f64, ps.gp),
+        stl: per_game(ps.stl as f64, ps.gp),
+        blk: per_game(ps.blk as f64, ps.gp),
+        per
+    }
+}
+
+fn get_winp(team_seasons: &[TeamSeasonAttrs], tid: i32) -> f64 {
+    let ts = team_seasons.iter().find(|ts| ts.tid == tid);
+    if ts.is_none() || ts.unwrap().gp == 0 {
+        return 0.5;
+    }
+    let ts = ts.unwrap();
+    calc_winp(ts.won, ts.lost, ts.tied, ts.otl)
+}
+
+fn pick_best<'a, 'b>(candidates: impl Iterator<Item = &'b Candidate<'a>>, score: impl Fn(&Candidate) -> f64) -> Option<&'b Candidate<'a>> {
+    candidates.max_by(|a, b| score(a).partial_cmp(&score(b)).unwrap())
+}
+
+fn mvp_score(c: &Candidate) -> f64 {
+    c.adv.ewa + MVP_WIN_FACTOR * (c.winp - 0.5)
+}
+
+// The best players by score fill the teams in order, each taking the first open spot at one of their positions
+fn pick_teams(candidates: &[Candidate], score: impl Fn(&Candidate) -> f64, teams: &[Team]) -> Vec<AwardTeam> {
+    let mut sorted: Vec<&Candidate> = candidates.iter().collect();
+    sorted.sort_by(|a, b| score(b).partial_cmp(&score(a)).unwrap());
+    let mut spots: Vec<Vec<Option<&Candidate>>> = TEAM_TITLES.iter().map(|_| vec![None; TEAM_POSITIONS.len()]).collect();
+    for c in sorted.iter() {
+        let positions = team_positions(&c.p.ratings_for_season(c.stats.season).pos);
+        let spot = spots.iter_mut().flat_map(|team| team.iter_mut().enumerate()).find(|(i, spot)| {
+            spot.is_none() && positions.contains(&TEAM_POSITIONS[*i])
+        });
+        if let Some(spot) = spot {
+            *spot.1 = Some(*c);
+        }
+    }
+    TEAM_TITLES
+        .iter()
+        .zip(spots.iter())
+        .map(|(title, team)| AwardTeam {
+            title: title.to_string(),
+            players: team.iter().flatten().map(|c| to_award_player(c.p, &c.stats, Some(c.adv.per), teams)).collect()
+        })
+        .filter(|team| !team.players.is_empty())
+        .collect()
+}
+
+// The champion's player with the highest Game Score over the finals, from the box scores
+fn get_finals_mvp(tx: &Tx, season: i32, players: &[Player], teams: &[Team]) -> Result<Option<AwardPlayer>, String> {
+    let g = tx.get_g()?;
+    let playoff_series = tx.get_playoff_series(season)?;
+    if playoff_series.is_none() {
+        return Ok(None);
+    }
+    let playoff_series = playoff_series.unwrap();
+    let champion = get_champion(&playoff_series, g.num_games_playoff_series());
+    if champion.is_none() {
+        return Ok(None);
+    }
+    let champion = champion.unwrap();
+    let finals = &playoff_series.series[playoff_series.current_round][0];
+    let tids = [finals.home.tid, finals.away.as_ref().map(|t| t.tid).unwrap_or(finals.home.tid)];
+
+    let mut totals: HashMap<i32, PlayerStats> = HashMap::new();
+    for game in tx.get_games(season)?.iter() {
+        if !game.playoffs || !game.teams.iter().all(|t| tids.contains(&t.tid)) {
+            continue;
+        }
+        let box_score = tx.get_box_score(game.gid)?;
+        if box_score.is_none() {
+            continue;
+        }
+        for t in box_score.unwrap().teams.iter().filter(|t| t.tid == champion) {
+            for line in t.players.iter() {
+                add_box_score_line(totals.entry(line.pid).or_insert_with(|| PlayerStats::new(season, champion, true)), line);
+            }
+        }
+    }
+    let best = totals.iter().max_by(|a, b| get_game_score(a.1).partial_cmp(&get_game_score(b.1)).unwrap());
+    if best.is_none() {
+        return Ok(None);
+    }
+    let (pid, ps) = best.unwrap();
+    let p = players.iter().find(|p| p.pid == *pid);
+    Ok(p.map(|p| to_award_player(p, ps, None, teams)))
+}
+
+// Gives out the awards for g.season() from the regular season stats, and the Finals MVP from the finals box scores. Each
+// winner also gets the award in their own history. Awards nobody is eligible for are left out. --fast games record
+// no stats, so a season with any of them gets no awards at all, rather than awards from part of the season.
+pub fn do_awards(tx: &Tx) -> Result<Option<SeasonAwards>, String> {
+    let g = tx.get_g()?;
+    let season = g.season();
+    if tx.get_games(season)?.len() != tx.get_game_sim_games(season)?.len() {
+        return Ok(None);
+    }
+    let teams = tx.get_teams()?;
+    let mut players = tx.get_players()?;
+    let team_seasons = tx.get_team_seasons(season)?;
+    let adv_stats = get_adv_stats(&players, &team_seasons, season);
+    let prev_adv_stats = get_adv_stats(&players, &tx.get_team_seasons(season - 1)?, season - 1);
+    let min_games = (MIN_GAMES_FRACTION * g.num_games() as f64).ceil() as i32;
+
+    let mut candidates = Vec::new();
+    for p in players.iter() {
+        let stats = get_season_stats(p, season, false);
+        if stats.is_none() || stats.as_ref().unwrap().gp < min_games || !adv_stats.contains_key(&p.pid) {
+            continue;
+        }
+        let stats = stats.unwrap();
+        let winp = get_winp(&team_seasons, stats.tid);
+        candidates.push(Candidate { p, stats, adv: adv_stats[&p.pid], winp });
+    }
+    let award = |c: Option<&Candidate>| c.map(|c| to_award_player(c.p, &c.stats, Some(c.adv.per), &teams));
+
+    let is_rookie = |c: &Candidate| c.p.draft.year == season - 1;
+    // Improvement in EWA over a previous season with at least half the games
+    let improvement = |c: &Candidate| -> Option<f64> {
+        let prev_stats = get_season_stats(c.p, season - 1, false);
+        if prev_stats.is_none() || prev_stats.unwrap().gp * 2 < min_games || !prev_adv_stats.contains_key(&c.p.pid) {
+            return None;
+        }
+        Some(c.adv.ewa - prev_adv_stats[&c.p.pid].ewa)
+    };
+
+    let awards = SeasonAwards {
+        season,
+        mvp: award(pick_best(candidates.iter(), mvp_score)),
+        dpoy: award(pick_best(candidates.iter(), |c| c.adv.dps)),
+        roy: award(pick_best(candidates.iter().filter(|c| is_rookie(c)), |c| c.adv.ewa)),
+        smoy: award(pick_best(candidates.iter().filter(|c| c.stats.gs * 2 < c.stats.gp), |c| c.adv.ewa)),
+        mip: award(pick_best(
+            candidates.iter().filter(|c| !is_rookie(c) && improvement(c).is_some()),
+            |c| improvement(c).unwrap()
+        )),
+        finals_mvp: get_finals_mvp(tx, season, &players, &teams)?,
+        all_league: pick_teams(&candidates, mvp_score, &teams),
+        all_defensive: pick_teams(&candidates, |c| c.adv.dps, &teams)
+    };
+
+    let mut winners: Vec<(i32, String)> = Vec::new();
+    let singles = [
+        (&awards.mvp, "Most Valuable Player"),
+        (&awards.dpoy, "Defensive Player of the Year"),
+        (&awards.roy, "Rookie of the Year"),
+        (&awards.smoy, "Sixth Man of the Year"),
+        (&awards.mip, "Most Improved Player"),
+        (&awards.finals_mvp, "Finals MVP"),
+    ];
+    for (winner, award_type) in singles.iter() {
+        if winner.is_some() {
+            winners.push((winner.as_ref().unwrap().pid, award_type.to_string()));
+        }
+    }
+    for (award_teams, name) in [(&awards.all_league, "All-League"), (&awards.all_defensive, "All-Defensive")] {
+        for team in award_teams.iter() {
+            for winner in team.players.iter() {
+                winners.push((winner.pid, format!("{} {}", team.title, name)));
+            }
+        }
+    }
+
+    for p in players.iter_mut() {
+        let won: Vec<&String> = winners.iter().filter(|(pid, _)| *pid == p.pid).map(|(_, award_type)| award_type).collect();
+        if won.is_empty() && !p.awards.iter().any(|award| award.season == season) {
+            continue;
+        }
+        // Replaces any earlier awards for the season
+        p.awards.retain(|award| award.season != season);
+        for award_type in won {
+            p.awards.push(PlayerAward { season, award_type: award_type.clone() });
+        }
+        tx.put_player(p)?;
+    }
+    tx.put_awards(&awards)?;
+    Ok(Some(awards))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::constants::PHASE_PLAYOFFS;
+    use crate::common::types::GameResult;
+    use crate::common::types::GameResultTeam;
+    use crate::worker::core::league::gen_random_players::gen_random_players;
+    use crate::worker::core::league::League;
+    use crate::worker::core::player::gen_ratings::TalentDistribution;
+    use crate::worker::db::LeagueDb;
+    use crate::worker::util::g::G;
+
+    // Regular season totals over gp games, at ppg points per game and everything else in proportion
+    fn stat_line(tid: i32, gp: i32, ppg: i32) -> PlayerStats {
+        let mut ps = PlayerStats::new(2025, tid, false);
+        ps.gp = gp;
+        ps.gs = gp;
+        ps.min = (gp * (10 + ppg)) as f64;
+        ps.pts = gp * ppg;
+        ps.fg = gp * ppg * 2 / 5;
+        ps.fga = gp * ppg * 4 / 5;
+        ps.ft = gp * ppg / 5;
+        ps.fta = gp * ppg / 4;
+        ps.orb = gp * ppg / 10;
+        ps.drb = gp * ppg / 4;
+        ps.ast = gp * ppg / 5;
+        ps.stl = gp * ppg / 20;
+        ps.blk = gp * ppg / 20;
+        ps.tov = gp * ppg / 10;
+        ps.pf = gp * 2;
+        ps
+    }
+
+    // Two teams that played all 82 games, where the first player scores 40 a game in star_gp of them and everyone else
+    // scores 10 in all of them. Returns the first player's pid.
+    fn new_league_db(star_gp: i32) -> (LeagueDb, i32) {
+        let g = G::from_defaults(2025).unwrap();
+        let teams: Vec<Team> = (0..2).map(|tid| Team {
+            tid,
+            cid: 0,
+            did: 0,
+            region: format!("Region {}", tid),
+            name: format!("Team {}", tid),
+            abbrev: format!("T{}", tid),
+            pop: 1.0,
+            coaching_rank: None
+        }).collect();
+        let mut players = gen_random_players(&teams, &g, &TalentDistribution::default(), 1).unwrap();
+        players.retain(|p| p.tid >= 0);
+        for (i, p) in players.iter_mut().enumerate() {
+            p.stats.push(if i == 0 { stat_line(p.tid, star_gp, 40) } else { stat_line(p.tid, 82, 10) });
+        }
+        let star = players[0].pid;
+        let team_seasons = (0..2).map(|tid| {
+            let mut ts = TeamSeasonAttrs::new(tid, 2025, 0, 0);
+            ts.gp = 82;
+            ts.won = 41;
+            ts.lost = 41;
+            ts.pts = 82 * 100;
+            ts.opp_pts = 82 * 100;
+            ts
+        }).collect();
+
+        let mut db = LeagueDb::open_in_memory().unwrap();
+        db.transaction(|tx| tx.put_league(&League {
+            g,
+            starting_season: 2025,
+            phase: PHASE_PLAYOFFS,
+            teams,
+            players,
+            games: Vec::new(),
+            team_seasons,
+            draft_picks: Vec::new(),
+            events: Vec::new(),
+            awards: Vec::new()
+        })).unwrap();
+        (db, star)
+    }
+
+    #[test]
+    fn awards_need_seventy_percent_of_the_games() {
+        // 58 of 82 games is enough, 57 isn't
+        let (mut db, star) = new_league_db(58);
+        let awards = db.transaction(do_awards).unwrap().unwrap();
+        assert_eq!(awards.mvp.unwrap().pid, star);
+
+        let (mut db, star) = new_league_db(57);
+        let awards = db.transaction(do_awards).unwrap().unwrap();
+        assert_ne!(awards.mvp.unwrap().pid, star);
+        assert!(!awards.all_league.iter().flat_map(|team| team.players.iter()).any(|p| p.pid == star));
+        let p = db.transaction(|tx| tx.get_player(star)).unwrap().unwrap();
+        assert!(p.awards.is_empty());
+    }
+
+    #[test]
+    fn fast_games_mean_no_awards() {
+        let (mut db, _) = new_league_db(82);
+        db.transaction(|tx| tx.put_game(&GameResult {
+            gid: 0,
+            season: 2025,
+            playoffs: false,
+            overtimes: 0,
+            teams: [GameResultTeam { tid: 0, pts: 100 }, GameResultTeam { tid: 1, pts: 90 }]
+        })).unwrap();
+        assert!(db.transaction(do_awards).unwrap().is_none());
+        assert!(db.transaction(|tx| tx.get_all_awards()).unwrap().is_empty());
+    }
+}
\ No newline at end of file
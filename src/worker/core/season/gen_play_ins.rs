@@ -0,0 +1,244 @@
+use crate::common::types::GameResult;
+use crate::common::types::PlayIn;
+use crate::common::types::PlayInGame;
+use crate::common::types::PlayoffSeriesTeam;
+
+// Every play-in format takes the 4 teams after the direct qualifiers and produces the last 2 seeds
+pub const NUM_PLAY_IN_TEAMS: usize = 4;
+pub const NUM_PLAY_IN_SPOTS: usize = 2;
+
+pub fn is_play_in_enabled(play_in_type: &str) -> bool {
+    !play_in_type.is_empty()
+}
+
+fn make_game(home: &PlayoffSeriesTeam, away: &PlayoffSeriesTeam) -> PlayInGame {
+    PlayInGame {
+        home: home.clone(),
+        away: away.clone(),
+        winner_tid: None
+    }
+}
+
+// qualifiers are all teams by seed, including the ones that will be in the play-in
+pub fn gen_play_in(play_in_type: &str, cid: Option<u8>, qualifiers: Vec<PlayoffSeriesTeam>) -> Result<PlayIn, String> {
+    if qualifiers.len() < NUM_PLAY_IN_TEAMS {
+        return Err(format!("playInType: the play-in needs at least {} teams", NUM_PLAY_IN_TEAMS));
+    }
+    let num_direct = qualifiers.len() - NUM_PLAY_IN_TEAMS;
+    let direct_teams = qualifiers[..num_direct].to_vec();
+    let teams = qualifiers[num_direct..].to_vec();
+
+    let games = match play_in_type {
+        // 7v8 and 9v10, then the loser of 7v8 hosts the winner of 9v10
+        "nba" => vec![make_game(&teams[0], &teams[1]), make_game(&teams[2], &teams[3])],
+        // 7v10 and 8v9, winners advance
+        "singleElimination" => vec![make_game(&teams[0], &teams[3]), make_game(&teams[1], &teams[2])],
+        _ => {
+            return Err(format!("playInType: unknown play-in type \"{}\"", play_in_type));
+        }
+    };
+
+    Ok(PlayIn {
+        play_in_type: play_in_type.to_string(),
+        cid,
+        direct_teams,
+        teams,
+        games
+    })
+}
+
+pub fn get_pending_play_in_games(play_in: &PlayIn) -> Vec<&PlayInGame> {
+    play_in.games.iter().filter(|game| game.winner_tid.is_none()).collect()
+}
+
+// Returns false if the game was not part of any play-in, and Err for a tie, which can't decide it
+pub fn record_play_in_game(play_ins: &mut [PlayIn], game: &GameResult) -> Result<bool, String> {
+    if game.teams[0].pts == game.teams[1].pts {
+        return Err(format!("Play-in game {} ended in a tie", game.gid));
+    }
+    let winner_tid = if game.teams[0].pts > game.teams[1].pts {
+        game.teams[0].tid
+    } else {
+        game.teams[1].tid
+    };
+
+    for play_in in play_ins.iter_mut() {
+        for play_in_game in play_in.games.iter_mut() {
+            if play_in_game.winner_tid.is_some() {
+                continue;
+            }
+            let tids = [play_in_game.home.tid, play_in_game.away.tid];
+            if tids.contains(&game.teams[0].tid) && tids.contains(&game.teams[1].tid) {
+                play_in_game.winner_tid = Some(winner_tid);
+                return Ok(true);
+            }
+        }
+    }
+    Ok(false)
+}
+
+fn get_loser(game: &PlayInGame) -> &PlayoffSeriesTeam {
+    if game.winner_tid == Some(game.home.tid) {
+        return &game.away;
+    }
+    &game.home
+}
+
+fn get_winner(game: &PlayInGame) -> &PlayoffSeriesTeam {
+    if game.winner_tid == Some(game.home.tid) {
+        return &game.home;
+    }
+    &game.away
+}
+
+// Schedules the next play-in game once the previous day is done
+pub fn advance_play_in(play_in: &mut PlayIn) {
+    if !get_pending_play_in_games(play_in).is_empty() {
+        return;
+    }
+    if play_in.play_in_type == "nba" && play_in.games.len() == 2 {
+        let game = make_game(get_loser(&play_in.games[0]), get_winner(&play_in.games[1]));
+        play_in.games.push(game);
+    }
+}
+
+pub fn is_play_in_over(play_in: &PlayIn) -> bool {
+    if !get_pending_play_in_games(play_in).is_empty() {
+        return false;
+    }
+    if play_in.play_in_type == "nba" {
+        return play_in.games.len() == 3;
+    }
+    true
+}
+
+// All playoff teams for this bracket, with the play-in winners reassigned to the last seeds
+pub fn get_play_in_seeds(play_in: &PlayIn) -> Vec<PlayoffSeriesTeam> {
+    let mut winners: Vec<PlayoffSeriesTeam> = if play_in.play_in_type == "nba" {
+        vec![get_winner(&play_in.games[0]).clone(), get_winner(&play_in.games[2]).clone()]
+    } else {
+        let mut winners: Vec<PlayoffSeriesTeam> = play_in.games.iter().map(|game| get_winner(game).clone()).collect();
+        winners.sort_by_key(|t| t.seed);
+        winners
+    };
+
+    let mut teams = play_in.direct_teams.clone();
+    for winner in winners.iter_mut() {
+        winner.seed = teams.len() as u8 + 1;
+        teams.push(winner.clone());
+    }
+    teams
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::types::GameResultTeam;
+
+    // Seeds 1 to 10, with tid equal to seed
+    fn gen_test_play_in(play_in_type: &str) -> PlayIn {
+        let qualifiers: Vec<PlayoffSeriesTeam> = (1..=10).map(|seed| PlayoffSeriesTeam {
+            tid: seed as i32,
+            cid: 0,
+            seed,
+            league_rank: seed as usize,
+            won: 0
+        }).collect();
+        gen_play_in(play_in_type, Some(0), qualifiers).unwrap()
+    }
+
+    // Plays the pending game between winner_tid and loser_tid
+    fn play(play_in: &mut PlayIn, winner_tid: i32, loser_tid: i32) {
+        let game = GameResult {
+            gid: 0,
+            season: 2025,
+            playoffs: true,
+            overtimes: 0,
+            teams: [
+                GameResultTeam {
+                    tid: winner_tid,
+                    pts: 100
+                },
+                GameResultTeam {
+                    tid: loser_tid,
+                    pts: 90
+                }
+            ]
+        };
+        assert!(record_play_in_game(std::slice::from_mut(play_in), &game).unwrap());
+        advance_play_in(play_in);
+    }
+
+    fn get_matchups(play_in: &PlayIn) -> Vec<(i32, i32)> {
+        play_in.games.iter().map(|game| (game.home.tid, game.away.tid)).collect()
+    }
+
+    fn get_last_seeds(play_in: &PlayIn) -> Vec<(u8, i32)> {
+        get_play_in_seeds(play_in)[6..].iter().map(|t| (t.seed, t.tid)).collect()
+    }
+
+    #[test]
+    fn nba_winner_of_seven_eight_is_the_seventh_seed() {
+        let mut play_in = gen_test_play_in("nba");
+        assert_eq!(play_in.direct_teams.len(), 6);
+        assert_eq!(get_matchups(&play_in), vec![(7, 8), (9, 10)]);
+
+        play(&mut play_in, 7, 8);
+        assert!(!is_play_in_over(&play_in));
+        play(&mut play_in, 9, 10);
+        // The loser of 7v8 hosts the winner of 9v10
+        assert_eq!(get_matchups(&play_in), vec![(7, 8), (9, 10), (8, 9)]);
+        assert!(!is_play_in_over(&play_in));
+        play(&mut play_in, 8, 9);
+        assert!(is_play_in_over(&play_in));
+        assert_eq!(get_last_seeds(&play_in), vec![(7, 7), (8, 8)]);
+    }
+
+    #[test]
+    fn nba_upsets_are_reseeded() {
+        let mut play_in = gen_test_play_in("nba");
+        play(&mut play_in, 8, 7);
+        play(&mut play_in, 10, 9);
+        assert_eq!(get_matchups(&play_in)[2], (7, 10));
+        play(&mut play_in, 10, 7);
+        assert_eq!(get_last_seeds(&play_in), vec![(7, 8), (8, 10)]);
+    }
+
+    #[test]
+    fn single_elimination_winners_keep_their_order() {
+        let mut play_in = gen_test_play_in("singleElimination");
+        assert_eq!(get_matchups(&play_in), vec![(7, 10), (8, 9)]);
+        play(&mut play_in, 10, 7);
+        assert!(!is_play_in_over(&play_in));
+        play(&mut play_in, 9, 8);
+        assert!(is_play_in_over(&play_in));
+        assert_eq!(play_in.games.len(), 2);
+        assert_eq!(get_last_seeds(&play_in), vec![(7, 9), (8, 10)]);
+    }
+
+    #[test]
+    fn ties_and_other_games_dont_count() {
+        let mut play_in = gen_test_play_in("nba");
+        let mut game = GameResult {
+            gid: 0,
+            season: 2025,
+            playoffs: true,
+            overtimes: 1,
+            teams: [
+                GameResultTeam {
+                    tid: 7,
+                    pts: 100
+                },
+                GameResultTeam {
+                    tid: 8,
+                    pts: 100
+                }
+            ]
+        };
+        assert!(record_play_in_game(std::slice::from_mut(&mut play_in), &game).is_err());
+        game.teams[1].tid = 9;
+        game.teams[1].pts = 90;
+        assert!(!record_play_in_game(std::slice::from_mut(&mut play_in), &game).unwrap());
+        assert_eq!(get_pending_play_in_games(&play_in).len(), 2);
+    }
+}
\ No newline at end of file
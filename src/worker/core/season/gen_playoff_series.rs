@@ -0,0 +1,218 @@
+use crate::common::types::PlayoffSeries;
+use crate::common::types::PlayoffSeriesMatchup;
+use crate::common::types::PlayoffSeriesTeam;
+use crate::worker::core::season::gen_play_ins::gen_play_in;
+use crate::worker::core::season::gen_play_ins::is_play_in_enabled;
+use crate::worker::core::season::gen_play_ins::NUM_PLAY_IN_SPOTS;
+use crate::worker::core::season::gen_play_ins::NUM_PLAY_IN_TEAMS;
+use crate::worker::core::season::get_standings::Standings;
+use crate::worker::core::season::get_standings::StandingsGroup;
+use crate::worker::util::g::G;
+use std::collections::HashSet;
+
+pub fn get_num_playoff_teams(g: &G) -> usize {
+    let num_rounds = g.num_games_playoff_series().len() as u32;
+    let bracket_size = 2_usize.pow(num_rounds);
+    bracket_size.saturating_sub(g.num_playoff_byes() as usize)
+}
+
+// Split the bracket by conference only when there are exactly 2 confs that can each fill half of it
+pub fn get_playoffs_by_conf(standings: &Standings, g: &G) -> bool {
+    let num_playoff_teams = get_num_playoff_teams(g);
+    if standings.confs.len() != 2 ||
+        g.num_games_playoff_series().len() < 2 ||
+        !num_playoff_teams.is_multiple_of(2) ||
+        !g.num_playoff_byes().is_multiple_of(2) {
+        return false;
+    }
+    standings.confs.iter().all(|conf| conf.teams.len() >= num_playoff_teams / 2)
+}
+
+// Seeds 1 and 2 meet in the final round, seeds 1 and 4 in the round before that, etc. For a bracket of 8 this is
+// [1, 8, 4, 5, 2, 7, 3, 6], and every adjacent pair is a first round matchup.
+pub fn get_bracket_order(bracket_size: usize) -> Vec<usize> {
+    let mut order = vec![1];
+    while order.len() < bracket_size {
+        let n = order.len() * 2;
+        order = order.iter().flat_map(|seed| vec![*seed, n + 1 - *seed]).collect();
+    }
+    order
+}
+
+// The top playoffs_num_teams_div teams in each division are guaranteed a spot, the rest are filled by record. Seeds
+// follow the order of the group standings, so a division winner with a worse record is not moved up.
+pub fn get_playoff_qualifiers(group: &StandingsGroup, standings: &Standings, num_teams: usize, playoffs_num_teams_div: u8) -> Result<Vec<PlayoffSeriesTeam>, String> {
+    let in_group: HashSet<i32> = group.teams.iter().map(|entry| entry.season_attrs.tid).collect();
+
+    let mut qualified: HashSet<i32> = HashSet::new();
+    for div in standings.divs.iter() {
+        for entry in div.teams.iter().take(playoffs_num_teams_div as usize) {
+            if in_group.contains(&entry.season_attrs.tid) {
+                qualified.insert(entry.season_attrs.tid);
+            }
+        }
+    }
+    if qualified.len() > num_teams {
+        return Err(format!(
+            "playoffsNumTeamsDiv: {} guaranteed playoff spots in {}, but only {} teams make the playoffs",
+            qualified.len(),
+            group.name,
+            num_teams
+        ));
+    }
+
+    for entry in group.teams.iter() {
+        if qualified.len() >= num_teams {
+            break;
+        }
+        qualified.insert(entry.season_attrs.tid);
+    }
+    if qualified.len() < num_teams {
+        return Err(format!("{} has {} teams, but {} are needed for the playoffs", group.name, group.teams.len(), num_teams));
+    }
+
+    let mut teams = Vec::new();
+    for entry in group.teams.iter() {
+        if !qualified.contains(&entry.season_attrs.tid) {
+            continue;
+        }
+        let league_rank = standings.league.teams
+            .iter()
+            .find(|league_entry| league_entry.season_attrs.tid == entry.season_attrs.tid)
+            .unwrap()
+            .rank;
+        teams.push(PlayoffSeriesTeam {
+            tid: entry.season_attrs.tid,
+            cid: entry.season_attrs.cid,
+            seed: teams.len() as u8 + 1,
+            league_rank,
+            won: 0
+        });
+    }
+
+    Ok(teams)
+}
+
+// First round matchups for one bracket. Seeds beyond the number of teams don't exist, so the top seeds get byes.
+pub fn make_matchups(teams: &[PlayoffSeriesTeam], bracket_size: usize) -> Vec<PlayoffSeriesMatchup> {
+    let order = get_bracket_order(bracket_size);
+    let mut matchups = Vec::new();
+    for i in (0..order.len()).step_by(2) {
+        let home = teams.iter().find(|t| t.seed as usize == order[i]).cloned();
+        let away = teams.iter().find(|t| t.seed as usize == order[i + 1]).cloned();
+        matchups.push(PlayoffSeriesMatchup {
+            home: home.unwrap(),
+            away
+        });
+    }
+    matchups
+}
+
+pub fn get_bracket_size(num_rounds: usize, by_conf: bool) -> usize {
+    if by_conf {
+        return 2_usize.pow(num_rounds as u32 - 1);
+    }
+    2_usize.pow(num_rounds as u32)
+}
+
+pub fn gen_playoff_series(standings: &Standings, g: &G) -> Result<PlayoffSeries, String> {
+    let season = g.season();
+    let num_rounds = g.num_games_playoff_series().len();
+    if num_rounds == 0 {
+        return Err("numGamesPlayoffSeries: at least one playoff round is required".to_string());
+    }
+    let num_playoff_teams = get_num_playoff_teams(g);
+    let num_playoff_byes = g.num_playoff_byes() as usize;
+    if num_playoff_teams < 2 || num_playoff_byes > 2_usize.pow(num_rounds as u32) / 2 {
+        return Err("numPlayoffByes: too many byes for the number of playoff rounds".to_string());
+    }
+    let playoffs_num_teams_div = g.playoffs_num_teams_div();
+
+    let by_conf = get_playoffs_by_conf(standings, g);
+    let bracket_size = get_bracket_size(num_rounds, by_conf);
+
+    let groups: Vec<&StandingsGroup> = if by_conf {
+        standings.confs.iter().collect()
+    } else {
+        vec![&standings.league]
+    };
+    let num_teams_per_group = num_playoff_teams / groups.len();
+
+    let play_in_type = g.play_in_type();
+    let play_in_enabled = is_play_in_enabled(play_in_type);
+    if play_in_enabled && num_teams_per_group < NUM_PLAY_IN_SPOTS {
+        return Err("playInType: not enough playoff teams for a play-in".to_string());
+    }
+
+    let mut first_round = Vec::new();
+    let mut play_ins = Vec::new();
+    for group in groups {
+        if play_in_enabled {
+            let num_teams = num_teams_per_group - NUM_PLAY_IN_SPOTS + NUM_PLAY_IN_TEAMS;
+            let teams = get_playoff_qualifiers(group, standings, num_teams, playoffs_num_teams_div)?;
+            play_ins.push(gen_play_in(play_in_type, group.cid, teams)?);
+        } else {
+            let teams = get_playoff_qualifiers(group, standings, num_teams_per_group, playoffs_num_teams_div)?;
+            first_round.extend(make_matchups(&teams, bracket_size));
+        }
+    }
+
+    if play_in_enabled {
+        return Ok(PlayoffSeries {
+            season,
+            current_round: 0,
+            by_conf,
+            play_ins: Some(play_ins),
+            series: Vec::new()
+        });
+    }
+
+    Ok(PlayoffSeries {
+        season,
+        current_round: 0,
+        by_conf,
+        play_ins: None,
+        series: vec![first_round]
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::league_settings::from_toml;
+
+    fn get_teams(num_teams: u8) -> Vec<PlayoffSeriesTeam> {
+        (1..=num_teams).map(|seed| PlayoffSeriesTeam {
+            tid: 100 + seed as i32,
+            cid: 0,
+            seed,
+            league_rank: seed as usize,
+            won: 0
+        }).collect()
+    }
+
+    fn get_seeds(matchups: &[PlayoffSeriesMatchup]) -> Vec<(u8, Option<u8>)> {
+        matchups.iter().map(|matchup| (matchup.home.seed, matchup.away.as_ref().map(|t| t.seed))).collect()
+    }
+
+    #[test]
+    fn bracket_order() {
+        assert_eq!(get_bracket_order(2), vec![1, 2]);
+        assert_eq!(get_bracket_order(8), vec![1, 8, 4, 5, 2, 7, 3, 6]);
+    }
+
+    #[test]
+    fn top_seeds_get_byes() {
+        assert_eq!(get_seeds(&make_matchups(&get_teams(8), 8)), vec![(1, Some(8)), (4, Some(5)), (2, Some(7)), (3, Some(6))]);
+        assert_eq!(get_seeds(&make_matchups(&get_teams(6), 8)), vec![(1, None), (4, Some(5)), (2, None), (3, Some(6))]);
+        assert_eq!(get_seeds(&make_matchups(&get_teams(5), 8)), vec![(1, None), (4, Some(5)), (2, None), (3, None)]);
+    }
+
+    #[test]
+    fn byes_take_spots_from_the_bracket() {
+        let g = G::new(from_toml("numGamesPlayoffSeries = [7, 7, 7]\nnumPlayoffByes = 2").unwrap(), 2025).unwrap();
+        assert_eq!(get_num_playoff_teams(&g), 6);
+        assert_eq!(get_bracket_size(3, false), 8);
+        assert_eq!(get_bracket_size(3, true), 4);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,311 @@
+use crate::common::types::GameResult;
+use crate::common::types::PlayoffSeries;
+use crate::common::types::PlayoffSeriesTeam;
+use crate::common::types::ScheduleGame;
+use crate::common::types::TeamSeasonAttrs;
+use crate::worker::core::game::play::GameToSim;
+use crate::worker::core::game::sim_games::SimGame;
+use crate::worker::core::season::gen_playoff_series::gen_playoff_series;
+use crate::worker::core::season::get_standings::get_standings;
+use crate::worker::core::season::get_standings::update_season_attrs;
+use crate::worker::core::season::new_schedule_playoffs_day::get_next_games;
+use crate::worker::core::season::new_schedule_playoffs_day::get_num_games_to_win;
+use crate::worker::core::season::new_schedule_playoffs_day::get_series_winner;
+use crate::worker::core::season::new_schedule_playoffs_day::new_schedule_playoffs_day;
+use crate::worker::core::season::new_schedule_playoffs_day::record_playoff_game;
+use crate::worker::util::g::G;
+use crate::worker::util::parallel::parallel_map;
+use crate::worker::util::random::derive_seed;
+use crate::worker::util::random::rng_from_seed;
+use std::collections::HashMap;
+
+pub struct TeamPlayoffOdds {
+    pub tid: i32,
+    // seeds[i] is the probability of finishing the regular season as seed i + 1 of the team's bracket (conference or
+    // league), before the play-in
+    pub seeds: Vec<f64>,
+    pub play_in: f64,
+    // Probability of being in the first round, directly or through the play-in
+    pub playoffs: f64,
+    // rounds[i] is the probability of winning round i, so the last one is the title
+    pub rounds: Vec<f64>
+}
+
+impl TeamPlayoffOdds {
+    pub fn title(&self) -> f64 {
+        self.rounds.last().copied().unwrap_or(0.0)
+    }
+}
+
+// Where the season stands: the team seasons, played games and remaining schedule, or the playoffs so far if they
+// started. coin_flip_seed should be the one the real standings use, so coin_flip tiebreakers come out the way they will.
+pub struct SeasonSoFar<'a> {
+    pub team_seasons: &'a [TeamSeasonAttrs],
+    pub games: &'a [GameResult],
+    pub schedule: &'a [ScheduleGame],
+    pub playoff_series: Option<&'a PlayoffSeries>,
+    pub coin_flip_seed: u64
+}
+
+// What happened to the playoff teams in one simulated season
+struct RunResult {
+    seeds: Vec<(i32, u8)>,
+    play_in: Vec<i32>,
+    playoffs: Vec<i32>,
+    rounds_won: HashMap<i32, usize>
+}
+
+fn get_run_result(playoff_series: &PlayoffSeries, num_games_playoff_series: &[u8]) -> RunResult {
+    let mut seeds = Vec::new();
+    let mut play_in = Vec::new();
+    if let Some(play_ins) = &playoff_series.play_ins {
+        for p in play_ins.iter() {
+            seeds.extend(p.direct_teams.iter().map(|t| (t.tid, t.seed)));
+            seeds.extend(p.teams.iter().map(|t| (t.tid, t.seed)));
+            play_in.extend(p.teams.iter().map(|t| t.tid));
+        }
+    }
+
+    let mut playoffs = Vec::new();
+    let mut rounds_won = HashMap::new();
+    for (round, matchups) in playoff_series.series.iter().enumerate() {
+        let num_games_to_win = get_num_games_to_win(num_games_playoff_series[round]);
+        for matchup in matchups.iter() {
+            if round == 0 {
+                let mut teams: Vec<&PlayoffSeriesTeam> = vec![&matchup.home];
+                if let Some(away) = &matchup.away {
+                    teams.push(away);
+                }
+                for t in teams {
+                    playoffs.push(t.tid);
+                    // Without a play-in, the first round seeds are the regular season seeds
+                    if playoff_series.play_ins.is_none() {
+                        seeds.push((t.tid, t.seed));
+                    }
+                }
+            }
+            let winner = get_series_winner(matchup, num_games_to_win);
+            if let Some(winner) = winner {
+                rounds_won.insert(winner.tid, round + 1);
+            }
+        }
+    }
+
+    RunResult {
+        seeds,
+        play_in,
+        playoffs,
+        rounds_won
+    }
+}
+
+// Plays the rest of the regular season (unless playoff_series already exists) and the rest of the playoffs
+fn sim_run(g: &G, season: &SeasonSoFar, run_seed: u64, sim_game: &SimGame) -> Result<RunResult, String> {
+    let mut rng = rng_from_seed(run_seed);
+    let mut gid = season.games.iter().map(|game| game.gid).chain(season.schedule.iter().map(|game| game.gid)).max().unwrap_or(-1) + 1;
+    let mut day = season.schedule.iter().map(|game| game.day).max().unwrap_or(0) + 1;
+
+    let mut playoff_series = match season.playoff_series {
+        Some(playoff_series) => playoff_series.clone(),
+        None => {
+            let mut games = season.games.to_vec();
+            for game in season.schedule.iter() {
+                let sim_game_result = sim_game(g, &GameToSim {
+                    gid: game.gid,
+                    season: g.season(),
+                    day: game.day,
+                    home_tid: game.home_tid,
+                    away_tid: game.away_tid,
+                    playoffs: false
+                }, &mut rng)?;
+                games.push(sim_game_result.result);
+            }
+            let mut team_seasons = season.team_seasons.to_vec();
+            update_season_attrs(&mut team_seasons, &games, g.otl());
+            gen_playoff_series(&get_standings(&team_seasons, &games, g, season.coin_flip_seed), g)?
+        }
+    };
+
+    let num_games_playoff_series = g.num_games_playoff_series();
+    while !new_schedule_playoffs_day(&mut playoff_series, num_games_playoff_series) {
+        for game in get_next_games(&playoff_series, num_games_playoff_series) {
+            let sim_game_result = sim_game(g, &GameToSim {
+                gid,
+                season: g.season(),
+                day,
+                home_tid: game.home_tid,
+                away_tid: game.away_tid,
+                playoffs: true
+            }, &mut rng)?;
+            record_playoff_game(&mut playoff_series, &sim_game_result.result)?;
+            gid += 1;
+        }
+        day += 1;
+    }
+
+    Ok(get_run_result(&playoff_series, num_games_playoff_series))
+}
+
+// A team in the playoffs with no team season has no odds to add to
+fn get_odds_index(indexes: &HashMap<i32, usize>, tid: i32) -> Result<usize, String> {
+    let index = indexes.get(&tid);
+    if index.is_none() {
+        return Err(format!("Team {} is in the playoffs but has no team season", tid));
+    }
+    Ok(*index.unwrap())
+}
+
+// Projects the playoffs by playing the rest of the season num_runs times with sim_game. Runs are played on up to
+// num_threads threads, with results that only depend on seed. Returns one entry per team in season.team_seasons, in
+// the same order.
+pub fn get_playoff_odds(g: &G, season: &SeasonSoFar, num_runs: usize, seed: u64, num_threads: usize, sim_game: &SimGame) -> Result<Vec<TeamPlayoffOdds>, String> {
+    if num_runs == 0 {
+        return Err("numRuns: must be at least 1".to_string());
+    }
+
+    let run_results = parallel_map(num_runs, num_threads, |run| {
+        let run_seed = derive_seed(seed, &[g.season() as i64, run as i64]);
+        sim_run(g, season, run_seed, sim_game)
+    });
+    let run_results: Vec<RunResult> = run_results.into_iter().collect::<Result<_, _>>()?;
+
+    let num_seeds = run_results
+        .iter()
+        .flat_map(|run_result| run_result.seeds.iter().map(|(_, seed)| *seed as usize))
+        .max()
+        .unwrap_or(0);
+    let num_rounds = g.num_games_playoff_series().len();
+    let mut all_odds: Vec<TeamPlayoffOdds> = season.team_seasons.iter().map(|ts| TeamPlayoffOdds {
+        tid: ts.tid,
+        seeds: vec![0.0; num_seeds],
+        play_in: 0.0,
+        playoffs: 0.0,
+        rounds: vec![0.0; num_rounds]
+    }).collect();
+    let indexes: HashMap<i32, usize> = all_odds.iter().enumerate().map(|(i, odds)| (odds.tid, i)).collect();
+
+    let weight = 1.0 / num_runs as f64;
+    for run_result in run_results.iter() {
+        for (tid, seed) in run_result.seeds.iter() {
+            all_odds[get_odds_index(&indexes, *tid)?].seeds[*seed as usize - 1] += weight;
+        }
+        for tid in run_result.play_in.iter() {
+            all_odds[get_odds_index(&indexes, *tid)?].play_in += weight;
+        }
+        for tid in run_result.playoffs.iter() {
+            all_odds[get_odds_index(&indexes, *tid)?].playoffs += weight;
+        }
+        for (tid, rounds_won) in run_result.rounds_won.iter() {
+            for round in 0..*rounds_won {
+                all_odds[get_odds_index(&indexes, *tid)?].rounds[round] += weight;
+            }
+        }
+    }
+
+    Ok(all_odds)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::types::GameResultTeam;
+    use crate::worker::core::game::play::SimGameResult;
+    use rand::rngs::StdRng;
+    use rand::Rng;
+
+    // Home teams win 60% of their games by a point
+    fn sim_game(_: &G, game: &GameToSim, rng: &mut StdRng) -> Result<SimGameResult, String> {
+        let margin = if rng.gen_bool(0.6) { 1 } else { -1 };
+        Ok(SimGameResult {
+            result: GameResult {
+                gid: game.gid,
+                season: game.season,
+                playoffs: game.playoffs,
+                overtimes: 0,
+                teams: [
+                    GameResultTeam { tid: game.home_tid, pts: 100 + margin },
+                    GameResultTeam { tid: game.away_tid, pts: 100 }
+                ]
+            },
+            box_score: None,
+            play_by_play: None
+        })
+    }
+
+    // 8 teams, 4 in each of the default conferences' first divisions, with a 2 round playoff of 2 teams per conference
+    // and a round robin left to play
+    fn new_season() -> (G, Vec<TeamSeasonAttrs>, Vec<ScheduleGame>) {
+        let mut g = G::from_defaults(2025).unwrap();
+        g.set_num_games_playoff_series(i32::MIN, vec![7, 7]).unwrap();
+        g.set_play_in_type(i32::MIN, "").unwrap();
+        let team_seasons: Vec<TeamSeasonAttrs> = (0..8).map(|tid| {
+            let cid = (tid % 2) as u8;
+            TeamSeasonAttrs::new(tid, 2025, cid, cid * 3)
+        }).collect();
+        let mut schedule = Vec::new();
+        for home_tid in 0..8 {
+            for away_tid in 0..8 {
+                if home_tid != away_tid {
+                    schedule.push(ScheduleGame { gid: schedule.len() as i32, day: schedule.len() as i32, home_tid, away_tid });
+                }
+            }
+        }
+        (g, team_seasons, schedule)
+    }
+
+    fn season_so_far<'a>(team_seasons: &'a [TeamSeasonAttrs], schedule: &'a [ScheduleGame], playoff_series: Option<&'a PlayoffSeries>) -> SeasonSoFar<'a> {
+        SeasonSoFar {
+            team_seasons,
+            games: &[],
+            schedule,
+            playoff_series,
+            coin_flip_seed: 1
+        }
+    }
+
+    #[test]
+    fn odds_add_up() {
+        let (g, team_seasons, schedule) = new_season();
+        let all_odds = get_playoff_odds(&g, &season_so_far(&team_seasons, &schedule, None), 200, 1, 2, &sim_game).unwrap();
+        assert_eq!(all_odds.iter().map(|odds| odds.tid).collect::<Vec<i32>>(), (0..8).collect::<Vec<i32>>());
+
+        let sum = |f: &dyn Fn(&TeamPlayoffOdds) -> f64| all_odds.iter().map(f).sum::<f64>();
+        assert!((sum(&|odds| odds.playoffs) - 4.0).abs() < 1e-9);
+        assert!((sum(&|odds| odds.rounds[0]) - 2.0).abs() < 1e-9);
+        assert!((sum(&|odds| odds.title()) - 1.0).abs() < 1e-9);
+        // One first and one second seed in each conference
+        assert!((sum(&|odds| odds.seeds[0]) - 2.0).abs() < 1e-9);
+        assert!((sum(&|odds| odds.seeds[1]) - 2.0).abs() < 1e-9);
+        for odds in all_odds.iter() {
+            assert!((odds.seeds.iter().sum::<f64>() - odds.playoffs).abs() < 1e-9);
+            assert!(odds.title() <= odds.rounds[0] && odds.rounds[0] <= odds.playoffs);
+        }
+    }
+
+    #[test]
+    fn odds_dont_depend_on_num_threads() {
+        let (g, team_seasons, schedule) = new_season();
+        let get_title_odds = |num_threads: usize| -> Vec<f64> {
+            let all_odds = get_playoff_odds(&g, &season_so_far(&team_seasons, &schedule, None), 50, 3, num_threads, &sim_game).unwrap();
+            all_odds.iter().map(|odds| odds.title()).collect()
+        };
+        assert_eq!(get_title_odds(1), get_title_odds(4));
+    }
+
+    #[test]
+    fn playoff_team_without_team_season_is_an_error() {
+        let (g, team_seasons, _) = new_season();
+        let playoff_series = gen_playoff_series(&get_standings(&team_seasons, &[], &g, 1), &g).unwrap();
+        let champion = playoff_series.series[0][0].home.tid;
+        let team_seasons: Vec<TeamSeasonAttrs> = team_seasons.into_iter().filter(|ts| ts.tid != champion).collect();
+        let result = get_playoff_odds(&g, &season_so_far(&team_seasons, &[], Some(&playoff_series)), 10, 1, 1, &sim_game);
+        assert_eq!(result.err().unwrap(), format!("Team {} is in the playoffs but has no team season", champion));
+    }
+
+    #[test]
+    fn no_runs_is_an_error() {
+        let (g, team_seasons, schedule) = new_season();
+        let result = get_playoff_odds(&g, &season_so_far(&team_seasons, &schedule, None), 0, 1, 1, &sim_game);
+        assert_eq!(result.err().unwrap(), "numRuns: must be at least 1");
+    }
+}
\ No newline at end of file
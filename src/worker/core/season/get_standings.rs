@@ -0,0 +1,174 @@
+use crate::common::constants::TIEBREAKERS;
+use crate::common::points_formula::PointsFormula;
+use crate::common::types::Div;
+use crate::common::types::GameResult;
+use crate::common::types::TeamSeasonAttrs;
+use crate::worker::util::g::G;
+use crate::worker::util::order_teams::get_standings_value;
+use crate::worker::util::order_teams::order_teams;
+use crate::worker::util::order_teams::OrderedTeam;
+use crate::worker::util::order_teams::TiebreakerContext;
+use std::collections::HashSet;
+
+pub struct StandingsEntry {
+    pub rank: usize,
+    pub season_attrs: TeamSeasonAttrs,
+    // Standings points, only set when the league uses a points_formula
+    pub points: Option<f64>,
+    pub tiebreaker: Option<String>
+}
+
+impl StandingsEntry {
+    // Human readable name of the tiebreaker that decided this placement, for the playoff race explanation
+    pub fn tiebreaker_name(&self) -> Option<String> {
+        let tiebreaker = self.tiebreaker.as_ref()?;
+        TIEBREAKERS::new().get(tiebreaker).cloned()
+    }
+}
+
+pub struct StandingsGroup {
+    pub name: String,
+    pub cid: Option<u8>,
+    pub teams: Vec<StandingsEntry>
+}
+
+pub struct Standings {
+    pub divs: Vec<StandingsGroup>,
+    pub confs: Vec<StandingsGroup>,
+    pub league: StandingsGroup
+}
+
+// Fills won/lost/tied/otl/pts/opp_pts/gp from the regular season games. With otl disabled, overtime losses are
+// plain losses.
+pub fn update_season_attrs(team_seasons: &mut [TeamSeasonAttrs], games: &[GameResult], otl: bool) {
+    for team_season in team_seasons.iter_mut() {
+        team_season.won = 0;
+        team_season.lost = 0;
+        team_season.tied = 0;
+        team_season.otl = 0;
+        team_season.pts = 0;
+        team_season.opp_pts = 0;
+        team_season.gp = 0;
+    }
+
+    for game in games.iter() {
+        if game.playoffs {
+            continue;
+        }
+        for t in 0..2 {
+            let opp_t = 1 - t;
+            let team_season = team_seasons.iter_mut().find(|ts| ts.tid == game.teams[t].tid);
+            if team_season.is_none() {
+                continue;
+            }
+            let team_season = team_season.unwrap();
+            team_season.gp += 1;
+            team_season.pts += game.teams[t].pts;
+            team_season.opp_pts += game.teams[opp_t].pts;
+            if game.teams[t].pts > game.teams[opp_t].pts {
+                team_season.won += 1;
+            } else if game.teams[t].pts < game.teams[opp_t].pts {
+                if otl && game.overtimes > 0 {
+                    team_season.otl += 1;
+                } else {
+                    team_season.lost += 1;
+                }
+            } else {
+                team_season.tied += 1;
+            }
+        }
+    }
+}
+
+fn to_group(name: String, cid: Option<u8>, ordered: Vec<OrderedTeam>, team_seasons: &[TeamSeasonAttrs], points_formula: Option<&PointsFormula>) -> StandingsGroup {
+    let teams = ordered.into_iter().enumerate().map(|(i, ordered_team)| {
+        let season_attrs = team_seasons.iter().find(|ts| ts.tid == ordered_team.tid).unwrap().clone();
+        let points = if points_formula.is_some() {
+            Some(get_standings_value(&season_attrs, points_formula))
+        } else {
+            None
+        };
+        StandingsEntry {
+            rank: i + 1,
+            season_attrs,
+            points,
+            tiebreaker: ordered_team.tiebreaker
+        }
+    }).collect();
+
+    StandingsGroup {
+        name,
+        cid,
+        teams
+    }
+}
+
+pub fn get_div_winners(team_seasons: &[TeamSeasonAttrs], games: &[GameResult], divs: &[Div], tiebreakers: &[String], otl: bool, points_formula: Option<&PointsFormula>, coin_flip_seed: u64) -> HashSet<i32> {
+    // div_winner can't be used to decide who wins the division
+    let div_tiebreakers: Vec<String> = tiebreakers
+        .iter()
+        .filter(|key| key.as_str() != "divWinner")
+        .cloned()
+        .collect();
+    let ctx = TiebreakerContext {
+        all_teams: team_seasons,
+        games,
+        div_winners: HashSet::new(),
+        otl,
+        points_formula,
+        coin_flip_seed
+    };
+
+    let mut div_winners = HashSet::new();
+    for div in divs.iter() {
+        let teams: Vec<TeamSeasonAttrs> = team_seasons.iter().filter(|ts| ts.did == div.did).cloned().collect();
+        let ordered = order_teams(&teams, &ctx, &div_tiebreakers);
+        if !ordered.is_empty() {
+            div_winners.insert(ordered[0].tid);
+        }
+    }
+
+    div_winners
+}
+
+// Ranks teams within each division, each conference and the whole league. coin_flip_seed decides the coin_flip
+// tiebreaker, see random::coin_flip_seed.
+pub fn get_standings(team_seasons: &[TeamSeasonAttrs], games: &[GameResult], g: &G, coin_flip_seed: u64) -> Standings {
+    let confs = g.confs();
+    let divs = g.divs();
+    let tiebreakers = g.tiebreakers();
+    let otl = g.otl();
+    let points_formula = g.points_formula();
+
+    let ctx = TiebreakerContext {
+        all_teams: team_seasons,
+        games,
+        div_winners: get_div_winners(team_seasons, games, divs, &tiebreakers, otl, points_formula, coin_flip_seed),
+        otl,
+        points_formula,
+        coin_flip_seed
+    };
+
+    let mut div_groups = Vec::new();
+    for div in divs.iter() {
+        let teams: Vec<TeamSeasonAttrs> = team_seasons.iter().filter(|ts| ts.did == div.did).cloned().collect();
+        let ordered = order_teams(&teams, &ctx, &tiebreakers);
+        div_groups.push(to_group(div.name.clone(), Some(div.cid), ordered, team_seasons, points_formula));
+    }
+
+    let mut conf_groups = Vec::new();
+    for conf in confs.iter() {
+        let teams: Vec<TeamSeasonAttrs> = team_seasons.iter().filter(|ts| ts.cid == conf.cid).cloned().collect();
+        let ordered = order_teams(&teams, &ctx, &tiebreakers);
+        conf_groups.push(to_group(conf.name.clone(), Some(conf.cid), ordered, team_seasons, points_formula));
+    }
+
+    let ordered = order_teams(team_seasons, &ctx, &tiebreakers);
+    let league = to_group("League".to_string(), None, ordered, team_seasons, points_formula);
+
+    Standings {
+        divs: div_groups,
+        confs: conf_groups,
+        league
+    }
+}
\ No newline at end of file
@@ -0,0 +1,8 @@
+pub mod get_standings;
+pub mod gen_playoff_series;
+pub mod gen_play_ins;
+pub mod new_schedule;
+pub mod new_schedule_playoffs_day;
+pub mod get_playoff_odds;
+pub mod adv_stats;
+pub mod do_awards;
\ No newline at end of file
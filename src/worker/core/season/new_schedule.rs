@@ -0,0 +1,76 @@
+use crate::common::types::ScheduleGame;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+
+// Round robin with the circle method, repeated until every team has num_games games. Each round is one day, home and
+// away alternate between repeats. With an odd number of teams, one team sits out each round.
+pub fn new_schedule(tids: &[i32], num_games: i32, first_gid: i32, rng: &mut StdRng) -> Vec<ScheduleGame> {
+    let mut slots: Vec<Option<i32>> = tids.iter().map(|tid| Some(*tid)).collect();
+    slots.shuffle(rng);
+    if slots.len() % 2 == 1 {
+        slots.push(None);
+    }
+    let num_slots = slots.len();
+
+    let mut num_games_by_tid: Vec<(i32, i32)> = tids.iter().map(|tid| (*tid, 0)).collect();
+    let mut schedule = Vec::new();
+    if num_slots < 2 {
+        return schedule;
+    }
+
+    let mut day = 0;
+    let mut round = 0;
+    // Stop after a full cycle of rounds where nobody could play, which only happens when num_games can't be reached
+    // by every team (odd numbers of teams and games)
+    let mut num_empty_rounds = 0;
+    while num_empty_rounds < num_slots - 1 {
+        let mut scheduled_today = false;
+        for i in 0..num_slots / 2 {
+            let a = slots[i];
+            let b = slots[num_slots - 1 - i];
+            if a.is_none() || b.is_none() {
+                continue;
+            }
+            let (a, b) = (a.unwrap(), b.unwrap());
+            let num_a = num_games_by_tid.iter().find(|(tid, _)| *tid == a).unwrap().1;
+            let num_b = num_games_by_tid.iter().find(|(tid, _)| *tid == b).unwrap().1;
+            if num_a >= num_games || num_b >= num_games {
+                continue;
+            }
+
+            // Flip home court every other cycle, and for every other pairing so slot 0 isn't always home
+            let cycle = round / (num_slots - 1);
+            let (home_tid, away_tid) = if (cycle + i).is_multiple_of(2) {
+                (a, b)
+            } else {
+                (b, a)
+            };
+            schedule.push(ScheduleGame {
+                gid: first_gid + schedule.len() as i32,
+                day,
+                home_tid,
+                away_tid
+            });
+            for entry in num_games_by_tid.iter_mut() {
+                if entry.0 == a || entry.0 == b {
+                    entry.1 += 1;
+                }
+            }
+            scheduled_today = true;
+        }
+
+        if scheduled_today {
+            day += 1;
+            num_empty_rounds = 0;
+        } else {
+            num_empty_rounds += 1;
+        }
+        round += 1;
+
+        // Slot 0 stays put, everyone else rotates one place
+        let last = slots.pop().unwrap();
+        slots.insert(1, last);
+    }
+
+    schedule
+}
\ No newline at end of file
@@ -0,0 +1,319 @@
+use crate::common::types::GameResult;
+use crate::common::types::PlayoffSeries;
+use crate::common::types::PlayoffSeriesMatchup;
+use crate::common::types::PlayoffSeriesTeam;
+use crate::worker::core::season::gen_play_ins::advance_play_in;
+use crate::worker::core::season::gen_play_ins::get_pending_play_in_games;
+use crate::worker::core::season::gen_play_ins::get_play_in_seeds;
+use crate::worker::core::season::gen_play_ins::is_play_in_over;
+use crate::worker::core::season::gen_play_ins::record_play_in_game;
+use crate::worker::core::season::gen_playoff_series::get_bracket_size;
+use crate::worker::core::season::gen_playoff_series::make_matchups;
+
+pub struct PlayoffGame {
+    pub home_tid: i32,
+    pub away_tid: i32
+}
+
+// true means the higher seed is at home. Best of 7 is 2-2-1-1-1, best of 5 is 2-2-1, best of 3 is 1-1-1.
+pub fn get_home_court_pattern(num_games: u8) -> Vec<bool> {
+    let num_games = num_games as usize;
+    if num_games <= 3 {
+        return (0..num_games).map(|i| i % 2 == 0).collect();
+    }
+    let mut pattern = vec![true, true, false, false];
+    while pattern.len() < num_games {
+        pattern.push(pattern.len() % 2 == 0);
+    }
+    pattern.truncate(num_games);
+    pattern
+}
+
+pub fn get_num_games_to_win(num_games: u8) -> u8 {
+    num_games / 2 + 1
+}
+
+pub fn get_series_winner(matchup: &PlayoffSeriesMatchup, num_games_to_win: u8) -> Option<&PlayoffSeriesTeam> {
+    if matchup.away.is_none() {
+        return Some(&matchup.home);
+    }
+    let away = matchup.away.as_ref().unwrap();
+    if matchup.home.won >= num_games_to_win {
+        return Some(&matchup.home);
+    }
+    if away.won >= num_games_to_win {
+        return Some(away);
+    }
+    None
+}
+
+pub fn is_in_play_in(playoff_series: &PlayoffSeries) -> bool {
+    playoff_series.series.is_empty()
+}
+
+fn get_num_games_current_round(playoff_series: &PlayoffSeries, num_games_playoff_series: &[u8]) -> u8 {
+    num_games_playoff_series[playoff_series.current_round]
+}
+
+// Games to be played today, one for every series in the current round that is not over yet
+pub fn get_next_games(playoff_series: &PlayoffSeries, num_games_playoff_series: &[u8]) -> Vec<PlayoffGame> {
+    if is_in_play_in(playoff_series) {
+        let mut games = Vec::new();
+        for play_in in playoff_series.play_ins.as_ref().unwrap().iter() {
+            for game in get_pending_play_in_games(play_in) {
+                games.push(PlayoffGame {
+                    home_tid: game.home.tid,
+                    away_tid: game.away.tid
+                });
+            }
+        }
+        return games;
+    }
+
+    let num_games = get_num_games_current_round(playoff_series, num_games_playoff_series);
+    let num_games_to_win = get_num_games_to_win(num_games);
+    let home_court_pattern = get_home_court_pattern(num_games);
+
+    let mut games = Vec::new();
+    for matchup in playoff_series.series[playoff_series.current_round].iter() {
+        if get_series_winner(matchup, num_games_to_win).is_some() {
+            continue;
+        }
+        let away = matchup.away.as_ref().unwrap();
+        let game_num = (matchup.home.won + away.won) as usize;
+        if home_court_pattern[game_num] {
+            games.push(PlayoffGame {
+                home_tid: matchup.home.tid,
+                away_tid: away.tid
+            });
+        } else {
+            games.push(PlayoffGame {
+                home_tid: away.tid,
+                away_tid: matchup.home.tid
+            });
+        }
+    }
+    games
+}
+
+pub fn record_playoff_game(playoff_series: &mut PlayoffSeries, game: &GameResult) -> Result<(), String> {
+    if is_in_play_in(playoff_series) {
+        record_play_in_game(playoff_series.play_ins.as_mut().unwrap(), game)?;
+        return Ok(());
+    }
+    if game.teams[0].pts == game.teams[1].pts {
+        return Err(format!("Playoff game {} ended in a tie", game.gid));
+    }
+
+    let winner_tid = if game.teams[0].pts > game.teams[1].pts {
+        game.teams[0].tid
+    } else {
+        game.teams[1].tid
+    };
+
+    let current_round = playoff_series.current_round;
+    for matchup in playoff_series.series[current_round].iter_mut() {
+        if matchup.away.is_none() {
+            continue;
+        }
+        let away = matchup.away.as_mut().unwrap();
+        let tids = [matchup.home.tid, away.tid];
+        if !tids.contains(&game.teams[0].tid) || !tids.contains(&game.teams[1].tid) {
+            continue;
+        }
+        if matchup.home.tid == winner_tid {
+            matchup.home.won += 1;
+        } else {
+            away.won += 1;
+        }
+        return Ok(());
+    }
+    Ok(())
+}
+
+fn is_higher_seed(a: &PlayoffSeriesTeam, b: &PlayoffSeriesTeam) -> bool {
+    if a.seed != b.seed {
+        return a.seed < b.seed;
+    }
+    a.league_rank < b.league_rank
+}
+
+pub fn is_round_over(playoff_series: &PlayoffSeries, num_games_playoff_series: &[u8]) -> bool {
+    if is_in_play_in(playoff_series) {
+        return false;
+    }
+    let num_games_to_win = get_num_games_to_win(get_num_games_current_round(playoff_series, num_games_playoff_series));
+    playoff_series.series[playoff_series.current_round]
+        .iter()
+        .all(|matchup| get_series_winner(matchup, num_games_to_win).is_some())
+}
+
+pub fn get_champion(playoff_series: &PlayoffSeries, num_games_playoff_series: &[u8]) -> Option<i32> {
+    if playoff_series.current_round + 1 < num_games_playoff_series.len() || !is_round_over(playoff_series, num_games_playoff_series) {
+        return None;
+    }
+    let num_games_to_win = get_num_games_to_win(get_num_games_current_round(playoff_series, num_games_playoff_series));
+    let finals = &playoff_series.series[playoff_series.current_round][0];
+    get_series_winner(finals, num_games_to_win).map(|t| t.tid)
+}
+
+// Once every play-in is over, the winners take the last seeds and the first round is created
+fn new_schedule_play_in_day(playoff_series: &mut PlayoffSeries, num_games_playoff_series: &[u8]) {
+    let play_ins = playoff_series.play_ins.as_mut().unwrap();
+    for play_in in play_ins.iter_mut() {
+        advance_play_in(play_in);
+    }
+    if !play_ins.iter().all(is_play_in_over) {
+        return;
+    }
+
+    let bracket_size = get_bracket_size(num_games_playoff_series.len(), playoff_series.by_conf);
+    let mut first_round = Vec::new();
+    for play_in in play_ins.iter() {
+        first_round.extend(make_matchups(&get_play_in_seeds(play_in), bracket_size));
+    }
+    playoff_series.series.push(first_round);
+}
+
+// If the current round is over, pair up the winners of adjacent series for the next round. Returns true when the
+// playoffs are over.
+pub fn new_schedule_playoffs_day(playoff_series: &mut PlayoffSeries, num_games_playoff_series: &[u8]) -> bool {
+    if is_in_play_in(playoff_series) {
+        new_schedule_play_in_day(playoff_series, num_games_playoff_series);
+        return false;
+    }
+    if !is_round_over(playoff_series, num_games_playoff_series) {
+        return false;
+    }
+    if playoff_series.current_round + 1 >= num_games_playoff_series.len() {
+        return true;
+    }
+
+    let num_games_to_win = get_num_games_to_win(get_num_games_current_round(playoff_series, num_games_playoff_series));
+    let winners: Vec<PlayoffSeriesTeam> = playoff_series.series[playoff_series.current_round]
+        .iter()
+        .map(|matchup| {
+            let mut winner = get_series_winner(matchup, num_games_to_win).unwrap().clone();
+            winner.won = 0;
+            winner
+        })
+        .collect();
+
+    let mut next_round = Vec::new();
+    for i in (0..winners.len()).step_by(2) {
+        let a = winners[i].clone();
+        let b = winners[i + 1].clone();
+        if is_higher_seed(&a, &b) {
+            next_round.push(PlayoffSeriesMatchup {
+                home: a,
+                away: Some(b)
+            });
+        } else {
+            next_round.push(PlayoffSeriesMatchup {
+                home: b,
+                away: Some(a)
+            });
+        }
+    }
+
+    playoff_series.series.push(next_round);
+    playoff_series.current_round += 1;
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::types::GameResultTeam;
+    use crate::worker::core::season::gen_playoff_series::make_matchups;
+
+    fn get_playoff_series(num_teams: u8, bracket_size: usize) -> PlayoffSeries {
+        let teams: Vec<PlayoffSeriesTeam> = (1..=num_teams).map(|seed| PlayoffSeriesTeam {
+            tid: seed as i32,
+            cid: 0,
+            seed,
+            league_rank: seed as usize,
+            won: 0
+        }).collect();
+        PlayoffSeries {
+            season: 2025,
+            current_round: 0,
+            by_conf: false,
+            play_ins: None,
+            series: vec![make_matchups(&teams, bracket_size)]
+        }
+    }
+
+    fn get_game(home_tid: i32, home_pts: i32, away_tid: i32, away_pts: i32) -> GameResult {
+        GameResult {
+            gid: 0,
+            season: 2025,
+            playoffs: true,
+            overtimes: 0,
+            teams: [
+                GameResultTeam {
+                    tid: home_tid,
+                    pts: home_pts
+                },
+                GameResultTeam {
+                    tid: away_tid,
+                    pts: away_pts
+                }
+            ]
+        }
+    }
+
+    #[test]
+    fn home_court_pattern() {
+        assert_eq!(get_home_court_pattern(1), vec![true]);
+        assert_eq!(get_home_court_pattern(3), vec![true, false, true]);
+        assert_eq!(get_home_court_pattern(5), vec![true, true, false, false, true]);
+        assert_eq!(get_home_court_pattern(7), vec![true, true, false, false, true, false, true]);
+    }
+
+    #[test]
+    fn teams_with_byes_wait_for_the_next_round() {
+        // Seeds 1 and 2 have byes, and the lower seeds win the other series
+        let num_games_playoff_series = [1, 1, 1];
+        let mut playoff_series = get_playoff_series(6, 8);
+        let games = get_next_games(&playoff_series, &num_games_playoff_series);
+        let tids: Vec<(i32, i32)> = games.iter().map(|game| (game.home_tid, game.away_tid)).collect();
+        assert_eq!(tids, vec![(4, 5), (3, 6)]);
+        record_playoff_game(&mut playoff_series, &get_game(4, 90, 5, 100)).unwrap();
+        assert!(!is_round_over(&playoff_series, &num_games_playoff_series));
+        record_playoff_game(&mut playoff_series, &get_game(3, 90, 6, 100)).unwrap();
+        assert!(is_round_over(&playoff_series, &num_games_playoff_series));
+
+        assert!(!new_schedule_playoffs_day(&mut playoff_series, &num_games_playoff_series));
+        assert_eq!(playoff_series.current_round, 1);
+        let second_round: Vec<(i32, Option<i32>, u8)> = playoff_series.series[1]
+            .iter()
+            .map(|matchup| (matchup.home.tid, matchup.away.as_ref().map(|t| t.tid), matchup.home.won))
+            .collect();
+        assert_eq!(second_round, vec![(1, Some(5), 0), (2, Some(6), 0)]);
+    }
+
+    #[test]
+    fn best_of_three_series() {
+        let num_games_playoff_series = [3];
+        let mut playoff_series = get_playoff_series(2, 2);
+        // The higher seed hosts games 1 and 3
+        assert_eq!(get_next_games(&playoff_series, &num_games_playoff_series)[0].home_tid, 1);
+        record_playoff_game(&mut playoff_series, &get_game(1, 100, 2, 90)).unwrap();
+        assert_eq!(get_next_games(&playoff_series, &num_games_playoff_series)[0].home_tid, 2);
+        record_playoff_game(&mut playoff_series, &get_game(2, 100, 1, 90)).unwrap();
+        assert_eq!(get_champion(&playoff_series, &num_games_playoff_series), None);
+        record_playoff_game(&mut playoff_series, &get_game(1, 80, 2, 90)).unwrap();
+        assert_eq!(get_champion(&playoff_series, &num_games_playoff_series), Some(2));
+        assert!(get_next_games(&playoff_series, &num_games_playoff_series).is_empty());
+        assert!(new_schedule_playoffs_day(&mut playoff_series, &num_games_playoff_series));
+    }
+
+    #[test]
+    fn ties_are_rejected() {
+        let mut playoff_series = get_playoff_series(2, 2);
+        assert!(record_playoff_game(&mut playoff_series, &get_game(1, 100, 2, 100)).is_err());
+        assert_eq!(playoff_series.series[0][0].home.won, 0);
+        assert_eq!(playoff_series.series[0][0].away.as_ref().unwrap().won, 0);
+    }
+}
\ No newline at end of file
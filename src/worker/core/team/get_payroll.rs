@@ -0,0 +1,11 @@
+use crate::common::types::Player;
+use crate::worker::db::Tx;
+
+pub fn sum_contracts(players: &[Player]) -> f64 {
+    players.iter().map(|p| p.contract.amount).sum()
+}
+
+// Total of the contracts of everyone on the team's roster
+pub fn get_payroll(tx: &Tx, tid: i32) -> Result<f64, String> {
+    Ok(sum_contracts(&tx.get_players_by_tid(tid)?))
+}
\ No newline at end of file
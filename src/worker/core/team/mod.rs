@@ -0,0 +1 @@
+pub mod get_payroll;
\ No newline at end of file
@@ -0,0 +1,63 @@
+use crate::common::types::TradeTeam;
+use crate::worker::core::trade::summary::summary;
+use crate::worker::core::trade::value_change::value_change;
+use crate::worker::db::Tx;
+
+// teams with one more player or pick from teams[0], for each one teams[0] could add
+fn get_candidates(tx: &Tx, teams: &[TradeTeam; 2]) -> Result<Vec<[TradeTeam; 2]>, String> {
+    let mut candidates = Vec::new();
+    for p in tx.get_players_by_tid(teams[0].tid)? {
+        if !teams[0].pids.contains(&p.pid) {
+            let mut candidate = teams.clone();
+            candidate[0].pids.push(p.pid);
+            candidates.push(candidate);
+        }
+    }
+    for dp in tx.get_all_draft_picks()? {
+        if dp.tid == teams[0].tid && !teams[0].dpids.contains(&dp.dpid) {
+            let mut candidate = teams.clone();
+            candidate[0].dpids.push(dp.dpid);
+            candidates.push(candidate);
+        }
+    }
+    Ok(candidates)
+}
+
+// The counter-proposal from teams[1]: teams adds players and picks from teams[0], one at a time, until teams[1]
+// would accept. Each time it asks for the asset that makes the trade work with the least to spare, or the one that
+// gets it closest if none does. None if nothing teams[0] could add is enough.
+pub fn make_it_work(tx: &Tx, teams: &[TradeTeam; 2]) -> Result<Option<[TradeTeam; 2]>, String> {
+    let mut teams = teams.clone();
+    loop {
+        let current = summary(tx, &teams)?;
+        if current.warnings.is_empty() && value_change(tx, &current, 1)? > 0.0 {
+            return Ok(Some(teams));
+        }
+
+        let mut best: Option<([TradeTeam; 2], f64)> = None;
+        for candidate in get_candidates(tx, &teams)? {
+            let candidate_summary = summary(tx, &candidate)?;
+            if !candidate_summary.warnings.is_empty() {
+                continue;
+            }
+            let dv = value_change(tx, &candidate_summary, 1)?;
+            let is_better = match best.as_ref() {
+                None => true,
+                Some((_, best_dv)) => {
+                    if *best_dv > 0.0 {
+                        dv > 0.0 && dv < *best_dv
+                    } else {
+                        dv > *best_dv
+                    }
+                }
+            };
+            if is_better {
+                best = Some((candidate, dv));
+            }
+        }
+        if best.is_none() {
+            return Ok(None);
+        }
+        teams = best.unwrap().0;
+    }
+}
\ No newline at end of file
@@ -0,0 +1,5 @@
+pub mod summary;
+pub mod value_change;
+pub mod make_it_work;
+pub mod process_trade;
+pub mod propose;
\ No newline at end of file
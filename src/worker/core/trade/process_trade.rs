@@ -0,0 +1,74 @@
+use crate::common::types::DraftPick;
+use crate::common::types::Event;
+use crate::common::types::Team;
+use crate::worker::core::trade::summary::TradeSummary;
+use crate::worker::core::trade::summary::TradeSummaryTeam;
+use crate::worker::db::Tx;
+
+fn abbrev(teams: &[Team], tid: i32) -> String {
+    teams.iter().find(|t| t.tid == tid).map(|t| t.abbrev.clone()).unwrap_or(tid.to_string())
+}
+
+// Like "2026 round 1 pick", with the pick number once it's known and the original team if it changed hands
+pub fn describe_pick(dp: &DraftPick, teams: &[Team]) -> String {
+    let mut description = format!("{} round {} pick", dp.season, dp.round);
+    if dp.pick > 0 {
+        description.push_str(&format!(" #{}", dp.pick));
+    }
+    if dp.tid != dp.original_tid {
+        description.push_str(&format!(" ({})", abbrev(teams, dp.original_tid)));
+    }
+    description
+}
+
+fn describe_assets(t: &TradeSummaryTeam, teams: &[Team]) -> String {
+    let mut assets: Vec<String> = t.players.iter().map(|p| p.name()).collect();
+    assets.extend(t.picks.iter().map(|dp| describe_pick(dp, teams)));
+    if assets.is_empty() {
+        return "nothing".to_string();
+    }
+    assets.join(", ")
+}
+
+// Swaps the players and picks in summary, which should have no warnings, and logs the trade
+pub fn process_trade(tx: &Tx, summary: &TradeSummary) -> Result<Event, String> {
+    let g = tx.get_g()?;
+    let teams = tx.get_teams()?;
+    let text = format!(
+        "{} traded {} to {} for {}",
+        abbrev(&teams, summary.teams[0].tid),
+        describe_assets(&summary.teams[0], &teams),
+        abbrev(&teams, summary.teams[1].tid),
+        describe_assets(&summary.teams[1], &teams)
+    );
+
+    let mut pids = Vec::new();
+    let mut dpids = Vec::new();
+    for i in 0..2 {
+        let other_tid = summary.teams[1 - i].tid;
+        for p in summary.teams[i].players.iter() {
+            let mut p = p.clone();
+            p.tid = other_tid;
+            tx.put_player(&p)?;
+            pids.push(p.pid);
+        }
+        for dp in summary.teams[i].picks.iter() {
+            let mut dp = dp.clone();
+            dp.tid = other_tid;
+            tx.put_draft_pick(&dp)?;
+            dpids.push(dp.dpid);
+        }
+    }
+
+    let event = Event {
+        eid: tx.get_next_eid()?,
+        event_type: "trade".to_string(),
+        season: g.season(),
+        text,
+        tids: vec![summary.teams[0].tid, summary.teams[1].tid],
+        pids,
+        dpids
+    };
+    tx.put_event(&event)?;
+    Ok(event)
+}
\ No newline at end of file
@@ -0,0 +1,151 @@
+use crate::common::constants::PHASE_DRAFT;
+use crate::common::constants::PHASE_DRAFT_LOTTERY;
+use crate::common::constants::PHASE_FREE_AGENCY;
+use crate::common::constants::PHASE_PRESEASON;
+use crate::common::constants::PHASE_REGULAR_SEASON;
+use crate::common::constants::PHASE_RESIGN_PLAYERS;
+use crate::common::types::TradeTeam;
+use crate::worker::core::trade::process_trade::process_trade;
+use crate::worker::core::trade::summary::summary;
+use crate::worker::core::trade::value_change::value_change;
+use crate::worker::core::phase::get_phase::check_phase;
+use crate::worker::db::Tx;
+
+pub struct TradeResult {
+    pub accepted: bool,
+    // How much better off the trade leaves teams[1], as value_change sees it
+    pub dv: f64,
+    pub message: String
+}
+
+// No trades from the trade deadline until the playoffs are over, or during the expansion draft
+pub fn check_trades_allowed(tx: &Tx) -> Result<(), String> {
+    let phases = [PHASE_PRESEASON, PHASE_REGULAR_SEASON, PHASE_DRAFT_LOTTERY, PHASE_DRAFT, PHASE_RESIGN_PLAYERS, PHASE_FREE_AGENCY];
+    check_phase(tx, &phases, "make trades")?;
+    Ok(())
+}
+
+// teams[0] offers the trade to teams[1], which accepts if it comes out ahead. With force, the trade is made whatever
+// teams[1] thinks of it. Trades that break the cap or roster rules are an error either way.
+pub fn propose(tx: &Tx, teams: &[TradeTeam; 2], force: bool) -> Result<TradeResult, String> {
+    check_trades_allowed(tx)?;
+    if teams.iter().all(|t| t.pids.is_empty() && t.dpids.is_empty()) {
+        return Err("A trade needs at least one player or draft pick".to_string());
+    }
+    let trade_summary = summary(tx, teams)?;
+    if !trade_summary.warnings.is_empty() {
+        return Err(trade_summary.warnings.join("\n"));
+    }
+
+    let dv = value_change(tx, &trade_summary, 1)?;
+    if dv <= 0.0 && !force {
+        return Ok(TradeResult {
+            accepted: false,
+            dv,
+            message: "Trade rejected".to_string()
+        });
+    }
+    let event = process_trade(tx, &trade_summary)?;
+    Ok(TradeResult {
+        accepted: true,
+        dv,
+        message: event.text
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::types::Team;
+    use crate::common::types::TeamSeasonAttrs;
+    use crate::worker::core::league::gen_random_players::gen_random_players;
+    use crate::worker::core::league::League;
+    use crate::worker::core::player::gen_ratings::TalentDistribution;
+    use crate::worker::db::LeagueDb;
+    use crate::worker::util::g::G;
+
+    // Two teams of 13 players in the regular season. Every contract on a team is for amounts[tid], except its best
+    // player's, which is for stars[tid]. Returns each roster's pids, best first.
+    fn new_league_db(amounts: [f64; 2], stars: [f64; 2]) -> (LeagueDb, [Vec<i32>; 2]) {
+        let g = G::from_defaults(2025).unwrap();
+        let teams: Vec<Team> = (0..2).map(|tid| Team {
+            tid,
+            cid: 0,
+            did: 0,
+            region: format!("Region {}", tid),
+            name: format!("Team {}", tid),
+            abbrev: format!("T{}", tid),
+            pop: 1.0,
+            coaching_rank: None
+        }).collect();
+        let mut players = gen_random_players(&teams, &g, &TalentDistribution::default(), 1).unwrap();
+        players.sort_by(|a, b| b.value.total_cmp(&a.value));
+        let mut pids = [Vec::new(), Vec::new()];
+        for p in players.iter_mut().filter(|p| p.tid >= 0) {
+            let t = p.tid as usize;
+            p.contract.amount = if pids[t].is_empty() { stars[t] } else { amounts[t] };
+            pids[t].push(p.pid);
+        }
+
+        let mut db = LeagueDb::open_in_memory().unwrap();
+        db.transaction(|tx| tx.put_league(&League {
+            team_seasons: (0..2).map(|tid| TeamSeasonAttrs::new(tid, g.season(), 0, 0)).collect(),
+            g,
+            starting_season: 2025,
+            phase: PHASE_REGULAR_SEASON,
+            teams,
+            players,
+            games: Vec::new(),
+            draft_picks: Vec::new(),
+            events: Vec::new(),
+            awards: Vec::new()
+        })).unwrap();
+        (db, pids)
+    }
+
+    fn get_trade_teams(pids0: &[i32], pids1: &[i32]) -> [TradeTeam; 2] {
+        [
+            TradeTeam {
+                tid: 0,
+                pids: pids0.to_vec(),
+                dpids: Vec::new()
+            },
+            TradeTeam {
+                tid: 1,
+                pids: pids1.to_vec(),
+                dpids: Vec::new()
+            }
+        ]
+    }
+
+    #[test]
+    fn over_the_cap_team_cant_take_back_more_salary() {
+        // T1 has a payroll of $143M, over the $140M soft cap, and would take back $30M for $11M
+        let (mut db, pids) = new_league_db([5000.0, 11000.0], [30000.0, 11000.0]);
+        let teams = get_trade_teams(&pids[0][..1], &pids[1][12..]);
+        let error = db.transaction(|tx| propose(tx, &teams, true).map(|_| ())).unwrap_err();
+        assert_eq!(error, "T1 is over the cap after the trade, so it can take back at most $13.8M for the $11.0M it sends out, not $30.0M");
+
+        // Nothing changed hands
+        let roster = db.transaction(|tx| tx.get_players_by_tid(1)).unwrap();
+        assert!(!roster.iter().any(|p| p.pid == pids[0][0]));
+    }
+
+    #[test]
+    fn over_the_cap_team_can_shed_salary() {
+        let (mut db, pids) = new_league_db([5000.0, 11000.0], [30000.0, 11000.0]);
+        let teams = get_trade_teams(&pids[0][12..], &pids[1][12..]);
+        let result = db.transaction(|tx| propose(tx, &teams, true)).unwrap();
+        assert!(result.accepted);
+        let roster = db.transaction(|tx| tx.get_players_by_tid(1)).unwrap();
+        assert!(roster.iter().any(|p| p.pid == pids[0][12]));
+    }
+
+    #[test]
+    fn roster_cant_drop_below_the_minimum() {
+        let (mut db, pids) = new_league_db([5000.0, 5000.0], [5000.0, 5000.0]);
+        let teams = get_trade_teams(&pids[0][11..], &pids[1][12..]);
+        let error = db.transaction(|tx| propose(tx, &teams, true).map(|_| ())).unwrap_err();
+        assert_eq!(error, "T0 would have 12 players, fewer than the minimum of 13");
+    }
+}
\ No newline at end of file
@@ -0,0 +1,122 @@
+use crate::common::helpers::format_currency;
+use crate::common::types::DraftPick;
+use crate::common::types::Player;
+use crate::common::types::TradeTeam;
+use crate::worker::core::team::get_payroll::sum_contracts;
+use crate::worker::db::Tx;
+
+// Over the cap after a soft cap trade, a team can take back at most this share of the salary it sends out, plus
+// SALARY_MATCH_EXTRA
+const SALARY_MATCH_RATIO: f64 = 1.25;
+const SALARY_MATCH_EXTRA: f64 = 100.0;
+
+pub struct TradeSummaryTeam {
+    pub tid: i32,
+    // What the team gives up
+    pub players: Vec<Player>,
+    pub picks: Vec<DraftPick>,
+    pub salary_out: f64,
+    pub payroll_before: f64,
+    pub payroll_after: f64,
+    pub roster_size_after: usize
+}
+
+pub struct TradeSummary {
+    pub teams: [TradeSummaryTeam; 2],
+    // Cap and roster rules the trade breaks. A trade with warnings can't be made.
+    pub warnings: Vec<String>
+}
+
+fn summarize_team(tx: &Tx, t: &TradeTeam) -> Result<TradeSummaryTeam, String> {
+    let roster = tx.get_players_by_tid(t.tid)?;
+    let mut players = Vec::new();
+    for pid in t.pids.iter() {
+        let p = roster.iter().find(|p| p.pid == *pid);
+        if p.is_none() {
+            return Err(format!("pids: player {} is not on team {}", pid, t.tid));
+        }
+        if players.iter().any(|other: &Player| other.pid == *pid) {
+            return Err(format!("pids: player {} is in the trade twice", pid));
+        }
+        players.push(p.unwrap().clone());
+    }
+
+    let mut picks = Vec::new();
+    for dpid in t.dpids.iter() {
+        let dp = tx.get_draft_pick(*dpid)?;
+        if dp.is_none() || dp.as_ref().unwrap().tid != t.tid {
+            return Err(format!("dpids: draft pick {} doesn't belong to team {}", dpid, t.tid));
+        }
+        if picks.iter().any(|other: &DraftPick| other.dpid == *dpid) {
+            return Err(format!("dpids: draft pick {} is in the trade twice", dpid));
+        }
+        picks.push(dp.unwrap());
+    }
+
+    let payroll_before = sum_contracts(&roster);
+    let salary_out = sum_contracts(&players);
+    Ok(TradeSummaryTeam {
+        tid: t.tid,
+        players,
+        picks,
+        salary_out,
+        payroll_before,
+        payroll_after: payroll_before - salary_out,
+        roster_size_after: roster.len() - t.pids.len()
+    })
+}
+
+// What each team gives up and how its payroll and roster change. Err for assets that aren't the teams' to trade,
+// warnings for trades that break the salary cap or roster limits.
+pub fn summary(tx: &Tx, teams: &[TradeTeam; 2]) -> Result<TradeSummary, String> {
+    if teams[0].tid == teams[1].tid {
+        return Err("A team can't trade with itself".to_string());
+    }
+    let g = tx.get_g()?;
+    let mut summary_teams = [summarize_team(tx, &teams[0])?, summarize_team(tx, &teams[1])?];
+    for i in 0..2 {
+        let salary_in = summary_teams[1 - i].salary_out;
+        let num_in = summary_teams[1 - i].players.len();
+        summary_teams[i].payroll_after += salary_in;
+        summary_teams[i].roster_size_after += num_in;
+    }
+
+    let mut warnings = Vec::new();
+    let team_names = tx.get_teams()?;
+    for i in 0..2 {
+        let t = &summary_teams[i];
+        let salary_in = summary_teams[1 - i].salary_out;
+        let abbrev = team_names.iter().find(|team| team.tid == t.tid).map(|team| team.abbrev.clone()).unwrap_or(t.tid.to_string());
+        if t.roster_size_after > g.max_roster_size() as usize {
+            warnings.push(format!("{} would have {} players, more than the maximum of {}", abbrev, t.roster_size_after, g.max_roster_size()));
+        }
+        if t.roster_size_after < g.min_roster_size() as usize {
+            warnings.push(format!("{} would have {} players, fewer than the minimum of {}", abbrev, t.roster_size_after, g.min_roster_size()));
+        }
+        // Taking back less salary is always fine, so teams over the cap can still shed salary
+        if t.payroll_after <= g.salary_cap() || salary_in <= t.salary_out {
+            continue;
+        }
+        if g.salary_cap_type() == "hard" {
+            warnings.push(format!(
+                "{} would have a payroll of {}, over the hard cap of {}",
+                abbrev,
+                format_currency(t.payroll_after),
+                format_currency(g.salary_cap())
+            ));
+        } else if g.salary_cap_type() == "soft" && salary_in > SALARY_MATCH_RATIO * t.salary_out + SALARY_MATCH_EXTRA {
+            warnings.push(format!(
+                "{} is over the cap after the trade, so it can take back at most {} for the {} it sends out, not {}",
+                abbrev,
+                format_currency(SALARY_MATCH_RATIO * t.salary_out + SALARY_MATCH_EXTRA),
+                format_currency(t.salary_out),
+                format_currency(salary_in)
+            ));
+        }
+    }
+
+    Ok(TradeSummary {
+        teams: summary_teams,
+        warnings
+    })
+}
\ No newline at end of file
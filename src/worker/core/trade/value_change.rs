@@ -0,0 +1,139 @@
+use crate::common::constants::PLAYER_UNDRAFTED;
+use crate::common::helpers::calc_winp;
+use crate::common::types::DraftPick;
+use crate::common::types::Player;
+use crate::worker::core::player::gen_contract::gen_contract;
+use crate::worker::core::player::gen_contract::AMOUNT_PER_VALUE;
+use crate::worker::core::trade::summary::TradeSummary;
+use crate::worker::db::Tx;
+use crate::worker::util::g::G;
+
+// Asset values are raised to VALUE_EXPONENT before they are added up, so one star is worth more than several role
+// players with the same total value. VALUE_SCALE keeps the results around 1.
+const VALUE_EXPONENT: f64 = 7.0;
+const VALUE_SCALE: f64 = 60.0;
+
+// Value per season left for each point of value a contract pays under (or over) what the player would ask for
+const CONTRACT_WEIGHT_CONTENDING: f64 = 0.1;
+const CONTRACT_WEIGHT_REBUILDING: f64 = 0.2;
+
+// Rebuilding teams take off this much value per year a player is older than REBUILDING_PEAK_AGE
+const REBUILDING_AGE_PENALTY: f64 = 1.0;
+const REBUILDING_PEAK_AGE: i32 = 26;
+
+// Contending teams take this much value off draft prospects, who won't help them win now
+const CONTENDING_PICK_DISCOUNT: f64 = 5.0;
+
+// Each season until the draft pulls a pick's projected spot this much of the way back to the middle of the round
+const PICK_REGRESSION: f64 = 0.5;
+
+// Value of a prospect when there is no draft class to go by
+const DEFAULT_PROSPECT_VALUE: f64 = 40.0;
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum Strategy {
+    Contending,
+    Rebuilding
+}
+
+// tids from best to worst, by winning percentage, or by the value of their rosters before any games are played
+pub fn get_team_ranks(tx: &Tx) -> Result<Vec<i32>, String> {
+    let g = tx.get_g()?;
+    let team_seasons = tx.get_team_seasons(g.season())?;
+    let mut ranks: Vec<(i32, f64)> = Vec::new();
+    if !team_seasons.is_empty() && team_seasons.iter().any(|ts| ts.gp > 0) {
+        for ts in team_seasons.iter() {
+            ranks.push((ts.tid, calc_winp(ts.won, ts.lost, ts.tied, ts.otl)));
+        }
+    } else {
+        for t in tx.get_teams()? {
+            let mut values: Vec<f64> = tx.get_players_by_tid(t.tid)?.iter().map(|p| p.value_no_pot).collect();
+            values.sort_by(|a, b| b.total_cmp(a));
+            ranks.push((t.tid, values.iter().take(10).sum()));
+        }
+    }
+    ranks.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+    Ok(ranks.into_iter().map(|(tid, _)| tid).collect())
+}
+
+// Teams in the top half contend, the rest rebuild
+pub fn get_strategy(ranks: &[i32], tid: i32) -> Strategy {
+    let rank = ranks.iter().position(|other| *other == tid).unwrap_or(ranks.len());
+    if rank < ranks.len() / 2 {
+        return Strategy::Contending;
+    }
+    Strategy::Rebuilding
+}
+
+// A player's value to a team with strategy, counting what their contract pays over or under their worth
+pub fn player_value(p: &Player, g: &G, strategy: Strategy) -> f64 {
+    let (mut value, contract_weight) = match strategy {
+        Strategy::Contending => (0.75 * p.value_no_pot + 0.25 * p.value, CONTRACT_WEIGHT_CONTENDING),
+        Strategy::Rebuilding => (
+            p.value - REBUILDING_AGE_PENALTY * (p.age(g.season()) - REBUILDING_PEAK_AGE).max(0) as f64,
+            CONTRACT_WEIGHT_REBUILDING
+        ),
+    };
+    let asking_amount = gen_contract(p, g, g.season()).amount;
+    let surplus = (asking_amount - p.contract.amount) / (AMOUNT_PER_VALUE * (g.max_contract() - g.min_contract()));
+    let seasons_left = (p.contract.exp - g.season() + 1).max(1);
+    value += contract_weight * surplus * seasons_left as f64;
+    value
+}
+
+// Values of the prospects in the draft class for season, best first. Classes that aren't generated yet are assumed
+// to look like the nearest one that is.
+fn get_class_values(prospects: &[Player], season: i32) -> Vec<f64> {
+    let nearest = prospects.iter().map(|p| p.draft.year).min_by_key(|year| (year - season).abs());
+    if nearest.is_none() {
+        return Vec::new();
+    }
+    let mut values: Vec<f64> = prospects.iter().filter(|p| p.draft.year == nearest.unwrap()).map(|p| p.value).collect();
+    values.sort_by(|a, b| b.total_cmp(a));
+    values
+}
+
+// The value of the prospect dp is expected to bring. Until the order is set, the worst teams are expected to pick
+// first, less surely the further away the draft is.
+pub fn pick_value(dp: &DraftPick, g: &G, ranks: &[i32], prospects: &[Player], strategy: Strategy) -> f64 {
+    let num_teams = ranks.len().max(1);
+    let spot = if dp.pick > 0 {
+        dp.pick as f64
+    } else {
+        let rank = ranks.iter().position(|tid| *tid == dp.original_tid).unwrap_or(num_teams / 2);
+        let spot = (num_teams - rank) as f64;
+        let middle = (num_teams + 1) as f64 / 2.0;
+        middle + (spot - middle) * PICK_REGRESSION.powi((dp.season - g.season()).max(0))
+    };
+    let index = (dp.round - 1).max(0) as usize * num_teams + spot.round().max(1.0) as usize - 1;
+    let values = get_class_values(prospects, dp.season);
+    let mut value = if values.is_empty() { DEFAULT_PROSPECT_VALUE } else { values[index.min(values.len() - 1)] };
+    if strategy == Strategy::Contending {
+        value -= CONTENDING_PICK_DISCOUNT;
+    }
+    value
+}
+
+fn asset_value(value: f64) -> f64 {
+    (value.max(0.0) / VALUE_SCALE).powf(VALUE_EXPONENT)
+}
+
+// How much better off the trade leaves summary.teams[i], as judged by an AI team. Positive means the team gets more
+// than it gives up.
+pub fn value_change(tx: &Tx, summary: &TradeSummary, i: usize) -> Result<f64, String> {
+    let g = tx.get_g()?;
+    let ranks = get_team_ranks(tx)?;
+    let strategy = get_strategy(&ranks, summary.teams[i].tid);
+    let prospects = tx.get_players_by_tid(PLAYER_UNDRAFTED)?;
+
+    let mut dv = 0.0;
+    for (j, sign) in [(1 - i, 1.0), (i, -1.0)] {
+        for p in summary.teams[j].players.iter() {
+            dv += sign * asset_value(player_value(p, &g, strategy));
+        }
+        for dp in summary.teams[j].picks.iter() {
+            dv += sign * asset_value(pick_value(dp, &g, &ranks, &prospects, strategy));
+        }
+    }
+    Ok(dv)
+}
\ No newline at end of file
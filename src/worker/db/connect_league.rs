@@ -0,0 +1,54 @@
+use rusqlite::Connection;
+use std::path::Path;
+
+// Bumped whenever the schema changes, stored in PRAGMA user_version
+pub const SCHEMA_VERSION: i32 = 1;
+
+// Like the object stores in BBGM, each row is a JSON document plus the keys it is looked up by
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS meta (key TEXT PRIMARY KEY, data TEXT NOT NULL);
+CREATE TABLE IF NOT EXISTS teams (tid INTEGER PRIMARY KEY, data TEXT NOT NULL);
+CREATE TABLE IF NOT EXISTS players (pid INTEGER PRIMARY KEY, tid INTEGER NOT NULL, data TEXT NOT NULL);
+CREATE INDEX IF NOT EXISTS players_tid ON players (tid);
+CREATE TABLE IF NOT EXISTS teamSeasons (tid INTEGER NOT NULL, season INTEGER NOT NULL, data TEXT NOT NULL, PRIMARY KEY (tid, season));
+CREATE TABLE IF NOT EXISTS games (gid INTEGER PRIMARY KEY, season INTEGER NOT NULL, data TEXT NOT NULL);
+CREATE INDEX IF NOT EXISTS games_season ON games (season);
+CREATE TABLE IF NOT EXISTS schedule (gid INTEGER PRIMARY KEY, day INTEGER NOT NULL, data TEXT NOT NULL);
+CREATE TABLE IF NOT EXISTS boxScores (gid INTEGER PRIMARY KEY, data TEXT NOT NULL);
+CREATE TABLE IF NOT EXISTS playByPlay (gid INTEGER PRIMARY KEY, data TEXT NOT NULL);
+CREATE TABLE IF NOT EXISTS playoffSeries (season INTEGER PRIMARY KEY, data TEXT NOT NULL);
+CREATE TABLE IF NOT EXISTS draftPicks (dpid INTEGER PRIMARY KEY, season INTEGER NOT NULL, data TEXT NOT NULL);
+CREATE INDEX IF NOT EXISTS draftPicks_season ON draftPicks (season);
+CREATE TABLE IF NOT EXISTS events (eid INTEGER PRIMARY KEY, season INTEGER NOT NULL, data TEXT NOT NULL);
+CREATE INDEX IF NOT EXISTS events_season ON events (season);
+CREATE TABLE IF NOT EXISTS awards (season INTEGER PRIMARY KEY, data TEXT NOT NULL);
+";
+
+fn setup(conn: Connection) -> Result<Connection, String> {
+    let version: i32 = conn
+        .query_row("PRAGMA user_version", [], |row| row.get(0))
+        .map_err(|err| err.to_string())?;
+    if version > SCHEMA_VERSION {
+        return Err(format!("League database has schema version {}, this version only supports up to {}", version, SCHEMA_VERSION));
+    }
+
+    // Default rollback journal with full syncs, so a crash leaves the last committed day intact
+    conn.execute_batch("PRAGMA foreign_keys = ON; PRAGMA synchronous = FULL;")
+        .map_err(|err| err.to_string())?;
+    conn.execute_batch(SCHEMA).map_err(|err| err.to_string())?;
+    conn.execute_batch(&format!("PRAGMA user_version = {}", SCHEMA_VERSION))
+        .map_err(|err| err.to_string())?;
+    Ok(conn)
+}
+
+// Opens the league file, creating it and its tables if needed
+pub fn connect_league(path: &Path) -> Result<Connection, String> {
+    let conn = Connection::open(path).map_err(|err| format!("Can't open {}: {}", path.display(), err))?;
+    setup(conn)
+}
+
+#[cfg(test)]
+pub fn connect_league_in_memory() -> Result<Connection, String> {
+    let conn = Connection::open_in_memory().map_err(|err| err.to_string())?;
+    setup(conn)
+}
\ No newline at end of file
@@ -0,0 +1,458 @@
+pub mod connect_league;
+
+use crate::common::league_settings::LeagueSettingsFile;
+use crate::common::types::BoxScore;
+use crate::common::types::DraftPick;
+use crate::common::types::Event;
+use crate::common::types::GameResult;
+use crate::common::types::PlayByPlayEvent;
+use crate::common::types::Player;
+use crate::common::types::PlayoffSeries;
+use crate::common::types::ScheduleGame;
+use crate::common::types::SeasonAwards;
+use crate::common::types::Team;
+use crate::common::types::TeamSeasonAttrs;
+use crate::worker::core::game::fast_sim::FastModel;
+use crate::worker::core::league::League;
+use crate::worker::core::phase::get_phase::get_phase;
+use crate::worker::db::connect_league::connect_league;
+#[cfg(test)]
+use crate::worker::db::connect_league::connect_league_in_memory;
+use crate::worker::util::g::G;
+use rusqlite::params_from_iter;
+use rusqlite::Connection;
+use rusqlite::OptionalExtension;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::path::Path;
+
+// A league stored in a single SQLite file. Every read and write goes through transaction, so a simulated day is
+// either saved completely or not at all.
+pub struct LeagueDb {
+    conn: Connection
+}
+
+impl LeagueDb {
+    pub fn open(path: &Path) -> Result<Self, String> {
+        Ok(LeagueDb {
+            conn: connect_league(path)?
+        })
+    }
+
+    #[cfg(test)]
+    pub fn open_in_memory() -> Result<Self, String> {
+        Ok(LeagueDb {
+            conn: connect_league_in_memory()?
+        })
+    }
+
+    // Commits if f succeeds, rolls back if it fails
+    pub fn transaction<T>(&mut self, f: impl FnOnce(&Tx) -> Result<T, String>) -> Result<T, String> {
+        let transaction = self.conn.transaction().map_err(|err| err.to_string())?;
+        let result = f(&Tx {
+            conn: &transaction
+        });
+        if result.is_ok() {
+            transaction.commit().map_err(|err| err.to_string())?;
+        }
+        // Dropping the transaction without committing rolls it back
+        result
+    }
+}
+
+pub struct Tx<'a> {
+    conn: &'a Connection
+}
+
+fn to_json<T: Serialize>(value: &T) -> String {
+    serde_json::to_string(value).unwrap()
+}
+
+fn from_json<T: DeserializeOwned>(table: &str, data: &str) -> Result<T, String> {
+    serde_json::from_str(data).map_err(|err| format!("Corrupt row in {}: {}", table, err))
+}
+
+impl<'a> Tx<'a> {
+    fn put<T: Serialize>(&self, table: &str, key_columns: &[&str], keys: &[i64], value: &T) -> Result<(), String> {
+        let columns = key_columns.join(", ");
+        let placeholders: Vec<String> = (1..=key_columns.len() + 1).map(|i| format!("?{}", i)).collect();
+        let sql = format!("INSERT OR REPLACE INTO {} ({}, data) VALUES ({})", table, columns, placeholders.join(", "));
+        let mut params: Vec<rusqlite::types::Value> = keys.iter().map(|key| rusqlite::types::Value::Integer(*key)).collect();
+        params.push(rusqlite::types::Value::Text(to_json(value)));
+        self.conn.execute(&sql, params_from_iter(params)).map_err(|err| err.to_string())?;
+        Ok(())
+    }
+
+    // condition is a WHERE/ORDER BY clause on the key columns, with ?1, ?2... for keys
+    fn get_all<T: DeserializeOwned>(&self, table: &str, condition: &str, keys: &[i64]) -> Result<Vec<T>, String> {
+        let sql = format!("SELECT data FROM {} {}", table, condition);
+        let mut statement = self.conn.prepare(&sql).map_err(|err| err.to_string())?;
+        let rows = statement
+            .query_map(params_from_iter(keys.iter()), |row| row.get::<_, String>(0))
+            .map_err(|err| err.to_string())?;
+        let mut values = Vec::new();
+        for row in rows {
+            let data = row.map_err(|err| err.to_string())?;
+            values.push(from_json(table, &data)?);
+        }
+        Ok(values)
+    }
+
+    fn get_one<T: DeserializeOwned>(&self, table: &str, condition: &str, keys: &[i64]) -> Result<Option<T>, String> {
+        let mut values = self.get_all(table, condition, keys)?;
+        if values.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(values.remove(0)))
+    }
+
+    fn get_meta<T: DeserializeOwned>(&self, key: &str) -> Result<Option<T>, String> {
+        let data: Option<String> = self.conn
+            .query_row("SELECT data FROM meta WHERE key = ?1", [key], |row| row.get(0))
+            .optional()
+            .map_err(|err| err.to_string())?;
+        if data.is_none() {
+            return Ok(None);
+        }
+        from_json("meta", &data.unwrap()).map(Some)
+    }
+
+    fn put_meta<T: Serialize>(&self, key: &str, value: &T) -> Result<(), String> {
+        self.conn
+            .execute("INSERT OR REPLACE INTO meta (key, data) VALUES (?1, ?2)", [key, &to_json(value)])
+            .map_err(|err| err.to_string())?;
+        Ok(())
+    }
+
+    // Settings are stored in the league settings file format, so they are validated again when loaded
+    pub fn get_g(&self) -> Result<G, String> {
+        let settings: Option<LeagueSettingsFile> = self.get_meta("gameAttributes")?;
+        let season: Option<i32> = self.get_meta("season")?;
+        if settings.is_none() || season.is_none() {
+            return Err("League database has no settings, it needs to be created with a new league first".to_string());
+        }
+        G::new(settings.unwrap().to_game_attributes()?, season.unwrap())
+    }
+
+    pub fn put_g(&self, g: &G) -> Result<(), String> {
+        self.put_meta("gameAttributes", &LeagueSettingsFile::from_game_attributes(g.game_attributes()))?;
+        self.put_meta("season", &g.season())?;
+        Ok(())
+    }
+
+    pub fn get_starting_season(&self) -> Result<Option<i32>, String> {
+        self.get_meta("startingSeason")
+    }
+
+    pub fn put_starting_season(&self, starting_season: i32) -> Result<(), String> {
+        self.put_meta("startingSeason", &starting_season)
+    }
+
+    // Base seed for every game in the league, see util::random::game_seed. Set once when the league is created.
+    pub fn get_seed(&self) -> Result<u64, String> {
+        let seed: Option<u64> = self.get_meta("seed")?;
+        if seed.is_none() {
+            return Err("League database has no seed, it needs to be created with a new league first".to_string());
+        }
+        Ok(seed.unwrap())
+    }
+
+    pub fn put_seed(&self, seed: u64) -> Result<(), String> {
+        self.put_meta("seed", &seed)
+    }
+
+    // Days simulated so far in the current season, counting regular season and playoffs
+    pub fn get_day(&self) -> Result<i32, String> {
+        Ok(self.get_meta("day")?.unwrap_or(0))
+    }
+
+    pub fn put_day(&self, day: i32) -> Result<(), String> {
+        self.put_meta("day", &day)
+    }
+
+    // The calibrated fast model, or the default one if calibrate was never run
+    pub fn get_fast_model(&self) -> Result<FastModel, String> {
+        Ok(self.get_meta("fastModel")?.unwrap_or_default())
+    }
+
+    pub fn put_fast_model(&self, model: &FastModel) -> Result<(), String> {
+        self.put_meta("fastModel", model)
+    }
+
+    pub fn get_teams(&self) -> Result<Vec<Team>, String> {
+        self.get_all("teams", "ORDER BY tid", &[])
+    }
+
+    pub fn put_team(&self, t: &Team) -> Result<(), String> {
+        self.put("teams", &["tid"], &[t.tid as i64], t)
+    }
+
+    pub fn get_players(&self) -> Result<Vec<Player>, String> {
+        self.get_all("players", "ORDER BY pid", &[])
+    }
+
+    pub fn get_players_by_tid(&self, tid: i32) -> Result<Vec<Player>, String> {
+        self.get_all("players", "WHERE tid = ?1 ORDER BY pid", &[tid as i64])
+    }
+
+    pub fn get_player(&self, pid: i32) -> Result<Option<Player>, String> {
+        self.get_one("players", "WHERE pid = ?1", &[pid as i64])
+    }
+
+    pub fn put_player(&self, p: &Player) -> Result<(), String> {
+        self.put("players", &["pid", "tid"], &[p.pid as i64, p.tid as i64], p)
+    }
+
+    pub fn get_team_seasons(&self, season: i32) -> Result<Vec<TeamSeasonAttrs>, String> {
+        self.get_all("teamSeasons", "WHERE season = ?1 ORDER BY tid", &[season as i64])
+    }
+
+    pub fn get_all_team_seasons(&self) -> Result<Vec<TeamSeasonAttrs>, String> {
+        self.get_all("teamSeasons", "ORDER BY season, tid", &[])
+    }
+
+    pub fn put_team_season(&self, team_season: &TeamSeasonAttrs) -> Result<(), String> {
+        self.put("teamSeasons", &["tid", "season"], &[team_season.tid as i64, team_season.season as i64], team_season)
+    }
+
+    pub fn get_games(&self, season: i32) -> Result<Vec<GameResult>, String> {
+        self.get_all("games", "WHERE season = ?1 ORDER BY gid", &[season as i64])
+    }
+
+    // Games played by GameSim, the only ones with box scores
+    pub fn get_game_sim_games(&self, season: i32) -> Result<Vec<GameResult>, String> {
+        self.get_all("games", "WHERE season = ?1 AND gid IN (SELECT gid FROM boxScores) ORDER BY gid", &[season as i64])
+    }
+
+    pub fn get_all_games(&self) -> Result<Vec<GameResult>, String> {
+        self.get_all("games", "ORDER BY gid", &[])
+    }
+
+    pub fn get_game(&self, gid: i32) -> Result<Option<GameResult>, String> {
+        self.get_one("games", "WHERE gid = ?1", &[gid as i64])
+    }
+
+    pub fn put_game(&self, game: &GameResult) -> Result<(), String> {
+        self.put("games", &["gid", "season"], &[game.gid as i64, game.season as i64], game)
+    }
+
+    // Next unused gid, across played and scheduled games
+    pub fn get_next_gid(&self) -> Result<i32, String> {
+        let max_gid: Option<i32> = self.conn
+            .query_row("SELECT MAX(gid) FROM (SELECT gid FROM games UNION ALL SELECT gid FROM schedule)", [], |row| row.get(0))
+            .map_err(|err| err.to_string())?;
+        Ok(max_gid.map(|gid| gid + 1).unwrap_or(0))
+    }
+
+    pub fn get_schedule(&self) -> Result<Vec<ScheduleGame>, String> {
+        self.get_all("schedule", "ORDER BY day, gid", &[])
+    }
+
+    pub fn put_schedule_game(&self, game: &ScheduleGame) -> Result<(), String> {
+        self.put("schedule", &["gid", "day"], &[game.gid as i64, game.day as i64], game)
+    }
+
+    pub fn delete_schedule_game(&self, gid: i32) -> Result<(), String> {
+        self.conn.execute("DELETE FROM schedule WHERE gid = ?1", [gid]).map_err(|err| err.to_string())?;
+        Ok(())
+    }
+
+    pub fn get_box_score(&self, gid: i32) -> Result<Option<BoxScore>, String> {
+        self.get_one("boxScores", "WHERE gid = ?1", &[gid as i64])
+    }
+
+    pub fn put_box_score(&self, box_score: &BoxScore) -> Result<(), String> {
+        self.put("boxScores", &["gid"], &[box_score.gid as i64], box_score)
+    }
+
+    pub fn get_play_by_play(&self, gid: i32) -> Result<Option<Vec<PlayByPlayEvent>>, String> {
+        self.get_one("playByPlay", "WHERE gid = ?1", &[gid as i64])
+    }
+
+    pub fn put_play_by_play(&self, gid: i32, play_by_play: &[PlayByPlayEvent]) -> Result<(), String> {
+        self.put("playByPlay", &["gid"], &[gid as i64], &play_by_play)
+    }
+
+    // Play-by-play takes more space than everything else together, so only the current season's is kept
+    pub fn delete_play_by_play_before(&self, season: i32) -> Result<(), String> {
+        self.conn
+            .execute("DELETE FROM playByPlay WHERE gid IN (SELECT gid FROM games WHERE season < ?1)", [season])
+            .map_err(|err| err.to_string())?;
+        Ok(())
+    }
+
+    pub fn get_playoff_series(&self, season: i32) -> Result<Option<PlayoffSeries>, String> {
+        self.get_one("playoffSeries", "WHERE season = ?1", &[season as i64])
+    }
+
+    pub fn put_playoff_series(&self, playoff_series: &PlayoffSeries) -> Result<(), String> {
+        self.put("playoffSeries", &["season"], &[playoff_series.season as i64], playoff_series)
+    }
+
+    // Season of the last draft that was finished
+    pub fn get_last_draft_season(&self) -> Result<Option<i32>, String> {
+        self.get_meta("lastDraftSeason")
+    }
+
+    pub fn put_last_draft_season(&self, season: i32) -> Result<(), String> {
+        self.put_meta("lastDraftSeason", &season)
+    }
+
+    // One of the PHASE constants, None for leagues from before phases were stored
+    pub fn get_phase(&self) -> Result<Option<i32>, String> {
+        self.get_meta("phase")
+    }
+
+    pub fn put_phase(&self, phase: i32) -> Result<(), String> {
+        self.put_meta("phase", &phase)
+    }
+
+    // Days of free agency left after g.season(), None when free agency isn't running
+    pub fn get_free_agency_days_left(&self) -> Result<Option<i32>, String> {
+        Ok(self.get_meta::<Option<i32>>("freeAgencyDaysLeft")?.flatten())
+    }
+
+    pub fn put_free_agency_days_left(&self, days_left: Option<i32>) -> Result<(), String> {
+        self.put_meta("freeAgencyDaysLeft", &days_left)
+    }
+
+    // Ordered by round and pick once the draft order is set, and by round and original team before that
+    pub fn get_draft_picks(&self, season: i32) -> Result<Vec<DraftPick>, String> {
+        let mut draft_picks: Vec<DraftPick> = self.get_all("draftPicks", "WHERE season = ?1", &[season as i64])?;
+        draft_picks.sort_by_key(|dp| (dp.round, dp.pick, dp.original_tid));
+        Ok(draft_picks)
+    }
+
+    pub fn get_all_draft_picks(&self) -> Result<Vec<DraftPick>, String> {
+        let mut draft_picks: Vec<DraftPick> = self.get_all("draftPicks", "", &[])?;
+        draft_picks.sort_by_key(|dp| (dp.season, dp.round, dp.pick, dp.original_tid));
+        Ok(draft_picks)
+    }
+
+    pub fn get_draft_pick(&self, dpid: i32) -> Result<Option<DraftPick>, String> {
+        self.get_one("draftPicks", "WHERE dpid = ?1", &[dpid as i64])
+    }
+
+    pub fn put_draft_pick(&self, dp: &DraftPick) -> Result<(), String> {
+        self.put("draftPicks", &["dpid", "season"], &[dp.dpid as i64, dp.season as i64], dp)
+    }
+
+    pub fn delete_draft_pick(&self, dpid: i32) -> Result<(), String> {
+        self.conn.execute("DELETE FROM draftPicks WHERE dpid = ?1", [dpid]).map_err(|err| err.to_string())?;
+        Ok(())
+    }
+
+    // Next unused dpid, including picks that were already used and deleted
+    pub fn get_next_dpid(&self) -> Result<i32, String> {
+        let next_dpid: Option<i32> = self.get_meta("nextDpid")?;
+        let max_dpid: Option<i32> = self.conn
+            .query_row("SELECT MAX(dpid) FROM draftPicks", [], |row| row.get(0))
+            .map_err(|err| err.to_string())?;
+        let dpid = next_dpid.unwrap_or(0).max(max_dpid.map(|dpid| dpid + 1).unwrap_or(0));
+        self.put_meta("nextDpid", &(dpid + 1))?;
+        Ok(dpid)
+    }
+
+    pub fn get_events(&self, season: i32) -> Result<Vec<Event>, String> {
+        self.get_all("events", "WHERE season = ?1 ORDER BY eid", &[season as i64])
+    }
+
+    pub fn get_all_events(&self) -> Result<Vec<Event>, String> {
+        self.get_all("events", "ORDER BY eid", &[])
+    }
+
+    pub fn put_event(&self, event: &Event) -> Result<(), String> {
+        self.put("events", &["eid", "season"], &[event.eid as i64, event.season as i64], event)
+    }
+
+    pub fn get_next_eid(&self) -> Result<i32, String> {
+        let max_eid: Option<i32> = self.conn
+            .query_row("SELECT MAX(eid) FROM events", [], |row| row.get(0))
+            .map_err(|err| err.to_string())?;
+        Ok(max_eid.map(|eid| eid + 1).unwrap_or(0))
+    }
+
+    pub fn get_all_awards(&self) -> Result<Vec<SeasonAwards>, String> {
+        self.get_all("awards", "ORDER BY season", &[])
+    }
+
+    pub fn put_awards(&self, awards: &SeasonAwards) -> Result<(), String> {
+        self.put("awards", &["season"], &[awards.season as i64], awards)
+    }
+
+    // Next unused pid
+    pub fn get_next_pid(&self) -> Result<i32, String> {
+        let max_pid: Option<i32> = self.conn
+            .query_row("SELECT MAX(pid) FROM players", [], |row| row.get(0))
+            .map_err(|err| err.to_string())?;
+        Ok(max_pid.map(|pid| pid + 1).unwrap_or(0))
+    }
+
+    // Everything except box scores, play-by-play, the schedule and playoff series, which League doesn't have
+    pub fn get_league(&self) -> Result<League, String> {
+        let g = self.get_g()?;
+        let starting_season = self.get_starting_season()?.unwrap_or(g.season());
+        Ok(League {
+            g,
+            starting_season,
+            phase: get_phase(self)?,
+            teams: self.get_teams()?,
+            players: self.get_players()?,
+            games: self.get_all_games()?,
+            team_seasons: self.get_all_team_seasons()?,
+            draft_picks: self.get_all_draft_picks()?,
+            events: self.get_all_events()?,
+            awards: self.get_all_awards()?
+        })
+    }
+
+    pub fn put_league(&self, league: &League) -> Result<(), String> {
+        self.put_g(&league.g)?;
+        self.put_starting_season(league.starting_season)?;
+        self.put_phase(league.phase)?;
+        for t in league.teams.iter() {
+            self.put_team(t)?;
+        }
+        for p in league.players.iter() {
+            self.put_player(p)?;
+        }
+        for game in league.games.iter() {
+            self.put_game(game)?;
+        }
+        for team_season in league.team_seasons.iter() {
+            self.put_team_season(team_season)?;
+        }
+        for dp in league.draft_picks.iter() {
+            self.put_draft_pick(dp)?;
+        }
+        for event in league.events.iter() {
+            self.put_event(event)?;
+        }
+        for awards in league.awards.iter() {
+            self.put_awards(awards)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn failed_transaction_is_rolled_back() {
+        let mut db = LeagueDb::open_in_memory().unwrap();
+        db.transaction(|tx| tx.put_day(3)).unwrap();
+        let result: Result<(), String> = db.transaction(|tx| {
+            tx.put_day(4)?;
+            tx.put_schedule_game(&ScheduleGame { gid: 0, day: 4, home_tid: 0, away_tid: 1 })?;
+            Err("Game 0 failed".to_string())
+        });
+        assert_eq!(result.unwrap_err(), "Game 0 failed");
+        db.transaction(|tx| {
+            assert_eq!(tx.get_day()?, 3);
+            assert!(tx.get_schedule()?.is_empty());
+            Ok(())
+        }).unwrap();
+    }
+}
\ No newline at end of file
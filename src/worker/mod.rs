@@ -1,2 +1,3 @@
 pub mod core;
+pub mod db;
 pub mod util;
\ No newline at end of file
@@ -1,15 +1,249 @@
+use crate::common::points_formula::PointsFormula;
+use crate::common::types::Conf;
+use crate::common::types::Div;
 use crate::common::types::GameAttributes;
 use crate::common::types::GameAttributesLeague;
 use crate::common::types::GameAttributesLeagueWithHistory;
+use crate::common::types::GameAttributesWithHistory;
 
+// Single source of league settings. Everything that needs a setting reads it from here, and settings with history
+// are looked up for the current season. Settings can only be changed through methods that validate them again.
 pub struct G {
-    pub game_attributes: GameAttributes
+    game_attributes: GameAttributes,
+    season: i32,
+    // Parsed from the points_formula setting when G is created
+    points_formulas: GameAttributesWithHistory<Option<PointsFormula>>
 }
 
 impl G {
-    pub fn new(attributes:GameAttributes) -> Self {
-        return G {
-            game_attributes: attributes
-        };
+    pub fn new(attributes:GameAttributes, season: i32) -> Result<Self, String> {
+        if attributes.league_with_history.is_none() {
+            return Err("League settings are missing".to_string());
+        }
+        let league_with_history = attributes.league_with_history.as_ref().unwrap();
+        league_with_history.game_attributes_league.validate()?;
+        league_with_history.validate()?;
+
+        let mut points_formulas = GameAttributesWithHistory::new(None);
+        for change in league_with_history.points_formula.iter() {
+            points_formulas.set_from(change.start, league_with_history.get_points_formula(change.start)?);
+        }
+        Ok(G {
+            game_attributes: attributes,
+            season,
+            points_formulas
+        })
+    }
+
+    pub fn from_defaults(season: i32) -> Result<Self, String> {
+        G::new(GameAttributes::new(None), season)
+    }
+
+    pub fn game_attributes(&self) -> &GameAttributes {
+        &self.game_attributes
+    }
+
+    pub fn get_league_with_history(&self) -> &GameAttributesLeagueWithHistory {
+        self.game_attributes.league_with_history.as_ref().unwrap()
+    }
+
+    pub fn get_league(&self) -> &GameAttributesLeague {
+        &self.get_league_with_history().game_attributes_league
+    }
+
+    pub fn season(&self) -> i32 {
+        self.season
+    }
+
+    pub fn set_season(&mut self, season: i32) {
+        self.season = season;
+    }
+
+    // Changes are made to a copy, which replaces the current settings only if it is valid
+    fn update_league_with_history(&mut self, update: impl FnOnce(&mut GameAttributesLeagueWithHistory)) -> Result<(), String> {
+        let mut attributes = self.game_attributes.clone();
+        update(attributes.league_with_history.as_mut().unwrap());
+        *self = G::new(attributes, self.season)?;
+        Ok(())
+    }
+
+    // The value applies from start until the next change, i32::MIN for every season before it
+    pub fn set_num_games_playoff_series(&mut self, start: i32, num_games_playoff_series: Vec<u8>) -> Result<(), String> {
+        self.update_league_with_history(|league_with_history| {
+            league_with_history.num_games_playoff_series.set_from(start, num_games_playoff_series);
+        })
+    }
+
+    pub fn set_play_in_type(&mut self, start: i32, play_in_type: &str) -> Result<(), String> {
+        self.update_league_with_history(|league_with_history| {
+            league_with_history.play_in_type.set_from(start, play_in_type.to_string());
+        })
+    }
+
+    pub fn fouls_needed_to_foul_out(&self) -> i32 {
+        *self.get_league().fouls_needed_to_foul_out.value_at(self.season)
+    }
+
+    pub fn num_players_on_court(&self) -> i32 {
+        *self.get_league().num_players_on_court.value_at(self.season)
+    }
+
+    pub fn quarter_length(&self) -> f64 {
+        *self.get_league().quarter_length.value_at(self.season)
+    }
+
+    pub fn disable_injuries(&self) -> bool {
+        *self.get_league().disable_injuries.value_at(self.season)
+    }
+
+    pub fn pace(&self) -> f64 {
+        *self.get_league().pace.value_at(self.season)
+    }
+
+    pub fn fouls_until_bonus(&self) -> &Vec<i32> {
+        self.get_league().fouls_until_bonus.value_at(self.season)
+    }
+
+    pub fn foul_rate_factor(&self) -> f64 {
+        *self.get_league().foul_rate_factor.value_at(self.season)
+    }
+
+    pub fn turnover_factor(&self) -> f64 {
+        *self.get_league().turnover_factor.value_at(self.season)
+    }
+
+    pub fn draft_type(&self) -> &str {
+        self.get_league().draft_type.value_at(self.season)
+    }
+
+    pub fn num_draft_rounds(&self) -> i32 {
+        *self.get_league().num_draft_rounds.value_at(self.season)
+    }
+
+    pub fn salary_cap(&self) -> f64 {
+        *self.get_league().salary_cap.value_at(self.season)
+    }
+
+    pub fn salary_cap_type(&self) -> &str {
+        self.get_league().salary_cap_type.value_at(self.season)
+    }
+
+    pub fn min_payroll(&self) -> f64 {
+        *self.get_league().min_payroll.value_at(self.season)
+    }
+
+    pub fn luxury_payroll(&self) -> f64 {
+        *self.get_league().luxury_payroll.value_at(self.season)
+    }
+
+    pub fn luxury_tax(&self) -> f64 {
+        *self.get_league().luxury_tax.value_at(self.season)
+    }
+
+    pub fn min_contract(&self) -> f64 {
+        *self.get_league().min_contract.value_at(self.season)
+    }
+
+    pub fn max_contract(&self) -> f64 {
+        *self.get_league().max_contract.value_at(self.season)
+    }
+
+    pub fn min_contract_length(&self) -> i32 {
+        *self.get_league().min_contract_length.value_at(self.season)
+    }
+
+    pub fn max_contract_length(&self) -> i32 {
+        *self.get_league().max_contract_length.value_at(self.season)
+    }
+
+    pub fn rookie_contract_lengths(&self) -> &Vec<i32> {
+        self.get_league().rookie_contract_lengths.value_at(self.season)
+    }
+
+    pub fn draft_pick_auto_contract_percent(&self) -> f64 {
+        *self.get_league().draft_pick_auto_contract_percent.value_at(self.season)
+    }
+
+    pub fn draft_pick_auto_contract_rounds(&self) -> i32 {
+        *self.get_league().draft_pick_auto_contract_rounds.value_at(self.season)
+    }
+
+    pub fn max_roster_size(&self) -> i32 {
+        *self.get_league().max_roster_size.value_at(self.season)
+    }
+
+    pub fn min_roster_size(&self) -> i32 {
+        *self.get_league().min_roster_size.value_at(self.season)
+    }
+
+    pub fn trade_deadline(&self) -> f64 {
+        *self.get_league().trade_deadline.value_at(self.season)
+    }
+
+    pub fn confs(&self) -> &Vec<Conf> {
+        self.get_league_with_history().confs.value_at(self.season)
+    }
+
+    pub fn divs(&self) -> &Vec<Div> {
+        self.get_league_with_history().divs.value_at(self.season)
+    }
+
+    pub fn num_games(&self) -> i32 {
+        *self.get_league_with_history().num_games.value_at(self.season)
+    }
+
+    pub fn num_games_playoff_series(&self) -> &Vec<u8> {
+        self.get_league_with_history().num_games_playoff_series.value_at(self.season)
+    }
+
+    pub fn num_playoff_byes(&self) -> u8 {
+        *self.get_league_with_history().num_playoff_byes.value_at(self.season)
+    }
+
+    pub fn otl(&self) -> bool {
+        *self.get_league_with_history().otl.value_at(self.season)
+    }
+
+    pub fn ties(&self) -> bool {
+        *self.get_league_with_history().ties.value_at(self.season)
+    }
+
+    pub fn playoffs_num_teams_div(&self) -> u8 {
+        *self.get_league_with_history().playoffs_num_teams_div.value_at(self.season)
+    }
+
+    pub fn play_in_type(&self) -> &str {
+        self.get_league_with_history().play_in_type.value_at(self.season)
+    }
+
+    pub fn points_formula(&self) -> Option<&PointsFormula> {
+        self.points_formulas.value_at(self.season).as_ref()
+    }
+
+    pub fn tiebreakers(&self) -> Vec<String> {
+        self.get_league_with_history().get_tiebreakers(self.season)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invalid_changes_are_rejected() {
+        let mut g = G::from_defaults(2025).unwrap();
+        assert_eq!(
+            g.set_num_games_playoff_series(2030, vec![7, 6]).unwrap_err(),
+            "numGamesPlayoffSeries: every round needs an odd number of games, got 6"
+        );
+        assert!(g.set_play_in_type(i32::MIN, "doubleElimination").is_err());
+        g.set_season(2030);
+        assert_eq!(*g.num_games_playoff_series(), vec![7, 7, 7, 7]);
+        assert_eq!(g.play_in_type(), "nba");
+
+        g.set_num_games_playoff_series(2030, vec![5, 7]).unwrap();
+        assert_eq!(*g.num_games_playoff_series(), vec![5, 7]);
+        g.set_season(2029);
+        assert_eq!(*g.num_games_playoff_series(), vec![7, 7, 7, 7]);
     }
 }
\ No newline at end of file
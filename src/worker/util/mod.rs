@@ -1,2 +1,5 @@
 pub mod helpers;
-pub mod g;
\ No newline at end of file
+pub mod g;
+pub mod order_teams;
+pub mod parallel;
+pub mod random;
\ No newline at end of file
@@ -0,0 +1,473 @@
+use crate::common::helpers as common_helpers;
+use crate::common::points_formula::PointsFormula;
+use crate::common::types::GameResult;
+use crate::common::types::TeamSeasonAttrs;
+use crate::worker::util::random::derive_seed;
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+pub struct TiebreakerContext<'a> {
+    pub all_teams: &'a [TeamSeasonAttrs],
+    pub games: &'a [GameResult],
+    pub div_winners: HashSet<i32>,
+    // Losses in overtime are recorded separately when the league has the otl setting enabled
+    pub otl: bool,
+    pub points_formula: Option<&'a PointsFormula>,
+    // Each team gets one coin flip value per context, so a coin flip between the same teams comes out the same way in
+    // the division, conference and league standings
+    pub coin_flip_seed: u64
+}
+
+#[derive(Clone)]
+pub struct OrderedTeam {
+    pub tid: i32,
+    // Key from TIEBREAKERS that decided this team's placement, None if it was not tied
+    pub tiebreaker: Option<String>
+}
+
+struct Record {
+    won: i32,
+    lost: i32,
+    tied: i32,
+    otl: i32
+}
+
+impl Record {
+    fn gp(&self) -> i32 {
+        self.won + self.lost + self.tied + self.otl
+    }
+
+    fn winp(&self) -> f64 {
+        common_helpers::calc_winp(self.won, self.lost, self.tied, self.otl)
+    }
+}
+
+const EPSILON: f64 = 1e-9;
+
+fn get_record(ctx: &TiebreakerContext, tid: i32, is_opponent: &dyn Fn(i32) -> bool) -> Record {
+    let mut record = Record { won: 0, lost: 0, tied: 0, otl: 0 };
+    for game in ctx.games.iter() {
+        if game.playoffs {
+            continue;
+        }
+        for t in 0..2 {
+            let opp_t = 1 - t;
+            if game.teams[t].tid != tid || !is_opponent(game.teams[opp_t].tid) {
+                continue;
+            }
+            if game.teams[t].pts > game.teams[opp_t].pts {
+                record.won += 1;
+            } else if game.teams[t].pts < game.teams[opp_t].pts {
+                if ctx.otl && game.overtimes > 0 {
+                    record.otl += 1;
+                } else {
+                    record.lost += 1;
+                }
+            } else {
+                record.tied += 1;
+            }
+        }
+    }
+    record
+}
+
+fn get_opponents(ctx: &TiebreakerContext, tid: i32) -> HashSet<i32> {
+    let mut opponents = HashSet::new();
+    for game in ctx.games.iter() {
+        if game.playoffs {
+            continue;
+        }
+        if game.teams[0].tid == tid {
+            opponents.insert(game.teams[1].tid);
+        } else if game.teams[1].tid == tid {
+            opponents.insert(game.teams[0].tid);
+        }
+    }
+    opponents
+}
+
+// Average winning percentage of the opponents in games where include_game returns true, weighted by number of games
+fn get_opponents_winp(ctx: &TiebreakerContext, teams_by_tid: &HashMap<i32, &TeamSeasonAttrs>, tid: i32, include_game: &dyn Fn(i32, i32) -> bool) -> f64 {
+    let mut sum = 0.0;
+    let mut count = 0;
+    for game in ctx.games.iter() {
+        if game.playoffs {
+            continue;
+        }
+        for t in 0..2 {
+            let opp_t = 1 - t;
+            if game.teams[t].tid != tid || !include_game(game.teams[t].pts, game.teams[opp_t].pts) {
+                continue;
+            }
+            let opp = teams_by_tid.get(&game.teams[opp_t].tid);
+            if let Some(opp) = opp {
+                sum += common_helpers::calc_winp(opp.won, opp.lost, opp.tied, opp.otl);
+                count += 1;
+            }
+        }
+    }
+    if count == 0 {
+        return 0.0;
+    }
+    sum / count as f64
+}
+
+// Returns None if the tiebreaker does not apply to this group of teams, so the next one should be tried
+fn get_tiebreaker_values(ctx: &TiebreakerContext, teams_by_tid: &HashMap<i32, &TeamSeasonAttrs>, tiebreaker: &str, group: &[i32]) -> Option<Vec<f64>> {
+    let in_group: HashSet<i32> = group.iter().cloned().collect();
+
+    match tiebreaker {
+        "headToHeadRecord" => {
+            let mut values = Vec::new();
+            for tid in group.iter() {
+                let tid = *tid;
+                let record = get_record(ctx, tid, &|opp| opp != tid && in_group.contains(&opp));
+                if record.gp() == 0 {
+                    return None;
+                }
+                values.push(record.winp());
+            }
+            Some(values)
+        }
+        "divWinner" => {
+            // Within a division, the division winner is decided by this tiebreak, so it can't be used to decide it
+            let did = teams_by_tid.get(&group[0]).unwrap().did;
+            if group.iter().all(|tid| teams_by_tid.get(tid).unwrap().did == did) {
+                return None;
+            }
+            Some(group.iter().map(|tid| {
+                if ctx.div_winners.contains(tid) {
+                    1.0
+                } else {
+                    0.0
+                }
+            }).collect())
+        }
+        "divRecordIfSame" | "confRecordIfSame" => {
+            let same_div = tiebreaker == "divRecordIfSame";
+            let key = |tid: &i32| {
+                let t = teams_by_tid.get(tid).unwrap();
+                if same_div {
+                    t.did
+                } else {
+                    t.cid
+                }
+            };
+            let first = key(&group[0]);
+            if group.iter().any(|tid| key(tid) != first) {
+                return None;
+            }
+            let mut values = Vec::new();
+            for tid in group.iter() {
+                let tid = *tid;
+                let record = get_record(ctx, tid, &|opp| {
+                    opp != tid && teams_by_tid.get(&opp).is_some() && key(&opp) == first
+                });
+                values.push(record.winp());
+            }
+            Some(values)
+        }
+        "commonOpponentsRecord" => {
+            let mut common: Option<HashSet<i32>> = None;
+            for tid in group.iter() {
+                let opponents = get_opponents(ctx, *tid);
+                common = Some(match common {
+                    Some(c) => c.intersection(&opponents).cloned().collect(),
+                    None => opponents,
+                });
+            }
+            let common: HashSet<i32> = common
+                .unwrap()
+                .into_iter()
+                .filter(|tid| !in_group.contains(tid))
+                .collect();
+            if common.is_empty() {
+                return None;
+            }
+            Some(group.iter().map(|tid| {
+                get_record(ctx, *tid, &|opp| common.contains(&opp)).winp()
+            }).collect())
+        }
+        "marginOfVictory" => {
+            Some(group.iter().map(|tid| {
+                let t = teams_by_tid.get(tid).unwrap();
+                if t.gp == 0 {
+                    0.0
+                } else {
+                    (t.pts - t.opp_pts) as f64 / t.gp as f64
+                }
+            }).collect())
+        }
+        "strengthOfVictory" => {
+            Some(group.iter().map(|tid| {
+                get_opponents_winp(ctx, teams_by_tid, *tid, &|pts, opp_pts| pts > opp_pts)
+            }).collect())
+        }
+        "strengthOfSchedule" => {
+            Some(group.iter().map(|tid| {
+                get_opponents_winp(ctx, teams_by_tid, *tid, &|_, _| true)
+            }).collect())
+        }
+        "coinFlip" => {
+            Some(group.iter().map(|tid| {
+                derive_seed(ctx.coin_flip_seed, &[*tid as i64]) as f64
+            }).collect())
+        }
+        _ => {
+            None
+        }
+    }
+}
+
+fn break_ties(ctx: &TiebreakerContext, teams_by_tid: &HashMap<i32, &TeamSeasonAttrs>, tiebreakers: &[String], group: &[i32]) -> Vec<OrderedTeam> {
+    if group.len() == 1 {
+        return vec![OrderedTeam {
+            tid: group[0],
+            tiebreaker: None
+        }];
+    }
+
+    for tiebreaker in tiebreakers.iter() {
+        let values = get_tiebreaker_values(ctx, teams_by_tid, tiebreaker, group);
+        if values.is_none() {
+            continue;
+        }
+        let values = values.unwrap();
+
+        let mut sorted: Vec<(i32, f64)> = group.iter().cloned().zip(values).collect();
+        sorted.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+
+        let mut subgroups: Vec<Vec<i32>> = Vec::new();
+        let mut prev_value = f64::NAN;
+        for (tid, value) in sorted.iter() {
+            if subgroups.is_empty() || (prev_value - value).abs() > EPSILON {
+                subgroups.push(Vec::new());
+            }
+            subgroups.last_mut().unwrap().push(*tid);
+            prev_value = *value;
+        }
+
+        if subgroups.len() == 1 {
+            continue;
+        }
+
+        // Once a tie is partially broken, any remaining smaller tie starts over from the first tiebreaker
+        let mut ordered = Vec::new();
+        for subgroup in subgroups.iter() {
+            if subgroup.len() == 1 {
+                ordered.push(OrderedTeam {
+                    tid: subgroup[0],
+                    tiebreaker: Some(tiebreaker.clone())
+                });
+            } else {
+                ordered.extend(break_ties(ctx, teams_by_tid, tiebreakers, subgroup));
+            }
+        }
+        return ordered;
+    }
+
+    group.iter().map(|tid| OrderedTeam {
+        tid: *tid,
+        tiebreaker: None
+    }).collect()
+}
+
+// Points if the league uses a points_formula, otherwise winning percentage
+pub fn get_standings_value(t: &TeamSeasonAttrs, points_formula: Option<&PointsFormula>) -> f64 {
+    if let Some(points_formula) = points_formula {
+        return points_formula.evaluate(t.won, t.lost, t.tied, t.otl);
+    }
+    common_helpers::calc_winp(t.won, t.lost, t.tied, t.otl)
+}
+
+// Sorts teams by points or winning percentage and applies the tiebreakers in order to any ties
+pub fn order_teams(teams: &[TeamSeasonAttrs], ctx: &TiebreakerContext, tiebreakers: &[String]) -> Vec<OrderedTeam> {
+    let teams_by_tid: HashMap<i32, &TeamSeasonAttrs> = ctx.all_teams.iter().map(|t| (t.tid, t)).collect();
+
+    let mut sorted: Vec<(i32, f64)> = teams
+        .iter()
+        .map(|t| (t.tid, get_standings_value(t, ctx.points_formula)))
+        .collect();
+    sorted.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+
+    let mut ordered = Vec::new();
+    let mut i = 0;
+    while i < sorted.len() {
+        let mut group = vec![sorted[i].0];
+        let mut j = i + 1;
+        while j < sorted.len() && (sorted[j].1 - sorted[i].1).abs() < EPSILON {
+            group.push(sorted[j].0);
+            j += 1;
+        }
+        ordered.extend(break_ties(ctx, &teams_by_tid, tiebreakers, &group));
+        i = j;
+    }
+
+    ordered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::types::GameResultTeam;
+
+    // [home tid, home pts, away tid, away pts]
+    fn get_games(scores: &[[i32; 4]]) -> Vec<GameResult> {
+        scores.iter().enumerate().map(|(gid, score)| GameResult {
+            gid: gid as i32,
+            season: 2025,
+            playoffs: false,
+            overtimes: 0,
+            teams: [
+                GameResultTeam {
+                    tid: score[0],
+                    pts: score[1]
+                },
+                GameResultTeam {
+                    tid: score[2],
+                    pts: score[3]
+                }
+            ]
+        }).collect()
+    }
+
+    // Team seasons for tids 0 to num_teams - 1 in the same division, with records from games
+    fn get_team_seasons(num_teams: i32, games: &[GameResult]) -> Vec<TeamSeasonAttrs> {
+        let mut team_seasons: Vec<TeamSeasonAttrs> = (0..num_teams).map(|tid| TeamSeasonAttrs::new(tid, 2025, 0, 0)).collect();
+        for game in games.iter() {
+            for t in 0..2 {
+                let team_season = &mut team_seasons[game.teams[t].tid as usize];
+                let pts = game.teams[t].pts;
+                let opp_pts = game.teams[1 - t].pts;
+                team_season.gp += 1;
+                team_season.pts += pts;
+                team_season.opp_pts += opp_pts;
+                if pts > opp_pts {
+                    team_season.won += 1;
+                } else if pts < opp_pts {
+                    team_season.lost += 1;
+                } else {
+                    team_season.tied += 1;
+                }
+            }
+        }
+        team_seasons
+    }
+
+    fn get_order(team_seasons: &[TeamSeasonAttrs], games: &[GameResult], tiebreakers: &[&str]) -> Vec<(i32, Option<String>)> {
+        get_order_with_div_winners(team_seasons, games, &[], tiebreakers)
+    }
+
+    fn get_order_with_div_winners(team_seasons: &[TeamSeasonAttrs], games: &[GameResult], div_winners: &[i32], tiebreakers: &[&str]) -> Vec<(i32, Option<String>)> {
+        let ctx = TiebreakerContext {
+            all_teams: team_seasons,
+            games,
+            div_winners: div_winners.iter().cloned().collect(),
+            otl: false,
+            points_formula: None,
+            coin_flip_seed: 1
+        };
+        let tiebreakers: Vec<String> = tiebreakers.iter().map(|tiebreaker| tiebreaker.to_string()).collect();
+        order_teams(team_seasons, &ctx, &tiebreakers)
+            .into_iter()
+            .map(|t| (t.tid, t.tiebreaker))
+            .collect()
+    }
+
+    fn decided_by(tid: i32, tiebreaker: &str) -> (i32, Option<String>) {
+        (tid, Some(tiebreaker.to_string()))
+    }
+
+    #[test]
+    fn two_way_tie_goes_to_head_to_head() {
+        // 0 and 1 are both 2-1, and 0 beat 1
+        let games = get_games(&[
+            [0, 100, 1, 90],
+            [1, 100, 2, 90],
+            [1, 100, 3, 90],
+            [2, 100, 0, 90],
+            [0, 100, 3, 90]
+        ]);
+        let team_seasons = get_team_seasons(4, &games);
+        assert_eq!(get_order(&team_seasons, &games, &["headToHeadRecord", "coinFlip"]), vec![
+            decided_by(0, "headToHeadRecord"),
+            decided_by(1, "headToHeadRecord"),
+            (2, None),
+            (3, None)
+        ]);
+    }
+
+    #[test]
+    fn multi_way_tie_skips_tiebreakers_that_dont_split_it() {
+        // Everyone is 1-1 and 1-1 head to head, so margin of victory decides
+        let games = get_games(&[
+            [0, 110, 1, 100],
+            [1, 105, 2, 100],
+            [2, 101, 0, 100]
+        ]);
+        let team_seasons = get_team_seasons(3, &games);
+        assert_eq!(get_order(&team_seasons, &games, &["headToHeadRecord", "marginOfVictory", "coinFlip"]), vec![
+            decided_by(0, "marginOfVictory"),
+            decided_by(2, "marginOfVictory"),
+            decided_by(1, "marginOfVictory")
+        ]);
+    }
+
+    #[test]
+    fn multi_way_tie_restarts_for_the_teams_still_tied() {
+        // 0, 1 and 2 are 2-2. 0 swept the others, and 1 and 2 split their games, so head to head only separates 0, and
+        // the tie between 1 and 2 starts over and goes to margin of victory.
+        let games = get_games(&[
+            [0, 100, 1, 90],
+            [0, 100, 2, 90],
+            [1, 100, 2, 99],
+            [2, 100, 1, 80],
+            [3, 100, 0, 90],
+            [3, 100, 0, 90],
+            [1, 100, 3, 90],
+            [2, 100, 3, 90],
+            [4, 100, 3, 90]
+        ]);
+        let team_seasons = get_team_seasons(5, &games);
+        assert_eq!(get_order(&team_seasons, &games, &["headToHeadRecord", "marginOfVictory", "coinFlip"]), vec![
+            (4, None),
+            decided_by(0, "headToHeadRecord"),
+            decided_by(2, "marginOfVictory"),
+            decided_by(1, "marginOfVictory"),
+            (3, None)
+        ]);
+    }
+
+    #[test]
+    fn coin_flip_is_the_same_every_time() {
+        // Identical records with no games between them
+        let games = get_games(&[
+            [0, 100, 2, 90],
+            [1, 100, 3, 90]
+        ]);
+        let team_seasons = get_team_seasons(4, &games);
+        let order = get_order(&team_seasons, &games, &["headToHeadRecord", "commonOpponentsRecord", "marginOfVictory", "coinFlip"]);
+        assert_eq!(order[0].1, Some("coinFlip".to_string()));
+        assert_eq!(order[1].1, Some("coinFlip".to_string()));
+        assert_eq!(order, get_order(&team_seasons, &games, &["coinFlip"]));
+    }
+
+    #[test]
+    fn div_winner_only_breaks_ties_between_divisions() {
+        // 0 and 1 are tied with no games between them
+        let games = get_games(&[
+            [0, 100, 2, 90],
+            [1, 100, 3, 90]
+        ]);
+        let mut team_seasons = get_team_seasons(4, &games);
+
+        // In the same division, being the division winner can't decide who wins the division
+        let order = get_order_with_div_winners(&team_seasons, &games, &[1], &["divWinner"]);
+        assert_eq!(order[0].1, None);
+        assert_eq!(order[1].1, None);
+
+        team_seasons[1].did = 1;
+        let order = get_order_with_div_winners(&team_seasons, &games, &[1], &["divWinner"]);
+        assert_eq!(order[0], decided_by(1, "divWinner"));
+    }
+}
\ No newline at end of file
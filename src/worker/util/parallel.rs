@@ -0,0 +1,38 @@
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Mutex;
+use std::thread;
+
+// Number of threads to use when the caller doesn't say
+pub fn default_num_threads() -> usize {
+    thread::available_parallelism().map(|n| n.get()).unwrap_or(1)
+}
+
+// Calls f(0), f(1)... f(num_items - 1) on up to num_threads threads and returns the results in index order. Threads
+// take the next index as they finish, so uneven work (overtimes, long playoff runs) balances out. f must not depend
+// on which thread it runs on, which is what keeps results the same for every thread count.
+pub fn parallel_map<T: Send, F: Fn(usize) -> T + Sync>(num_items: usize, num_threads: usize, f: F) -> Vec<T> {
+    let num_threads = num_threads.max(1).min(num_items.max(1));
+    if num_threads == 1 {
+        return (0..num_items).map(&f).collect();
+    }
+
+    let next = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<T>>> = Mutex::new((0..num_items).map(|_| None).collect());
+    thread::scope(|scope| {
+        for _ in 0..num_threads {
+            scope.spawn(|| {
+                loop {
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+                    if i >= num_items {
+                        break;
+                    }
+                    let result = f(i);
+                    results.lock().unwrap()[i] = Some(result);
+                }
+            });
+        }
+    });
+
+    results.into_inner().unwrap().into_iter().map(|result| result.unwrap()).collect()
+}
\ No newline at end of file
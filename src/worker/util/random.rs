@@ -0,0 +1,77 @@
+use rand::rngs::StdRng;
+use rand::Rng;
+use rand::SeedableRng;
+
+// SplitMix64 finalizer, which spreads nearby inputs (consecutive gids, run numbers) over the whole seed space
+fn mix(mut x: u64) -> u64 {
+    x = x.wrapping_add(0x9e3779b97f4a7c15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d049bb133111eb);
+    x ^ (x >> 31)
+}
+
+// Seed for one unit of work (a game, a Monte Carlo run) derived from a base seed and the unit's keys. It depends
+// only on its inputs, not on which thread runs the work or in what order.
+pub fn derive_seed(seed: u64, keys: &[i64]) -> u64 {
+    let mut derived = mix(seed);
+    for key in keys.iter() {
+        derived = mix(derived ^ (*key as u64));
+    }
+    derived
+}
+
+pub fn game_seed(seed: u64, season: i32, gid: i32) -> u64 {
+    derive_seed(seed, &[season as i64, gid as i64])
+}
+
+// Seed for a season's standings coin flips, so tied teams are ordered the same way every time the standings are shown
+pub fn coin_flip_seed(seed: u64, season: i32) -> u64 {
+    derive_seed(seed, &[season as i64, -1])
+}
+
+// Seed for the order of a season's schedule
+pub fn schedule_seed(seed: u64, season: i32) -> u64 {
+    derive_seed(seed, &[season as i64, -2])
+}
+
+// Seed for a player's development in the offseason before season
+pub fn progression_seed(seed: u64, season: i32, pid: i32) -> u64 {
+    derive_seed(seed, &[season as i64, -3, pid as i64])
+}
+
+// Seed for generating prospects in a draft class, starting from first_pid so that topping up a class doesn't repeat
+// the players already in it
+pub fn draft_class_seed(seed: u64, draft_year: i32, first_pid: i32) -> u64 {
+    derive_seed(seed, &[draft_year as i64, -4, first_pid as i64])
+}
+
+// Seed for the players in a new random league
+pub fn random_players_seed(seed: u64) -> u64 {
+    derive_seed(seed, &[-5])
+}
+
+// Seed for a season's draft lottery
+pub fn lottery_seed(seed: u64, season: i32) -> u64 {
+    derive_seed(seed, &[season as i64, -6])
+}
+
+// Seed for AI signings on one day of free agency after season
+pub fn free_agency_seed(seed: u64, season: i32, day: i32) -> u64 {
+    derive_seed(seed, &[season as i64, -7, day as i64])
+}
+
+pub fn rng_from_seed(seed: u64) -> StdRng {
+    StdRng::seed_from_u64(seed)
+}
+
+// For when no seed was given. The result is returned so it can be printed or saved, to replay the same games.
+pub fn random_seed() -> u64 {
+    rand::random()
+}
+
+// Normally distributed number, from the Box-Muller transform
+pub fn gauss(rng: &mut StdRng, mu: f64, sigma: f64) -> f64 {
+    let u1: f64 = 1.0 - rng.gen::<f64>();
+    let u2: f64 = rng.gen();
+    mu + sigma * (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+}
\ No newline at end of file